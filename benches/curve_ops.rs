@@ -0,0 +1,75 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use elliptic_curve::curves;
+use num_bigint::BigUint;
+
+// Field, point and signature operations on secp256k1, so performance changes are measured
+// rather than guessed. Run with `cargo bench`; `cargo bench -- field` filters by name
+
+fn scalar(hex: &str) -> BigUint {
+    BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()
+}
+
+fn field_ops(c: &mut Criterion) {
+    let secp = curves::secp256k1();
+    let field = secp.curve.field();
+    let x = scalar("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+    let y = scalar("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+
+    c.bench_function("field mul", |b| b.iter(|| field.mul(black_box(&x), black_box(&y))));
+    c.bench_function("field mul_assign", |b| {
+        b.iter(|| {
+            let mut z = x.clone();
+            field.mul_assign(&mut z, black_box(&y));
+            z
+        })
+    });
+    c.bench_function("field inversion (Fermat)", |b| b.iter(|| field.div(&BigUint::from(1u32), black_box(&x))));
+    c.bench_function("field inversion (Euclid)", |b| b.iter(|| field.invert(black_box(&x))));
+}
+
+fn point_ops(c: &mut Criterion) {
+    let secp = curves::secp256k1();
+    let g = secp.generator.clone();
+    let h = secp.curve.double(&g);
+    let (pg, ph) = (secp.curve.to_projective(&g), secp.curve.to_projective(&h));
+
+    c.bench_function("point add", |b| b.iter(|| secp.curve.add(black_box(&g), black_box(&h))));
+    c.bench_function("point double", |b| b.iter(|| secp.curve.double(black_box(&g))));
+    c.bench_function("point add (complete, projective)", |b| {
+        b.iter(|| secp.curve.add_complete(black_box(&pg), black_box(&ph)))
+    });
+}
+
+fn scalar_mult(c: &mut Criterion) {
+    let secp = curves::secp256k1();
+    let k = scalar("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5");
+    let point = secp.curve.scalar_mult(&secp.generator, &BigUint::from(7u32));
+
+    let mut group = c.benchmark_group("scalar mult");
+    group.sample_size(20);
+    group.bench_function("fixed base", |b| b.iter(|| secp.curve.scalar_mult(&secp.generator, black_box(&k))));
+    group.bench_function("variable base", |b| b.iter(|| secp.curve.scalar_mult(black_box(&point), black_box(&k))));
+    group.bench_function("ladder (complete formulas)", |b| {
+        b.iter(|| secp.curve.scalar_mult_complete(black_box(&point), black_box(&k)))
+    });
+    group.finish();
+}
+
+fn signatures(c: &mut Criterion) {
+    let secp = curves::secp256k1();
+    let ecdsa = secp.ecdsa();
+    let keypair = ecdsa.generate_keypair();
+    let message = b"benchmark message";
+    let signature = ecdsa.sign(message, &keypair.private_key).unwrap();
+
+    let mut group = c.benchmark_group("ecdsa");
+    group.sample_size(20);
+    group.bench_function("sign", |b| b.iter(|| ecdsa.sign(black_box(message), &keypair.private_key)));
+    group.bench_function("verify", |b| {
+        b.iter(|| ecdsa.verify(black_box(message), &signature, &keypair.public_key))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, field_ops, point_ops, scalar_mult, signatures);
+criterion_main!(benches);
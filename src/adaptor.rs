@@ -0,0 +1,233 @@
+use crate::{FiniteField, Point};
+use crate::ecdsa::{hash_to_scalar, ECDSASignature, ECDSA};
+use crate::schnorr::{Schnorr, SchnorrSignature};
+use crate::zkp::{DLEQProof, DLEQStatement, Transcript};
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use num_bigint::BigUint;
+
+// Adaptor signatures: a pre-signature bound to an adaptor point T = t * G that anyone can
+// check, but which only becomes a valid signature once completed with t; publishing the
+// completed signature then reveals t to the pre-signature holder. This is the atomic
+// "signature for a secret" exchange behind atomic swaps, PTLCs and DLCs
+
+const ECDSA_ADAPTOR_DOMAIN: &[u8] = b"ecdsa-adaptor";
+
+// Schnorr pre-signature: R = k * G and ŝ = k + e * x with e = H(R + T || P || m)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchnorrPreSignature {
+    pub r: Point,
+    pub s: BigUint,
+}
+
+// ECDSA pre-signature: R = k * T (whose x gives r), R_a = k * G, ŝ = k⁻¹ (z + r d),
+// and a DLEQ proof that R and R_a share k
+#[derive(Clone, Debug, PartialEq)]
+pub struct ECDSAPreSignature {
+    pub r: Point,
+    pub r_a: Point,
+    pub s: BigUint,
+    pub proof: DLEQProof,
+}
+
+impl Schnorr {
+    pub fn pre_sign(&self, message: &[u8], private_key: &BigUint, adaptor_point: &Point) -> Result<SchnorrPreSignature, &'static str> {
+        if *adaptor_point == Point::Identity || !self.params.curve.is_on_curve(adaptor_point) {
+            return Err("invalid adaptor point");
+        }
+        let field = FiniteField { p: self.params.order.clone() };
+        let public_key = self.params.generate_public_key(private_key);
+        loop {
+            let k = self.params.generate_private_key();
+            let r = self.params.curve.scalar_mult(&self.params.generator, &k);
+            let r_adapted = self.params.curve.add(&r, adaptor_point);
+            if r_adapted == Point::Identity {
+                continue;
+            }
+            let e = self.challenge(&r_adapted, &public_key, message);
+            return Ok(SchnorrPreSignature { r, s: field.add(&k, &field.mul(&e, private_key)) });
+        }
+    }
+
+    // ŝ * G = R + e * P with e computed over R + T
+    pub fn pre_verify(&self, message: &[u8], pre_signature: &SchnorrPreSignature, public_key: &Point, adaptor_point: &Point) -> bool {
+        if pre_signature.s >= self.params.order || !self.params.curve.is_on_curve(&pre_signature.r) || !self.params.curve.is_on_curve(adaptor_point) {
+            return false;
+        }
+        let r_adapted = self.params.curve.add(&pre_signature.r, adaptor_point);
+        if r_adapted == Point::Identity {
+            return false;
+        }
+        let e = self.challenge(&r_adapted, public_key, message);
+        let lhs = self.params.curve.scalar_mult(&self.params.generator, &pre_signature.s);
+        let rhs = self.params.curve.add(&pre_signature.r, &self.params.curve.scalar_mult(public_key, &e));
+        lhs == rhs
+    }
+
+    // (R + T, ŝ + t)
+    pub fn adapt(&self, pre_signature: &SchnorrPreSignature, adaptor_secret: &BigUint) -> SchnorrSignature {
+        let field = FiniteField { p: self.params.order.clone() };
+        let t = adaptor_secret % &self.params.order;
+        SchnorrSignature {
+            r: self.params.curve.add(&pre_signature.r, &self.params.curve.scalar_mult(&self.params.generator, &t)),
+            s: field.add(&pre_signature.s, &t),
+        }
+    }
+
+    // t = s - ŝ, checked against T
+    pub fn extract_adaptor_secret(&self, pre_signature: &SchnorrPreSignature, signature: &SchnorrSignature, adaptor_point: &Point) -> Result<BigUint, &'static str> {
+        let field = FiniteField { p: self.params.order.clone() };
+        let t = field.sub(&signature.s, &pre_signature.s);
+        if self.params.curve.scalar_mult(&self.params.generator, &t) != *adaptor_point {
+            return Err("signature was not completed from this pre-signature");
+        }
+        Ok(t)
+    }
+}
+
+impl ECDSA {
+    pub fn pre_sign(&self, message: &[u8], private_key: &BigUint, adaptor_point: &Point) -> Result<ECDSAPreSignature, &'static str> {
+        if *adaptor_point == Point::Identity || !self.is_in_prime_subgroup(adaptor_point) {
+            return Err("invalid adaptor point");
+        }
+        let field = FiniteField { p: self.order.clone() };
+        let z = hash_to_scalar(message, &self.order);
+        let mut rng = Entropy;
+        loop {
+            let k = Scalar::random(&self.order, &mut rng).into_value();
+            let statement = DLEQStatement::new(&self.curve, &self.generator, adaptor_point, &k);
+
+            let r = match &statement.b {
+                Point::Coordinate(x, _) => x % &self.order,
+                Point::Identity => continue,
+            };
+            let s = field.mul(&field.div(&BigUint::from(1u32), &k), &field.add(&z, &field.mul(&r, private_key)));
+            if r == BigUint::from(0u32) || s == BigUint::from(0u32) {
+                continue;
+            }
+
+            let proof = DLEQProof::prove(&self.curve, &self.order, &statement, &k, &mut Transcript::new(ECDSA_ADAPTOR_DOMAIN))?;
+            return Ok(ECDSAPreSignature { r: statement.b, r_a: statement.a, s, proof });
+        }
+    }
+
+    // DLEQ(R_a = k * G, R = k * T) and ŝ⁻¹ (z * G + r * P) = R_a
+    pub fn pre_verify(&self, message: &[u8], pre_signature: &ECDSAPreSignature, public_key: &Point, adaptor_point: &Point) -> bool {
+        if pre_signature.s == BigUint::from(0u32) || pre_signature.s >= self.order {
+            return false;
+        }
+        let r = match &pre_signature.r {
+            Point::Coordinate(x, _) => x % &self.order,
+            Point::Identity => return false,
+        };
+        if r == BigUint::from(0u32) {
+            return false;
+        }
+
+        let statement = DLEQStatement {
+            g: self.generator.clone(),
+            a: pre_signature.r_a.clone(),
+            h: adaptor_point.clone(),
+            b: pre_signature.r.clone(),
+        };
+        if !pre_signature.proof.verify(&self.curve, &self.order, &statement, &mut Transcript::new(ECDSA_ADAPTOR_DOMAIN)) {
+            return false;
+        }
+
+        let field = FiniteField { p: self.order.clone() };
+        let z = hash_to_scalar(message, &self.order);
+        let w = field.div(&BigUint::from(1u32), &pre_signature.s);
+        let u1 = self.curve.scalar_mult(&self.generator, &field.mul(&z, &w));
+        let u2 = self.curve.scalar_mult(public_key, &field.mul(&r, &w));
+        self.curve.add(&u1, &u2) == pre_signature.r_a
+    }
+
+    // s = ŝ * t⁻¹: then s⁻¹ (z * G + r * P) = t * R_a = R, whose x is r
+    pub fn adapt(&self, pre_signature: &ECDSAPreSignature, adaptor_secret: &BigUint) -> Result<ECDSASignature, &'static str> {
+        let t = adaptor_secret % &self.order;
+        if t == BigUint::from(0u32) {
+            return Err("adaptor secret is zero");
+        }
+        let field = FiniteField { p: self.order.clone() };
+        let Point::Coordinate(x, _) = &pre_signature.r else {
+            return Err("pre-signature nonce is the point at infinity");
+        };
+        Ok(ECDSASignature {
+            r: x % &self.order,
+            s: field.div(&pre_signature.s, &t),
+        })
+    }
+
+    // t = ŝ * s⁻¹; a normalized (negated) s yields -t, so check both against T
+    pub fn extract_adaptor_secret(&self, pre_signature: &ECDSAPreSignature, signature: &ECDSASignature, adaptor_point: &Point) -> Result<BigUint, &'static str> {
+        if signature.s == BigUint::from(0u32) || signature.s >= self.order {
+            return Err("invalid signature");
+        }
+        let field = FiniteField { p: self.order.clone() };
+        let t = field.div(&pre_signature.s, &signature.s);
+        let candidate = self.curve.scalar_mult(&self.generator, &t);
+        if candidate == *adaptor_point {
+            Ok(t)
+        } else if self.curve.neg(&candidate) == *adaptor_point {
+            Ok(field.sub(&BigUint::from(0u32), &t))
+        } else {
+            Err("signature was not completed from this pre-signature")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    #[test]
+    fn test_schnorr_adaptor() {
+        let scheme = curves::p256().schnorr();
+        let keypair = scheme.generate_keypair();
+        let t = scheme.params.generate_private_key();
+        let adaptor_point = scheme.params.generate_public_key(&t);
+        let message = b"pay 1 BTC if you reveal t";
+
+        let pre_signature = scheme.pre_sign(message, &keypair.private_key, &adaptor_point).unwrap();
+        assert!(scheme.pre_verify(message, &pre_signature, &keypair.public_key, &adaptor_point));
+
+        // A pre-signature alone isn't a signature
+        let premature = SchnorrSignature { r: pre_signature.r.clone(), s: pre_signature.s.clone() };
+        assert!(!scheme.verify(message, &premature, &keypair.public_key));
+
+        let signature = scheme.adapt(&pre_signature, &t);
+        assert!(scheme.verify(message, &signature, &keypair.public_key));
+        assert_eq!(scheme.extract_adaptor_secret(&pre_signature, &signature, &adaptor_point).unwrap(), t);
+
+        let other_point = scheme.params.generate_public_key(&(&t + 1u32));
+        assert!(!scheme.pre_verify(message, &pre_signature, &keypair.public_key, &other_point));
+    }
+
+    #[test]
+    fn test_ecdsa_adaptor() {
+        let ecdsa = curves::p256().ecdsa();
+        let keypair = ecdsa.generate_keypair();
+        let t = ecdsa.generate_private_key();
+        let adaptor_point = ecdsa.generate_public_key(&t);
+        let message = b"atomic swap leg 2";
+
+        let pre_signature = ecdsa.pre_sign(message, &keypair.private_key, &adaptor_point).unwrap();
+        assert!(ecdsa.pre_verify(message, &pre_signature, &keypair.public_key, &adaptor_point));
+        assert!(!ecdsa.pre_verify(b"other message", &pre_signature, &keypair.public_key, &adaptor_point));
+
+        let signature = ecdsa.adapt(&pre_signature, &t).unwrap();
+        assert!(ecdsa.verify(message, &signature, &keypair.public_key));
+        assert_eq!(ecdsa.extract_adaptor_secret(&pre_signature, &signature, &adaptor_point).unwrap(), t);
+
+        // Low-s normalization (s -> n - s) still lets the secret be extracted
+        let field = FiniteField { p: ecdsa.order.clone() };
+        let negated = ECDSASignature { r: signature.r.clone(), s: field.sub(&BigUint::from(0u32), &signature.s) };
+        assert!(ecdsa.verify(message, &negated, &keypair.public_key));
+        assert_eq!(ecdsa.extract_adaptor_secret(&pre_signature, &negated, &adaptor_point).unwrap(), t);
+
+        // Completing with the wrong secret doesn't give a valid signature
+        let wrong = ecdsa.adapt(&pre_signature, &(&t + 1u32)).unwrap();
+        assert!(!ecdsa.verify(message, &wrong, &keypair.public_key));
+    }
+}
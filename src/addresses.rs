@@ -0,0 +1,3 @@
+// Blockchain address formats built on the point encodings
+pub mod bitcoin;
+pub mod ethereum;
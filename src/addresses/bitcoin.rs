@@ -0,0 +1,159 @@
+use crate::Point;
+use crate::bip340::{tagged_hash, Bip340};
+use crate::curves;
+use crate::encoding::{self, Bech32Variant, to_fixed_bytes};
+use crate::hd::hash160;
+use crate::wif::Network;
+use num_bigint::BigUint;
+
+// Bitcoin addresses from secp256k1 public keys
+//
+//   P2PKH   base58check(0x00 || HASH160(public key)), with the SEC1 encoding the key is
+//           used with: compressed and uncompressed keys give different addresses
+//   P2WPKH  segwit v0: bech32(hrp, 0 || HASH160(compressed public key))
+//   P2TR    segwit v1: bech32m(hrp, 1 || x(Q)), where Q = P + H_TapTweak(x(P)) * G is the
+//           internal key P (even y) tweaked to commit to "no script tree" (BIP-86)
+// HASH160 = RIPEMD160(SHA256(x)); hd::hash160 computes it
+
+fn p2pkh_version(network: Network) -> u8 {
+    match network {
+        Network::Mainnet => 0x00,
+        Network::Testnet => 0x6f,
+    }
+}
+
+fn segwit_hrp(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => "bc",
+        Network::Testnet => "tb",
+    }
+}
+
+pub fn p2pkh(network: Network, public_key: &Point, compressed: bool) -> Result<String, &'static str> {
+    if *public_key == Point::Identity {
+        return Err("public key is the point at infinity");
+    }
+    let encoded = encoding::encode_point(&curves::secp256k1().curve, public_key, compressed);
+    let mut payload = vec![p2pkh_version(network)];
+    payload.extend_from_slice(&hash160(&encoded));
+    Ok(encoding::to_base58check(&payload))
+}
+
+pub fn p2wpkh(network: Network, public_key: &Point) -> Result<String, &'static str> {
+    if *public_key == Point::Identity {
+        return Err("public key is the point at infinity");
+    }
+    let encoded = encoding::encode_point(&curves::secp256k1().curve, public_key, true);
+    segwit_address(network, 0, &hash160(&encoded))
+}
+
+// BIP-341 key-path-only output key: x(P + t * G) with t = H_TapTweak(x(P))
+pub fn taproot_output_key(internal_key: &Point) -> Result<BigUint, &'static str> {
+    let Point::Coordinate(x, _) = internal_key else {
+        return Err("public key is the point at infinity");
+    };
+    let bip340 = Bip340::new();
+    let internal_key = bip340.lift_x(x)?;
+    let tweak = BigUint::from_bytes_be(&tagged_hash("TapTweak", &to_fixed_bytes(x, 32)));
+    if tweak >= bip340.params.order {
+        return Err("taproot tweak out of range");
+    }
+    let output_key = bip340.params.curve.add(&internal_key, &bip340.params.curve.scalar_mult(&bip340.params.generator, &tweak));
+    match output_key {
+        Point::Coordinate(x, _) => Ok(x),
+        Point::Identity => Err("tweaked key is the point at infinity"),
+    }
+}
+
+pub fn p2tr(network: Network, internal_key: &Point) -> Result<String, &'static str> {
+    let output_key = taproot_output_key(internal_key)?;
+    segwit_address(network, 1, &to_fixed_bytes(&output_key, 32))
+}
+
+// Witness version 0 uses bech32, later versions bech32m (BIP-350)
+pub fn segwit_address(network: Network, version: u8, program: &[u8]) -> Result<String, &'static str> {
+    validate_witness_program(version, program)?;
+    let variant = if version == 0 { Bech32Variant::Bech32 } else { Bech32Variant::Bech32m };
+    let mut data = vec![version];
+    data.extend(encoding::convert_bits(program, 8, 5, true)?);
+    Ok(encoding::to_bech32(segwit_hrp(network), &data, variant))
+}
+
+// (witness version, witness program)
+pub fn decode_segwit_address(network: Network, address: &str) -> Result<(u8, Vec<u8>), &'static str> {
+    let (hrp, data, variant) = encoding::from_bech32(address)?;
+    if hrp != segwit_hrp(network) {
+        return Err("address is for another network");
+    }
+    let (&version, program) = data.split_first().ok_or("empty witness program")?;
+    let expected = if version == 0 { Bech32Variant::Bech32 } else { Bech32Variant::Bech32m };
+    if variant != expected {
+        return Err("wrong checksum variant for the witness version");
+    }
+    let program = encoding::convert_bits(program, 5, 8, false)?;
+    validate_witness_program(version, &program)?;
+    Ok((version, program))
+}
+
+fn validate_witness_program(version: u8, program: &[u8]) -> Result<(), &'static str> {
+    if version > 16 {
+        return Err("witness version above 16");
+    }
+    if program.len() < 2 || program.len() > 40 {
+        return Err("witness program must be 2 to 40 bytes");
+    }
+    if version == 0 && program.len() != 20 && program.len() != 32 {
+        return Err("v0 witness programs are 20 or 32 bytes");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bip39;
+    use crate::hd::Bip32;
+
+    #[test]
+    fn test_generator_addresses() {
+        // Private key 1: public key G
+        let g = curves::secp256k1().generator;
+        assert_eq!(p2pkh(Network::Mainnet, &g, true).unwrap(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(p2pkh(Network::Mainnet, &g, false).unwrap(), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+
+        // BIP-173 example
+        let address = p2wpkh(Network::Mainnet, &g).unwrap();
+        assert_eq!(address, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let (version, program) = decode_segwit_address(Network::Mainnet, &address).unwrap();
+        assert_eq!((version, encoding::to_hex(&program)), (0, "751e76e8199196d454941c45d1b3a323f1433bd6".to_string()));
+        assert!(decode_segwit_address(Network::Testnet, &address).is_err());
+    }
+
+    #[test]
+    fn test_bip86_taproot_address() {
+        // BIP-86 vector: m/86'/0'/0'/0/0 from the all-"abandon" mnemonic
+        let bip32 = Bip32::new();
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let seed = bip39::mnemonic_to_seed(mnemonic, "").unwrap();
+        let key = bip32.derive_path(&bip32.master_key(&seed).unwrap(), "m/86'/0'/0'/0/0").unwrap();
+        let internal_key = bip32.neuter(&key).public_key;
+
+        assert_eq!(
+            encoding::to_hex(&to_fixed_bytes(&taproot_output_key(&internal_key).unwrap(), 32)),
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+        );
+        let address = p2tr(Network::Mainnet, &internal_key).unwrap();
+        assert_eq!(address, "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr");
+        assert_eq!(decode_segwit_address(Network::Mainnet, &address).unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_rejects_wrong_checksum_variant() {
+        // A v1 program under a bech32 (not bech32m) checksum is invalid since BIP-350
+        let mut data = vec![1u8];
+        data.extend(encoding::convert_bits(&[0xab; 32], 8, 5, true).unwrap());
+        let wrong = encoding::to_bech32("bc", &data, Bech32Variant::Bech32);
+        assert!(decode_segwit_address(Network::Mainnet, &wrong).is_err());
+        assert!(segwit_address(Network::Mainnet, 0, &[0u8; 21]).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::Point;
+use crate::curves;
+use crate::encoding::{self, to_hex};
+use sha3::{Digest, Keccak256};
+
+// Ethereum addresses: the last 20 bytes of Keccak-256(x || y), from the uncompressed
+// secp256k1 public key without its 0x04 prefix
+//
+// EIP-55 checksum: hex letters are uppercased where the matching nibble of
+// Keccak-256(lowercase hex address) is ≥ 8. All-lowercase or all-uppercase addresses carry
+// no checksum. Keccak-256 is the original Keccak submission, not NIST SHA3-256 (different padding)
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+pub fn address_bytes(public_key: &Point) -> Result<[u8; 20], &'static str> {
+    if *public_key == Point::Identity {
+        return Err("public key is the point at infinity");
+    }
+    let encoded = encoding::encode_point(&curves::secp256k1().curve, public_key, false);
+    Ok(keccak256(&encoded[1..])[12..].try_into().unwrap())
+}
+
+// "0x" followed by the EIP-55 mixed-case hex address
+pub fn from_public_key(public_key: &Point) -> Result<String, &'static str> {
+    Ok(to_checksum_address(&address_bytes(public_key)?))
+}
+
+pub fn to_checksum_address(address: &[u8; 20]) -> String {
+    let lower = to_hex(address);
+    let hash = keccak256(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+// Parse a hex address, enforcing the EIP-55 checksum when the case is mixed
+pub fn parse_address(address: &str) -> Result<[u8; 20], &'static str> {
+    let hex = address.strip_prefix("0x").ok_or("address must start with 0x")?;
+    if hex.len() != 40 {
+        return Err("address must be 20 bytes");
+    }
+    let bytes: [u8; 20] = encoding::from_hex(&hex.to_ascii_lowercase())?.try_into().unwrap();
+    let mixed_case = hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case && to_checksum_address(&bytes) != address {
+        return Err("EIP-55 checksum mismatch");
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak256() {
+        // Keccak-256(""), distinct from SHA3-256("") = a7ffc6f8...
+        assert_eq!(to_hex(&keccak256(b"")), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+    }
+
+    #[test]
+    fn test_address_from_public_key() {
+        // Private key 1
+        let g = curves::secp256k1().generator;
+        assert_eq!(from_public_key(&g).unwrap(), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+        assert!(from_public_key(&Point::Identity).is_err());
+    }
+
+    #[test]
+    fn test_eip55_checksums() {
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let bytes = parse_address(address).unwrap();
+            assert_eq!(to_checksum_address(&bytes), address);
+        }
+
+        // Wrong case on one letter breaks the checksum; uniform case has none to check
+        assert!(parse_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+        assert!(parse_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_ok());
+        assert!(parse_address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_err());
+    }
+}
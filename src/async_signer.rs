@@ -0,0 +1,93 @@
+use crate::signer::{Error, Signer};
+use std::future::Future;
+
+// Signing keys that live somewhere else: a cloud KMS, an HSM behind a network hop, a signing
+// service. Signing is a request that completes later, so the trait is async; the signatures
+// come back as this crate's types (ECDSASignature, SchnorrSignature, Ed25519Signature) and are
+// checked with the ordinary synchronous Verifier impls, the same path as local keys.
+// The future is Send so it can be spawned on a multi-threaded runtime. No runtime is assumed:
+// LocalSigner wraps any in-memory Signer and is ready on first poll
+
+pub trait AsyncSigner<S> {
+    fn sign_async(&self, message: &[u8]) -> impl Future<Output = Result<S, Error>> + Send;
+}
+
+// A local key (SecretKey, Ed25519SigningKey, ...) as an AsyncSigner
+#[derive(Clone, Debug)]
+pub struct LocalSigner<K>(pub K);
+
+impl<S: Send, K: Signer<S> + Sync> AsyncSigner<S> for LocalSigner<K> {
+    fn sign_async(&self, message: &[u8]) -> impl Future<Output = Result<S, Error>> + Send {
+        let result = self.0.try_sign(message);
+        async move { result }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use crate::ecdsa::ECDSASignature;
+    use crate::ed25519::Ed25519Signature;
+    use crate::secret_key::SecretKey;
+    use crate::signer::{Ed25519SigningKey, Keypair, Verifier};
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    // Polls to completion; enough for futures that only yield, without pulling in a runtime
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    // Stands in for a KMS client: answers after a round trip
+    struct RemoteSigner {
+        key: SecretKey,
+    }
+
+    impl AsyncSigner<ECDSASignature> for RemoteSigner {
+        async fn sign_async(&self, message: &[u8]) -> Result<ECDSASignature, Error> {
+            let mut pending = true;
+            std::future::poll_fn(|context| {
+                if std::mem::take(&mut pending) {
+                    context.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                Poll::Ready(())
+            })
+            .await;
+            self.key.try_sign(message)
+        }
+    }
+
+    // Generic over where the key lives
+    fn sign_and_verify<S, A, V>(signer: &A, verifying_key: &V, message: &[u8]) -> bool
+    where
+        A: AsyncSigner<S>,
+        V: Verifier<S>,
+    {
+        let signature = block_on(signer.sign_async(message)).unwrap();
+        verifying_key.verify(message, &signature).is_ok()
+    }
+
+    #[test]
+    fn test_local_and_remote_signers() {
+        let key = SecretKey::random(&curves::p256());
+        let verifying_key = key.verifying_key();
+        assert!(sign_and_verify::<ECDSASignature, _, _>(&LocalSigner(key.clone()), &verifying_key, b"release v1.2"));
+        assert!(sign_and_verify::<ECDSASignature, _, _>(&RemoteSigner { key }, &verifying_key, b"release v1.2"));
+
+        let ed = Ed25519SigningKey::from_bytes(&[3u8; 32]);
+        let ed_verifying_key = ed.verifying_key();
+        assert!(sign_and_verify::<Ed25519Signature, _, _>(&LocalSigner(ed), &ed_verifying_key, b"release v1.2"));
+
+        // A signature from the wrong remote key fails the shared verification
+        let remote = RemoteSigner { key: SecretKey::random(&curves::p256()) };
+        assert!(!sign_and_verify::<ECDSASignature, _, _>(&remote, &verifying_key, b"release v1.2"));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::FiniteField;
+use crate::ecdsa::{ECDSASignature, hash_to_scalar};
+use num_bigint::BigUint;
+
+pub mod invalid_curve;
+pub mod mov;
+pub mod smart;
+
+pub use mov::mov_reduce;
+pub use smart::smart_attack;
+
+// Private key recovery from two ECDSA signatures that reused the nonce k
+//
+// Same k => same R => same r, and:
+//   s1 = k^(-1) * (z1 + r * d)
+//   s2 = k^(-1) * (z2 + r * d)
+// Subtracting: s1 - s2 = k^(-1) * (z1 - z2), so k = (z1 - z2) / (s1 - s2)
+// Then d = (s1 * k - z1) / r
+pub fn recover_key_from_nonce_reuse(
+    sig1: &ECDSASignature,
+    sig2: &ECDSASignature,
+    msg1: &[u8],
+    msg2: &[u8],
+    order: &BigUint,
+) -> Result<BigUint, &'static str> {
+    let field = FiniteField { p: order.clone() };
+    let zero = BigUint::from(0u32);
+
+    // Different r values mean different nonces
+    if sig1.r != sig2.r {
+        return Err("signatures do not share a nonce");
+    }
+    if sig1.r == zero {
+        return Err("invalid signature: r = 0");
+    }
+    // s1 = s2 happens when z1 = z2: nothing to solve for
+    if sig1.s == sig2.s {
+        return Err("signatures have equal s, messages hash to the same scalar");
+    }
+
+    let z1 = hash_to_scalar(msg1, order);
+    let z2 = hash_to_scalar(msg2, order);
+
+    // k = (z1 - z2) / (s1 - s2) mod n
+    let k = field.div(&field.sub(&z1, &z2), &field.sub(&sig1.s, &sig2.s));
+
+    // d = (s1 * k - z1) / r mod n
+    let private_key = field.div(&field.sub(&field.mul(&sig1.s, &k), &z1), &sig1.r);
+
+    Ok(private_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EllipticCurve, Point};
+    use crate::ecdsa::ECDSA;
+
+    fn setup_ecdsa() -> ECDSA {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        ECDSA::new(curve, generator, BigUint::from(19u32))
+    }
+
+    #[test]
+    fn test_recover_key_from_nonce_reuse() {
+        let ecdsa = setup_ecdsa();
+        let private_key = BigUint::from(7u32);
+        let k = BigUint::from(5u32);
+
+        let msg1 = b"first message";
+        let msg2 = b"second message";
+        let sig1 = ecdsa.sign_with_nonce(msg1, &private_key, &k).unwrap();
+        let sig2 = ecdsa.sign_with_nonce(msg2, &private_key, &k).unwrap();
+        assert_eq!(sig1.r, sig2.r);
+
+        let recovered = recover_key_from_nonce_reuse(&sig1, &sig2, msg1, msg2, &ecdsa.order).unwrap();
+        assert_eq!(recovered, private_key);
+    }
+
+    #[test]
+    fn test_distinct_nonces_rejected() {
+        let ecdsa = setup_ecdsa();
+        let private_key = BigUint::from(7u32);
+
+        let msg1 = b"first message";
+        let msg2 = b"second message";
+        let sig1 = ecdsa.sign_with_nonce(msg1, &private_key, &BigUint::from(5u32)).unwrap();
+        let sig2 = ecdsa.sign_with_nonce(msg2, &private_key, &BigUint::from(6u32)).unwrap();
+
+        assert!(recover_key_from_nonce_reuse(&sig1, &sig2, msg1, msg2, &ecdsa.order).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{EllipticCurve, Point};
+use crate::ntheory::{crt, factorize};
+use num_bigint::BigUint;
+
+// Invalid-curve attack on ECDH implementations that skip public key validation
+//
+// The addition and doubling formulas only use a and p, never b. A victim computing
+// d * Q for an unchecked Q therefore does the arithmetic on whatever curve
+// y² = x³ + ax + b' the point actually lies on. The attacker picks b' so that the
+// group has a small prime order subgroup, sends a point Q of order r there and gets
+// back d * Q, which only has r possible values: d mod r falls out by brute force.
+// Repeat for enough distinct primes and CRT gives d.
+
+// A point of small prime order on the invalid curve y² = x³ + ax + b'
+#[derive(Clone, Debug)]
+pub struct LowOrderPoint {
+    pub curve: EllipticCurve,
+    pub point: Point,
+    pub order: BigUint,
+}
+
+// Search curves y² = x³ + ax + b' (same a and p, b' != b) for points of distinct
+// prime orders r <= max_order. Needs point counting, so small p only
+pub fn find_low_order_points(curve: &EllipticCurve, max_order: &BigUint) -> Vec<LowOrderPoint> {
+    let field = curve.field();
+    let zero = BigUint::from(0u32);
+    let mut found: Vec<LowOrderPoint> = Vec::new();
+
+    let mut b = zero.clone();
+    while b < *curve.p() {
+        let invalid_curve = EllipticCurve::new(curve.a().clone(), b.clone(), curve.p().clone());
+        b += 1u32;
+
+        if invalid_curve.b() == curve.b() || is_singular(&invalid_curve) {
+            continue;
+        }
+        let group_order = match invalid_curve.count_points() {
+            Ok(n) => n,
+            Err(_) => continue,
+        };
+
+        for (prime, _) in factorize(&group_order) {
+            if prime > *max_order || found.iter().any(|f| f.order == prime) {
+                continue;
+            }
+
+            // (#E' / r) * Q has order r unless it's the identity; try points until one works
+            let cofactor = &group_order / &prime;
+            let mut x = zero.clone();
+            while x < *curve.p() {
+                let x_cubed = field.mul(&field.mul(&x, &x), &x);
+                let rhs = field.add(&field.add(&x_cubed, &field.mul(invalid_curve.a(), &x)), invalid_curve.b());
+                if let Some(y) = field.sqrt(&rhs) {
+                    let candidate = invalid_curve.scalar_mult(&Point::Coordinate(x.clone(), y), &cofactor);
+                    if candidate != Point::Identity {
+                        found.push(LowOrderPoint {
+                            curve: invalid_curve.clone(),
+                            point: candidate,
+                            order: prime.clone(),
+                        });
+                        break;
+                    }
+                }
+                x += 1u32;
+            }
+        }
+    }
+
+    found
+}
+
+// Run the attack against an ECDH oracle returning d * Q for a submitted Q
+// (None when the victim refuses the point). Returns d once the residues cover the order
+pub fn recover_key<F>(curve: &EllipticCurve, order: &BigUint, mut oracle: F) -> Result<BigUint, &'static str>
+where
+    F: FnMut(&Point) -> Option<Point>,
+{
+    let mut congruences = Vec::new();
+    let mut modulus = BigUint::from(1u32);
+
+    for low_order in find_low_order_points(curve, order) {
+        let response = match oracle(&low_order.point) {
+            Some(response) => response,
+            None => continue,
+        };
+
+        // d * Q only has r possible values: find t with t * Q = d * Q, then d ≡ t (mod r)
+        let mut t = BigUint::from(0u32);
+        let mut multiple = Point::Identity;
+        while t < low_order.order && multiple != response {
+            multiple = low_order.curve.add(&multiple, &low_order.point);
+            t += 1u32;
+        }
+        if multiple != response {
+            continue;
+        }
+
+        modulus *= &low_order.order;
+        congruences.push((t, low_order.order));
+
+        if modulus > *order {
+            let (private_key, _) = crt(&congruences)?;
+            return Ok(private_key);
+        }
+    }
+
+    Err("not enough low-order points answered to recover the key")
+}
+
+// 4a³ + 27b² = 0 (mod p): the cubic has a repeated root and there's no group
+fn is_singular(curve: &EllipticCurve) -> bool {
+    let field = curve.field();
+    let a_cubed = field.mul(&field.mul(curve.a(), curve.a()), curve.a());
+    let b_squared = field.mul(curve.b(), curve.b());
+    let discriminant = field.add(
+        &field.mul(&BigUint::from(4u32), &a_cubed),
+        &field.mul(&BigUint::from(27u32), &b_squared),
+    );
+    discriminant == BigUint::from(0u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecdh::ECDH;
+
+    fn setup_ecdh() -> ECDH {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        ECDH::new(curve, generator, BigUint::from(19u32))
+    }
+
+    #[test]
+    fn test_low_order_points() {
+        let ecdh = setup_ecdh();
+        let points = find_low_order_points(&ecdh.curve, &ecdh.order);
+        assert!(!points.is_empty());
+
+        for low_order in &points {
+            assert!(low_order.curve.is_on_curve(&low_order.point));
+            assert!(!ecdh.curve.is_on_curve(&low_order.point));
+            assert_eq!(low_order.curve.scalar_mult(&low_order.point, &low_order.order), Point::Identity);
+        }
+    }
+
+    #[test]
+    fn test_attack_recovers_unvalidated_key() {
+        let ecdh = setup_ecdh();
+        let private_key = BigUint::from(13u32);
+
+        // A naive victim: d * Q with no validation of Q
+        let naive_victim = |point: &Point| Some(ecdh.curve.scalar_mult(point, &private_key));
+
+        let recovered = recover_key(&ecdh.curve, &ecdh.order, naive_victim).unwrap();
+        assert_eq!(recovered, private_key);
+    }
+
+    #[test]
+    fn test_validated_ecdh_resists_attack() {
+        let ecdh = setup_ecdh();
+        let private_key = BigUint::from(13u32);
+
+        // ECDH::shared_secret rejects every off-curve point, so the oracle never answers
+        let validated_victim = |point: &Point| ecdh.shared_secret(&private_key, point).ok();
+
+        assert!(recover_key(&ecdh.curve, &ecdh.order, validated_victim).is_err());
+    }
+}
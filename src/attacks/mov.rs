@@ -0,0 +1,140 @@
+use crate::{EllipticCurve, Point};
+use crate::extension::{ExtElement, ExtPoint, ExtensionField};
+use crate::pairing::{TatePairing, embedding_degree};
+use num_bigint::BigUint;
+use std::collections::HashMap;
+
+// MOV attack (Menezes–Okamoto–Vanstone): pairings move the ECDLP Q = dP into F_{p^k}*
+//
+// With S any point where e(P, S) != 1, bilinearity gives
+//   e(Q, S) = e(dP, S) = e(P, S)^d
+// so d is a discrete log in the order-r subgroup of F_{p^k}*, where index calculus
+// applies and subexponential attacks exist. For small k (supersingular curves have
+// k <= 6) that field is barely bigger than F_p: the curve is no stronger than F_{p^k}*
+
+// The finite-field DLP instance the ECDLP was mapped to, and its solution
+#[derive(Clone, Debug)]
+pub struct MovReduction {
+    pub embedding_degree: u32,
+    pub field: ExtensionField,
+    // α = e(P, S)
+    pub base: ExtElement,
+    // β = e(Q, S) = α^d
+    pub target: ExtElement,
+    pub discrete_log: BigUint,
+}
+
+// Solve Q = dP for P of prime order r, provided the embedding degree is at most max_k
+pub fn mov_reduce(
+    curve: &EllipticCurve,
+    point: &Point,
+    order: &BigUint,
+    public_point: &Point,
+    max_k: u32,
+) -> Result<MovReduction, &'static str> {
+    let k = embedding_degree(curve.p(), order, max_k).ok_or("embedding degree too large for the MOV reduction")?;
+
+    // Tate pairing into F_{p^k}; for k = 1 a quadratic extension still gives E[r] room
+    let field = ExtensionField::find(curve.p(), k.max(2) as usize);
+    let pairing = TatePairing::new(curve, order.clone(), field.clone());
+
+    // Walk through points of E(F_{p^k}) with x outside F_p until e(P, S) != 1
+    let field_size = field.order();
+    let mut index = curve.p().clone();
+    while index < field_size {
+        let x = field.element_from_index(&index);
+        index += 1u32;
+
+        let ext = &pairing.curve;
+        let x_cubed = field.mul(&field.mul(&x, &x), &x);
+        let rhs = field.add(&field.add(&x_cubed, &field.mul(&ext.a, &x)), &ext.b);
+        let Some(y) = field.sqrt(&rhs) else {
+            continue;
+        };
+        let s = ExtPoint::Coordinate(x, y);
+
+        let Ok(base) = pairing.pair(point, &s) else {
+            continue;
+        };
+        if base == field.one() {
+            continue;
+        }
+        let Ok(target) = pairing.pair(public_point, &s) else {
+            continue;
+        };
+
+        let discrete_log = field_discrete_log(&field, &base, &target, order)
+            .ok_or("target is not a power of the pairing value")?;
+
+        return Ok(MovReduction {
+            embedding_degree: k,
+            field,
+            base,
+            target,
+            discrete_log,
+        });
+    }
+
+    Err("no point S with a non-degenerate pairing value found")
+}
+
+// Baby-step giant-step in the order-r subgroup of F_{p^k}*: x with base^x = target
+fn field_discrete_log(field: &ExtensionField, base: &ExtElement, target: &ExtElement, order: &BigUint) -> Option<BigUint> {
+    let m = order.sqrt() + BigUint::from(1u32);
+
+    // Baby steps: base^j for 0 <= j < m
+    let mut baby_steps = HashMap::new();
+    let mut current = field.one();
+    let mut j = BigUint::from(0u32);
+    while j < m {
+        baby_steps.entry(current.coeffs.clone()).or_insert_with(|| j.clone());
+        current = field.mul(&current, base);
+        j += 1u32;
+    }
+
+    // Giant steps: target * base^(-im); a match with base^j gives x = im + j
+    let giant_step = field.inv(&field.pow(base, &m));
+    let mut gamma = target.clone();
+    let mut i = BigUint::from(0u32);
+    while i < m {
+        if let Some(j) = baby_steps.get(&gamma.coeffs) {
+            return Some((&i * &m + j) % order);
+        }
+        gamma = field.mul(&gamma, &giant_step);
+        i += 1u32;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mov_solves_supersingular_ecdlp() {
+        // y² = x³ + x over F_43 is supersingular: embedding degree 2
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(0u32), BigUint::from(43u32));
+        let point = Point::Coordinate(BigUint::from(4u32), BigUint::from(5u32));
+        let order = BigUint::from(11u32);
+
+        for d in [3u32, 7, 10] {
+            let public_point = curve.scalar_mult(&point, &BigUint::from(d));
+            let reduction = mov_reduce(&curve, &point, &order, &public_point, 6).unwrap();
+
+            assert_eq!(reduction.embedding_degree, 2);
+            assert_eq!(reduction.discrete_log, BigUint::from(d));
+            assert_eq!(reduction.field.pow(&reduction.base, &BigUint::from(d)), reduction.target);
+        }
+    }
+
+    #[test]
+    fn test_mov_rejects_large_embedding_degree() {
+        // The ECDSA demo curve has embedding degree 9 for r = 19
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        let public_point = curve.scalar_mult(&generator, &BigUint::from(5u32));
+
+        assert!(mov_reduce(&curve, &generator, &BigUint::from(19u32), &public_point, 6).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{EllipticCurve, FiniteField, Point};
+use num_bigint::BigUint;
+
+// Smart's attack: the ECDLP Q = dP on an anomalous curve (#E = p) in linear time
+//
+// Lift E, P and Q to a curve Ẽ over Z/p² (same b, a + kp, y-coordinates by Hensel). pP̃ reduces
+// to the identity mod p, so it lies in the kernel of reduction, which the p-adic elliptic log
+// ψ(x, y) = -x/y maps homomorphically into pZ/p²Z:
+//   ψ(pQ̃) = d ψ(pP̃)   so   d = (ψ(pQ̃)/p) / (ψ(pP̃)/p) mod p
+// pP̃ itself is at infinity mod p, but adding P̃ to (p - 1)P̃ = (x1, y1) only needs the slope
+// λ = (y1 - y) / (x1 - x) = N / (pu), and ψ = 1/λ + O(p³) gives ψ/p = u/N mod p.
+// If Ẽ happens to be the canonical lift, ψ(pP̃) = 0 and nothing is learned; another a fixes
+// that. The cost is two scalar multiplications mod p², which is why #E = p must be excluded:
+// security_report flags it
+
+// Lifts of a tried before giving up
+const MAX_LIFTS: u32 = 8;
+
+// y² = x³ + ax + b over Z/p², affine points only: the attack never leaves the units
+struct LiftedCurve<'a> {
+    ring: FiniteField,
+    p: &'a BigUint,
+    a: BigUint,
+    b: &'a BigUint,
+}
+
+impl LiftedCurve<'_> {
+    // (x, y + tp) with t chosen so the equation holds mod p²; y != 0 on an odd-order curve
+    fn lift(&self, point: &Point) -> Option<(BigUint, BigUint)> {
+        let Point::Coordinate(x, y) = point else { return None };
+        let ring = &self.ring;
+        let rhs = ring.add(&ring.add(&ring.mul(&ring.mul(x, x), x), &ring.mul(&self.a, x)), self.b);
+        let error = ring.sub(&ring.mul(y, y), &rhs) / self.p;
+        let field = FiniteField { p: self.p.clone() };
+        let t = field.div(&field.sub(&BigUint::from(0u32), &error), &field.add(y, y));
+        Some((x.clone(), y + t * self.p))
+    }
+
+    fn add(&self, a: &Point, b: &Point) -> Result<Point, &'static str> {
+        let ring = &self.ring;
+        let ((x1, y1), (x2, y2)) = match (a, b) {
+            (Point::Identity, _) => return Ok(b.clone()),
+            (_, Point::Identity) => return Ok(a.clone()),
+            (Point::Coordinate(x1, y1), Point::Coordinate(x2, y2)) => ((x1, y1), (x2, y2)),
+        };
+        let slope = if x1 == x2 && y1 == y2 {
+            let numerator = ring.add(&ring.mul(&BigUint::from(3u32), &ring.mul(x1, x1)), &self.a);
+            ring.mul(&numerator, &ring.invert(&ring.add(y1, y1)).ok_or("?doubling a non-unit y?")?)
+        } else {
+            let denominator = ring.invert(&ring.sub(x2, x1)).ok_or("?points agree mod p?")?;
+            ring.mul(&ring.sub(y2, y1), &denominator)
+        };
+        let x3 = ring.sub(&ring.sub(&ring.mul(&slope, &slope), x1), x2);
+        let y3 = ring.sub(&ring.mul(&slope, &ring.sub(x1, &x3)), y1);
+        Ok(Point::Coordinate(x3, y3))
+    }
+
+    fn mul(&self, point: &Point, k: &BigUint) -> Result<Point, &'static str> {
+        let mut result = Point::Identity;
+        for i in (0..k.bits()).rev() {
+            result = self.add(&result, &result)?;
+            if k.bit(i) {
+                result = self.add(&result, point)?;
+            }
+        }
+        Ok(result)
+    }
+
+    // ψ(pP̃) / p mod p
+    fn elliptic_log(&self, point: &Point) -> Result<BigUint, &'static str> {
+        let (x, y) = self.lift(point).ok_or("?lifting the identity?")?;
+        let Point::Coordinate(x1, y1) = self.mul(&Point::Coordinate(x.clone(), y.clone()), &(self.p - 1u32))? else {
+            return Err("?(p - 1)P is the identity?");
+        };
+        let field = FiniteField { p: self.p.clone() };
+        let u = self.ring.sub(&x1, &x) / self.p;
+        Ok(field.div(&u, &(self.ring.sub(&y1, &y) % self.p)))
+    }
+}
+
+// d with Q = dP, for P of order p (so #E = p); fails on any other curve
+pub fn smart_attack(curve: &EllipticCurve, point: &Point, public_point: &Point) -> Result<BigUint, &'static str> {
+    let p = curve.p();
+    if *point == Point::Identity || curve.scalar_mult(point, p) != Point::Identity {
+        return Err("point order is not p: the curve is not anomalous");
+    }
+    if !curve.is_on_curve(public_point) {
+        return Err("public point is not on the curve");
+    }
+    if *public_point == Point::Identity {
+        return Ok(BigUint::from(0u32));
+    }
+
+    let field = FiniteField { p: p.clone() };
+    for k in 0..MAX_LIFTS {
+        let lifted = LiftedCurve { ring: FiniteField { p: p * p }, p, a: curve.a() + p * k, b: curve.b() };
+        let log_point = lifted.elliptic_log(point)?;
+        if log_point == BigUint::from(0u32) {
+            continue;
+        }
+        let d = field.div(&lifted.elliptic_log(public_point)?, &log_point);
+        if curve.scalar_mult(point, &d) == *public_point {
+            return Ok(d);
+        }
+    }
+    Err("no lift of the curve gave a usable p-adic logarithm")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::Entropy;
+    use num_bigint::RandBigInt;
+
+    fn hex(s: &str) -> BigUint {
+        BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+    }
+
+    #[test]
+    fn test_small_anomalous_curve() {
+        // y² = x³ + 5x + 33 over F_1019 has exactly 1019 points
+        let curve = EllipticCurve::new(BigUint::from(5u32), BigUint::from(33u32), BigUint::from(1019u32));
+        let order = curve.count_points().unwrap();
+        assert!(curve.security_report(&order).unwrap().anomalous);
+
+        let point = curve.random_point(&mut Entropy).unwrap();
+        for d in [1u32, 2, 500, 1018] {
+            let public_point = curve.scalar_mult(&point, &BigUint::from(d));
+            assert_eq!(smart_attack(&curve, &point, &public_point), Ok(BigUint::from(d)));
+        }
+        assert_eq!(smart_attack(&curve, &point, &Point::Identity), Ok(BigUint::from(0u32)));
+    }
+
+    #[test]
+    fn test_large_anomalous_curve() {
+        // 126 bits, 4p = 1 + 163v² (j = -640320³ reduced mod p): far beyond rho, solved instantly
+        let p = hex("28c00000000000051800000000000029");
+        let curve = EllipticCurve::new(hex("138602d1a693f7ba6a91fab0b3888124"), hex("270c05a34d27ef74d523f56167110248"), p.clone());
+        let point = curve.lift_x(&BigUint::from(6u32)).unwrap().0;
+        assert_eq!(curve.scalar_mult(&point, &p), Point::Identity);
+
+        let d = Entropy.gen_biguint_below(&p);
+        let public_point = curve.scalar_mult(&point, &d);
+        assert_eq!(smart_attack(&curve, &point, &public_point), Ok(d));
+    }
+
+    #[test]
+    fn test_rejects_other_curves() {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        let public_point = curve.scalar_mult(&generator, &BigUint::from(7u32));
+        assert!(smart_attack(&curve, &generator, &public_point).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use elliptic_curve::curves;
+use elliptic_curve::encoding;
+use elliptic_curve::pkcs8::Pbes2Params;
+use elliptic_curve::secret_key::SecretKey;
+use elliptic_curve::sigfile::DetachedSignature;
+use elliptic_curve::ssh::{SshPrivateKey, SshPublicKey};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+// ec keygen [-c <curve>] -o <private key file>
+// ec sign-file -k <openssh private key> [-x <signature file>] [-t <trusted comment>] <file>
+// ec verify-file -p <openssh public key> [-x <signature file>] <file>
+//
+// keygen writes a PKCS#8 PEM key (P-256 unless -c names another curve), encrypted with PBES2
+// (scrypt, AES-256-CBC) when $EC_PASSPHRASE is set, and prints the compressed public key.
+// Detached signatures in the sigfile.rs format, next to the file as <file>.sig by default.
+// An encrypted private key is unlocked with $EC_PASSPHRASE. The default trusted comment is
+// minisign's "timestamp:<unix time>\tfile:<name>"
+
+const USAGE: &str = "usage:
+  ec keygen [-c <curve>] -o <private key file>
+  ec sign-file -k <private key> [-x <signature file>] [-t <trusted comment>] <file>
+  ec verify-file -p <public key> [-x <signature file>] <file>";
+
+struct Args {
+    key: String,
+    signature_file: Option<String>,
+    trusted_comment: Option<String>,
+    file: String,
+}
+
+// key_flag is -k for sign-file and -p for verify-file; -t only makes sense when signing
+fn parse_args(args: &[String], key_flag: &str) -> Result<Args, String> {
+    let (mut key, mut signature_file, mut trusted_comment, mut file) = (None, None, None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            flag if flag == key_flag => key = Some(value()?),
+            "-x" => signature_file = Some(value()?),
+            "-t" if key_flag == "-k" => trusted_comment = Some(value()?),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {}", flag)),
+            _ if file.is_none() => file = Some(arg.clone()),
+            _ => return Err("only one file can be given".to_string()),
+        }
+    }
+    Ok(Args {
+        key: key.ok_or(format!("{} <key> is required", key_flag))?,
+        signature_file,
+        trusted_comment,
+        file: file.ok_or("no file given")?,
+    })
+}
+
+fn keygen(args: &[String]) -> Result<(), String> {
+    let (mut curve, mut output) = (curves::p256(), None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "-c" => {
+                let name = value()?;
+                curve = curves::by_name(&name).ok_or(format!("unknown curve {}", name))?;
+            }
+            "-o" => output = Some(value()?),
+            other => return Err(format!("unknown option {}", other)),
+        }
+    }
+    let output = output.ok_or("-o <private key file> is required")?;
+
+    let key = SecretKey::random(&curve);
+    let pem = match env::var("EC_PASSPHRASE") {
+        Ok(passphrase) => key.to_encrypted_pkcs8_pem(&passphrase, &Pbes2Params::default())?,
+        Err(_) => key.to_pkcs8_pem(),
+    };
+    write_private(&output, &pem)?;
+    println!("wrote {}", output);
+    println!("public key: {}", encoding::to_hex(&encoding::encode_point(&curve.curve, &key.public_key(), true)));
+    Ok(())
+}
+
+// Readable by the owner only, where the platform has modes
+fn write_private(path: &str, contents: &str) -> Result<(), String> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path).map_err(|e| format!("{}: {}", path, e))?;
+    std::io::Write::write_all(&mut file, contents.as_bytes()).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn read(path: &str) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn read_text(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn sign_file(args: Args) -> Result<(), String> {
+    let passphrase = env::var("EC_PASSPHRASE").ok();
+    let (key, _) = SshPrivateKey::from_openssh(&read_text(&args.key)?, passphrase.as_deref()).map_err(|e| format!("{}: {}", args.key, e))?;
+    let message = read(&args.file)?;
+
+    let trusted_comment = args.trusted_comment.unwrap_or_else(|| {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let name = Path::new(&args.file).file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        format!("timestamp:{}\tfile:{}", timestamp, name)
+    });
+    let untrusted_comment = format!("signature from ec, key {}", key.public_key().fingerprint());
+    let signature = DetachedSignature::sign(&key, &message, &trusted_comment, &untrusted_comment)?;
+
+    let signature_file = args.signature_file.unwrap_or(format!("{}.sig", args.file));
+    fs::write(&signature_file, signature.to_text()).map_err(|e| format!("{}: {}", signature_file, e))?;
+    println!("wrote {}", signature_file);
+    Ok(())
+}
+
+fn verify_file(args: Args) -> Result<(), String> {
+    let (public_key, _) = SshPublicKey::from_openssh(&read_text(&args.key)?).map_err(|e| format!("{}: {}", args.key, e))?;
+    let signature_file = args.signature_file.unwrap_or(format!("{}.sig", args.file));
+    let signature = DetachedSignature::from_text(&read_text(&signature_file)?).map_err(|e| format!("{}: {}", signature_file, e))?;
+    signature.verify(&public_key, &read(&args.file)?)?;
+    println!("signature and comment signature verified");
+    println!("trusted comment: {}", signature.trusted_comment);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("keygen") => keygen(&args[1..]),
+        Some("sign-file") => parse_args(&args[1..], "-k").and_then(sign_file),
+        Some("verify-file") => parse_args(&args[1..], "-p").and_then(verify_file),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("ec: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
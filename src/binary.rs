@@ -0,0 +1,282 @@
+use crate::Point;
+use num_bigint::BigUint;
+
+// Binary fields GF(2^m) and the curves y² + xy = x³ + a x² + b over them
+//
+// Elements are polynomials over GF(2) of degree < m, stored as the bits of a BigUint in the
+// polynomial basis: addition is XOR (so every element is its own negative and there is no
+// carry), multiplication is carry-less and then reduced by an irreducible trinomial or
+// pentanomial f(x). Inverses come from the extended Euclidean algorithm on polynomials.
+//
+// Characteristic 2 changes the curve equation and its formulas:
+//   -P = (x, x + y)
+//   P + Q:  λ = (y1 + y2) / (x1 + x2),  x3 = λ² + λ + x1 + x2 + a,  y3 = λ (x1 + x3) + x3 + y1
+//   2P:     λ = x1 + y1 / x1,           x3 = λ² + λ + a,            y3 = x1² + (λ + 1) x3
+// The points with x = 0 have order 2 and double to the identity.
+// Koblitz curves (a, b in {0, 1}) admit the Frobenius map (x, y) -> (x², y²) for fast
+// multiplication; K-163/233/283 are the SEC 2 sect163k1/sect233k1/sect283k1 curves
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryField {
+    pub m: u64,
+    pub modulus: BigUint,
+}
+
+impl BinaryField {
+    // From the exponents of the reduction polynomial, e.g. [163, 7, 6, 3, 0]
+    pub fn new(exponents: &[u64]) -> Self {
+        let mut modulus = BigUint::from(0u32);
+        for &e in exponents {
+            modulus.set_bit(e, true);
+        }
+        BinaryField { m: modulus.bits() - 1, modulus }
+    }
+
+    pub fn add(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        x ^ y
+    }
+
+    // Clear every bit at or above m by subtracting (XORing) shifted copies of f
+    fn reduce(&self, mut x: BigUint) -> BigUint {
+        while x.bits() > self.m {
+            let shift = x.bits() - 1 - self.m;
+            x ^= &self.modulus << shift;
+        }
+        x
+    }
+
+    pub fn mul(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        let mut product = BigUint::from(0u32);
+        for i in 0..y.bits() {
+            if y.bit(i) {
+                product ^= x << i;
+            }
+        }
+        self.reduce(product)
+    }
+
+    pub fn square(&self, x: &BigUint) -> BigUint {
+        self.mul(x, x)
+    }
+
+    pub fn inv(&self, x: &BigUint) -> BigUint {
+        let zero = BigUint::from(0u32);
+        let one = BigUint::from(1u32);
+        let x = self.reduce(x.clone());
+        if x == zero {
+            panic!("?division by zero in binary field?");
+        }
+
+        // Invariants: g1 x ≡ u and g2 x ≡ v (mod f); cancel the top of u until it is 1
+        let (mut u, mut v) = (x, self.modulus.clone());
+        let (mut g1, mut g2) = (one.clone(), zero);
+        while u != one {
+            if u.bits() < v.bits() {
+                std::mem::swap(&mut u, &mut v);
+                std::mem::swap(&mut g1, &mut g2);
+            }
+            let shift = u.bits() - v.bits();
+            u ^= &v << shift;
+            g1 ^= &g2 << shift;
+        }
+        self.reduce(g1)
+    }
+
+    pub fn div(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        self.mul(x, &self.inv(y))
+    }
+}
+
+// y² + xy = x³ + a x² + b over GF(2^m), b ≠ 0
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryCurve {
+    pub a: BigUint,
+    pub b: BigUint,
+    pub field: BinaryField,
+}
+
+impl BinaryCurve {
+    pub fn is_on_curve(&self, point: &Point) -> bool {
+        match point {
+            Point::Identity => true,
+            Point::Coordinate(x, y) => {
+                let f = &self.field;
+                let x_squared = f.square(x);
+                let lhs = f.add(&f.square(y), &f.mul(x, y));
+                let rhs = f.add(&f.add(&f.mul(&x_squared, x), &f.mul(&self.a, &x_squared)), &self.b);
+                lhs == rhs
+            }
+        }
+    }
+
+    pub fn neg(&self, point: &Point) -> Point {
+        match point {
+            Point::Identity => Point::Identity,
+            Point::Coordinate(x, y) => Point::Coordinate(x.clone(), self.field.add(x, y)),
+        }
+    }
+
+    pub fn add(&self, p: &Point, q: &Point) -> Point {
+        match (p, q) {
+            (Point::Identity, _) => q.clone(),
+            (_, Point::Identity) => p.clone(),
+            (Point::Coordinate(x1, y1), Point::Coordinate(x2, y2)) => {
+                if x1 == x2 {
+                    // Same x: either Q = P or Q = -P
+                    return if y1 == y2 { self.double(p) } else { Point::Identity };
+                }
+                let f = &self.field;
+                let slope = f.div(&f.add(y1, y2), &f.add(x1, x2));
+                let x3 = f.add(&f.add(&f.add(&f.square(&slope), &slope), &f.add(x1, x2)), &self.a);
+                let y3 = f.add(&f.add(&f.mul(&slope, &f.add(x1, &x3)), &x3), y1);
+                Point::Coordinate(x3, y3)
+            }
+        }
+    }
+
+    pub fn double(&self, p: &Point) -> Point {
+        match p {
+            Point::Identity => Point::Identity,
+            Point::Coordinate(x, y) => {
+                if *x == BigUint::from(0u32) {
+                    return Point::Identity;
+                }
+                let f = &self.field;
+                let slope = f.add(x, &f.div(y, x));
+                let x3 = f.add(&f.add(&f.square(&slope), &slope), &self.a);
+                let y3 = f.add(&f.square(x), &f.mul(&f.add(&slope, &BigUint::from(1u32)), &x3));
+                Point::Coordinate(x3, y3)
+            }
+        }
+    }
+
+    pub fn scalar_mult(&self, point: &Point, k: &BigUint) -> Point {
+        let mut result = Point::Identity;
+        for i in (0..k.bits()).rev() {
+            result = self.double(&result);
+            if k.bit(i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    // Frobenius endomorphism (x, y) -> (x², y²), a group homomorphism on Koblitz curves
+    pub fn frobenius(&self, point: &Point) -> Point {
+        match point {
+            Point::Identity => Point::Identity,
+            Point::Coordinate(x, y) => Point::Coordinate(self.field.square(x), self.field.square(y)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedBinaryCurve {
+    pub name: &'static str,
+    pub curve: BinaryCurve,
+    pub generator: Point,
+    pub order: BigUint,
+    pub cofactor: BigUint,
+}
+
+fn hex(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 16).expect("?invalid curve constant?")
+}
+
+fn koblitz(name: &'static str, exponents: &[u64], a: u32, gx: &str, gy: &str, n: &str, h: u32) -> NamedBinaryCurve {
+    NamedBinaryCurve {
+        name,
+        curve: BinaryCurve {
+            a: BigUint::from(a),
+            b: BigUint::from(1u32),
+            field: BinaryField::new(exponents),
+        },
+        generator: Point::Coordinate(hex(gx), hex(gy)),
+        order: hex(n),
+        cofactor: BigUint::from(h),
+    }
+}
+
+// K-163 / sect163k1: f = x^163 + x^7 + x^6 + x^3 + 1, a = 1
+pub fn sect163k1() -> NamedBinaryCurve {
+    koblitz(
+        "sect163k1",
+        &[163, 7, 6, 3, 0],
+        1,
+        "02fe13c0537bbc11acaa07d793de4e6d5e5c94eee8",
+        "0289070fb05d38ff58321f2e800536d538ccdaa3d9",
+        "04000000000000000000020108a2e0cc0d99f8a5ef",
+        2,
+    )
+}
+
+// K-233 / sect233k1: f = x^233 + x^74 + 1, a = 0
+pub fn sect233k1() -> NamedBinaryCurve {
+    koblitz(
+        "sect233k1",
+        &[233, 74, 0],
+        0,
+        "017232ba853a7e731af129f22ff4149563a419c26bf50a4c9d6eefad6126",
+        "01db537dece819b7f70f555a67c427a8cd9bf18aeb9b56e0c11056fae6a3",
+        "8000000000000000000000000000069d5bb915bcd46efb1ad5f173abdf",
+        4,
+    )
+}
+
+// K-283 / sect283k1: f = x^283 + x^12 + x^7 + x^5 + 1, a = 0
+pub fn sect283k1() -> NamedBinaryCurve {
+    koblitz(
+        "sect283k1",
+        &[283, 12, 7, 5, 0],
+        0,
+        "0503213f78ca44883f1a3b8162f188e553cd265f23c1567a16876913b0c2ac2458492836",
+        "01ccda380f1c9e318d90f95d07e5426fe87e45c0e8184698e45962364e34116177dd2259",
+        "01ffffffffffffffffffffffffffffffffffe9ae2ed07577265dff7f94451e061e163c61",
+        4,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_arithmetic() {
+        // GF(2^4) with x^4 + x + 1: x^3 * x = x + 1, and (x^3 + 1)^-1 = x
+        let f = BinaryField::new(&[4, 1, 0]);
+        assert_eq!(f.m, 4);
+        assert_eq!(f.mul(&BigUint::from(0b1000u32), &BigUint::from(0b10u32)), BigUint::from(0b11u32));
+        assert_eq!(f.inv(&BigUint::from(0b1001u32)), BigUint::from(0b10u32));
+        for x in 1u32..16 {
+            let x = BigUint::from(x);
+            assert_eq!(f.mul(&x, &f.inv(&x)), BigUint::from(1u32));
+            assert_eq!(f.add(&x, &x), BigUint::from(0u32));
+        }
+    }
+
+    #[test]
+    fn test_koblitz_curves() {
+        for named in [sect163k1(), sect233k1(), sect283k1()] {
+            let curve = &named.curve;
+            let g = &named.generator;
+            assert!(curve.is_on_curve(g), "{}", named.name);
+            assert_eq!(curve.scalar_mult(g, &named.order), Point::Identity, "{}", named.name);
+            assert_eq!(curve.add(g, &curve.neg(g)), Point::Identity);
+            assert!(curve.is_on_curve(&curve.frobenius(g)));
+        }
+    }
+
+    #[test]
+    fn test_diffie_hellman_on_k163() {
+        let named = sect163k1();
+        let curve = &named.curve;
+        let (a, b) = (BigUint::from(0xdeadbeefu32), BigUint::from(0x1234567u32));
+        let a_pub = curve.scalar_mult(&named.generator, &a);
+        let b_pub = curve.scalar_mult(&named.generator, &b);
+        assert_eq!(curve.scalar_mult(&b_pub, &a), curve.scalar_mult(&a_pub, &b));
+
+        // 3G by doubling and adding agrees with 3G by scalar multiplication
+        let g = &named.generator;
+        assert_eq!(curve.add(&curve.double(g), g), curve.scalar_mult(g, &BigUint::from(3u32)));
+    }
+}
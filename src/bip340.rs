@@ -0,0 +1,216 @@
+use crate::{FiniteField, Point};
+use crate::curves::{self, DomainParams};
+use crate::encoding::to_fixed_bytes;
+use crate::entropy::Entropy;
+use num_bigint::BigUint;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+// BIP-340 Schnorr signatures over secp256k1 (Bitcoin Taproot)
+//
+// Differences from the generic Schnorr module:
+//   public keys and R are x-only (32 bytes); the implied point is the one with even y,
+//   so signing negates d or k whenever its point has odd y
+//   all hashes are tagged: SHA256(SHA256(tag) || SHA256(tag) || data)
+//   nonces are derived from the key, message and auxiliary randomness
+//   signature = x(R) || s, verified as s * G - e * P having even y and x = x(R)
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bip340Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+impl Bip340Signature {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = to_fixed_bytes(&self.r, 32);
+        bytes.extend(to_fixed_bytes(&self.s, 32));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != 64 {
+            return Err("BIP-340 signatures are 64 bytes");
+        }
+        Ok(Bip340Signature {
+            r: BigUint::from_bytes_be(&bytes[..32]),
+            s: BigUint::from_bytes_be(&bytes[32..]),
+        })
+    }
+}
+
+// SHA256(SHA256(tag) || SHA256(tag) || data)
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    Sha256::new()
+        .chain_update(tag_hash)
+        .chain_update(tag_hash)
+        .chain_update(data)
+        .finalize()
+        .into()
+}
+
+pub struct Bip340 {
+    pub params: DomainParams,
+}
+
+impl Bip340 {
+    pub fn new() -> Self {
+        Bip340 { params: curves::secp256k1().params() }
+    }
+
+    // x-only public key: x(d * G)
+    pub fn public_key(&self, private_key: &BigUint) -> Result<BigUint, &'static str> {
+        if *private_key == BigUint::from(0u32) || *private_key >= self.params.order {
+            return Err("private key out of range");
+        }
+        match self.params.generate_public_key(private_key) {
+            Point::Coordinate(x, _) => Ok(x),
+            Point::Identity => Err("private key out of range"),
+        }
+    }
+
+    // The point with x-coordinate x and even y
+    pub fn lift_x(&self, x: &BigUint) -> Result<Point, &'static str> {
+        if x >= self.params.curve.p() {
+            return Err("x is not a field element");
+        }
+        let field = self.params.curve.field();
+        let rhs = field.add(&field.mul(&field.mul(x, x), x), self.params.curve.b());
+        let y = field.sqrt(&rhs).ok_or("x is not on the curve")?;
+        let y = if y.bit(0) { field.sub(&BigUint::from(0u32), &y) } else { y };
+        Ok(Point::Coordinate(x.clone(), y))
+    }
+
+    // e = int(hash_BIP0340/challenge(x(R) || x(P) || m)) mod n
+    pub fn challenge(&self, r: &BigUint, public_key: &BigUint, message: &[u8]) -> BigUint {
+        let mut data = to_fixed_bytes(r, 32);
+        data.extend(to_fixed_bytes(public_key, 32));
+        data.extend_from_slice(message);
+        BigUint::from_bytes_be(&tagged_hash("BIP0340/challenge", &data)) % &self.params.order
+    }
+
+    // Sign with fresh auxiliary randomness
+    pub fn sign(&self, message: &[u8], private_key: &BigUint) -> Result<Bip340Signature, &'static str> {
+        let mut aux = [0u8; 32];
+        Entropy.fill_bytes(&mut aux);
+        self.sign_with_aux(message, private_key, &aux)
+    }
+
+    // Sign with caller-supplied auxiliary randomness (all zeros is still secure, just deterministic)
+    pub fn sign_with_aux(&self, message: &[u8], private_key: &BigUint, aux_rand: &[u8; 32]) -> Result<Bip340Signature, &'static str> {
+        let field = FiniteField { p: self.params.order.clone() };
+        if *private_key == BigUint::from(0u32) || *private_key >= self.params.order {
+            return Err("private key out of range");
+        }
+        let Point::Coordinate(px, py) = self.params.generate_public_key(private_key) else {
+            unreachable!("d is in [1, n-1]")
+        };
+        let d = if py.bit(0) { field.sub(&BigUint::from(0u32), private_key) } else { private_key.clone() };
+
+        // t = d xor hash_BIP0340/aux(a), k' = hash_BIP0340/nonce(t || x(P) || m) mod n
+        let aux_hash = tagged_hash("BIP0340/aux", aux_rand);
+        let mut nonce_input: Vec<u8> = to_fixed_bytes(&d, 32).iter().zip(aux_hash.iter()).map(|(a, b)| a ^ b).collect();
+        nonce_input.extend(to_fixed_bytes(&px, 32));
+        nonce_input.extend_from_slice(message);
+        let k0 = BigUint::from_bytes_be(&tagged_hash("BIP0340/nonce", &nonce_input)) % &self.params.order;
+        if k0 == BigUint::from(0u32) {
+            return Err("derived nonce is zero");
+        }
+
+        let Point::Coordinate(rx, ry) = self.params.generate_public_key(&k0) else {
+            unreachable!("k' is in [1, n-1]")
+        };
+        let k = if ry.bit(0) { field.sub(&BigUint::from(0u32), &k0) } else { k0 };
+        let e = self.challenge(&rx, &px, message);
+        let s = field.add(&k, &field.mul(&e, &d));
+        Ok(Bip340Signature { r: rx, s })
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &Bip340Signature, public_key: &BigUint) -> bool {
+        let Ok(point) = self.lift_x(public_key) else {
+            return false;
+        };
+        if signature.r >= *self.params.curve.p() || signature.s >= self.params.order {
+            return false;
+        }
+
+        // R = s * G - e * P
+        let e = self.challenge(&signature.r, public_key, message);
+        let s_g = self.params.curve.scalar_mult(&self.params.generator, &signature.s);
+        let e_p = self.params.curve.scalar_mult(&point, &e);
+        match self.params.curve.add(&s_g, &self.params.curve.neg(&e_p)) {
+            Point::Coordinate(x, y) => !y.bit(0) && x == signature.r,
+            Point::Identity => false,
+        }
+    }
+}
+
+impl Default for Bip340 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::from_hex;
+
+    fn hex(s: &str) -> BigUint {
+        BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+    }
+
+    #[test]
+    fn test_bip340_vectors() {
+        // BIP-340 test vectors 0 and 1
+        let bip340 = Bip340::new();
+        let cases = [
+            (
+                "3",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+            ),
+            (
+                "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+            ),
+        ];
+
+        for (secret, aux, message, public_key, signature) in cases {
+            let private_key = hex(secret);
+            let aux: [u8; 32] = from_hex(aux).unwrap().try_into().unwrap();
+            let message = from_hex(message).unwrap();
+
+            let pk = bip340.public_key(&private_key).unwrap();
+            assert_eq!(pk, hex(public_key));
+
+            let sig = bip340.sign_with_aux(&message, &private_key, &aux).unwrap();
+            assert_eq!(sig.to_bytes(), from_hex(signature).unwrap());
+            assert!(bip340.verify(&message, &sig, &pk));
+        }
+    }
+
+    #[test]
+    fn test_bip340_rejects_tampering() {
+        let bip340 = Bip340::new();
+        let private_key = BigUint::from(0xc0ffeeu32);
+        let pk = bip340.public_key(&private_key).unwrap();
+        let sig = bip340.sign(b"message", &private_key).unwrap();
+        assert!(bip340.verify(b"message", &sig, &pk));
+        assert!(!bip340.verify(b"massage", &sig, &pk));
+
+        let roundtrip = Bip340Signature::from_bytes(&sig.to_bytes()).unwrap();
+        assert_eq!(roundtrip, sig);
+
+        let mut bad = sig.clone();
+        bad.s = &bip340.params.order - &bad.s;
+        assert!(!bip340.verify(b"message", &bad, &pk));
+        assert!(Bip340Signature::from_bytes(&[0u8; 63]).is_err());
+    }
+}
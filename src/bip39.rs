@@ -0,0 +1,157 @@
+use crate::entropy::Entropy;
+use rand::RngCore;
+use sha2::{Digest, Sha256, Sha512};
+
+// BIP-39 mnemonics (English wordlist)
+//
+// Entropy of 128-256 bits (a multiple of 32) gets a checksum of entropy_bits / 32 bits,
+// the first bits of SHA-256(entropy), appended; every 11 bits then pick one of 2048 words,
+// so 128 bits -> 12 words and 256 bits -> 24 words.
+// The seed is PBKDF2-HMAC-SHA512(mnemonic, "mnemonic" || passphrase, 2048 rounds, 64 bytes),
+// which feeds hd::Bip32::master_key. BIP-39 NFKD-normalizes both strings; the English words
+// are plain ASCII, and passphrases are used as given, so non-ASCII ones must arrive normalized
+
+const WORDLIST: &str = include_str!("bip39_english.txt");
+const PBKDF2_ROUNDS: u32 = 2048;
+
+fn words() -> impl Iterator<Item = &'static str> {
+    WORDLIST.lines()
+}
+
+fn word_index(word: &str) -> Option<usize> {
+    words().position(|w| w == word)
+}
+
+pub fn entropy_to_mnemonic(entropy: &[u8]) -> Result<String, &'static str> {
+    if entropy.len() < 16 || entropy.len() > 32 || !entropy.len().is_multiple_of(4) {
+        return Err("entropy must be 16, 20, 24, 28 or 32 bytes");
+    }
+    let checksum_bits = entropy.len() / 4;
+    let hash = Sha256::digest(entropy);
+    let mut bits: Vec<bool> = entropy.iter().flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1 == 1)).collect();
+    bits.extend((0..checksum_bits).map(|i| hash[i / 8] >> (7 - i % 8) & 1 == 1));
+
+    let wordlist: Vec<&str> = words().collect();
+    let mnemonic: Vec<&str> = bits
+        .chunks(11)
+        .map(|chunk| wordlist[chunk.iter().fold(0usize, |acc, &bit| acc << 1 | bit as usize)])
+        .collect();
+    Ok(mnemonic.join(" "))
+}
+
+// Random mnemonic of 12, 15, 18, 21 or 24 words
+pub fn generate_mnemonic(word_count: usize) -> Result<String, &'static str> {
+    if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+        return Err("mnemonics have 12, 15, 18, 21 or 24 words");
+    }
+    let mut entropy = vec![0u8; word_count * 4 / 3];
+    Entropy.fill_bytes(&mut entropy);
+    entropy_to_mnemonic(&entropy)
+}
+
+// Entropy back from the words, checking every word and the checksum
+pub fn mnemonic_to_entropy(mnemonic: &str) -> Result<Vec<u8>, &'static str> {
+    let indices = mnemonic
+        .split_whitespace()
+        .map(|word| word_index(word).ok_or("word not in the BIP-39 wordlist"))
+        .collect::<Result<Vec<usize>, _>>()?;
+    if !matches!(indices.len(), 12 | 15 | 18 | 21 | 24) {
+        return Err("mnemonics have 12, 15, 18, 21 or 24 words");
+    }
+
+    let bits: Vec<bool> = indices.iter().flat_map(|&index| (0..11).rev().map(move |i| index >> i & 1 == 1)).collect();
+    let checksum_bits = bits.len() / 33;
+    let (entropy_bits, checksum) = bits.split_at(bits.len() - checksum_bits);
+    let entropy: Vec<u8> = entropy_bits
+        .chunks(8)
+        .map(|chunk| chunk.iter().fold(0u8, |acc, &bit| acc << 1 | bit as u8))
+        .collect();
+
+    let hash = Sha256::digest(&entropy);
+    if (0..checksum_bits).any(|i| (hash[i / 8] >> (7 - i % 8) & 1 == 1) != checksum[i]) {
+        return Err("mnemonic checksum mismatch");
+    }
+    Ok(entropy)
+}
+
+pub fn validate_mnemonic(mnemonic: &str) -> bool {
+    mnemonic_to_entropy(mnemonic).is_ok()
+}
+
+// 64-byte seed; the mnemonic is validated first, with words rejoined by single spaces
+pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> Result<[u8; 64], &'static str> {
+    mnemonic_to_entropy(mnemonic)?;
+    let normalized = mnemonic.split_whitespace().collect::<Vec<_>>().join(" ");
+    let salt = format!("mnemonic{}", passphrase);
+    let mut seed = [0u8; 64];
+    pbkdf2::pbkdf2_hmac::<Sha512>(normalized.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+    Ok(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_hex;
+    use crate::hd::Bip32;
+    use crate::curves;
+
+    #[test]
+    fn test_wordlist() {
+        assert_eq!(words().count(), 2048);
+        assert_eq!(word_index("abandon"), Some(0));
+        assert_eq!(word_index("zoo"), Some(2047));
+    }
+
+    #[test]
+    fn test_trezor_vectors() {
+        let vectors = [
+            (
+                vec![0u8; 16],
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                vec![0x7f; 16],
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+            (
+                vec![0xff; 32],
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+                "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+            ),
+        ];
+        for (entropy, mnemonic, seed) in vectors {
+            assert_eq!(entropy_to_mnemonic(&entropy).unwrap(), mnemonic);
+            assert_eq!(mnemonic_to_entropy(mnemonic).unwrap(), entropy);
+            assert_eq!(to_hex(&mnemonic_to_seed(mnemonic, "TREZOR").unwrap()), seed);
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_mnemonics() {
+        // Last word changed: valid words, wrong checksum
+        assert!(!validate_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"));
+        assert!(!validate_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon satoshi"));
+        assert!(!validate_mnemonic("abandon about"));
+        assert!(entropy_to_mnemonic(&[0u8; 15]).is_err());
+        assert!(generate_mnemonic(13).is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_to_signing_key() {
+        // 24 words -> seed -> BIP-44 key -> ECDSA signature on secp256k1
+        let mnemonic = generate_mnemonic(24).unwrap();
+        assert_eq!(mnemonic.split(' ').count(), 24);
+        assert!(validate_mnemonic(&mnemonic));
+
+        let bip32 = Bip32::new();
+        let seed = mnemonic_to_seed(&mnemonic, "").unwrap();
+        let key = bip32.derive_path(&bip32.master_key(&seed).unwrap(), "m/44'/0'/0'/0/0").unwrap();
+        let public_key = bip32.neuter(&key).public_key;
+
+        let ecdsa = curves::secp256k1().ecdsa();
+        let signature = ecdsa.sign(b"spend", &key.private_key).unwrap();
+        assert!(ecdsa.verify(b"spend", &signature, &public_key));
+    }
+}
@@ -0,0 +1,226 @@
+use crate::edwards::{EdwardsCurve, EdwardsPoint};
+use crate::montgomery::MontgomeryCurve;
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+
+// Maps between the three curve models
+//
+//   short Weierstrass  y² = x³ + a x + b
+//   Montgomery         B v² = u³ + A u² + u
+//   twisted Edwards    a x² + y² = 1 + d x² y²
+//
+// Every Montgomery curve has a Weierstrass form: x = (u + A/3) / B, y = v / B, with
+//   a = (3 - A²) / (3 B²),  b = (2 A³ - 9 A) / (27 B³)
+// The converse needs a point of order 2 (a root α of x³ + a x + b) with 3 α² + a a square s⁻²:
+// then B = s, A = 3 α s and the same formulas run backwards. Prime-order curves such as P-256
+// and secp256k1 have no 2-torsion, so they have no Montgomery (or Edwards) form.
+//
+// Montgomery and twisted Edwards curves correspond one-to-one: a = (A + 2) / B, d = (A - 2) / B,
+//   (u, v) -> (x, y) = (u / v, (u - 1) / (u + 1)),  (x, y) -> (u, v) = ((1 + y) / (1 - y), u / x)
+// The identity and (0, 0) go to (0, 1) and (0, -1). Points with v = 0 or u = -1 land at
+// infinity on the Edwards side, which only happens when d is a square (incomplete addition).
+// Maps between points are group homomorphisms, so scalar multiples carry across.
+// The Weierstrass formulas divide by 3, so p > 3 is assumed throughout
+
+pub fn montgomery_to_weierstrass_curve(curve: &MontgomeryCurve) -> EllipticCurve {
+    let f = curve.field();
+    let (a, b) = (curve.a(), curve.b());
+    let n = |k: u32| BigUint::from(k);
+    let a_sq = f.mul(a, a);
+    let a_cubed = f.mul(&a_sq, a);
+    let b_sq = f.mul(b, b);
+    EllipticCurve::new(
+        f.div(&f.sub(&n(3), &a_sq), &f.mul(&n(3), &b_sq)),
+        f.div(&f.sub(&f.mul(&n(2), &a_cubed), &f.mul(&n(9), a)), &f.mul(&n(27), &f.mul(&b_sq, b))),
+        curve.p().clone(),
+    )
+}
+
+pub fn montgomery_to_weierstrass(curve: &MontgomeryCurve, point: &Point) -> Point {
+    match point {
+        Point::Identity => Point::Identity,
+        Point::Coordinate(u, v) => {
+            let f = curve.field();
+            let a_third = f.div(curve.a(), &BigUint::from(3u32));
+            Point::Coordinate(f.div(&f.add(u, &a_third), curve.b()), f.div(v, curve.b()))
+        }
+    }
+}
+
+pub fn weierstrass_to_montgomery(curve: &MontgomeryCurve, point: &Point) -> Point {
+    match point {
+        Point::Identity => Point::Identity,
+        Point::Coordinate(x, y) => {
+            let f = curve.field();
+            let a_third = f.div(curve.a(), &BigUint::from(3u32));
+            Point::Coordinate(f.sub(&f.mul(curve.b(), x), &a_third), f.mul(curve.b(), y))
+        }
+    }
+}
+
+// Montgomery form of a Weierstrass curve from the x-coordinate alpha of a point of order 2
+// Points then move across with weierstrass_to_montgomery on the returned curve
+pub fn weierstrass_to_montgomery_curve(curve: &EllipticCurve, alpha: &BigUint) -> Result<MontgomeryCurve, &'static str> {
+    let f = curve.field();
+    let zero = BigUint::from(0u32);
+    if !curve.is_on_curve(&Point::Coordinate(alpha % curve.p(), zero.clone())) {
+        return Err("alpha is not a root of x³ + ax + b");
+    }
+    let t = f.add(&f.mul(&BigUint::from(3u32), &f.mul(alpha, alpha)), curve.a());
+    let root = match f.sqrt(&t) {
+        Some(root) if root != zero => root,
+        _ => return Err("3 alpha² + a is not a non-zero square: no Montgomery form over this field"),
+    };
+    let s = f.div(&BigUint::from(1u32), &root);
+    Ok(MontgomeryCurve::new(f.mul(&f.mul(&BigUint::from(3u32), alpha), &s), s, curve.p().clone()))
+}
+
+pub fn montgomery_to_edwards_curve(curve: &MontgomeryCurve) -> EdwardsCurve {
+    let f = curve.field();
+    let two = BigUint::from(2u32);
+    EdwardsCurve::new(f.div(&f.add(curve.a(), &two), curve.b()), f.div(&f.sub(curve.a(), &two), curve.b()), curve.p().clone())
+}
+
+pub fn edwards_to_montgomery_curve(curve: &EdwardsCurve) -> Result<MontgomeryCurve, &'static str> {
+    let f = curve.field();
+    let a_minus_d = f.sub(curve.a(), curve.d());
+    if a_minus_d == BigUint::from(0u32) {
+        return Err("a = d is a singular curve");
+    }
+    Ok(MontgomeryCurve::new(
+        f.div(&f.mul(&BigUint::from(2u32), &f.add(curve.a(), curve.d())), &a_minus_d),
+        f.div(&BigUint::from(4u32), &a_minus_d),
+        curve.p().clone(),
+    ))
+}
+
+pub fn montgomery_to_edwards(curve: &MontgomeryCurve, point: &Point) -> Result<EdwardsPoint, &'static str> {
+    let f = curve.field();
+    let zero = BigUint::from(0u32);
+    let one = BigUint::from(1u32);
+    match point {
+        Point::Identity => Ok(EdwardsPoint::identity()),
+        Point::Coordinate(u, v) if *u == zero && *v == zero => Ok(EdwardsPoint { x: zero.clone(), y: f.sub(&zero, &one) }),
+        Point::Coordinate(u, v) => {
+            let u_plus_one = f.add(u, &one);
+            if *v == zero || u_plus_one == zero {
+                return Err("point maps to infinity on the Edwards curve");
+            }
+            Ok(EdwardsPoint {
+                x: f.div(u, v),
+                y: f.div(&f.sub(u, &one), &u_plus_one),
+            })
+        }
+    }
+}
+
+pub fn edwards_to_montgomery(curve: &EdwardsCurve, point: &EdwardsPoint) -> Point {
+    let f = curve.field();
+    let zero = BigUint::from(0u32);
+    let one = BigUint::from(1u32);
+    if point.x == zero {
+        // (0, 1) is the identity, (0, -1) the point of order 2 at (0, 0)
+        return if point.y == one { Point::Identity } else { Point::Coordinate(zero.clone(), zero) };
+    }
+    // x ≠ 0 forces y ≠ 1 on the curve
+    let u = f.div(&f.add(&one, &point.y), &f.sub(&one, &point.y));
+    let v = f.div(&u, &point.x);
+    Point::Coordinate(u, v)
+}
+
+// Isomorphism (x, y) -> (c x, y) onto a x² / c² + y² = 1 + d x² y² / c², used to reach a = -1
+// e.g. Curve25519's Edwards form has a = 486664 and scaling by c = sqrt(-486664) gives edwards25519
+pub fn scale_edwards_curve(curve: &EdwardsCurve, c: &BigUint) -> EdwardsCurve {
+    let f = curve.field();
+    let c_sq = f.mul(c, c);
+    EdwardsCurve::new(f.div(curve.a(), &c_sq), f.div(curve.d(), &c_sq), curve.p().clone())
+}
+
+pub fn scale_edwards_point(curve: &EdwardsCurve, point: &EdwardsPoint, c: &BigUint) -> EdwardsPoint {
+    EdwardsPoint {
+        x: curve.field().mul(&point.x, c),
+        y: point.y.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecdsa::ECDSA;
+    use crate::ed25519::Ed25519;
+    use crate::montgomery::curve25519;
+
+    // Curve25519's base point u = 9 with its v-coordinate
+    fn curve25519_base() -> Point {
+        let curve = curve25519();
+        let f = curve.field();
+        let u = BigUint::from(9u32);
+        let rhs = f.add(&f.mul(&f.mul(&u, &u), &f.add(&u, curve.a())), &u);
+        Point::Coordinate(u, f.sqrt(&rhs).unwrap())
+    }
+
+    #[test]
+    fn test_curve25519_through_ecdsa() {
+        // Wei25519: the Weierstrass form of Curve25519, then plain ECDSA on it
+        let montgomery = curve25519();
+        let weierstrass = montgomery_to_weierstrass_curve(&montgomery);
+        let generator = montgomery_to_weierstrass(&montgomery, &curve25519_base());
+        assert!(weierstrass.is_on_curve(&generator));
+        let Point::Coordinate(x, _) = &generator else { panic!() };
+        assert_eq!(format!("{:x}", x), "2aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaad245a");
+        assert_eq!(weierstrass_to_montgomery(&montgomery, &generator), curve25519_base());
+
+        let order = Ed25519::new().order;
+        assert_eq!(weierstrass.scalar_mult(&generator, &order), Point::Identity);
+        let ecdsa = ECDSA::with_cofactor(weierstrass, generator, order, BigUint::from(8u32));
+        let keypair = ecdsa.generate_keypair();
+        let signature = ecdsa.sign(b"birational", &keypair.private_key).unwrap();
+        assert!(ecdsa.verify(b"birational", &signature, &keypair.public_key));
+    }
+
+    #[test]
+    fn test_weierstrass_to_montgomery() {
+        // alpha = A / 3B is the 2-torsion point (0, 0) seen from the Weierstrass side
+        let montgomery = curve25519();
+        let weierstrass = montgomery_to_weierstrass_curve(&montgomery);
+        let f = montgomery.field();
+        let alpha = f.div(montgomery.a(), &BigUint::from(3u32));
+        // Either square root works; -s gives the curve (-A, -B) with u negated
+        let recovered = weierstrass_to_montgomery_curve(&weierstrass, &alpha).unwrap();
+        assert_eq!(montgomery_to_weierstrass_curve(&recovered), weierstrass);
+        let point = montgomery_to_weierstrass(&montgomery, &curve25519_base());
+        let moved = weierstrass_to_montgomery(&recovered, &point);
+        assert_eq!(montgomery_to_weierstrass(&recovered, &moved), point);
+
+        assert!(weierstrass_to_montgomery_curve(&weierstrass, &BigUint::from(5u32)).is_err());
+    }
+
+    #[test]
+    fn test_curve25519_to_edwards25519() {
+        let montgomery = curve25519();
+        let ed = Ed25519::new();
+        let f = montgomery.field();
+        let edwards = montgomery_to_edwards_curve(&montgomery);
+        let point = montgomery_to_edwards(&montgomery, &curve25519_base()).unwrap();
+        assert!(edwards.is_on_curve(&point));
+
+        // Rescale to a = -1: this is exactly edwards25519, and u = 9 is its base point up to sign
+        let c = f.sqrt(&f.sub(&BigUint::from(0u32), &BigUint::from(486664u32))).unwrap();
+        let scaled_curve = scale_edwards_curve(&edwards, &c);
+        assert_eq!(scaled_curve, ed.curve);
+        let scaled = scale_edwards_point(&edwards, &point, &c);
+        assert!(scaled == ed.base || scaled == ed.curve.neg(&ed.base));
+
+        // The maps respect the group law: 5P computed on either side agrees
+        let k = BigUint::from(5u32);
+        let weierstrass = montgomery_to_weierstrass_curve(&montgomery);
+        let five_p = weierstrass.scalar_mult(&montgomery_to_weierstrass(&montgomery, &curve25519_base()), &k);
+        let five_p = weierstrass_to_montgomery(&montgomery, &five_p);
+        assert_eq!(montgomery_to_edwards(&montgomery, &five_p).unwrap(), edwards.scalar_mult(&point, &k));
+        assert_eq!(edwards_to_montgomery(&edwards, &edwards.scalar_mult(&point, &k)), five_p);
+
+        let back = edwards_to_montgomery_curve(&edwards).unwrap();
+        assert_eq!(back, montgomery);
+        assert_eq!(edwards_to_montgomery(&edwards, &EdwardsPoint::identity()), Point::Identity);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{FiniteField, Point};
+use crate::schnorr::{Schnorr, SchnorrSignature};
+use crate::entropy::Entropy;
+use num_bigint::{BigUint, RandBigInt};
+
+// Blind Schnorr signatures: the signer issues a signature on a message it never sees,
+// and can't later link the signature to the session that produced it (e-cash, anonymous tokens)
+//
+//   signer -> user:  R = k * G
+//   user -> signer:  α, β random, R' = R + α * G + β * P, e' = H(R' || P || m), e = e' + β
+//   signer -> user:  s = k + e * x
+//   user:            s' = s + α, signature (R', s')
+// and s' * G = R + α * G + (e' + β) * P = R' + e' * P verifies as a normal Schnorr signature
+//
+// Running many signer sessions concurrently is insecure (the ROS attack of Benhamouda et al.
+// forges one more signature than issued), so a signer should finish each session before
+// starting the next
+
+// Signer state after the first move; consumed by respond so k is used exactly once
+pub struct BlindSigner {
+    nonce: BigUint,
+    pub commitment: Point,
+}
+
+impl BlindSigner {
+    pub fn commit(scheme: &Schnorr) -> Self {
+        let nonce = scheme.params.generate_private_key();
+        BlindSigner {
+            commitment: scheme.params.curve.scalar_mult(&scheme.params.generator, &nonce),
+            nonce,
+        }
+    }
+
+    // s = k + e * x for the user's blinded challenge e
+    pub fn respond(self, scheme: &Schnorr, private_key: &BigUint, blinded_challenge: &BigUint) -> Result<BigUint, &'static str> {
+        if *blinded_challenge >= scheme.params.order {
+            return Err("challenge out of range");
+        }
+        let field = FiniteField { p: scheme.params.order.clone() };
+        Ok(field.add(&self.nonce, &field.mul(blinded_challenge, private_key)))
+    }
+}
+
+// User state between sending the blinded challenge and unblinding the response
+pub struct BlindUser {
+    alpha: BigUint,
+    blinded_commitment: Point,
+    signer_commitment: Point,
+    pub challenge: BigUint,
+}
+
+impl BlindUser {
+    // Blind the signer's R for message m; challenge is what gets sent back to the signer
+    pub fn blind(scheme: &Schnorr, public_key: &Point, commitment: &Point, message: &[u8]) -> Result<Self, &'static str> {
+        if *commitment == Point::Identity || !scheme.params.curve.is_on_curve(commitment) {
+            return Err("invalid signer commitment");
+        }
+        if *public_key == Point::Identity || !scheme.params.curve.is_on_curve(public_key) {
+            return Err("invalid signer public key");
+        }
+
+        let mut rng = Entropy;
+        let field = FiniteField { p: scheme.params.order.clone() };
+        loop {
+            let alpha = rng.gen_biguint_below(&scheme.params.order);
+            let beta = rng.gen_biguint_below(&scheme.params.order);
+
+            // R' = R + α * G + β * P
+            let shift = scheme.params.curve.add(
+                &scheme.params.curve.scalar_mult(&scheme.params.generator, &alpha),
+                &scheme.params.curve.scalar_mult(public_key, &beta),
+            );
+            let blinded_commitment = scheme.params.curve.add(commitment, &shift);
+            if blinded_commitment == Point::Identity {
+                continue;
+            }
+
+            // e = e' + β
+            let unblinded_challenge = scheme.challenge(&blinded_commitment, public_key, message);
+            return Ok(BlindUser {
+                challenge: field.add(&unblinded_challenge, &beta),
+                alpha,
+                blinded_commitment,
+                signer_commitment: commitment.clone(),
+            });
+        }
+    }
+
+    // Check the signer's s against R and the blinded challenge, then s' = s + α
+    pub fn unblind(self, scheme: &Schnorr, public_key: &Point, response: &BigUint) -> Result<SchnorrSignature, &'static str> {
+        let lhs = scheme.params.curve.scalar_mult(&scheme.params.generator, response);
+        let rhs = scheme.params.curve.add(&self.signer_commitment, &scheme.params.curve.scalar_mult(public_key, &self.challenge));
+        if lhs != rhs {
+            return Err("signer response does not verify");
+        }
+
+        let field = FiniteField { p: scheme.params.order.clone() };
+        let signature = SchnorrSignature {
+            r: self.blinded_commitment,
+            s: field.add(response, &self.alpha),
+        };
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    #[test]
+    fn test_blind_signature_verifies() {
+        let scheme = curves::p256().schnorr();
+        let signer_key = scheme.generate_keypair();
+        let message = b"one e-cash coin, serial 0x5eed";
+
+        let signer = BlindSigner::commit(&scheme);
+        let user = BlindUser::blind(&scheme, &signer_key.public_key, &signer.commitment, message).unwrap();
+        let signer_commitment = signer.commitment.clone();
+        let blinded_challenge = user.challenge.clone();
+        let response = signer.respond(&scheme, &signer_key.private_key, &blinded_challenge).unwrap();
+        let signature = user.unblind(&scheme, &signer_key.public_key, &response).unwrap();
+
+        // The ordinary Schnorr verifier accepts it
+        assert!(scheme.verify(message, &signature, &signer_key.public_key));
+        assert!(!scheme.verify(b"another coin", &signature, &signer_key.public_key));
+
+        // Nothing the signer saw appears in the signature
+        assert_ne!(signature.r, signer_commitment);
+        assert_ne!(signature.s, response);
+    }
+
+    #[test]
+    fn test_user_rejects_bad_response() {
+        let scheme = curves::p256().schnorr();
+        let signer_key = scheme.generate_keypair();
+
+        let signer = BlindSigner::commit(&scheme);
+        let user = BlindUser::blind(&scheme, &signer_key.public_key, &signer.commitment, b"msg").unwrap();
+        let wrong_key = scheme.params.generate_private_key();
+        let challenge = user.challenge.clone();
+        let response = signer.respond(&scheme, &wrong_key, &challenge).unwrap();
+        assert!(user.unblind(&scheme, &signer_key.public_key, &response).is_err());
+
+        assert!(BlindUser::blind(&scheme, &signer_key.public_key, &Point::Identity, b"msg").is_err());
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{EllipticCurve, Point};
+use crate::bls12_381::{self, Bls12Pairing};
+use crate::extension::{ExtPoint, ExtensionCurve};
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use num_bigint::BigUint;
+use sha2::{Sha256, Digest};
+
+// BLS signatures (Boneh–Lynn–Shacham) over BLS12-381, "minimal signature size" variant:
+// signatures in G1 (48 bytes compressed), public keys in G2
+//
+//   sign:   σ = d * H(m)
+//   verify: e(σ, G2) = e(H(m), Q)   since e(dH(m), G2) = e(H(m), dG2)
+//
+// Signatures add up: σ1 + σ2 verifies against e(H(m1), Q1) * e(H(m2), Q2). When several
+// keys sign the same message, a rogue key Q' = X - Q lets an attacker fake an aggregate,
+// so same-message aggregation is only safe for keys with a verified proof of possession
+
+// Domain separation tags: signatures and proofs of possession never hash to the same points
+const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G1_TAI_SHA-256_POP_";
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G1_TAI_SHA-256_POP_";
+
+#[derive(Clone, Debug)]
+pub struct BLSKeyPair {
+    pub private_key: BigUint,
+    pub public_key: ExtPoint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BLSSignature {
+    pub point: Point,
+}
+
+pub struct BLS {
+    pub g1: EllipticCurve,
+    pub g2: ExtensionCurve,
+    pub generator: ExtPoint,
+    pub order: BigUint,
+    pub pairing: Bls12Pairing,
+    neg_generator: ExtPoint,
+}
+
+impl BLS {
+    pub fn new() -> Self {
+        let g2 = bls12_381::g2_curve();
+        let generator = bls12_381::g2_generator();
+        BLS {
+            g1: bls12_381::g1_curve(),
+            neg_generator: g2.neg(&generator),
+            g2,
+            generator,
+            order: bls12_381::scalar_order(),
+            pairing: Bls12Pairing::new(),
+        }
+    }
+
+    // Generate private key: random in [1, r-1]
+    pub fn generate_private_key(&self) -> BigUint {
+        Scalar::random(&self.order, &mut Entropy).into_value()
+    }
+
+    // Generate public key: Q = d * G2
+    pub fn generate_public_key(&self, private_key: &BigUint) -> ExtPoint {
+        self.g2.scalar_mult(&self.generator, private_key)
+    }
+
+    // Generate keypair
+    pub fn generate_keypair(&self) -> BLSKeyPair {
+        let private_key = self.generate_private_key();
+        let public_key = self.generate_public_key(&private_key);
+        BLSKeyPair {
+            private_key,
+            public_key,
+        }
+    }
+
+    // Hash to G1 by try-and-increment: x = H(dst || counter || m) mod p until x³ + 4 is a
+    // square, then clear the cofactor. Simple, but not constant time and not the
+    // RFC 9380 hash_to_curve, so signatures won't interoperate with other BLS libraries
+    pub fn hash_to_g1(&self, message: &[u8], dst: &[u8]) -> Point {
+        let field = bls12_381::fp();
+        let mut counter = 0u32;
+        loop {
+            // 512 bits of hash output reduced mod p: negligible bias
+            let mut wide = Vec::with_capacity(64);
+            for block in 0u8..2 {
+                let mut hasher = Sha256::new();
+                hasher.update(dst);
+                hasher.update(counter.to_be_bytes());
+                hasher.update([block]);
+                hasher.update(message);
+                wide.extend_from_slice(&hasher.finalize());
+            }
+            let x = BigUint::from_bytes_be(&wide) % &field.p;
+            counter += 1;
+
+            let rhs = field.add(&field.mul(&field.mul(&x, &x), &x), self.g1.b());
+            if let Some(y) = field.sqrt(&rhs) {
+                // Pick one of ±y from a hash bit so both roots are reachable
+                let y = if wide[63] & 1 == 1 { field.sub(&BigUint::from(0u32), &y) } else { y };
+                let point = bls12_381::clear_cofactor_g1(&Point::Coordinate(x, y));
+                if point != Point::Identity {
+                    return point;
+                }
+            }
+        }
+    }
+
+    // σ = d * H(m)
+    pub fn sign(&self, message: &[u8], private_key: &BigUint) -> BLSSignature {
+        let hashed = self.hash_to_g1(message, SIGNATURE_DST);
+        BLSSignature { point: self.g1.scalar_mult(&hashed, private_key) }
+    }
+
+    // e(σ, G2) = e(H(m), Q), checked as e(σ, -G2) * e(H(m), Q) = 1 with one final exponentiation
+    pub fn verify(&self, message: &[u8], signature: &BLSSignature, public_key: &ExtPoint) -> bool {
+        if !self.validate_public_key(public_key) || !self.validate_signature(signature) {
+            return false;
+        }
+        let hashed = self.hash_to_g1(message, SIGNATURE_DST);
+        self.pairing_check(&signature.point, &[(hashed, public_key.clone())])
+    }
+
+    // σ = σ1 + σ2 + ... (order doesn't matter)
+    pub fn aggregate(&self, signatures: &[BLSSignature]) -> Result<BLSSignature, &'static str> {
+        if signatures.is_empty() {
+            return Err("nothing to aggregate");
+        }
+        let point = signatures.iter().fold(Point::Identity, |acc, sig| self.g1.add(&acc, &sig.point));
+        Ok(BLSSignature { point })
+    }
+
+    // Q = Q1 + Q2 + ... for same-message verification
+    // Each key is validated first: an identity or non-G2 key would otherwise vanish into the sum
+    pub fn aggregate_public_keys(&self, public_keys: &[ExtPoint]) -> Result<ExtPoint, &'static str> {
+        if public_keys.is_empty() {
+            return Err("nothing to aggregate");
+        }
+        if !public_keys.iter().all(|key| self.validate_public_key(key)) {
+            return Err("public key is not in G2");
+        }
+        Ok(public_keys.iter().fold(ExtPoint::Identity, |acc, key| self.g2.add(&acc, key)))
+    }
+
+    // Aggregate of signatures on distinct messages: e(σ, G2) = ∏ e(H(m_i), Q_i)
+    // Distinct messages rule out rogue-key forgeries without proofs of possession
+    pub fn aggregate_verify(&self, messages: &[&[u8]], public_keys: &[ExtPoint], signature: &BLSSignature) -> bool {
+        if messages.is_empty() || messages.len() != public_keys.len() {
+            return false;
+        }
+        for (i, message) in messages.iter().enumerate() {
+            if messages[..i].contains(message) {
+                return false;
+            }
+        }
+        if !self.validate_signature(signature) || !public_keys.iter().all(|key| self.validate_public_key(key)) {
+            return false;
+        }
+
+        let pairs = messages
+            .iter()
+            .zip(public_keys)
+            .map(|(message, key)| (self.hash_to_g1(message, SIGNATURE_DST), key.clone()))
+            .collect::<Vec<_>>();
+        self.pairing_check(&signature.point, &pairs)
+    }
+
+    // Aggregate of signatures on one message: e(σ, G2) = e(H(m), Q1 + Q2 + ...)
+    // Only sound when every key's proof of possession has been checked (verify_possession)
+    pub fn fast_aggregate_verify(&self, message: &[u8], public_keys: &[ExtPoint], signature: &BLSSignature) -> bool {
+        let Ok(aggregate_key) = self.aggregate_public_keys(public_keys) else {
+            return false;
+        };
+        if aggregate_key == ExtPoint::Identity || !self.validate_signature(signature) {
+            return false;
+        }
+        let hashed = self.hash_to_g1(message, SIGNATURE_DST);
+        self.pairing_check(&signature.point, &[(hashed, aggregate_key)])
+    }
+
+    // Proof of possession: a signature on the public key itself, under its own DST
+    // A rogue key X - Q can't produce one because nobody knows its discrete log
+    pub fn prove_possession(&self, private_key: &BigUint) -> BLSSignature {
+        let public_key = self.generate_public_key(private_key);
+        let hashed = self.hash_to_g1(&encode_g2(&public_key), POP_DST);
+        BLSSignature { point: self.g1.scalar_mult(&hashed, private_key) }
+    }
+
+    pub fn verify_possession(&self, public_key: &ExtPoint, proof: &BLSSignature) -> bool {
+        if !self.validate_public_key(public_key) || !self.validate_signature(proof) {
+            return false;
+        }
+        let hashed = self.hash_to_g1(&encode_g2(public_key), POP_DST);
+        self.pairing_check(&proof.point, &[(hashed, public_key.clone())])
+    }
+
+    // Public keys must be non-identity members of G2: the identity verifies everything
+    fn validate_public_key(&self, public_key: &ExtPoint) -> bool {
+        *public_key != ExtPoint::Identity && bls12_381::is_in_g2(public_key)
+    }
+
+    fn validate_signature(&self, signature: &BLSSignature) -> bool {
+        signature.point != Point::Identity && bls12_381::is_in_g1(&signature.point)
+    }
+
+    // e(σ, -G2) * ∏ e(P_i, Q_i) = 1
+    fn pairing_check(&self, signature: &Point, pairs: &[(Point, ExtPoint)]) -> bool {
+        let mut all_pairs = vec![(signature.clone(), self.neg_generator.clone())];
+        all_pairs.extend_from_slice(pairs);
+        match self.pairing.pairing_product(&all_pairs) {
+            Ok(value) => value == self.pairing.tate.curve.field.one(),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Default for BLS {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Uncompressed G2 encoding (x.c0 || x.c1 || y.c0 || y.c1, 48 bytes each), used as PoP input
+fn encode_g2(point: &ExtPoint) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(192);
+    if let ExtPoint::Coordinate(x, y) = point {
+        for c in x.coeffs.iter().chain(&y.coeffs) {
+            let c_bytes = c.to_bytes_be();
+            bytes.extend(std::iter::repeat_n(0u8, 48 - c_bytes.len()));
+            bytes.extend_from_slice(&c_bytes);
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_verify() {
+        let bls = BLS::new();
+        let keypair = bls.generate_keypair();
+        let message = b"hello bls";
+
+        let signature = bls.sign(message, &keypair.private_key);
+        assert!(bls.verify(message, &signature, &keypair.public_key));
+        assert!(!bls.verify(b"wrong message", &signature, &keypair.public_key));
+    }
+
+    #[test]
+    fn test_hash_to_g1() {
+        let bls = BLS::new();
+        let h1 = bls.hash_to_g1(b"message", SIGNATURE_DST);
+        assert!(bls12_381::is_in_g1(&h1));
+
+        // Deterministic, and separated by DST
+        assert_eq!(h1, bls.hash_to_g1(b"message", SIGNATURE_DST));
+        assert_ne!(h1, bls.hash_to_g1(b"message", POP_DST));
+    }
+
+    #[test]
+    fn test_aggregate_distinct_messages() {
+        let bls = BLS::new();
+        let alice = bls.generate_keypair();
+        let bob = bls.generate_keypair();
+        let messages: [&[u8]; 2] = [b"from alice", b"from bob"];
+
+        let signature = bls.aggregate(&[
+            bls.sign(messages[0], &alice.private_key),
+            bls.sign(messages[1], &bob.private_key),
+        ]).unwrap();
+        let keys = [alice.public_key.clone(), bob.public_key.clone()];
+        assert!(bls.aggregate_verify(&messages, &keys, &signature));
+
+        // Swapped keys or duplicated messages fail
+        let swapped = [bob.public_key, alice.public_key];
+        assert!(!bls.aggregate_verify(&messages, &swapped, &signature));
+        let duplicated: [&[u8]; 2] = [b"same", b"same"];
+        assert!(!bls.aggregate_verify(&duplicated, &keys, &signature));
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify_with_possession() {
+        let bls = BLS::new();
+        let alice = bls.generate_keypair();
+        let bob = bls.generate_keypair();
+        let message = b"shared message";
+
+        assert!(bls.verify_possession(&alice.public_key, &bls.prove_possession(&alice.private_key)));
+        assert!(bls.verify_possession(&bob.public_key, &bls.prove_possession(&bob.private_key)));
+
+        let signature = bls.aggregate(&[
+            bls.sign(message, &alice.private_key),
+            bls.sign(message, &bob.private_key),
+        ]).unwrap();
+        let keys = [alice.public_key.clone(), bob.public_key.clone()];
+        assert!(bls.fast_aggregate_verify(message, &keys, &signature));
+        assert!(!bls.fast_aggregate_verify(b"other message", &keys, &signature));
+
+        // An identity or off-curve key in the list is refused before anything is summed
+        let field = bls12_381::fp2();
+        let off_curve = ExtPoint::Coordinate(field.one(), field.one());
+        for bad in [ExtPoint::Identity, off_curve] {
+            let with_bad = [alice.public_key.clone(), bad];
+            assert!(bls.aggregate_public_keys(&with_bad).is_err());
+            assert!(!bls.fast_aggregate_verify(message, &with_bad, &bls.sign(message, &alice.private_key)));
+        }
+    }
+
+    #[test]
+    fn test_rogue_key_has_no_proof_of_possession() {
+        let bls = BLS::new();
+        let alice = bls.generate_keypair();
+        let attacker = bls.generate_keypair();
+
+        // Rogue key Q' = X - Q_alice: Q' + Q_alice = X, which the attacker controls
+        let rogue_key = bls.g2.add(&attacker.public_key, &bls.g2.neg(&alice.public_key));
+        let message = b"alice agreed to this";
+        let forged = bls.sign(message, &attacker.private_key);
+        assert!(bls.fast_aggregate_verify(message, &[alice.public_key.clone(), rogue_key.clone()], &forged));
+
+        // ...which is why fast aggregation requires PoP: the attacker can't prove the rogue key
+        let fake_proof = bls.prove_possession(&attacker.private_key);
+        assert!(!bls.verify_possession(&rogue_key, &fake_proof));
+    }
+
+    #[test]
+    fn test_rejects_identity_inputs() {
+        let bls = BLS::new();
+        let keypair = bls.generate_keypair();
+        let message = b"message";
+
+        let identity_signature = BLSSignature { point: Point::Identity };
+        assert!(!bls.verify(message, &identity_signature, &keypair.public_key));
+
+        let signature = bls.sign(message, &keypair.private_key);
+        assert!(!bls.verify(message, &signature, &ExtPoint::Identity));
+        assert!(bls.aggregate(&[]).is_err());
+    }
+}
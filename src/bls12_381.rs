@@ -0,0 +1,302 @@
+use crate::{EllipticCurve, FiniteField, Point};
+use crate::extension::{ExtElement, ExtPoint, ExtensionCurve, ExtensionField};
+use crate::pairing::TatePairing;
+use num_bigint::BigUint;
+
+// BLS12-381, the pairing-friendly curve used by Zcash, Ethereum consensus and most BLS deployments
+//
+//   G1: y² = x³ + 4          over F_p
+//   G2: y² = x³ + 4(1 + i)   over F_p² = F_p[i] / (i² + 1), a sextic twist of G1's curve
+//
+// Everything derives from the BLS parameter x = -0xd201000000010000:
+//   r = x⁴ - x² + 1,  p = (x - 1)² r / 3 + x,  embedding degree 12
+// Both groups have the same prime order r; the full curve groups are h * r
+
+// Base field modulus p (381 bits)
+const P: &str = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+
+// Prime subgroup order r (255 bits)
+const R: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+
+// |x|, the (negative) BLS parameter
+pub const BLS_X: u64 = 0xd201000000010000;
+
+const G1_X: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+const G1_Y: &str = "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+
+// h1 = (x - 1)² / 3
+const G1_COFACTOR: &str = "396c8c005555e1568c00aaab0000aaab";
+
+// G2 generator coordinates as c0 + c1 * i
+const G2_X_C0: &str = "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+const G2_X_C1: &str = "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e";
+const G2_Y_C0: &str = "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801";
+const G2_Y_C1: &str = "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be";
+
+const G2_COFACTOR: &str = "5d543a95414e7f1091d50792876a202cd91de4547085abaa68a205b2e5a7ddfa628f1cb4d9e82ef21537e293a6691ae1616ec6e786f0c70cf1c38e31c7238e5";
+
+fn hex(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 16).expect("?invalid curve constant?")
+}
+
+// F_p
+pub fn fp() -> FiniteField {
+    FiniteField { p: hex(P) }
+}
+
+// F_p² = F_p[i] / (i² + 1); -1 is a non-residue since p ≡ 3 (mod 4)
+pub fn fp2() -> ExtensionField {
+    ExtensionField::new(hex(P), vec![BigUint::from(1u32), BigUint::from(0u32)])
+}
+
+// r, the order of G1, G2 and the scalar field
+pub fn scalar_order() -> BigUint {
+    hex(R)
+}
+
+// y² = x³ + 4 over F_p
+pub fn g1_curve() -> EllipticCurve {
+    EllipticCurve::new(BigUint::from(0u32), BigUint::from(4u32), hex(P))
+}
+
+pub fn g1_generator() -> Point {
+    Point::Coordinate(hex(G1_X), hex(G1_Y))
+}
+
+pub fn g1_cofactor() -> BigUint {
+    hex(G1_COFACTOR)
+}
+
+// y² = x³ + 4(1 + i) over F_p²
+pub fn g2_curve() -> ExtensionCurve {
+    let field = fp2();
+    let four = BigUint::from(4u32);
+    ExtensionCurve {
+        a: field.zero(),
+        b: field.from_coeffs(&[four.clone(), four]),
+        field,
+    }
+}
+
+pub fn g2_generator() -> ExtPoint {
+    let field = fp2();
+    ExtPoint::Coordinate(
+        field.from_coeffs(&[hex(G2_X_C0), hex(G2_X_C1)]),
+        field.from_coeffs(&[hex(G2_Y_C0), hex(G2_Y_C1)]),
+    )
+}
+
+pub fn g2_cofactor() -> BigUint {
+    hex(G2_COFACTOR)
+}
+
+// Subgroup checks: on the curve and r * P = O
+// Both curves have points outside the order-r subgroup (h1, h2 > 1), and skipping this
+// check is how small-subgroup and pairing-forgery attacks get in
+pub fn is_in_g1(point: &Point) -> bool {
+    g1_curve().is_torsion_point(point, &scalar_order())
+}
+
+pub fn is_in_g2(point: &ExtPoint) -> bool {
+    g2_curve().is_torsion_point(point, &scalar_order())
+}
+
+// Map any curve point into G1 / G2 by multiplying with the cofactor
+pub fn clear_cofactor_g1(point: &Point) -> Point {
+    g1_curve().scalar_mult(point, &g1_cofactor())
+}
+
+pub fn clear_cofactor_g2(point: &ExtPoint) -> ExtPoint {
+    g2_curve().scalar_mult(point, &g2_cofactor())
+}
+
+// F_p¹² = F_p²[w] / (w⁶ - (1 + i)), flattened to F_p[w] / (w¹² - 2w⁶ + 2):
+// w⁶ = 1 + i gives (w⁶ - 1)² = i² = -1, so i = w⁶ - 1
+pub fn fp12() -> ExtensionField {
+    let p = hex(P);
+    let mut modulus = vec![BigUint::from(0u32); 12];
+    modulus[0] = BigUint::from(2u32);
+    modulus[6] = &p - BigUint::from(2u32);
+    ExtensionField::new(p, modulus)
+}
+
+// The pairing e: G1 × G2 -> μ_r ⊂ F_p¹²*
+// G2 points are untwisted into E(F_p¹²), then the reduced Tate pairing is applied
+pub struct Bls12Pairing {
+    pub tate: TatePairing,
+    w_squared_inv: ExtElement,
+    w_cubed_inv: ExtElement,
+}
+
+impl Bls12Pairing {
+    pub fn new() -> Self {
+        let field = fp12();
+        let w = field.from_coeffs(&[BigUint::from(0u32), BigUint::from(1u32)]);
+        let w_squared = field.mul(&w, &w);
+        let w_cubed = field.mul(&w_squared, &w);
+        Bls12Pairing {
+            w_squared_inv: field.inv(&w_squared),
+            w_cubed_inv: field.inv(&w_cubed),
+            tate: TatePairing::new(&g1_curve(), scalar_order(), field),
+        }
+    }
+
+    // Twist isomorphism E'(F_p²) -> E(F_p¹²): (x, y) -> (x / w², y / w³)
+    // y² = x³ + 4w⁶ divided through by w⁶ is exactly y² = x³ + 4
+    pub fn untwist(&self, point: &ExtPoint) -> ExtPoint {
+        match point {
+            ExtPoint::Identity => ExtPoint::Identity,
+            ExtPoint::Coordinate(x, y) => {
+                let f = &self.tate.curve.field;
+                ExtPoint::Coordinate(
+                    f.mul(&self.embed_fp2(x), &self.w_squared_inv),
+                    f.mul(&self.embed_fp2(y), &self.w_cubed_inv),
+                )
+            }
+        }
+    }
+
+    // e(P, Q) for P ∈ G1, Q ∈ G2
+    pub fn pair(&self, p: &Point, q: &ExtPoint) -> Result<ExtElement, &'static str> {
+        self.tate.pair(p, &self.untwist(q))
+    }
+
+    // ∏ e(P_i, Q_i), sharing one final exponentiation
+    pub fn pairing_product(&self, pairs: &[(Point, ExtPoint)]) -> Result<ExtElement, &'static str> {
+        let untwisted = pairs.iter().map(|(p, q)| (p.clone(), self.untwist(q))).collect::<Vec<_>>();
+        self.tate.pairing_product(&untwisted)
+    }
+
+    // a + b*i -> a + b(w⁶ - 1)
+    fn embed_fp2(&self, x: &ExtElement) -> ExtElement {
+        let f = &self.tate.curve.field;
+        let mut coeffs = vec![BigUint::from(0u32); 7];
+        coeffs[0] = &x.coeffs[0] + &f.p - &x.coeffs[1];
+        coeffs[6] = x.coeffs[1].clone();
+        f.from_coeffs(&coeffs)
+    }
+}
+
+impl Default for Bls12Pairing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameters_from_bls_x() {
+        // x is negative: r = x⁴ - x² + 1 and p = (x - 1)² r / 3 + x with x = -BLS_X
+        let x = BigUint::from(BLS_X);
+        let r = x.pow(4) - x.pow(2) + 1u32;
+        assert_eq!(r, scalar_order());
+
+        let x_minus_one_squared = (&x + 1u32).pow(2);
+        assert_eq!(&x_minus_one_squared * &r / 3u32 - &x, fp().p);
+        assert_eq!(x_minus_one_squared / 3u32, g1_cofactor());
+    }
+
+    #[test]
+    fn test_g1_generator() {
+        let generator = g1_generator();
+        assert!(g1_curve().is_on_curve(&generator));
+        assert!(is_in_g1(&generator));
+    }
+
+    #[test]
+    fn test_g2_generator() {
+        let generator = g2_generator();
+        assert!(g2_curve().is_on_curve(&generator));
+        assert!(is_in_g2(&generator));
+    }
+
+    #[test]
+    fn test_g1_subgroup_check() {
+        // x = 0: y² = 4 gives (0, 2), which is on the curve but has order 3
+        let point = Point::Coordinate(BigUint::from(0u32), BigUint::from(2u32));
+        assert!(g1_curve().is_on_curve(&point));
+        assert!(!is_in_g1(&point));
+
+        // A point with a large component outside G1; clearing the cofactor fixes it
+        let field = fp();
+        let curve = g1_curve();
+        let mut x = BigUint::from(1u32);
+        let point = loop {
+            let rhs = field.add(&field.mul(&field.mul(&x, &x), &x), curve.b());
+            if let Some(y) = field.sqrt(&rhs) {
+                break Point::Coordinate(x, y);
+            }
+            x += 1u32;
+        };
+        assert!(!is_in_g1(&point));
+        let cleared = clear_cofactor_g1(&point);
+        assert_ne!(cleared, Point::Identity);
+        assert!(is_in_g1(&cleared));
+    }
+
+    #[test]
+    fn test_g2_subgroup_check() {
+        let curve = g2_curve();
+        let field = &curve.field;
+
+        let mut c = BigUint::from(1u32);
+        let point = loop {
+            let x = field.from_coeffs(&[c.clone(), BigUint::from(1u32)]);
+            let rhs = field.add(&field.mul(&field.mul(&x, &x), &x), &curve.b);
+            if let Some(y) = field.sqrt(&rhs) {
+                break ExtPoint::Coordinate(x, y);
+            }
+            c += 1u32;
+        };
+        assert!(curve.is_on_curve(&point));
+        assert!(!is_in_g2(&point));
+
+        let cleared = clear_cofactor_g2(&point);
+        assert_ne!(cleared, ExtPoint::Identity);
+        assert!(is_in_g2(&cleared));
+    }
+
+    #[test]
+    fn test_untwisted_generator_on_curve() {
+        let pairing = Bls12Pairing::new();
+        let untwisted = pairing.untwist(&g2_generator());
+        assert!(pairing.tate.curve.is_on_curve(&untwisted));
+    }
+
+    #[test]
+    fn test_pairing_bilinear() {
+        let pairing = Bls12Pairing::new();
+        let f = &pairing.tate.curve.field;
+        let g1 = g1_generator();
+        let g2 = g2_generator();
+
+        let base = pairing.pair(&g1, &g2).unwrap();
+        assert_ne!(base, f.one());
+
+        // e(2P, 3Q) = e(P, Q)^6
+        let two_g1 = g1_curve().scalar_mult(&g1, &BigUint::from(2u32));
+        let three_g2 = g2_curve().scalar_mult(&g2, &BigUint::from(3u32));
+        assert_eq!(pairing.pair(&two_g1, &three_g2).unwrap(), f.pow(&base, &BigUint::from(6u32)));
+
+        // e(P, Q) * e(-P, Q) = 1
+        let neg_g1 = match &g1 {
+            Point::Coordinate(x, y) => Point::Coordinate(x.clone(), fp().sub(&BigUint::from(0u32), y)),
+            Point::Identity => Point::Identity,
+        };
+        let product = pairing.pairing_product(&[(g1, g2.clone()), (neg_g1, g2)]).unwrap();
+        assert_eq!(product, f.one());
+    }
+
+    #[test]
+    fn test_off_curve_points_rejected() {
+        let point = Point::Coordinate(BigUint::from(1u32), BigUint::from(1u32));
+        assert!(!is_in_g1(&point));
+
+        let field = fp2();
+        let point = ExtPoint::Coordinate(field.one(), field.one());
+        assert!(!is_in_g2(&point));
+    }
+}
@@ -0,0 +1,284 @@
+// CBOR (RFC 8949) data model and codec, the subset COSE needs: integers, byte and text
+// strings, arrays, maps, tags, booleans and null. No floats, no indefinite lengths
+//
+// Every item starts with a head byte: major type in the top 3 bits, then the argument
+// (a length, a value or a tag number) in the low 5 bits when below 24, or in the next
+// 1, 2, 4 or 8 bytes for 24-27. Major types:
+//   0 unsigned   1 negative (-1 - n)   2 bytes   3 text   4 array   5 map   6 tag   7 simple
+//
+// Encoding is deterministic (4.2.1): shortest heads, definite lengths, map keys sorted by
+// their encoded bytes, so equal values always give equal bytes. Decoding insists on shortest
+// heads too and rejects duplicate map keys, but takes keys in any order
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Unsigned(u64),
+    // -1 - n, so the whole 64-bit range of CBOR negatives fits
+    Negative(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Tag(u64, Box<Value>),
+    Bool(bool),
+    Null,
+}
+
+// Deeper nesting than this is rejected rather than risking the stack
+const MAX_DEPTH: usize = 64;
+
+impl Value {
+    pub fn int(x: i64) -> Value {
+        if x >= 0 { Value::Unsigned(x as u64) } else { Value::Negative(!x as u64) }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Unsigned(n) => i64::try_from(*n).ok(),
+            Value::Negative(n) => i64::try_from(*n).ok().map(|n| !n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&[(Value, Value)]> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    // Map lookup; None for missing keys and for non-maps
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.as_map()?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode_into(&mut out);
+        out
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) {
+        match self {
+            Value::Unsigned(n) => head(out, 0, *n),
+            Value::Negative(n) => head(out, 1, *n),
+            Value::Bytes(bytes) => {
+                head(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            Value::Text(text) => {
+                head(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Value::Array(items) => {
+                head(out, 4, items.len() as u64);
+                for item in items {
+                    item.encode_into(out);
+                }
+            }
+            Value::Map(entries) => {
+                let mut encoded: Vec<(Vec<u8>, Vec<u8>)> = entries.iter().map(|(k, v)| (k.encode(), v.encode())).collect();
+                encoded.sort();
+                head(out, 5, encoded.len() as u64);
+                for (k, v) in encoded {
+                    out.extend(k);
+                    out.extend(v);
+                }
+            }
+            Value::Tag(tag, item) => {
+                head(out, 6, *tag);
+                item.encode_into(out);
+            }
+            Value::Bool(b) => out.push(if *b { 0xf5 } else { 0xf4 }),
+            Value::Null => out.push(0xf6),
+        }
+    }
+
+    // Exactly one item, nothing after it
+    pub fn decode(bytes: &[u8]) -> Result<Value, &'static str> {
+        let mut pos = 0;
+        let value = decode_item(bytes, &mut pos, 0)?;
+        if pos != bytes.len() {
+            return Err("trailing data after CBOR item");
+        }
+        Ok(value)
+    }
+}
+
+fn head(out: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    match n {
+        0..=23 => out.push(major | n as u8),
+        24..=0xff => out.extend([major | 24, n as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend((n as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend((n as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend(n.to_be_bytes());
+        }
+    }
+}
+
+fn take<'a>(bytes: &'a [u8], pos: &mut usize, len: u64) -> Result<&'a [u8], &'static str> {
+    let len = usize::try_from(len).map_err(|_| "truncated CBOR item")?;
+    let end = pos.checked_add(len).ok_or("truncated CBOR item")?;
+    let out = bytes.get(*pos..end).ok_or("truncated CBOR item")?;
+    *pos = end;
+    Ok(out)
+}
+
+// (major type, argument) with the argument in its shortest form
+fn read_head(bytes: &[u8], pos: &mut usize) -> Result<(u8, u64), &'static str> {
+    let initial = take(bytes, pos, 1)?[0];
+    let (major, info) = (initial >> 5, initial & 0x1f);
+    let (n, min) = match info {
+        0..=23 => return Ok((major, info as u64)),
+        24 => (take(bytes, pos, 1)?[0] as u64, 24),
+        25 => (u16::from_be_bytes(take(bytes, pos, 2)?.try_into().unwrap()) as u64, 0x100),
+        26 => (u32::from_be_bytes(take(bytes, pos, 4)?.try_into().unwrap()) as u64, 0x1_0000),
+        27 => (u64::from_be_bytes(take(bytes, pos, 8)?.try_into().unwrap()), 0x1_0000_0000),
+        31 => return Err("indefinite-length CBOR is not supported"),
+        _ => return Err("reserved CBOR additional information"),
+    };
+    if n < min {
+        return Err("non-shortest CBOR head");
+    }
+    Ok((major, n))
+}
+
+fn decode_item(bytes: &[u8], pos: &mut usize, depth: usize) -> Result<Value, &'static str> {
+    if depth > MAX_DEPTH {
+        return Err("CBOR nested too deeply");
+    }
+    let (major, n) = read_head(bytes, pos)?;
+    Ok(match major {
+        0 => Value::Unsigned(n),
+        1 => Value::Negative(n),
+        2 => Value::Bytes(take(bytes, pos, n)?.to_vec()),
+        3 => Value::Text(String::from_utf8(take(bytes, pos, n)?.to_vec()).map_err(|_| "CBOR text is not UTF-8")?),
+        4 => Value::Array((0..n).map(|_| decode_item(bytes, pos, depth + 1)).collect::<Result<_, _>>()?),
+        5 => {
+            let mut entries: Vec<(Value, Value)> = Vec::new();
+            for _ in 0..n {
+                let key = decode_item(bytes, pos, depth + 1)?;
+                if entries.iter().any(|(k, _)| *k == key) {
+                    return Err("duplicate CBOR map key");
+                }
+                entries.push((key, decode_item(bytes, pos, depth + 1)?));
+            }
+            Value::Map(entries)
+        }
+        6 => Value::Tag(n, Box::new(decode_item(bytes, pos, depth + 1)?)),
+        _ => match n {
+            20 => Value::Bool(false),
+            21 => Value::Bool(true),
+            22 => Value::Null,
+            _ => return Err("unsupported CBOR simple value or float"),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    fn text(s: &str) -> Value {
+        Value::Text(s.to_string())
+    }
+
+    #[test]
+    fn test_rfc8949_examples() {
+        // RFC 8949 appendix A
+        let cases = [
+            (Value::int(0), "00"),
+            (Value::int(23), "17"),
+            (Value::int(24), "1818"),
+            (Value::int(100), "1864"),
+            (Value::int(1000), "1903e8"),
+            (Value::int(1000000), "1a000f4240"),
+            (Value::int(1000000000000), "1b000000e8d4a51000"),
+            (Value::Unsigned(u64::MAX), "1bffffffffffffffff"),
+            (Value::Negative(u64::MAX), "3bffffffffffffffff"),
+            (Value::int(-1), "20"),
+            (Value::int(-10), "29"),
+            (Value::int(-100), "3863"),
+            (Value::int(-1000), "3903e7"),
+            (Value::Bytes(vec![]), "40"),
+            (Value::Bytes(vec![1, 2, 3, 4]), "4401020304"),
+            (text(""), "60"),
+            (text("IETF"), "6449455446"),
+            (text("\u{00fc}"), "62c3bc"),
+            (Value::Array(vec![]), "80"),
+            (Value::Array(vec![Value::int(1), Value::Array(vec![Value::int(2), Value::int(3)]), Value::Array(vec![Value::int(4), Value::int(5)])]), "8301820203820405"),
+            (Value::Map(vec![]), "a0"),
+            (Value::Map(vec![(Value::int(1), Value::int(2)), (Value::int(3), Value::int(4))]), "a201020304"),
+            (Value::Map(vec![(text("a"), Value::int(1)), (text("b"), Value::Array(vec![Value::int(2), Value::int(3)]))]), "a26161016162820203"),
+            (Value::Tag(1, Box::new(Value::int(1363896240))), "c11a514b67b0"),
+            (Value::Bool(false), "f4"),
+            (Value::Bool(true), "f5"),
+            (Value::Null, "f6"),
+        ];
+        for (value, encoded) in cases {
+            assert_eq!(to_hex(&value.encode()), encoded);
+            assert_eq!(Value::decode(&from_hex(encoded).unwrap()).unwrap(), value);
+        }
+        assert_eq!(Value::Negative(u64::MAX).as_int(), None);
+        assert_eq!(Value::int(i64::MIN).as_int(), Some(i64::MIN));
+    }
+
+    #[test]
+    fn test_deterministic_maps() {
+        // Keys sort by their encoding: 1, 3, then -1 (0x20), -2, -3, then text
+        let map = Value::Map(vec![(text("z"), Value::Null), (Value::int(-2), Value::Null), (Value::int(3), Value::Null), (Value::int(-1), Value::Null), (Value::int(1), Value::Null)]);
+        assert_eq!(to_hex(&map.encode()), "a501f603f620f621f6617af6");
+        let decoded = Value::decode(&map.encode()).unwrap();
+        assert_eq!(decoded.get(&Value::int(-2)), Some(&Value::Null));
+        assert_eq!(decoded.get(&Value::int(2)), None);
+    }
+
+    #[test]
+    fn test_rejected_encodings() {
+        for bad in [
+            "1817",     // 23 in a one-byte argument
+            "190017",   // and in two
+            "5f4101ff", // indefinite-length bytes
+            "a2010201", // duplicate key
+            "f97e00",   // half-precision float
+            "62c3",     // truncated text
+            "62c328",   // invalid UTF-8
+            "0000",     // two items
+            "1c",       // reserved additional info
+        ] {
+            assert!(Value::decode(&from_hex(bad).unwrap()).is_err(), "{}", bad);
+        }
+        assert!(Value::decode(&[0x81; 100].iter().copied().chain([0x00]).collect::<Vec<u8>>()).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::bip340::Bip340Signature;
+use crate::cbor::Value;
+use crate::curves::{self, NamedCurve};
+use crate::ecdsa::ECDSASignature;
+use crate::ed25519::Ed25519Signature;
+use crate::encoding;
+use crate::schnorr::SchnorrSignature;
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+
+// Deterministic CBOR (feature "cbor") for points, public keys and signatures, the compact
+// binary counterpart of the JSON forms for constrained devices. There is exactly one encoding
+// per value and decoding accepts only that one, so encodings can be compared or hashed:
+//   point            bstr, compressed SEC1 (0x00 for the identity)
+//   public key       [tstr curve name, bstr compressed point], never the identity
+//   ECDSA signature  [bstr r, bstr s], big-endian without leading zeros
+//   Schnorr          [bstr compressed R, bstr s]
+//   Ed25519, BIP-340 bstr, the usual 64 bytes
+// Points are validated on decoding; signature values are range-checked by the verifier.
+// COSE keys (cose.rs) are the interoperable key format, this one covers every named curve
+
+fn point_value(curve: &EllipticCurve, point: &Point) -> Value {
+    Value::Bytes(encoding::encode_point(curve, point, true))
+}
+
+fn read_point(curve: &EllipticCurve, value: &Value) -> Result<Point, &'static str> {
+    let bytes = value.as_bytes().ok_or("CBOR point must be a byte string")?;
+    if bytes.len() > 1 && bytes[0] == 0x04 {
+        return Err("CBOR points are compressed");
+    }
+    encoding::decode_point(curve, bytes)
+}
+
+fn unsigned_value(x: &BigUint) -> Value {
+    let bytes = x.to_bytes_be();
+    Value::Bytes(if bytes == [0] { vec![] } else { bytes })
+}
+
+fn read_unsigned(value: &Value) -> Result<BigUint, &'static str> {
+    let bytes = value.as_bytes().ok_or("CBOR integer must be a byte string")?;
+    if bytes.first() == Some(&0) {
+        return Err("CBOR integer has leading zeros");
+    }
+    Ok(BigUint::from_bytes_be(bytes))
+}
+
+fn read_pair(bytes: &[u8]) -> Result<[Value; 2], &'static str> {
+    let Value::Array(items) = Value::decode(bytes)? else {
+        return Err("expected a CBOR array");
+    };
+    items.try_into().map_err(|_| "expected a two-element CBOR array")
+}
+
+impl Point {
+    pub fn to_cbor(&self, curve: &EllipticCurve) -> Vec<u8> {
+        point_value(curve, self).encode()
+    }
+
+    pub fn from_cbor(curve: &EllipticCurve, bytes: &[u8]) -> Result<Point, &'static str> {
+        read_point(curve, &Value::decode(bytes)?)
+    }
+}
+
+impl NamedCurve {
+    pub fn public_key_to_cbor(&self, public_key: &Point) -> Vec<u8> {
+        assert!(*public_key != Point::Identity, "?the identity is not a public key?");
+        Value::Array(vec![Value::Text(self.name.to_string()), point_value(&self.curve, public_key)]).encode()
+    }
+
+    // The curve is looked up by name, so only registry curves decode
+    pub fn public_key_from_cbor(bytes: &[u8]) -> Result<(NamedCurve, Point), &'static str> {
+        let [name, point] = read_pair(bytes)?;
+        let name = name.as_text().ok_or("CBOR curve name must be text")?;
+        let curve = curves::by_name(name).filter(|curve| curve.name == name).ok_or("unknown curve")?;
+        let point = read_point(&curve.curve, &point)?;
+        if point == Point::Identity {
+            return Err("public key is the point at infinity");
+        }
+        Ok((curve, point))
+    }
+}
+
+impl ECDSASignature {
+    pub fn to_cbor(&self) -> Vec<u8> {
+        Value::Array(vec![unsigned_value(&self.r), unsigned_value(&self.s)]).encode()
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, &'static str> {
+        let [r, s] = read_pair(bytes)?;
+        Ok(ECDSASignature { r: read_unsigned(&r)?, s: read_unsigned(&s)? })
+    }
+}
+
+impl SchnorrSignature {
+    pub fn to_cbor(&self, curve: &EllipticCurve) -> Vec<u8> {
+        Value::Array(vec![point_value(curve, &self.r), unsigned_value(&self.s)]).encode()
+    }
+
+    pub fn from_cbor(curve: &EllipticCurve, bytes: &[u8]) -> Result<Self, &'static str> {
+        let [r, s] = read_pair(bytes)?;
+        Ok(SchnorrSignature { r: read_point(curve, &r)?, s: read_unsigned(&s)? })
+    }
+}
+
+impl Ed25519Signature {
+    pub fn to_cbor(&self) -> Vec<u8> {
+        Value::Bytes(self.to_bytes().to_vec()).encode()
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, &'static str> {
+        Ed25519Signature::from_bytes(Value::decode(bytes)?.as_bytes().ok_or("CBOR signature must be a byte string")?)
+    }
+}
+
+impl Bip340Signature {
+    pub fn to_cbor(&self) -> Vec<u8> {
+        Value::Bytes(self.to_bytes()).encode()
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, &'static str> {
+        Bip340Signature::from_bytes(Value::decode(bytes)?.as_bytes().ok_or("CBOR signature must be a byte string")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    #[test]
+    fn test_points_and_keys() {
+        let p256 = curves::p256();
+        let generator = p256.generator.clone();
+        let encoded = generator.to_cbor(&p256.curve);
+        // bstr(33) 03 || x(G)
+        assert_eq!(to_hex(&encoded), "582103".to_string() + "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+        assert_eq!(Point::from_cbor(&p256.curve, &encoded).unwrap(), generator);
+        assert_eq!(Point::from_cbor(&p256.curve, &Point::Identity.to_cbor(&p256.curve)).unwrap(), Point::Identity);
+
+        // The uncompressed form is a second encoding of the same point
+        let uncompressed = Value::Bytes(encoding::encode_point(&p256.curve, &generator, false)).encode();
+        assert!(Point::from_cbor(&p256.curve, &uncompressed).is_err());
+
+        for curve in [curves::secp256k1(), curves::p384(), curves::p521()] {
+            let key = curve.public_key_to_cbor(&curve.generator);
+            let (decoded, point) = NamedCurve::public_key_from_cbor(&key).unwrap();
+            assert_eq!((decoded.name, point), (curve.name, curve.generator.clone()));
+        }
+        let identity = Value::Array(vec![Value::Text(p256.name.to_string()), Value::Bytes(vec![0])]).encode();
+        assert!(NamedCurve::public_key_from_cbor(&identity).is_err());
+        let unknown = Value::Array(vec![Value::Text("p-255".to_string()), point_value(&p256.curve, &generator)]).encode();
+        assert!(NamedCurve::public_key_from_cbor(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_signatures() {
+        let signature = ECDSASignature { r: BigUint::from(0x0100u32), s: BigUint::from(0u32) };
+        assert_eq!(to_hex(&signature.to_cbor()), "8242010040");
+        assert_eq!(ECDSASignature::from_cbor(&signature.to_cbor()).unwrap(), signature);
+        assert!(ECDSASignature::from_cbor(&from_hex("824300010040").unwrap()).is_err());
+        assert!(ECDSASignature::from_cbor(&from_hex("83420100404101").unwrap()).is_err());
+
+        let p256 = curves::p256();
+        let schnorr = SchnorrSignature { r: p256.generator.clone(), s: BigUint::from(7u32) };
+        assert_eq!(SchnorrSignature::from_cbor(&p256.curve, &schnorr.to_cbor(&p256.curve)).unwrap(), schnorr);
+
+        let ed25519 = Ed25519Signature::from_bytes(&[5; 64]).unwrap();
+        assert_eq!(ed25519.to_cbor()[..2], [0x58, 0x40]);
+        assert_eq!(Ed25519Signature::from_cbor(&ed25519.to_cbor()).unwrap(), ed25519);
+        let bip340 = Bip340Signature::from_bytes(&[6; 64]).unwrap();
+        assert_eq!(Bip340Signature::from_cbor(&bip340.to_cbor()).unwrap(), bip340);
+        assert!(Bip340Signature::from_cbor(&Value::Bytes(vec![6; 63]).encode()).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::curves::NamedCurve;
+use crate::ntheory;
+use crate::{EllipticCurve, FiniteField, Point};
+use num_bigint::{BigUint, RandBigInt};
+use rand::Rng;
+
+// Curve construction by complex multiplication, for the nine discriminants of class number one
+//
+// A curve over F_p whose endomorphism ring has discriminant D exists exactly when
+//   4p = t² + |D|v²
+// and then its order is p + 1 - t up to twisting. So rather than picking a curve and counting,
+// pick t and v, keep p if it's prime, keep the order if it's h * (prime) for a small h, and only
+// then write down a curve: for class number one its j-invariant is an integer known in advance,
+// and y² = x³ + 3kx + 2k with k = j / (1728 - j) has that j. Of it and its quadratic twist, the
+// one whose points are killed by the order is the right one. j = 0 (D = -3) and j = 1728
+// (D = -4) have six and four twists, y² = x³ + b and y² = x³ + ax, which are tried in turn.
+// For D = -4, -7 and -8, t is always even, so every order is even and needs a cofactor.
+// These curves are fine for experiments and teaching; the small discriminant gives them extra
+// structure (an efficient endomorphism, as on secp256k1), which is not a known weakness for
+// the DLP but is why standards prefer random curves
+
+// (D, j) for every imaginary quadratic order of class number one
+pub const CLASS_NUMBER_ONE: [(i64, i64); 9] = [
+    (-3, 0),
+    (-4, 1728),
+    (-7, -3375),
+    (-8, 8000),
+    (-11, -32768),
+    (-19, -884736),
+    (-43, -884736000),
+    (-67, -147197952000),
+    (-163, -262537412640768000),
+];
+
+// Curves tried for j = 0 and j = 1728, and random points checked per candidate curve
+const MAX_TWISTS: u32 = 100;
+const ORDER_CHECKS: u32 = 16;
+
+pub fn j_invariant(discriminant: i64) -> Option<i64> {
+    CLASS_NUMBER_ONE.iter().find(|(d, _)| *d == discriminant).map(|(_, j)| *j)
+}
+
+pub(crate) fn reduce(value: i64, p: &BigUint) -> BigUint {
+    let magnitude = BigUint::from(value.unsigned_abs()) % p;
+    if value < 0 { (p - magnitude) % p } else { magnitude }
+}
+
+// Whether n * P = O for a few random points: true for the curve of order n, and for any other
+// curve over F_p only if every sampled point's order divides gcd(n, #E), which is below n's
+// large prime factor
+fn has_order<R: Rng + ?Sized>(curve: &EllipticCurve, n: &BigUint, rng: &mut R) -> bool {
+    (0..ORDER_CHECKS).all(|_| curve.random_point(rng).is_ok_and(|point| curve.scalar_mult(&point, n) == Point::Identity))
+}
+
+// The curve with j-invariant j and order n over F_p
+fn curve_with_order<R: Rng + ?Sized>(j: i64, p: &BigUint, n: &BigUint, rng: &mut R) -> Option<EllipticCurve> {
+    let zero = BigUint::from(0u32);
+    let candidates: Vec<EllipticCurve> = match j {
+        0 => (1..=MAX_TWISTS).map(|b| EllipticCurve::new(zero.clone(), BigUint::from(b), p.clone())).collect(),
+        1728 => (1..=MAX_TWISTS).map(|a| EllipticCurve::new(BigUint::from(a), zero.clone(), p.clone())).collect(),
+        _ => {
+            let field = FiniteField { p: p.clone() };
+            let k = field.div(&reduce(j, p), &reduce(1728 - j, p));
+            let curve = EllipticCurve::new(field.mul(&k, &BigUint::from(3u32)), field.mul(&k, &BigUint::from(2u32)), p.clone());
+            let twist = curve.quadratic_twist().ok()?;
+            vec![curve, twist]
+        }
+    };
+    candidates.into_iter().find(|curve| has_order(curve, n, rng))
+}
+
+// Order h * q with q prime, q > 4√p and h <= max_cofactor, preferring the smallest h
+// q > 4√p makes h * q the only order in the Hasse interval that q divides
+fn split_order(n: &BigUint, p: &BigUint, max_cofactor: u32) -> Option<(BigUint, BigUint)> {
+    (1..=max_cofactor).find_map(|h| {
+        let h = BigUint::from(h);
+        let q = n / &h;
+        let large = &q * &q > p * 16u32;
+        (large && (n % &h) == BigUint::from(0u32) && ntheory::is_probable_prime(&q)).then_some((q, h))
+    })
+}
+
+// A curve over a `bits`-bit prime field with discriminant D and order h * q, q prime and
+// h <= max_cofactor (1 for prime order), with a generator of the order-q subgroup
+pub fn generate<R: Rng + ?Sized>(discriminant: i64, bits: u64, max_cofactor: u32, rng: &mut R) -> Result<NamedCurve, &'static str> {
+    let j = j_invariant(discriminant).ok_or("discriminant must have class number one")?;
+    if !(8..=521).contains(&bits) || max_cofactor == 0 {
+        return Err("unsupported field size or cofactor");
+    }
+    if max_cofactor == 1 && [-4, -7, -8].contains(&discriminant) {
+        return Err("orders for this discriminant are even, allow a cofactor");
+    }
+    let d = BigUint::from(discriminant.unsigned_abs());
+
+    // t between 2√(2^(bits-1)) and 2√(2^bits), so p ≈ t²/4 has the requested size
+    let low = (BigUint::from(1u32) << (bits + 1)).sqrt();
+    let high = (BigUint::from(1u32) << (bits + 2)).sqrt();
+    for _ in 0..100_000 {
+        let t = rng.gen_biguint_range(&low, &high);
+        let v = BigUint::from(rng.gen_range(1u32..=4));
+        let norm = &t * &t + &d * &v * &v;
+        // t and v need the same parity (D = 1 mod 4), or t even (D = 0 mod 4)
+        if norm.bit(0) || norm.bit(1) {
+            continue;
+        }
+        let p: BigUint = norm >> 2;
+        if p.bits() != bits || !ntheory::is_probable_prime(&p) {
+            continue;
+        }
+
+        for n in [&p + 1u32 - &t, &p + 1u32 + &t] {
+            let Some((q, h)) = split_order(&n, &p, max_cofactor) else { continue };
+            let Some(curve) = curve_with_order(j, &p, &n, rng) else { continue };
+            let generator = curve.find_generator_of_order(&q, Some(&n), rng)?;
+            return Ok(NamedCurve { name: "cm", curve, generator, order: q, cofactor: h });
+        }
+    }
+    Err("no suitable curve found")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::Entropy;
+
+    #[test]
+    fn test_small_curves() {
+        for (discriminant, _) in CLASS_NUMBER_ONE {
+            let even = [-4, -7, -8].contains(&discriminant);
+            let curve = generate(discriminant, 16, if even { 4 } else { 1 }, &mut Entropy).unwrap();
+            assert_eq!(curve.cofactor.bit(0), !even);
+            assert_eq!(curve.curve.p().bits(), 16);
+            assert_eq!(curve.curve.count_points(), Ok(&curve.order * &curve.cofactor));
+            assert_eq!(curve.ecdsa().validate(), Ok(()));
+        }
+        assert!(generate(-5, 32, 1, &mut Entropy).is_err());
+        assert!(generate(-7, 4, 1, &mut Entropy).is_err());
+        assert!(generate(-7, 32, 1, &mut Entropy).is_err());
+    }
+
+    #[test]
+    fn test_near_prime_order() {
+        // y² = x³ + ax always has a point of order 2; the order is checked by Schoof's algorithm
+        let curve = generate(-4, 40, 4, &mut Entropy).unwrap();
+        assert!(curve.cofactor == BigUint::from(2u32) || curve.cofactor == BigUint::from(4u32));
+        assert_eq!(curve.curve.count_points_schoof(), Ok(&curve.order * &curve.cofactor));
+        assert_eq!(curve.ecdsa().validate(), Ok(()));
+
+        // A 256-bit curve in the spirit of secp256k1 (also j = 0)
+        let curve = generate(-3, 256, 1, &mut Entropy).unwrap();
+        assert_eq!(*curve.curve.a(), BigUint::from(0u32));
+        assert_eq!(curve.ecdsa().validate(), Ok(()));
+    }
+}
@@ -0,0 +1,469 @@
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Curves with compile-time parameters
+//
+// The rest of the crate carries p, a, b as runtime BigUints, so every operation allocates and
+// walks arbitrary-length numbers. Here the modulus is an associated constant of a marker type,
+// field elements are fixed arrays of N 64-bit limbs, and the compiler monomorphizes (and
+// unrolls) the arithmetic for each curve. Elements live in Montgomery form x R mod p with
+// R = 2^(64 N), so a product needs no division: mont_mul(a R, b R) = a b R.
+//   INV = -p⁻¹ mod 2^64 and R² mod p are derived from p by const fns, at compile time
+// const_curve! declares a marker type with a modulus and short Weierstrass parameters;
+// P256 and Secp256k1 are defined with it below. Conversions to and from Point and
+// EllipticCurve let results be checked against (or handed to) the runtime code
+
+// Little-endian limbs from a big-endian hex string, usable in constants
+pub const fn limbs_from_hex<const N: usize>(hex: &str) -> [u64; N] {
+    let bytes = hex.as_bytes();
+    let mut limbs = [0u64; N];
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[bytes.len() - 1 - i];
+        let digit = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            _ => panic!("?invalid hex digit in curve constant?"),
+        };
+        if i / 16 < N {
+            limbs[i / 16] |= (digit as u64) << (4 * (i % 16));
+        } else if digit != 0 {
+            panic!("?curve constant does not fit in the limbs?");
+        }
+        i += 1;
+    }
+    limbs
+}
+
+const fn geq<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+const fn add_limbs<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], bool) {
+    let mut sum = [0u64; N];
+    let mut carry = false;
+    let mut i = 0;
+    while i < N {
+        let (s, c1) = a[i].overflowing_add(b[i]);
+        let (s, c2) = s.overflowing_add(carry as u64);
+        sum[i] = s;
+        carry = c1 || c2;
+        i += 1;
+    }
+    (sum, carry)
+}
+
+const fn sub_limbs<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], bool) {
+    let mut difference = [0u64; N];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < N {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        difference[i] = d;
+        borrow = b1 || b2;
+        i += 1;
+    }
+    (difference, borrow)
+}
+
+const fn add_mod<const N: usize>(a: &[u64; N], b: &[u64; N], p: &[u64; N]) -> [u64; N] {
+    let (sum, carry) = add_limbs(a, b);
+    if carry || geq(&sum, p) { sub_limbs(&sum, p).0 } else { sum }
+}
+
+const fn sub_mod<const N: usize>(a: &[u64; N], b: &[u64; N], p: &[u64; N]) -> [u64; N] {
+    let (difference, borrow) = sub_limbs(a, b);
+    if borrow { add_limbs(&difference, p).0 } else { difference }
+}
+
+// -p⁻¹ mod 2^64 by Newton iteration: each step doubles the number of correct bits
+const fn mont_inv(p0: u64) -> u64 {
+    let mut inv = 1u64;
+    let mut i = 0;
+    while i < 6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+// R² = 2^(128 N) mod p by repeated doubling
+const fn r_squared<const N: usize>(p: &[u64; N]) -> [u64; N] {
+    let mut r = [0u64; N];
+    r[0] = 1;
+    let mut i = 0;
+    while i < 128 * N {
+        r = add_mod(&r, &r, p);
+        i += 1;
+    }
+    r
+}
+
+// a b R⁻¹ mod p (CIOS: interleave multiplying by b[i] with cancelling the low limb)
+const fn mont_mul<const N: usize>(a: &[u64; N], b: &[u64; N], p: &[u64; N], inv: u64) -> [u64; N] {
+    let mut t = [0u64; N];
+    let mut t_high = 0u64;
+    let mut i = 0;
+    while i < N {
+        let mut carry = 0u64;
+        let mut j = 0;
+        while j < N {
+            let s = t[j] as u128 + (a[j] as u128) * (b[i] as u128) + carry as u128;
+            t[j] = s as u64;
+            carry = (s >> 64) as u64;
+            j += 1;
+        }
+        let s = t_high as u128 + carry as u128;
+        t_high = s as u64;
+        let t_overflow = (s >> 64) as u64;
+
+        // Add m p so the low limb becomes zero, then shift down one limb
+        let m = t[0].wrapping_mul(inv);
+        let s = t[0] as u128 + (m as u128) * (p[0] as u128);
+        carry = (s >> 64) as u64;
+        j = 1;
+        while j < N {
+            let s = t[j] as u128 + (m as u128) * (p[j] as u128) + carry as u128;
+            t[j - 1] = s as u64;
+            carry = (s >> 64) as u64;
+            j += 1;
+        }
+        let s = t_high as u128 + carry as u128;
+        t[N - 1] = s as u64;
+        t_high = t_overflow + (s >> 64) as u64;
+        i += 1;
+    }
+    if t_high != 0 || geq(&t, p) { sub_limbs(&t, p).0 } else { t }
+}
+
+fn limbs_to_biguint<const N: usize>(limbs: &[u64; N]) -> BigUint {
+    let digits: Vec<u32> = limbs.iter().flat_map(|&l| [l as u32, (l >> 32) as u32]).collect();
+    BigUint::from_slice(&digits)
+}
+
+fn biguint_to_limbs<const N: usize>(x: &BigUint) -> [u64; N] {
+    let mut limbs = [0u64; N];
+    for (limb, digit) in limbs.iter_mut().zip(x.iter_u64_digits()) {
+        *limb = digit;
+    }
+    limbs
+}
+
+// An odd prime modulus known at compile time
+pub trait Modulus<const N: usize>: Copy + std::fmt::Debug + PartialEq + Eq + 'static {
+    const P: [u64; N];
+    const INV: u64 = mont_inv(Self::P[0]);
+    const R2: [u64; N] = r_squared(&Self::P);
+
+    fn modulus() -> BigUint {
+        limbs_to_biguint(&Self::P)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fp<M: Modulus<N>, const N: usize> {
+    limbs: [u64; N],
+    modulus: PhantomData<M>,
+}
+
+impl<M: Modulus<N>, const N: usize> Fp<M, N> {
+    pub const fn zero() -> Self {
+        Fp { limbs: [0u64; N], modulus: PhantomData }
+    }
+
+    pub const fn one() -> Self {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        Self::from_limbs(one)
+    }
+
+    // From canonical limbs (< p), into Montgomery form
+    pub const fn from_limbs(limbs: [u64; N]) -> Self {
+        Fp { limbs: mont_mul(&limbs, &M::R2, &M::P, M::INV), modulus: PhantomData }
+    }
+
+    pub const fn from_hex(hex: &str) -> Self {
+        let limbs = limbs_from_hex(hex);
+        if geq(&limbs, &M::P) {
+            panic!("?field constant is not reduced?");
+        }
+        Self::from_limbs(limbs)
+    }
+
+    pub fn from_biguint(x: &BigUint) -> Self {
+        Self::from_limbs(biguint_to_limbs(&(x % M::modulus())))
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        limbs_to_biguint(&mont_mul(&self.limbs, &one, &M::P, M::INV))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs == [0u64; N]
+    }
+
+    pub fn square(&self) -> Self {
+        *self * *self
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> Self {
+        let mut result = Self::one();
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result = result * *self;
+            }
+        }
+        result
+    }
+
+    // Fermat: x^(p-2)
+    pub fn inv(&self) -> Self {
+        if self.is_zero() {
+            panic!("?division by zero in finite field?");
+        }
+        self.pow(&(M::modulus() - BigUint::from(2u32)))
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Add for Fp<M, N> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Fp { limbs: add_mod(&self.limbs, &other.limbs, &M::P), modulus: PhantomData }
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Sub for Fp<M, N> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Fp { limbs: sub_mod(&self.limbs, &other.limbs, &M::P), modulus: PhantomData }
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Mul for Fp<M, N> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Fp { limbs: mont_mul(&self.limbs, &other.limbs, &M::P, M::INV), modulus: PhantomData }
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Div for Fp<M, N> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inv()
+    }
+}
+
+impl<M: Modulus<N>, const N: usize> Neg for Fp<M, N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::zero() - self
+    }
+}
+
+// y² = x³ + ax + b over the marker type's own field
+pub trait ConstCurve<const N: usize>: Modulus<N> {
+    const A: Fp<Self, N>;
+    const B: Fp<Self, N>;
+    const GX: Fp<Self, N>;
+    const GY: Fp<Self, N>;
+    const ORDER: &'static str;
+
+    fn order() -> BigUint {
+        BigUint::parse_bytes(Self::ORDER.as_bytes(), 16).expect("?invalid curve constant?")
+    }
+
+    // The same curve with runtime parameters
+    fn runtime_curve() -> EllipticCurve {
+        EllipticCurve::new(Self::A.to_biguint(), Self::B.to_biguint(), Self::modulus())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConstPoint<C: ConstCurve<N>, const N: usize> {
+    Affine(Fp<C, N>, Fp<C, N>),
+    Identity,
+}
+
+impl<C: ConstCurve<N>, const N: usize> ConstPoint<C, N> {
+    pub fn generator() -> Self {
+        ConstPoint::Affine(C::GX, C::GY)
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        match *self {
+            ConstPoint::Identity => true,
+            ConstPoint::Affine(x, y) => y.square() == x.square() * x + C::A * x + C::B,
+        }
+    }
+
+    pub fn neg(&self) -> Self {
+        match *self {
+            ConstPoint::Identity => ConstPoint::Identity,
+            ConstPoint::Affine(x, y) => ConstPoint::Affine(x, -y),
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        match *self {
+            ConstPoint::Identity => ConstPoint::Identity,
+            ConstPoint::Affine(x, y) => {
+                if y.is_zero() {
+                    return ConstPoint::Identity;
+                }
+                let x_squared = x.square();
+                let slope = (x_squared + x_squared + x_squared + C::A) / (y + y);
+                let x3 = slope.square() - x - x;
+                ConstPoint::Affine(x3, slope * (x - x3) - y)
+            }
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        match (*self, *other) {
+            (ConstPoint::Identity, _) => *other,
+            (_, ConstPoint::Identity) => *self,
+            (ConstPoint::Affine(x1, y1), ConstPoint::Affine(x2, y2)) => {
+                if x1 == x2 {
+                    return if y1 == y2 { self.double() } else { ConstPoint::Identity };
+                }
+                let slope = (y2 - y1) / (x2 - x1);
+                let x3 = slope.square() - x1 - x2;
+                ConstPoint::Affine(x3, slope * (x1 - x3) - y1)
+            }
+        }
+    }
+
+    pub fn scalar_mult(&self, k: &BigUint) -> Self {
+        let mut result = ConstPoint::Identity;
+        for i in (0..k.bits()).rev() {
+            result = result.double();
+            if k.bit(i) {
+                result = result.add(self);
+            }
+        }
+        result
+    }
+
+    pub fn to_point(&self) -> Point {
+        match self {
+            ConstPoint::Identity => Point::Identity,
+            ConstPoint::Affine(x, y) => Point::Coordinate(x.to_biguint(), y.to_biguint()),
+        }
+    }
+
+    pub fn from_point(point: &Point) -> Result<Self, &'static str> {
+        let converted = match point {
+            Point::Identity => ConstPoint::Identity,
+            Point::Coordinate(x, y) => ConstPoint::Affine(Fp::from_biguint(x), Fp::from_biguint(y)),
+        };
+        if !converted.is_on_curve() {
+            return Err("point is not on the curve");
+        }
+        Ok(converted)
+    }
+}
+
+// Declares a marker type for a short Weierstrass curve with N-limb parameters:
+//   const_curve!(pub P256, 4, p = "...", a = "...", b = "...", gx = "...", gy = "...", n = "...");
+#[macro_export]
+macro_rules! const_curve {
+    ($vis:vis $name:ident, $limbs:literal, p = $p:literal, a = $a:literal, b = $b:literal, gx = $gx:literal, gy = $gy:literal, n = $n:literal) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        $vis struct $name;
+
+        impl $crate::const_curve::Modulus<$limbs> for $name {
+            const P: [u64; $limbs] = $crate::const_curve::limbs_from_hex($p);
+        }
+
+        impl $crate::const_curve::ConstCurve<$limbs> for $name {
+            const A: $crate::const_curve::Fp<$name, $limbs> = $crate::const_curve::Fp::from_hex($a);
+            const B: $crate::const_curve::Fp<$name, $limbs> = $crate::const_curve::Fp::from_hex($b);
+            const GX: $crate::const_curve::Fp<$name, $limbs> = $crate::const_curve::Fp::from_hex($gx);
+            const GY: $crate::const_curve::Fp<$name, $limbs> = $crate::const_curve::Fp::from_hex($gy);
+            const ORDER: &'static str = $n;
+        }
+    };
+}
+
+const_curve!(
+    pub P256,
+    4,
+    p = "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    a = "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+    b = "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+    gx = "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+    gy = "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+    n = "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+);
+
+const_curve!(
+    pub Secp256k1,
+    4,
+    p = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    a = "0",
+    b = "7",
+    gx = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    gy = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+    n = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use num_bigint::RandBigInt;
+
+    // Evaluated entirely by the compiler
+    const SEVEN: Fp<Secp256k1, 4> = Fp::from_hex("7");
+
+    #[test]
+    fn test_field_matches_biguint() {
+        let p = P256::modulus();
+        let mut rng = crate::entropy::Entropy;
+        for _ in 0..20 {
+            let (x, y) = (rng.gen_biguint_below(&p), rng.gen_biguint_below(&p));
+            let (fx, fy) = (Fp::<P256, 4>::from_biguint(&x), Fp::<P256, 4>::from_biguint(&y));
+            assert_eq!((fx + fy).to_biguint(), (&x + &y) % &p);
+            assert_eq!((fx - fy).to_biguint(), (&x + &p - &y) % &p);
+            assert_eq!((fx * fy).to_biguint(), (&x * &y) % &p);
+            assert_eq!((fx / fy * fy), fx);
+            assert_eq!((-fx + fx), Fp::zero());
+        }
+        assert_eq!(SEVEN, Secp256k1::B);
+        assert_eq!(SEVEN.to_biguint(), BigUint::from(7u32));
+    }
+
+    #[test]
+    fn test_const_curves_match_registry() {
+        assert_eq!(P256::runtime_curve(), curves::p256().curve);
+        assert_eq!(Secp256k1::runtime_curve(), curves::secp256k1().curve);
+        assert_eq!(ConstPoint::<P256, 4>::generator().to_point(), curves::p256().generator);
+
+        let g = ConstPoint::<Secp256k1, 4>::generator();
+        assert!(g.is_on_curve());
+        assert_eq!(g.scalar_mult(&Secp256k1::order()), ConstPoint::Identity);
+        assert_eq!(g.add(&g.neg()), ConstPoint::Identity);
+
+        let named = curves::secp256k1();
+        let k = BigUint::from(0x1234_5678_9abc_def0u64) << 100;
+        let expected = named.curve.scalar_mult(&named.generator, &k);
+        assert_eq!(g.scalar_mult(&k).to_point(), expected);
+        assert_eq!(ConstPoint::<Secp256k1, 4>::from_point(&expected).unwrap(), g.scalar_mult(&k));
+        assert!(ConstPoint::<Secp256k1, 4>::from_point(&Point::Coordinate(BigUint::from(1u32), BigUint::from(1u32))).is_err());
+    }
+}
@@ -0,0 +1,444 @@
+use crate::cbor::Value;
+use crate::curves::{self, NamedCurve};
+use crate::ecdsa::ECDSASignature;
+use crate::ed25519::{Ed25519, Ed25519Signature};
+use crate::encoding;
+use crate::Point;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+// COSE (RFC 9052 / 9053) keys and single-signer messages, as used by WebAuthn and CBOR IoT
+// protocols
+//
+// A COSE_Key is a CBOR map with integer labels:
+//   1 kty (2 = EC2, 1 = OKP)   3 alg   -1 crv   -2 x   -3 y (EC2 only)   -4 d (private)
+// EC2 keys are P-256/384/521 points, x and y big-endian at the field size (y may instead be
+// a bool, the sign bit of a compressed point); OKP keys here are Ed25519, x the encoded point
+//
+// COSE_Sign1 is tag 18 over [protected, unprotected, payload, signature]. protected is a map
+// serialized into a byte string, so it's signed exactly as received; the signature covers
+//   Sig_structure = ["Signature1", protected, external_aad, payload]
+// in CBOR. ECDSA signatures are r || s at the order's size, not DER. Verification requires
+// the protected alg to be the key's algorithm
+
+const SIGN1_TAG: u64 = 18;
+
+// Header and key parameter labels
+const ALG: i64 = 1;
+const KID: i64 = 4;
+const KTY: i64 = 1;
+const KEY_ALG: i64 = 3;
+const CRV: i64 = -1;
+const X: i64 = -2;
+const Y: i64 = -3;
+const D: i64 = -4;
+
+const KTY_OKP: i64 = 1;
+const KTY_EC2: i64 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoseAlgorithm {
+    ES256,
+    ES384,
+    ES512,
+    EdDSA,
+}
+
+impl CoseAlgorithm {
+    pub fn id(self) -> i64 {
+        match self {
+            CoseAlgorithm::ES256 => -7,
+            CoseAlgorithm::ES384 => -35,
+            CoseAlgorithm::ES512 => -36,
+            CoseAlgorithm::EdDSA => -8,
+        }
+    }
+
+    pub fn from_id(id: i64) -> Option<Self> {
+        [CoseAlgorithm::ES256, CoseAlgorithm::ES384, CoseAlgorithm::ES512, CoseAlgorithm::EdDSA].into_iter().find(|alg| alg.id() == id)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoseCurve {
+    P256,
+    P384,
+    P521,
+    Ed25519,
+}
+
+impl CoseCurve {
+    pub fn id(self) -> i64 {
+        match self {
+            CoseCurve::P256 => 1,
+            CoseCurve::P384 => 2,
+            CoseCurve::P521 => 3,
+            CoseCurve::Ed25519 => 6,
+        }
+    }
+
+    fn from_id(id: i64) -> Option<Self> {
+        [CoseCurve::P256, CoseCurve::P384, CoseCurve::P521, CoseCurve::Ed25519].into_iter().find(|crv| crv.id() == id)
+    }
+
+    // The signature algorithm keys on this curve are used with
+    pub fn algorithm(self) -> CoseAlgorithm {
+        match self {
+            CoseCurve::P256 => CoseAlgorithm::ES256,
+            CoseCurve::P384 => CoseAlgorithm::ES384,
+            CoseCurve::P521 => CoseAlgorithm::ES512,
+            CoseCurve::Ed25519 => CoseAlgorithm::EdDSA,
+        }
+    }
+
+    // The Weierstrass curve behind an EC2 key
+    fn named_curve(self) -> Option<NamedCurve> {
+        match self {
+            CoseCurve::P256 => Some(curves::p256()),
+            CoseCurve::P384 => Some(curves::p384()),
+            CoseCurve::P521 => Some(curves::p521()),
+            CoseCurve::Ed25519 => None,
+        }
+    }
+
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            CoseCurve::P384 => Sha384::digest(data).to_vec(),
+            CoseCurve::P521 => Sha512::digest(data).to_vec(),
+            _ => Sha256::digest(data).to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CoseKey {
+    // A point on P-256/384/521, with the private scalar when there is one
+    Ec2 { curve: CoseCurve, point: Point, d: Option<BigUint> },
+    // An encoded Ed25519 public key, with the 32-byte secret when there is one
+    Okp { x: [u8; 32], d: Option<[u8; 32]> },
+}
+
+impl CoseKey {
+    pub fn ec2_from_private(curve: CoseCurve, d: BigUint) -> Result<Self, &'static str> {
+        let named = curve.named_curve().ok_or("not an EC2 curve")?;
+        if d == BigUint::from(0u32) || d >= named.order {
+            return Err("private key out of range");
+        }
+        Ok(CoseKey::Ec2 { curve, point: named.ecdsa().generate_public_key(&d), d: Some(d) })
+    }
+
+    pub fn okp_from_secret(secret: [u8; 32]) -> Self {
+        CoseKey::Okp { x: Ed25519::new().public_key(&secret), d: Some(secret) }
+    }
+
+    pub fn curve(&self) -> CoseCurve {
+        match self {
+            CoseKey::Ec2 { curve, .. } => *curve,
+            CoseKey::Okp { .. } => CoseCurve::Ed25519,
+        }
+    }
+
+    // The same key without its private part
+    pub fn public_key(&self) -> CoseKey {
+        match self {
+            CoseKey::Ec2 { curve, point, .. } => CoseKey::Ec2 { curve: *curve, point: point.clone(), d: None },
+            CoseKey::Okp { x, .. } => CoseKey::Okp { x: *x, d: None },
+        }
+    }
+
+    pub fn to_value(&self) -> Result<Value, &'static str> {
+        let curve = self.curve();
+        let mut entries = vec![(Value::int(KEY_ALG), Value::int(curve.algorithm().id())), (Value::int(CRV), Value::int(curve.id()))];
+        match self {
+            CoseKey::Ec2 { point, d, .. } => {
+                let Point::Coordinate(x, y) = point else {
+                    return Err("the identity has no COSE_Key");
+                };
+                let named = curve.named_curve().unwrap();
+                let len = named.field_size();
+                entries.push((Value::int(KTY), Value::int(KTY_EC2)));
+                entries.push((Value::int(X), Value::Bytes(encoding::to_fixed_bytes(x, len))));
+                entries.push((Value::int(Y), Value::Bytes(encoding::to_fixed_bytes(y, len))));
+                if let Some(d) = d {
+                    entries.push((Value::int(D), Value::Bytes(encoding::to_fixed_bytes(d, named.scalar_size()))));
+                }
+            }
+            CoseKey::Okp { x, d } => {
+                entries.push((Value::int(KTY), Value::int(KTY_OKP)));
+                entries.push((Value::int(X), Value::Bytes(x.to_vec())));
+                if let Some(d) = d {
+                    entries.push((Value::int(D), Value::Bytes(d.to_vec())));
+                }
+            }
+        }
+        Ok(Value::Map(entries))
+    }
+
+    // The public point is validated, and a private key has to match it
+    pub fn from_value(value: &Value) -> Result<Self, &'static str> {
+        let param = |label: i64| value.get(&Value::int(label));
+        let bytes = |label: i64| param(label).and_then(Value::as_bytes).ok_or("missing or malformed COSE_Key parameter");
+        let kty = param(KTY).and_then(Value::as_int).ok_or("missing COSE_Key kty")?;
+        let curve = CoseCurve::from_id(param(CRV).and_then(Value::as_int).ok_or("missing COSE_Key crv")?).ok_or("unsupported COSE curve")?;
+        if param(KEY_ALG).is_some_and(|alg| alg.as_int() != Some(curve.algorithm().id())) {
+            return Err("COSE_Key alg does not match its curve");
+        }
+
+        let key = match (kty, curve.named_curve()) {
+            (KTY_EC2, Some(named)) => {
+                let len = named.field_size();
+                let x = bytes(X)?;
+                if x.len() != len {
+                    return Err("COSE_Key coordinate has the wrong length");
+                }
+                // y is either the coordinate or the sign bit of a compressed point
+                let encoded = match param(Y) {
+                    Some(Value::Bytes(y)) if y.len() == len => [&[0x04], x, y].concat(),
+                    Some(Value::Bool(sign)) => [&[0x02 | *sign as u8], x].concat(),
+                    _ => return Err("missing or malformed COSE_Key parameter"),
+                };
+                let point = encoding::decode_point(&named.curve, &encoded)?;
+                if point == Point::Identity {
+                    return Err("public key is the point at infinity");
+                }
+                let d = param(D).map(|_| bytes(D).map(BigUint::from_bytes_be)).transpose()?;
+                if let Some(d) = &d
+                    && (*d == BigUint::from(0u32) || *d >= named.order || named.ecdsa().generate_public_key(d) != point)
+                {
+                    return Err("COSE_Key private key does not match its public key");
+                }
+                CoseKey::Ec2 { curve, point, d }
+            }
+            (KTY_OKP, None) => {
+                let x: [u8; 32] = bytes(X)?.try_into().map_err(|_| "Ed25519 keys are 32 bytes")?;
+                Ed25519::new().decode(&x)?;
+                let d = param(D).map(|_| bytes(D)?.try_into().map_err(|_| "Ed25519 secrets are 32 bytes")).transpose()?;
+                if d.is_some_and(|d| Ed25519::new().public_key(&d) != x) {
+                    return Err("COSE_Key private key does not match its public key");
+                }
+                CoseKey::Okp { x, d }
+            }
+            _ => return Err("COSE_Key kty does not match its curve"),
+        };
+        Ok(key)
+    }
+
+    pub fn to_cbor(&self) -> Result<Vec<u8>, &'static str> {
+        Ok(self.to_value()?.encode())
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, &'static str> {
+        CoseKey::from_value(&Value::decode(bytes)?)
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, &'static str> {
+        match self {
+            CoseKey::Ec2 { curve, d: Some(d), .. } => {
+                let named = curve.named_curve().unwrap();
+                Ok(named.ecdsa().sign_digest(&curve.digest(data), d)?.to_fixed_bytes(named.scalar_size()))
+            }
+            CoseKey::Okp { d: Some(d), .. } => Ok(Ed25519::new().sign(d, data).to_bytes().to_vec()),
+            _ => Err("signing needs a private key"),
+        }
+    }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> bool {
+        match self {
+            CoseKey::Ec2 { curve, point, .. } => {
+                let named = curve.named_curve().unwrap();
+                match ECDSASignature::from_fixed_bytes(signature, named.scalar_size()) {
+                    Ok(signature) => named.ecdsa().verify_digest(&curve.digest(data), &signature, point),
+                    Err(_) => false,
+                }
+            }
+            CoseKey::Okp { x, .. } => match Ed25519Signature::from_bytes(signature) {
+                Ok(signature) => Ed25519::new().verify(x, data, &signature),
+                Err(_) => false,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoseSign1 {
+    // The serialized protected header map, kept as the exact bytes that were signed
+    pub protected: Vec<u8>,
+    pub unprotected: Value,
+    pub payload: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+fn sig_structure(protected: &[u8], external_aad: &[u8], payload: &[u8]) -> Vec<u8> {
+    Value::Array(vec![
+        Value::Text("Signature1".to_string()),
+        Value::Bytes(protected.to_vec()),
+        Value::Bytes(external_aad.to_vec()),
+        Value::Bytes(payload.to_vec()),
+    ])
+    .encode()
+}
+
+impl CoseSign1 {
+    // alg goes in the protected header, the kid (if any) in the unprotected one.
+    // external_aad is data the verifier must supply too, bound in without being sent
+    pub fn sign(key: &CoseKey, payload: &[u8], external_aad: &[u8], kid: Option<&[u8]>) -> Result<Self, &'static str> {
+        let protected = Value::Map(vec![(Value::int(ALG), Value::int(key.curve().algorithm().id()))]).encode();
+        let unprotected = Value::Map(kid.map(|kid| (Value::int(KID), Value::Bytes(kid.to_vec()))).into_iter().collect());
+        let signature = key.sign(&sig_structure(&protected, external_aad, payload))?;
+        Ok(CoseSign1 { protected, unprotected, payload: payload.to_vec(), signature })
+    }
+
+    // The protected alg, which is the only place alg is trusted from
+    pub fn algorithm(&self) -> Option<CoseAlgorithm> {
+        let protected = Value::decode(&self.protected).ok()?;
+        CoseAlgorithm::from_id(protected.get(&Value::int(ALG))?.as_int()?)
+    }
+
+    pub fn verify(&self, key: &CoseKey, external_aad: &[u8]) -> bool {
+        self.algorithm() == Some(key.curve().algorithm()) && key.verify(&sig_structure(&self.protected, external_aad, &self.payload), &self.signature)
+    }
+
+    pub fn kid(&self) -> Option<&[u8]> {
+        self.unprotected.get(&Value::int(KID))?.as_bytes()
+    }
+
+    // Tagged COSE_Sign1
+    pub fn to_cbor(&self) -> Vec<u8> {
+        Value::Tag(
+            SIGN1_TAG,
+            Box::new(Value::Array(vec![
+                Value::Bytes(self.protected.clone()),
+                self.unprotected.clone(),
+                Value::Bytes(self.payload.clone()),
+                Value::Bytes(self.signature.clone()),
+            ])),
+        )
+        .encode()
+    }
+
+    // Tagged or untagged; detached payloads (nil) aren't supported
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, &'static str> {
+        let value = Value::decode(bytes)?;
+        let message = match &value {
+            Value::Tag(SIGN1_TAG, inner) => inner.as_ref(),
+            Value::Tag(_, _) => return Err("not a COSE_Sign1 tag"),
+            _ => &value,
+        };
+        let [protected, unprotected, payload, signature] = message.as_array().ok_or("COSE_Sign1 is an array")? else {
+            return Err("COSE_Sign1 has four elements");
+        };
+        let protected = protected.as_bytes().ok_or("protected header must be a byte string")?;
+        if !protected.is_empty() && Value::decode(protected)?.as_map().is_none() {
+            return Err("protected header must be a map");
+        }
+        if unprotected.as_map().is_none() {
+            return Err("unprotected header must be a map");
+        }
+        Ok(CoseSign1 {
+            protected: protected.to_vec(),
+            unprotected: unprotected.clone(),
+            payload: payload.as_bytes().ok_or("payload must be a byte string")?.to_vec(),
+            signature: signature.as_bytes().ok_or("signature must be a byte string")?.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    // Key "11" from the COSE Working Group examples, the signer in RFC 9052 C.2.1
+    fn example_key() -> CoseKey {
+        CoseKey::ec2_from_private(CoseCurve::P256, BigUint::from_bytes_be(&from_hex("57c92077664146e876760c9520d054aa93c3afb04e306705db6090308507b4d3").unwrap())).unwrap()
+    }
+
+    #[test]
+    fn test_rfc9052_sign1() {
+        let key = example_key();
+        let CoseKey::Ec2 { point: Point::Coordinate(x, y), .. } = &key else { panic!() };
+        assert_eq!(to_hex(&x.to_bytes_be()), "bac5b11cad8f99f9c72b05cf4b9e26d244dc189f745228255a219a86d6a09eff");
+        assert_eq!(to_hex(&y.to_bytes_be()), "20138bf82dc1b6d562be0fa54ab7804a3a64b6d72ccfed6b6fb6ed28bbfc117e");
+
+        // C.2.1: ES256 over "This is the content." with kid '11'
+        let message = from_hex(concat!(
+            "d28443a10126a10442313154546869732069732074686520636f6e74656e742e58408eb33e4ca31d1c465ab05aac34cc6b23d58fef5c083106c4",
+            "d25a91aef0b0117e2af9a291aa32e14ab834dc56ed2a223444547e01f11d3b0916e5a4c345cacb36",
+        ))
+        .unwrap();
+        let sign1 = CoseSign1::from_cbor(&message).unwrap();
+        assert_eq!(sign1.algorithm(), Some(CoseAlgorithm::ES256));
+        assert_eq!(sign1.kid(), Some(&b"11"[..]));
+        assert_eq!(sign1.payload, b"This is the content.");
+        assert!(sign1.verify(&key.public_key(), b""));
+        assert!(!sign1.verify(&key.public_key(), b"aad"));
+        assert_eq!(sign1.to_cbor(), message);
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let keys = [
+            example_key(),
+            CoseKey::ec2_from_private(CoseCurve::P384, BigUint::from(12345u32)).unwrap(),
+            CoseKey::ec2_from_private(CoseCurve::P521, BigUint::from(67890u32)).unwrap(),
+            CoseKey::okp_from_secret([7; 32]),
+        ];
+        for key in keys {
+            let sign1 = CoseSign1::sign(&key, b"sensor reading", b"context", Some(b"device-1")).unwrap();
+            let parsed = CoseSign1::from_cbor(&sign1.to_cbor()).unwrap();
+            assert_eq!(parsed, sign1);
+            assert!(parsed.verify(&key.public_key(), b"context"));
+            assert!(!parsed.verify(&key.public_key(), b"other context"));
+
+            // A public-only key can't sign
+            assert!(CoseSign1::sign(&key.public_key(), b"x", b"", None).is_err());
+        }
+
+        // An ES256 message checked with an Ed25519 key
+        let sign1 = CoseSign1::sign(&example_key(), b"x", b"", None).unwrap();
+        assert!(!sign1.verify(&CoseKey::okp_from_secret([7; 32]).public_key(), b""));
+    }
+
+    #[test]
+    fn test_cose_keys() {
+        // A WebAuthn credential public key: {1: 2, 3: -7, -1: 1, -2: x, -3: y}
+        let key = example_key().public_key();
+        let encoded = key.to_cbor().unwrap();
+        assert_eq!(to_hex(&encoded[..10]), "a5010203262001215820");
+        assert_eq!(CoseKey::from_cbor(&encoded).unwrap(), key);
+
+        for key in [example_key(), CoseKey::okp_from_secret([9; 32]), CoseKey::okp_from_secret([9; 32]).public_key()] {
+            assert_eq!(CoseKey::from_cbor(&key.to_cbor().unwrap()).unwrap(), key);
+        }
+
+        // Compressed form: y is the sign bit
+        let CoseKey::Ec2 { point: Point::Coordinate(x, y), .. } = &key else { panic!() };
+        let compressed = Value::Map(vec![
+            (Value::int(KTY), Value::int(KTY_EC2)),
+            (Value::int(CRV), Value::int(1)),
+            (Value::int(X), Value::Bytes(x.to_bytes_be())),
+            (Value::int(Y), Value::Bool(y.bit(0))),
+        ]);
+        assert_eq!(CoseKey::from_value(&compressed).unwrap(), key);
+
+        // Wrong kty for the curve, mismatched alg, and a private key for another public key
+        let mut bad = key.to_value().unwrap();
+        let Value::Map(entries) = &mut bad else { panic!() };
+        entries.retain(|(k, _)| *k != Value::int(KTY));
+        entries.push((Value::int(KTY), Value::int(KTY_OKP)));
+        assert!(CoseKey::from_value(&bad).is_err());
+        let mut bad = key.to_value().unwrap();
+        let Value::Map(entries) = &mut bad else { panic!() };
+        entries.retain(|(k, _)| *k != Value::int(KEY_ALG));
+        entries.push((Value::int(KEY_ALG), Value::int(CoseAlgorithm::ES384.id())));
+        assert!(CoseKey::from_value(&bad).is_err());
+        let mut bad = key.to_value().unwrap();
+        let Value::Map(entries) = &mut bad else { panic!() };
+        entries.push((Value::int(D), Value::Bytes(vec![1; 32])));
+        assert!(CoseKey::from_value(&bad).is_err());
+
+        // The identity can't be written out
+        let identity = CoseKey::Ec2 { curve: CoseCurve::P256, point: Point::Identity, d: None };
+        assert!(identity.to_value().is_err() && identity.to_cbor().is_err());
+    }
+}
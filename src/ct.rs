@@ -0,0 +1,102 @@
+use num_bigint::BigUint;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+// Constant-time helpers for BigUint values, built on subtle's Choice
+//
+// BigUint drops leading zero limbs and its own == and < stop at the first difference, so
+// comparing or branching on a secret leaks where it differs through the timing.
+// These pad both operands to the same number of 64-bit limbs and touch every limb.
+// The length itself (the larger operand's limb count) is not hidden: pass values reduced mod
+// a public modulus, or use the _padded variants with the modulus' width
+
+fn limbs(x: &BigUint, len: usize) -> Vec<u64> {
+    let mut digits = x.to_u64_digits();
+    digits.resize(len, 0);
+    digits
+}
+
+fn width(a: &BigUint, b: &BigUint) -> usize {
+    a.to_u64_digits().len().max(b.to_u64_digits().len())
+}
+
+// Limb-slice equality; slices must have the same length
+pub fn limbs_eq(a: &[u64], b: &[u64]) -> Choice {
+    assert_eq!(a.len(), b.len(), "?limb slices of different lengths?");
+    a.iter().zip(b).fold(Choice::from(1u8), |acc, (x, y)| acc & x.ct_eq(y))
+}
+
+// a < b on limb slices: the final borrow of a - b
+pub fn limbs_lt(a: &[u64], b: &[u64]) -> Choice {
+    assert_eq!(a.len(), b.len(), "?limb slices of different lengths?");
+    let mut borrow = 0u64;
+    for (x, y) in a.iter().zip(b) {
+        let difference = (*x as u128).wrapping_sub(*y as u128).wrapping_sub(borrow as u128);
+        borrow = (difference >> 127) as u64;
+    }
+    Choice::from(borrow as u8)
+}
+
+pub fn eq(a: &BigUint, b: &BigUint) -> Choice {
+    let len = width(a, b);
+    limbs_eq(&limbs(a, len), &limbs(b, len))
+}
+
+pub fn lt(a: &BigUint, b: &BigUint) -> Choice {
+    let len = width(a, b);
+    limbs_lt(&limbs(a, len), &limbs(b, len))
+}
+
+// a if choice is 0, b if it's 1, working on len limbs (at least the width of a and b)
+pub fn select_padded(a: &BigUint, b: &BigUint, choice: Choice, len: usize) -> BigUint {
+    let (a, b) = (limbs(a, len), limbs(b, len));
+    let selected: Vec<u64> = a.iter().zip(&b).map(|(x, y)| u64::conditional_select(x, y, choice)).collect();
+    BigUint::from_slice(&selected.iter().flat_map(|limb| [*limb as u32, (limb >> 32) as u32]).collect::<Vec<u32>>())
+}
+
+pub fn select(a: &BigUint, b: &BigUint, choice: Choice) -> BigUint {
+    select_padded(a, b, choice, width(a, b))
+}
+
+// Swap a and b if choice is 1, leave them otherwise
+pub fn swap_padded(a: &mut BigUint, b: &mut BigUint, choice: Choice, len: usize) {
+    let new_a = select_padded(a, b, choice, len);
+    let new_b = select_padded(b, a, choice, len);
+    *a = new_a;
+    *b = new_b;
+}
+
+pub fn swap(a: &mut BigUint, b: &mut BigUint, choice: Choice) {
+    let len = width(a, b);
+    swap_padded(a, b, choice, len);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparisons() {
+        let small = BigUint::from(5u32);
+        let large = BigUint::parse_bytes(b"10000000000000000000000000005", 16).unwrap();
+        assert!(bool::from(eq(&small, &small)));
+        assert!(!bool::from(eq(&small, &large)));
+        assert!(bool::from(lt(&small, &large)));
+        assert!(!bool::from(lt(&large, &small)));
+        assert!(!bool::from(lt(&large, &large)));
+        assert!(bool::from(limbs_lt(&[u64::MAX, 0], &[0, 1])));
+    }
+
+    #[test]
+    fn test_select_and_swap() {
+        let mut a = BigUint::from(7u32);
+        let mut b = BigUint::parse_bytes(b"ffffffffffffffffffffffff", 16).unwrap();
+        assert_eq!(select(&a, &b, Choice::from(0)), a);
+        assert_eq!(select(&a, &b, Choice::from(1)), b);
+
+        let (old_a, old_b) = (a.clone(), b.clone());
+        swap(&mut a, &mut b, Choice::from(0));
+        assert_eq!((&a, &b), (&old_a, &old_b));
+        swap(&mut a, &mut b, Choice::from(1));
+        assert_eq!((a, b), (old_b, old_a));
+    }
+}
@@ -0,0 +1,585 @@
+use crate::{EllipticCurve, Point};
+use crate::der::{self, DerReader};
+use crate::ecdh::ECDH;
+use crate::ecdsa::ECDSA;
+use crate::encoding;
+use crate::entropy::Entropy;
+use crate::schnorr::Schnorr;
+use crate::scalar::Scalar;
+use num_bigint::BigUint;
+
+// Named curves: standard domain parameters (curve, generator, order, cofactor)
+// so callers don't have to copy hex constants around
+
+// The (curve, G, n, h) a scheme works over, named or not, with key generation in the order-n
+// subgroup. Schemes built on it take it whole rather than each repeating the four fields
+#[derive(Clone, Debug, PartialEq)]
+pub struct DomainParams {
+    pub curve: EllipticCurve,
+    pub generator: Point,
+    pub order: BigUint,
+    pub cofactor: BigUint,
+}
+
+impl DomainParams {
+    pub fn new(curve: EllipticCurve, generator: Point, order: BigUint) -> Self {
+        Self::with_cofactor(curve, generator, order, BigUint::from(1u32))
+    }
+
+    // Domain parameters for curves whose group order is h * n with h > 1
+    pub fn with_cofactor(curve: EllipticCurve, generator: Point, order: BigUint, cofactor: BigUint) -> Self {
+        DomainParams { curve, generator, order, cofactor }
+    }
+
+    // Private key: random in [1, n-1]
+    pub fn generate_private_key(&self) -> BigUint {
+        Scalar::random(&self.order, &mut Entropy).into_value()
+    }
+
+    // Public key: d * G, on the complete, rerandomized ladder since d is secret
+    pub fn generate_public_key(&self, private_key: &BigUint) -> Point {
+        self.curve.scalar_mult_complete(&self.generator, private_key)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedCurve {
+    pub name: &'static str,
+    pub curve: EllipticCurve,
+    pub generator: Point,
+    pub order: BigUint,
+    pub cofactor: BigUint,
+}
+
+impl NamedCurve {
+    // Length in bytes of a field element, the unit of every fixed-width encoding
+    pub fn field_size(&self) -> usize {
+        encoding::field_size(&self.curve)
+    }
+
+    // Length in bytes of a scalar mod n
+    pub fn scalar_size(&self) -> usize {
+        self.order.bits().div_ceil(8) as usize
+    }
+
+    pub fn params(&self) -> DomainParams {
+        DomainParams::with_cofactor(self.curve.clone(), self.generator.clone(), self.order.clone(), self.cofactor.clone())
+    }
+
+    pub fn ecdsa(&self) -> ECDSA {
+        ECDSA::with_cofactor(self.curve.clone(), self.generator.clone(), self.order.clone(), self.cofactor.clone())
+    }
+
+    pub fn ecdh(&self) -> ECDH {
+        ECDH::with_cofactor(self.curve.clone(), self.generator.clone(), self.order.clone(), self.cofactor.clone())
+    }
+
+    pub fn schnorr(&self) -> Schnorr {
+        Schnorr::new(self.params())
+    }
+
+    // Explicit ECParameters (SEC 1 C.2, RFC 3279 2.3.5): version 1, the prime field, a and b as
+    // field-size octet strings, the uncompressed base point, n and h. No seed is written
+    pub fn to_ec_parameters(&self) -> Vec<u8> {
+        let len = self.field_size();
+        der::sequence(&[
+            der::integer(&BigUint::from(1u32)),
+            der::sequence(&[der::oid(&PRIME_FIELD), der::integer(self.curve.p())]),
+            der::sequence(&[
+                der::octet_string(&encoding::to_fixed_bytes(self.curve.a(), len)),
+                der::octet_string(&encoding::to_fixed_bytes(self.curve.b(), len)),
+            ]),
+            der::octet_string(&encoding::encode_point(&self.curve, &self.generator, false)),
+            der::integer(&self.order),
+            der::integer(&self.cofactor),
+        ])
+    }
+
+    // Object identifier of a registry curve; None for explicit or ad-hoc parameters
+    pub fn oid(&self) -> Option<&'static [u64]> {
+        OIDS.iter().find(|(name, _)| *name == self.name).map(|(_, arcs)| *arcs)
+    }
+
+    // The ECParameters CHOICE of RFC 5480 as it appears in SPKI and PKCS#8 AlgorithmIdentifiers:
+    // the namedCurve OID where there is one, the explicit parameters otherwise
+    pub fn to_der_parameters(&self) -> Vec<u8> {
+        match self.oid() {
+            Some(arcs) => der::oid(arcs),
+            None => self.to_ec_parameters(),
+        }
+    }
+}
+
+// Parse either arm of the ECParameters CHOICE. implicitCurve (NULL) has nothing to go on
+pub fn from_der_parameters(bytes: &[u8]) -> Result<NamedCurve, &'static str> {
+    match bytes.first() {
+        Some(&der::OID) => {
+            let mut reader = DerReader::new(bytes);
+            let arcs = reader.read_oid()?;
+            reader.finish()?;
+            by_oid(&arcs).ok_or("unknown curve OID")
+        }
+        Some(&der::SEQUENCE) => from_ec_parameters(bytes),
+        _ => Err("unsupported EC parameters"),
+    }
+}
+
+// id-fieldType prime-field (X9.62); characteristic-two fields aren't supported
+const PRIME_FIELD: [u64; 6] = [1, 2, 840, 10045, 1, 1];
+
+// Parse explicit ECParameters, as written by HSMs and legacy software instead of a named-curve
+// OID. Parameters equal to a registry curve come back as that curve; anything else is named
+// "explicit" and only accepted if it's a sane curve: odd p > 3, a and b reduced, nonzero
+// discriminant, the base point on the curve with n * G = O, and h * n within the Hasse
+// interval. A missing cofactor is derived from the Hasse bound as ⌊(p + 1 + 2√p) / n⌋
+pub fn from_ec_parameters(bytes: &[u8]) -> Result<NamedCurve, &'static str> {
+    let mut outer = DerReader::new(bytes);
+    let mut params = outer.read_sequence()?;
+    outer.finish()?;
+
+    if params.read_integer()? != BigUint::from(1u32) {
+        return Err("unsupported ECParameters version");
+    }
+
+    let mut field_id = params.read_sequence()?;
+    if field_id.read_oid()? != PRIME_FIELD {
+        return Err("only prime fields are supported");
+    }
+    let p = field_id.read_integer()?;
+    field_id.finish()?;
+    if p <= BigUint::from(3u32) || !p.bit(0) {
+        return Err("field modulus must be an odd prime");
+    }
+
+    // Curve: a, b and an optional seed, which is only informational here
+    let mut curve_seq = params.read_sequence()?;
+    let a = BigUint::from_bytes_be(curve_seq.read_octet_string()?);
+    let b = BigUint::from_bytes_be(curve_seq.read_octet_string()?);
+    if curve_seq.peek_tag() == Some(der::BIT_STRING) {
+        curve_seq.read_bit_string()?;
+    }
+    curve_seq.finish()?;
+    if a >= p || b >= p {
+        return Err("curve coefficient is not a field element");
+    }
+    let curve = EllipticCurve::new(a, b, p);
+    let field = curve.field();
+    let discriminant = field.add(
+        &field.mul(&BigUint::from(4u32), &field.mul(&field.mul(curve.a(), curve.a()), curve.a())),
+        &field.mul(&BigUint::from(27u32), &field.mul(curve.b(), curve.b())),
+    );
+    if discriminant == BigUint::from(0u32) {
+        return Err("singular curve");
+    }
+
+    let generator = encoding::decode_point(&curve, params.read_octet_string()?)?;
+    let order = params.read_integer()?;
+    let cofactor = if params.is_empty() {
+        curve.hasse_bounds().1 / &order
+    } else {
+        params.read_integer()?
+    };
+    params.finish()?;
+
+    if generator == Point::Identity || order <= BigUint::from(1u32) {
+        return Err("invalid base point or order");
+    }
+    if curve.scalar_mult(&generator, &order) != Point::Identity {
+        return Err("base point does not have the given order");
+    }
+    if !curve.within_hasse_bounds(&(&cofactor * &order)) {
+        return Err("cofactor * order is outside the Hasse bound");
+    }
+
+    let explicit = NamedCurve { name: "explicit", curve, generator, order, cofactor };
+    Ok(all()
+        .into_iter()
+        .find(|named| named.curve == explicit.curve && named.generator == explicit.generator && named.order == explicit.order)
+        .unwrap_or(explicit))
+}
+
+fn hex(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 16).expect("?invalid curve constant?")
+}
+
+// Hex domain parameters as published in the standards
+struct CurveConstants {
+    name: &'static str,
+    p: &'static str,
+    a: &'static str,
+    b: &'static str,
+    gx: &'static str,
+    gy: &'static str,
+    n: &'static str,
+    h: u32,
+}
+
+impl CurveConstants {
+    fn build(&self) -> NamedCurve {
+        NamedCurve {
+            name: self.name,
+            curve: EllipticCurve::new(hex(self.a), hex(self.b), hex(self.p)),
+            generator: Point::Coordinate(hex(self.gx), hex(self.gy)),
+            order: hex(self.n),
+            cofactor: BigUint::from(self.h),
+        }
+    }
+}
+
+// NIST P-256 / secp256r1 / prime256v1 (FIPS 186-4, SEC 2)
+const P256: CurveConstants = CurveConstants {
+    name: "P-256",
+    p: "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    a: "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+    b: "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+    gx: "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+    gy: "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+    n: "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+    h: 1,
+};
+
+// secp256k1 (SEC 2), the Koblitz curve y² = x³ + 7 used by Bitcoin and Ethereum
+const SECP256K1: CurveConstants = CurveConstants {
+    name: "secp256k1",
+    p: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    a: "0",
+    b: "7",
+    gx: "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    gy: "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+    n: "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    h: 1,
+};
+
+// NIST P-384 / secp384r1 and P-521 / secp521r1 (FIPS 186-4), the larger NIST curves used by
+// ES384/ES512 and OpenSSH's nistp384/nistp521 keys
+const P384: CurveConstants = CurveConstants {
+    name: "P-384",
+    p: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff",
+    a: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000fffffffc",
+    b: "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+    gx: "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7",
+    gy: "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+    n: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973",
+    h: 1,
+};
+
+const P521: CurveConstants = CurveConstants {
+    name: "P-521",
+    p: "1ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    a: "1fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc",
+    b: "51953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef109e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00",
+    gx: "00c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5bd66",
+    gy: "011839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd16650",
+    n: "1fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e91386409",
+    h: 1,
+};
+
+// Smaller SEC 2 curves, still found on older smartcards and embedded devices
+// NIST P-192 / secp192r1 / prime192v1
+const SECP192R1: CurveConstants = CurveConstants {
+    name: "secp192r1",
+    p: "fffffffffffffffffffffffffffffffeffffffffffffffff",
+    a: "fffffffffffffffffffffffffffffffefffffffffffffffc",
+    b: "64210519e59c80e70fa7e9ab72243049feb8deecc146b9b1",
+    gx: "188da80eb03090f67cbf20eb43a18800f4ff0afd82ff1012",
+    gy: "07192b95ffc8da78631011ed6b24cdd573f977a11e794811",
+    n: "ffffffffffffffffffffffff99def836146bc9b1b4d22831",
+    h: 1,
+};
+
+// NIST P-224 / secp224r1
+const SECP224R1: CurveConstants = CurveConstants {
+    name: "secp224r1",
+    p: "ffffffffffffffffffffffffffffffff000000000000000000000001",
+    a: "fffffffffffffffffffffffffffffffefffffffffffffffffffffffe",
+    b: "b4050a850c04b3abf54132565044b0b7d7bfd8ba270b39432355ffb4",
+    gx: "b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21",
+    gy: "bd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34",
+    n: "ffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d",
+    h: 1,
+};
+
+// secp224k1: y² = x³ + 5, whose order is slightly larger than p (a 225-bit n)
+const SECP224K1: CurveConstants = CurveConstants {
+    name: "secp224k1",
+    p: "fffffffffffffffffffffffffffffffffffffffffffffffeffffe56d",
+    a: "0",
+    b: "5",
+    gx: "a1455b334df099df30fc28a169a467e9e47075a90f7e650eb6b7a45c",
+    gy: "7e089fed7fba344282cafbd6f7e319f7c0b0bd59e2ca4bdb556d61a5",
+    n: "010000000000000000000000000001dce8d2ec6184caf0a971769fb1f7",
+    h: 1,
+};
+
+// Brainpool (RFC 5639): primes and coefficients derived verifiably from digits of π and e,
+// with no special form, for users who won't rely on the NIST constants
+const BRAINPOOL_P256R1: CurveConstants = CurveConstants {
+    name: "brainpoolP256r1",
+    p: "a9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377",
+    a: "7d5a0975fc2c3057eef67530417affe7fb8055c126dc5c6ce94a4b44f330b5d9",
+    b: "26dc5c6ce94a4b44f330b5d9bbd77cbf958416295cf7e1ce6bccdc18ff8c07b6",
+    gx: "8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262",
+    gy: "547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997",
+    n: "a9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7",
+    h: 1,
+};
+
+const BRAINPOOL_P384R1: CurveConstants = CurveConstants {
+    name: "brainpoolP384r1",
+    p: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b412b1da197fb71123acd3a729901d1a71874700133107ec53",
+    a: "7bc382c63d8c150c3c72080ace05afa0c2bea28e4fb22787139165efba91f90f8aa5814a503ad4eb04a8c7dd22ce2826",
+    b: "04a8c7dd22ce28268b39b55416f0447c2fb77de107dcd2a62e880ea53eeb62d57cb4390295dbc9943ab78696fa504c11",
+    gx: "1d1c64f068cf45ffa2a63a81b7c13f6b8847a3e77ef14fe3db7fcafe0cbd10e8e826e03436d646aaef87b2e247d4af1e",
+    gy: "8abe1d7520f9c2a45cb1eb8e95cfd55262b70b29feec5864e19c054ff99129280e4646217791811142820341263c5315",
+    n: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b31f166e6cac0425a7cf3ab6af6b7fc3103b883202e9046565",
+    h: 1,
+};
+
+const BRAINPOOL_P512R1: CurveConstants = CurveConstants {
+    name: "brainpoolP512r1",
+    p: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca703308717d4d9b009bc66842aecda12ae6a380e62881ff2f2d82c68528aa6056583a48f3",
+    a: "7830a3318b603b89e2327145ac234cc594cbdd8d3df91610a83441caea9863bc2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a72bf2c7b9e7c1ac4d77fc94ca",
+    b: "3df91610a83441caea9863bc2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a72bf2c7b9e7c1ac4d77fc94cadc083e67984050b75ebae5dd2809bd638016f723",
+    gx: "81aee4bdd82ed9645a21322e9c4c6a9385ed9f70b5d916c1b43b62eef4d0098eff3b1f78e2d0d48d50d1687b93b97d5f7c6d5047406a5e688b352209bcb9f822",
+    gy: "7dde385d566332ecc0eabfa9cf7822fdf209f70024a57b1aa000c55b881f8111b2dcde494a5f485e5bca4bd88a2763aed1ca2b2fa8f0540678cd1e0f3ad80892",
+    n: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330870553e5c414ca92619418661197fac10471db1d381085ddaddb58796829ca90069",
+    h: 1,
+};
+
+pub fn p256() -> NamedCurve {
+    P256.build()
+}
+
+pub fn secp256k1() -> NamedCurve {
+    SECP256K1.build()
+}
+
+pub fn p384() -> NamedCurve {
+    P384.build()
+}
+
+pub fn p521() -> NamedCurve {
+    P521.build()
+}
+
+pub fn secp192r1() -> NamedCurve {
+    SECP192R1.build()
+}
+
+pub fn secp224r1() -> NamedCurve {
+    SECP224R1.build()
+}
+
+pub fn secp224k1() -> NamedCurve {
+    SECP224K1.build()
+}
+
+pub fn brainpool_p256r1() -> NamedCurve {
+    BRAINPOOL_P256R1.build()
+}
+
+pub fn brainpool_p384r1() -> NamedCurve {
+    BRAINPOOL_P384R1.build()
+}
+
+pub fn brainpool_p512r1() -> NamedCurve {
+    BRAINPOOL_P512R1.build()
+}
+
+// Every curve in the registry
+pub fn all() -> Vec<NamedCurve> {
+    vec![
+        p256(),
+        secp256k1(),
+        p384(),
+        p521(),
+        secp192r1(),
+        secp224r1(),
+        secp224k1(),
+        brainpool_p256r1(),
+        brainpool_p384r1(),
+        brainpool_p512r1(),
+    ]
+}
+
+// Look up a curve by any of its common names
+pub fn by_name(name: &str) -> Option<NamedCurve> {
+    match name.to_ascii_lowercase().as_str() {
+        "p-256" | "p256" | "secp256r1" | "prime256v1" => Some(p256()),
+        "secp256k1" => Some(secp256k1()),
+        "p-384" | "p384" | "secp384r1" => Some(p384()),
+        "p-521" | "p521" | "secp521r1" => Some(p521()),
+        "p-192" | "p192" | "secp192r1" | "prime192v1" => Some(secp192r1()),
+        "p-224" | "p224" | "secp224r1" => Some(secp224r1()),
+        "secp224k1" => Some(secp224k1()),
+        "brainpoolp256r1" => Some(brainpool_p256r1()),
+        "brainpoolp384r1" => Some(brainpool_p384r1()),
+        "brainpoolp512r1" => Some(brainpool_p512r1()),
+        _ => None,
+    }
+}
+
+// ASN.1 object identifiers of the registry curves (RFC 5480, SEC 2, RFC 5639)
+const OIDS: [(&str, &[u64]); 10] = [
+    ("P-256", &[1, 2, 840, 10045, 3, 1, 7]),
+    ("secp256k1", &[1, 3, 132, 0, 10]),
+    ("P-384", &[1, 3, 132, 0, 34]),
+    ("P-521", &[1, 3, 132, 0, 35]),
+    ("secp192r1", &[1, 2, 840, 10045, 3, 1, 1]),
+    ("secp224r1", &[1, 3, 132, 0, 33]),
+    ("secp224k1", &[1, 3, 132, 0, 32]),
+    ("brainpoolP256r1", &[1, 3, 36, 3, 3, 2, 8, 1, 1, 7]),
+    ("brainpoolP384r1", &[1, 3, 36, 3, 3, 2, 8, 1, 1, 11]),
+    ("brainpoolP512r1", &[1, 3, 36, 3, 3, 2, 8, 1, 1, 13]),
+];
+
+pub fn by_oid(arcs: &[u64]) -> Option<NamedCurve> {
+    OIDS.iter().find(|(_, oid)| *oid == arcs).and_then(|(name, _)| by_name(name))
+}
+
+// Same, from the dotted form ("1.2.840.10045.3.1.7")
+pub fn by_oid_str(oid: &str) -> Option<NamedCurve> {
+    let arcs: Result<Vec<u64>, _> = oid.split('.').map(str::parse).collect();
+    by_oid(&arcs.ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ec_parameters() {
+        // openssl ecparam -name secp256k1 -param_enc explicit -outform DER
+        let secp256k1_der = encoding::from_hex(concat!(
+            "3081e0020101302c06072a8648ce3d0101022100ffffffffffffffffffffffffffffffffffffffffffffff",
+            "fffffffffefffffc2f3044042000000000000000000000000000000000000000000000000000000000000000",
+            "000420000000000000000000000000000000000000000000000000000000000000000704410479be667ef9dc",
+            "bbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17",
+            "b448a68554199c47d08ffb10d4b8022100fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e",
+            "8cd0364141020101",
+        )).unwrap();
+        assert_eq!(secp256k1().to_ec_parameters(), secp256k1_der);
+        assert_eq!(from_ec_parameters(&secp256k1_der).unwrap().name, "secp256k1");
+
+        // P-256 from openssl carries the curve seed, which is skipped
+        let p256_der = encoding::from_hex(concat!(
+            "3081f7020101302c06072a8648ce3d0101022100ffffffff00000001000000000000000000000000ffffffff",
+            "ffffffffffffffff305b0420ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+            "04205ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b031500c49d360886e704",
+            "936a6678e1139d26b7819f7e900441046b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945",
+            "d898c2964fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5022100ffffffff00",
+            "000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551020101",
+        )).unwrap();
+        assert_eq!(from_ec_parameters(&p256_der).unwrap(), p256());
+
+        // A curve outside the registry, with the cofactor left out
+        let toy = NamedCurve {
+            name: "toy",
+            curve: EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32)),
+            generator: Point::Coordinate(BigUint::from(5u32), BigUint::from(4u32)),
+            order: BigUint::from(7u32),
+            cofactor: BigUint::from(4u32),
+        };
+        let parsed = from_ec_parameters(&toy.to_ec_parameters()).unwrap();
+        assert_eq!(parsed.name, "explicit");
+        assert_eq!((parsed.curve, parsed.generator, parsed.cofactor), (toy.curve.clone(), toy.generator.clone(), toy.cofactor.clone()));
+        let mut without_cofactor = toy.to_ec_parameters();
+        without_cofactor.truncate(without_cofactor.len() - 3);
+        without_cofactor[1] -= 3;
+        assert_eq!(from_ec_parameters(&without_cofactor).unwrap().cofactor, BigUint::from(4u32));
+
+        // Wrong order for the base point, and a characteristic-two field
+        let mut wrong_order = toy.clone();
+        wrong_order.order = BigUint::from(5u32);
+        assert!(from_ec_parameters(&wrong_order.to_ec_parameters()).is_err());
+        // A mistyped cofactor: 5 * 7 = 35 points don't fit over F_23 (15 to 33)
+        let mut wrong_cofactor = toy.clone();
+        wrong_cofactor.cofactor = BigUint::from(5u32);
+        assert_eq!(from_ec_parameters(&wrong_cofactor.to_ec_parameters()).err(), Some("cofactor * order is outside the Hasse bound"));
+        let mut binary = secp256k1_der.clone();
+        binary[13] = 0x02;
+        assert!(from_ec_parameters(&binary).is_err());
+    }
+
+    #[test]
+    fn test_named_curve_parameters() {
+        for named in all() {
+            assert!(named.curve.is_on_curve(&named.generator), "{}", named.name);
+            assert_eq!(named.curve.scalar_mult(&named.generator, &named.order), Point::Identity, "{}", named.name);
+        }
+        assert_eq!(p256().field_size(), 32);
+        assert_eq!(p256().scalar_size(), 32);
+        assert_eq!(secp224k1().field_size(), 28);
+        assert_eq!(secp224k1().scalar_size(), 29);
+        assert_eq!(p521().field_size(), 66);
+        assert_eq!(p521().scalar_size(), 66);
+    }
+
+    #[test]
+    fn test_by_name() {
+        assert_eq!(by_name("prime256v1"), Some(p256()));
+        assert_eq!(by_name("secp256r1").unwrap().name, "P-256");
+        assert_eq!(by_name("SECP256K1"), Some(secp256k1()));
+        assert_eq!(by_name("prime192v1"), Some(secp192r1()));
+        assert_eq!(by_name("P-224").unwrap().name, "secp224r1");
+        assert_eq!(by_name("brainpoolP384r1"), Some(brainpool_p384r1()));
+        assert_eq!(by_name("secp521r1"), Some(p521()));
+        assert_eq!(by_name("curve-that-does-not-exist"), None);
+    }
+
+    #[test]
+    fn test_oid_registry() {
+        for named in all() {
+            let arcs = named.oid().unwrap();
+            assert_eq!(by_oid(arcs).as_ref(), Some(&named));
+            assert_eq!(from_der_parameters(&named.to_der_parameters()).as_ref(), Ok(&named));
+        }
+        assert_eq!(by_oid_str("1.2.840.10045.3.1.7"), Some(p256()));
+        assert_eq!(by_oid_str("1.3.132.0.10"), Some(secp256k1()));
+        assert_eq!(by_oid_str("1.3.132.0.35").unwrap().name, "P-521");
+        assert_eq!(by_oid_str("1.3.132.0.99"), None);
+        assert_eq!(by_oid_str("not.an.oid"), None);
+
+        // openssl ecparam -name prime256v1 -outform DER
+        assert_eq!(p256().to_der_parameters(), encoding::from_hex("06082a8648ce3d030107").unwrap());
+        assert!(from_der_parameters(&[0x05, 0x00]).is_err());
+
+        // Curves outside the registry go out explicitly
+        let mut custom = p256();
+        custom.name = "explicit";
+        assert_eq!(custom.oid(), None);
+        assert_eq!(custom.to_der_parameters(), custom.to_ec_parameters());
+    }
+
+    #[test]
+    fn test_legacy_curves_sign() {
+        for named in [secp192r1(), secp224r1(), secp224k1()] {
+            let ecdsa = named.ecdsa();
+            let keypair = ecdsa.generate_keypair();
+            let signature = ecdsa.sign(b"legacy smartcard", &keypair.private_key).unwrap();
+            assert!(ecdsa.verify(b"legacy smartcard", &signature, &keypair.public_key), "{}", named.name);
+        }
+    }
+
+    #[test]
+    fn test_brainpool_ecdh_vector() {
+        // RFC 7027 A.1, ECDH on brainpoolP256r1
+        let named = brainpool_p256r1();
+        let ecdh = named.ecdh();
+        let d_a = hex("81db1ee100150ff2ea338d708271be38300cb54241d79950f77b063039804f1d");
+        let d_b = hex("55e40bc41e37e3e2ad25c3c6654511ffa8474a91a0032087593852d3e7d76bd3");
+        let q_a = ecdh.generate_public_key(&d_a);
+        assert_eq!(
+            q_a,
+            Point::Coordinate(
+                hex("44106e913f92bc02a1705d9953a8414db95e1aaa49e81d9e85f929a8e3100be5"),
+                hex("8ab4846f11caccb73ce49cbdd120f5a900a69fd32c272223f789ef10eb089bdc"),
+            )
+        );
+        let q_b = ecdh.generate_public_key(&d_b);
+        let shared = ecdh.shared_secret(&d_b, &q_a).unwrap();
+        assert_eq!(shared, ecdh.shared_secret(&d_a, &q_b).unwrap());
+        let Point::Coordinate(x, _) = shared else { panic!() };
+        assert_eq!(x, hex("89afc39d41d3b327814b80940b042590f96556ec91e6ae7939bce31f3a18bf2b"));
+    }
+}
@@ -0,0 +1,246 @@
+use num_bigint::BigUint;
+
+// Minimal DER (X.690) reader and writer for the structures the crate exchanges: signatures,
+// curve parameters, keys. Tag-length-value with the tag as a single byte, which covers the
+// universal types used here and context-specific tags [0] to [30].
+// Reading is strict DER, not BER: minimal lengths, no indefinite length, minimal non-negative
+// integers and no trailing bytes. Lenient parsers let one value have many encodings, which
+// breaks anything keyed on the bytes (Bitcoin's signature malleability)
+
+pub const INTEGER: u8 = 0x02;
+pub const BIT_STRING: u8 = 0x03;
+pub const OCTET_STRING: u8 = 0x04;
+pub const NULL: u8 = 0x05;
+pub const OID: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+
+// Constructed context-specific tag [n], as used for EXPLICIT tagging
+pub fn context(n: u8) -> u8 {
+    0xa0 | n
+}
+
+fn length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        return vec![len as u8];
+    }
+    let bytes: Vec<u8> = len.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+    let mut out = vec![0x80 | bytes.len() as u8];
+    out.extend(bytes);
+    out
+}
+
+pub fn write(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    out.extend(length(content.len()));
+    out.extend_from_slice(content);
+    out
+}
+
+// Big-endian magnitude with a 0x00 in front when the top bit is set (DER integers are signed)
+pub fn integer(x: &BigUint) -> Vec<u8> {
+    let mut magnitude = x.to_bytes_be();
+    if magnitude[0] & 0x80 != 0 {
+        magnitude.insert(0, 0);
+    }
+    write(INTEGER, &magnitude)
+}
+
+pub fn octet_string(bytes: &[u8]) -> Vec<u8> {
+    write(OCTET_STRING, bytes)
+}
+
+// Whole bytes only: the leading "unused bits" count is always 0
+pub fn bit_string(bytes: &[u8]) -> Vec<u8> {
+    let mut content = vec![0];
+    content.extend_from_slice(bytes);
+    write(BIT_STRING, &content)
+}
+
+pub fn sequence(parts: &[Vec<u8>]) -> Vec<u8> {
+    write(SEQUENCE, &parts.concat())
+}
+
+// First two arcs share a byte (40 * a + b), the rest are base-128 with continuation bits
+pub fn oid(arcs: &[u64]) -> Vec<u8> {
+    assert!(arcs.len() >= 2 && arcs[0] <= 2, "?invalid object identifier?");
+    let mut content = Vec::new();
+    for arc in std::iter::once(40 * arcs[0] + arcs[1]).chain(arcs[2..].iter().copied()) {
+        let mut groups = vec![(arc & 0x7f) as u8];
+        let mut rest = arc >> 7;
+        while rest > 0 {
+            groups.push((rest & 0x7f) as u8 | 0x80);
+            rest >>= 7;
+        }
+        content.extend(groups.into_iter().rev());
+    }
+    write(OID, &content)
+}
+
+// Reads TLVs one after another from a byte string
+pub struct DerReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> DerReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        DerReader { bytes, pos: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    // Everything has to have been read
+    pub fn finish(&self) -> Result<(), &'static str> {
+        if self.is_empty() { Ok(()) } else { Err("trailing data after DER value") }
+    }
+
+    // Content of the next TLV, which must carry the given tag
+    pub fn read(&mut self, tag: u8) -> Result<&'a [u8], &'static str> {
+        if self.peek_tag() != Some(tag) {
+            return Err("unexpected DER tag");
+        }
+        let (len, start) = self.read_length(self.pos + 1)?;
+        let end = start.checked_add(len).ok_or("DER length too long")?;
+        let content = self.bytes.get(start..end).ok_or("truncated DER value")?;
+        self.pos = end;
+        Ok(content)
+    }
+
+    // Reader over the contents of a SEQUENCE (or another constructed tag)
+    pub fn read_constructed(&mut self, tag: u8) -> Result<DerReader<'a>, &'static str> {
+        Ok(DerReader::new(self.read(tag)?))
+    }
+
+    pub fn read_sequence(&mut self) -> Result<DerReader<'a>, &'static str> {
+        self.read_constructed(SEQUENCE)
+    }
+
+    pub fn read_integer(&mut self) -> Result<BigUint, &'static str> {
+        let content = self.read(INTEGER)?;
+        if content.is_empty() {
+            return Err("empty DER integer");
+        }
+        if content[0] & 0x80 != 0 {
+            return Err("negative DER integer");
+        }
+        if content.len() > 1 && content[0] == 0 && content[1] & 0x80 == 0 {
+            return Err("non-minimal DER integer");
+        }
+        Ok(BigUint::from_bytes_be(content))
+    }
+
+    pub fn read_octet_string(&mut self) -> Result<&'a [u8], &'static str> {
+        self.read(OCTET_STRING)
+    }
+
+    // Whole-byte bit strings only
+    pub fn read_bit_string(&mut self) -> Result<&'a [u8], &'static str> {
+        match self.read(BIT_STRING)?.split_first() {
+            Some((0, bytes)) => Ok(bytes),
+            _ => Err("unsupported DER bit string"),
+        }
+    }
+
+    pub fn read_null(&mut self) -> Result<(), &'static str> {
+        if self.read(NULL)?.is_empty() { Ok(()) } else { Err("DER NULL with content") }
+    }
+
+    pub fn read_oid(&mut self) -> Result<Vec<u64>, &'static str> {
+        let content = self.read(OID)?;
+        let mut arcs = Vec::new();
+        let mut arc = 0u64;
+        for (i, byte) in content.iter().enumerate() {
+            if arc == 0 && *byte == 0x80 {
+                return Err("non-minimal OID arc");
+            }
+            if arc >> 57 != 0 {
+                return Err("OID arc too large");
+            }
+            arc = arc << 7 | (byte & 0x7f) as u64;
+            if byte & 0x80 == 0 {
+                if arcs.is_empty() {
+                    let first = (arc / 40).min(2);
+                    arcs.push(first);
+                    arcs.push(arc - 40 * first);
+                } else {
+                    arcs.push(arc);
+                }
+                arc = 0;
+            } else if i == content.len() - 1 {
+                return Err("truncated OID");
+            }
+        }
+        if arcs.is_empty() {
+            return Err("empty OID");
+        }
+        Ok(arcs)
+    }
+
+    // Length at bytes[pos..]: (length, position after it)
+    fn read_length(&self, pos: usize) -> Result<(usize, usize), &'static str> {
+        let first = *self.bytes.get(pos).ok_or("truncated DER length")?;
+        if first < 0x80 {
+            return Ok((first as usize, pos + 1));
+        }
+        let count = (first & 0x7f) as usize;
+        if count == 0 {
+            return Err("indefinite DER length");
+        }
+        if count > 4 {
+            return Err("DER length too long");
+        }
+        let length_bytes = self.bytes.get(pos + 1..pos + 1 + count).ok_or("truncated DER length")?;
+        if length_bytes[0] == 0 {
+            return Err("non-minimal DER length");
+        }
+        let len = length_bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        if len < 0x80 {
+            return Err("non-minimal DER length");
+        }
+        Ok((len, pos + 1 + count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oid() {
+        // id-ecPublicKey 1.2.840.10045.2.1
+        let encoded = oid(&[1, 2, 840, 10045, 2, 1]);
+        assert_eq!(encoded, vec![0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01]);
+        assert_eq!(DerReader::new(&encoded).read_oid().unwrap(), vec![1, 2, 840, 10045, 2, 1]);
+        assert!(DerReader::new(&[0x06, 0x02, 0x2a, 0x86]).read_oid().is_err());
+        assert!(DerReader::new(&[0x06, 0x02, 0x80, 0x01]).read_oid().is_err());
+    }
+
+    #[test]
+    fn test_lengths_and_integers() {
+        let long = vec![0xabu8; 200];
+        let encoded = octet_string(&long);
+        assert_eq!(&encoded[..3], &[0x04, 0x81, 200]);
+        let mut reader = DerReader::new(&encoded);
+        assert_eq!(reader.read_octet_string().unwrap(), &long[..]);
+        assert!(reader.finish().is_ok());
+
+        let encoded = sequence(&[integer(&BigUint::from(0x80u32)), integer(&BigUint::from(0u32))]);
+        assert_eq!(encoded, vec![0x30, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x01, 0x00]);
+        let mut reader = DerReader::new(&encoded).read_sequence().unwrap();
+        assert_eq!(reader.read_integer().unwrap(), BigUint::from(0x80u32));
+        assert_eq!(reader.read_integer().unwrap(), BigUint::from(0u32));
+
+        // Long form for a short length, indefinite length, padded and negative integers
+        assert!(DerReader::new(&[0x04, 0x81, 0x01, 0x00]).read_octet_string().is_err());
+        assert!(DerReader::new(&[0x30, 0x80, 0x00, 0x00]).read_sequence().is_err());
+        assert!(DerReader::new(&[0x02, 0x02, 0x00, 0x01]).read_integer().is_err());
+        assert!(DerReader::new(&[0x02, 0x01, 0x80]).read_integer().is_err());
+        assert!(DerReader::new(&[0x04, 0x05, 0x00]).read_octet_string().is_err());
+    }
+}
@@ -0,0 +1,405 @@
+use crate::{FiniteField, Point};
+use crate::curves::DomainParams;
+use crate::frost::{KeyPackage, PublicKeyPackage};
+use crate::secret_sharing::{self, Share};
+use crate::zkp::{SchnorrProof, Transcript};
+use num_bigint::BigUint;
+use std::collections::{BTreeMap, BTreeSet};
+
+// Pedersen distributed key generation (Feldman-VSS based, as in GJKR and the FROST paper)
+//
+// n parties jointly create a t-of-n shared key that no one ever holds in full:
+//   round 1 (broadcast):  party j picks f_j of degree t - 1, publishes C_j = [a_j0 G, .., a_j(t-1) G]
+//                         and a Schnorr proof of knowledge of a_j0 (stops rogue-key attacks)
+//   round 2 (private):    party j sends f_j(i) to each party i, who checks it against C_j
+//   complaints:           a bad share triggers a complaint; the accused must reveal that share
+//                         publicly, and is disqualified if it still doesn't verify
+//   result:               party i holds s_i = Σ_j f_j(i) over qualified j,
+//                         and the group key is Y = Σ_j a_j0 G
+// The key works with both the Schnorr (FROST) and ECDSA threshold protocols
+
+const POK_DOMAIN: &[u8] = b"pedersen-dkg-pok";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DkgPhase {
+    Round1,
+    Round2,
+    Complaints,
+    Finished,
+}
+
+// Broadcast in round 1
+#[derive(Clone, Debug)]
+pub struct Round1Package {
+    pub sender: u32,
+    pub commitments: Vec<Point>,
+    pub proof_of_knowledge: SchnorrProof,
+}
+
+// Sent privately from sender to receiver in round 2
+#[derive(Clone, Debug, PartialEq)]
+pub struct Round2Package {
+    pub sender: u32,
+    pub receiver: u32,
+    pub share: BigUint,
+}
+
+// Broadcast by a party whose round-2 share failed verification
+#[derive(Clone, Debug, PartialEq)]
+pub struct Complaint {
+    pub accuser: u32,
+    pub accused: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct DkgOutput {
+    pub identifier: u32,
+    pub threshold: usize,
+    pub secret_share: BigUint,
+    pub group_public_key: Point,
+    pub verifying_shares: Vec<(u32, Point)>,
+    pub qualified: Vec<u32>,
+}
+
+impl DkgOutput {
+    // FROST key material for this party
+    pub fn key_package(&self) -> KeyPackage {
+        let verifying_share = self
+            .verifying_shares
+            .iter()
+            .find(|(i, _)| *i == self.identifier)
+            .map(|(_, p)| p.clone())
+            .expect("?own verifying share?");
+        KeyPackage {
+            identifier: self.identifier,
+            signing_share: self.secret_share.clone(),
+            verifying_share,
+            group_public_key: self.group_public_key.clone(),
+            threshold: self.threshold,
+        }
+    }
+
+    pub fn public_key_package(&self) -> PublicKeyPackage {
+        PublicKeyPackage {
+            verifying_shares: self.verifying_shares.clone(),
+            group_public_key: self.group_public_key.clone(),
+            commitments: Vec::new(),
+        }
+    }
+}
+
+pub struct DkgParticipant {
+    pub params: DomainParams,
+    pub identifier: u32,
+    pub threshold: usize,
+    pub participants: usize,
+    pub phase: DkgPhase,
+    coefficients: Vec<BigUint>,
+    commitments: BTreeMap<u32, Vec<Point>>,
+    received_shares: BTreeMap<u32, BigUint>,
+    complaints: Vec<Complaint>,
+    disqualified: BTreeSet<u32>,
+}
+
+impl DkgParticipant {
+    pub fn new(params: DomainParams, identifier: u32, threshold: usize, participants: usize) -> Result<Self, &'static str> {
+        if threshold == 0 || threshold > participants {
+            return Err("threshold must be between 1 and the number of participants");
+        }
+        if identifier == 0 || identifier as usize > participants {
+            return Err("identifier must be in 1..=participants");
+        }
+        let secret = params.generate_private_key();
+        let coefficients = secret_sharing::random_polynomial(&secret, threshold, &params.order);
+        Ok(DkgParticipant {
+            params,
+            identifier,
+            threshold,
+            participants,
+            phase: DkgPhase::Round1,
+            coefficients,
+            commitments: BTreeMap::new(),
+            received_shares: BTreeMap::new(),
+            complaints: Vec::new(),
+            disqualified: BTreeSet::new(),
+        })
+    }
+
+    // Round 1: our commitments and proof of knowledge of the constant term
+    pub fn round1_package(&mut self) -> Round1Package {
+        let commitments = secret_sharing::commit_polynomial(&self.params.curve, &self.params.generator, &self.coefficients);
+        let proof_of_knowledge = SchnorrProof::prove(&self.params.curve, &self.params.order, &self.params.generator, &self.coefficients[0], &mut pok_transcript(self.identifier));
+        self.commitments.insert(self.identifier, commitments.clone());
+        Round1Package { sender: self.identifier, commitments, proof_of_knowledge }
+    }
+
+    // Round 1: store another party's broadcast after checking its shape and proof
+    // A party sending an invalid package is disqualified outright
+    pub fn receive_round1(&mut self, package: &Round1Package) -> Result<(), &'static str> {
+        self.expect_phase(DkgPhase::Round1)?;
+        if package.sender == self.identifier || package.sender == 0 || package.sender as usize > self.participants {
+            return Err("unexpected round-1 sender");
+        }
+        let valid = package.commitments.len() == self.threshold
+            && package.commitments.iter().all(|c| self.params.curve.is_on_curve(c))
+            && package.commitments[0] != Point::Identity
+            && package.proof_of_knowledge.verify(&self.params.curve, &self.params.order, &self.params.generator, &package.commitments[0], &mut pok_transcript(package.sender));
+        if !valid {
+            self.disqualified.insert(package.sender);
+            return Err("invalid round-1 package; sender disqualified");
+        }
+        self.commitments.insert(package.sender, package.commitments.clone());
+        Ok(())
+    }
+
+    // Round 2: f(i) for every other party, to be sent over private channels
+    pub fn round2_packages(&mut self) -> Result<Vec<Round2Package>, &'static str> {
+        self.expect_phase(DkgPhase::Round1)?;
+        if self.commitments.len() + self.disqualified.len() != self.participants {
+            return Err("round 1 is not complete");
+        }
+        self.phase = DkgPhase::Round2;
+
+        let own = secret_sharing::evaluate_polynomial(&self.coefficients, &BigUint::from(self.identifier), &self.params.order);
+        self.received_shares.insert(self.identifier, own);
+
+        Ok((1..=self.participants as u32)
+            .filter(|&i| i != self.identifier)
+            .map(|receiver| Round2Package {
+                sender: self.identifier,
+                receiver,
+                share: secret_sharing::evaluate_polynomial(&self.coefficients, &BigUint::from(receiver), &self.params.order),
+            })
+            .collect())
+    }
+
+    // Round 2: verify a received share; a bad one yields a complaint to broadcast
+    pub fn receive_round2(&mut self, package: &Round2Package) -> Result<(), Complaint> {
+        let complaint = Complaint { accuser: self.identifier, accused: package.sender };
+        if self.phase != DkgPhase::Round2 || package.receiver != self.identifier {
+            return Err(complaint);
+        }
+        if !self.share_is_valid(package.sender, self.identifier, &package.share) {
+            self.complaints.push(complaint.clone());
+            return Err(complaint);
+        }
+        self.received_shares.insert(package.sender, package.share.clone());
+        Ok(())
+    }
+
+    // The accused's answer to a complaint: the disputed share, revealed to everyone
+    pub fn answer_complaint(&self, complaint: &Complaint) -> Option<Round2Package> {
+        if complaint.accused != self.identifier {
+            return None;
+        }
+        Some(Round2Package {
+            sender: self.identifier,
+            receiver: complaint.accuser,
+            share: secret_sharing::evaluate_polynomial(&self.coefficients, &BigUint::from(complaint.accuser), &self.params.order),
+        })
+    }
+
+    // Every party rules on a complaint: disqualify the accused unless the revealed share verifies
+    // (the accuser adopts a verified share in place of the bad one)
+    pub fn resolve_complaint(&mut self, complaint: &Complaint, answer: Option<&Round2Package>) {
+        if self.phase == DkgPhase::Round2 {
+            self.phase = DkgPhase::Complaints;
+        }
+        let valid = match answer {
+            Some(revealed) => {
+                revealed.sender == complaint.accused
+                    && revealed.receiver == complaint.accuser
+                    && self.share_is_valid(complaint.accused, complaint.accuser, &revealed.share)
+            }
+            None => false,
+        };
+        if !valid {
+            self.disqualified.insert(complaint.accused);
+            self.received_shares.remove(&complaint.accused);
+        } else if complaint.accuser == self.identifier {
+            self.received_shares.insert(complaint.accused, answer.expect("?valid answer exists?").share.clone());
+        }
+    }
+
+    pub fn complaints(&self) -> &[Complaint] {
+        &self.complaints
+    }
+
+    pub fn disqualified(&self) -> Vec<u32> {
+        self.disqualified.iter().copied().collect()
+    }
+
+    // s_i = Σ f_j(i), Y = Σ C_j0 and Y_k = Σ_j C_j(k) over the qualified parties
+    pub fn finish(&mut self) -> Result<DkgOutput, &'static str> {
+        if self.phase != DkgPhase::Round2 && self.phase != DkgPhase::Complaints {
+            return Err("round 2 has not started");
+        }
+        let qualified: Vec<u32> = self.commitments.keys().copied().filter(|j| !self.disqualified.contains(j)).collect();
+        if qualified.len() < self.threshold {
+            return Err("too few qualified parties to reach the threshold");
+        }
+        if qualified.iter().any(|j| !self.received_shares.contains_key(j)) {
+            return Err("missing shares from qualified parties");
+        }
+
+        let field = FiniteField { p: self.params.order.clone() };
+        let secret_share = qualified.iter().fold(BigUint::from(0u32), |acc, j| field.add(&acc, &self.received_shares[j]));
+        let group_public_key = qualified.iter().fold(Point::Identity, |acc, j| self.params.curve.add(&acc, &self.commitments[j][0]));
+        let verifying_shares = (1..=self.participants as u32)
+            .map(|k| {
+                let share_point = qualified.iter().fold(Point::Identity, |acc, j| {
+                    self.params.curve.add(&acc, &secret_sharing::evaluate_commitments(&self.params.curve, &self.commitments[j], k, &self.params.order))
+                });
+                (k, share_point)
+            })
+            .collect();
+
+        self.phase = DkgPhase::Finished;
+        Ok(DkgOutput {
+            identifier: self.identifier,
+            threshold: self.threshold,
+            secret_share,
+            group_public_key,
+            verifying_shares,
+            qualified,
+        })
+    }
+
+    // f_sender(receiver) * G matches the sender's commitments
+    fn share_is_valid(&self, sender: u32, receiver: u32, share: &BigUint) -> bool {
+        match self.commitments.get(&sender) {
+            Some(commitments) => {
+                let share = Share { identifier: receiver, value: share.clone() };
+                secret_sharing::verify_share(&self.params.curve, &self.params.generator, &share, commitments, &self.params.order)
+            }
+            None => false,
+        }
+    }
+
+    fn expect_phase(&self, phase: DkgPhase) -> Result<(), &'static str> {
+        if self.phase != phase {
+            return Err("message received in the wrong protocol phase");
+        }
+        Ok(())
+    }
+}
+
+// Proofs are bound to the sender so one party can't replay another's
+fn pok_transcript(sender: u32) -> Transcript {
+    let mut transcript = Transcript::new(POK_DOMAIN);
+    transcript.append_message(b"sender", &sender.to_be_bytes());
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use crate::frost;
+
+    fn setup(threshold: usize, n: usize) -> (Vec<DkgParticipant>, Vec<Round1Package>) {
+        let p256 = curves::p256();
+        let mut parties: Vec<DkgParticipant> = (1..=n as u32)
+            .map(|i| DkgParticipant::new(p256.params(), i, threshold, n).unwrap())
+            .collect();
+        let round1: Vec<Round1Package> = parties.iter_mut().map(|p| p.round1_package()).collect();
+        for party in parties.iter_mut() {
+            for package in &round1 {
+                if package.sender != party.identifier {
+                    party.receive_round1(package).unwrap();
+                }
+            }
+        }
+        (parties, round1)
+    }
+
+    fn deliver(parties: &mut [DkgParticipant], packages: &[Round2Package]) -> Vec<Complaint> {
+        let mut complaints = Vec::new();
+        for package in packages {
+            let receiver = &mut parties[package.receiver as usize - 1];
+            if let Err(complaint) = receiver.receive_round2(package) {
+                complaints.push(complaint);
+            }
+        }
+        complaints
+    }
+
+    #[test]
+    fn test_dkg_then_frost_signing() {
+        let (mut parties, _) = setup(2, 3);
+        let round2: Vec<Round2Package> = parties.iter_mut().flat_map(|p| p.round2_packages().unwrap()).collect();
+        assert!(deliver(&mut parties, &round2).is_empty());
+
+        let outputs: Vec<DkgOutput> = parties.iter_mut().map(|p| p.finish().unwrap()).collect();
+        let group_key = outputs[0].group_public_key.clone();
+        assert!(outputs.iter().all(|o| o.group_public_key == group_key));
+
+        // Any two shares interpolate to the secret behind the group key
+        let p256 = curves::p256();
+        let shares: Vec<Share> = outputs[1..].iter().map(|o| Share { identifier: o.identifier, value: o.secret_share.clone() }).collect();
+        let secret = secret_sharing::reconstruct(&shares, &p256.order).unwrap();
+        assert_eq!(p256.curve.scalar_mult(&p256.generator, &secret), group_key);
+
+        // The shares plug straight into FROST
+        let scheme = p256.schnorr();
+        let keys: Vec<KeyPackage> = outputs.iter().map(|o| o.key_package()).collect();
+        let (n0, c0) = frost::commit(&scheme, &keys[0]);
+        let (n2, c2) = frost::commit(&scheme, &keys[2]);
+        let package = frost::SigningPackage { commitments: vec![c0, c2], message: b"dkg".to_vec() };
+        let shares = vec![
+            frost::sign(&scheme, &package, n0, &keys[0]).unwrap(),
+            frost::sign(&scheme, &package, n2, &keys[2]).unwrap(),
+        ];
+        let signature = frost::aggregate(&scheme, &package, &shares, &outputs[0].public_key_package()).unwrap();
+        assert!(scheme.verify(b"dkg", &signature, &group_key));
+    }
+
+    #[test]
+    fn test_complaint_disqualifies_cheater() {
+        let (mut parties, _) = setup(2, 3);
+        let mut round2: Vec<Round2Package> = parties.iter_mut().flat_map(|p| p.round2_packages().unwrap()).collect();
+
+        // Party 3 sends party 1 a corrupted share
+        let bad = round2.iter_mut().find(|p| p.sender == 3 && p.receiver == 1).unwrap();
+        bad.share += 1u32;
+        let complaints = deliver(&mut parties, &round2);
+        assert_eq!(complaints, vec![Complaint { accuser: 1, accused: 3 }]);
+
+        // Party 3 refuses to answer: everyone disqualifies it
+        for party in parties.iter_mut() {
+            party.resolve_complaint(&complaints[0], None);
+        }
+        let outputs: Vec<DkgOutput> = parties.iter_mut().map(|p| p.finish().unwrap()).collect();
+        assert!(outputs.iter().all(|o| o.qualified == vec![1, 2]));
+        assert_eq!(outputs[0].group_public_key, outputs[1].group_public_key);
+    }
+
+    #[test]
+    fn test_complaint_answered_honestly() {
+        let (mut parties, _) = setup(2, 3);
+        let round2: Vec<Round2Package> = parties.iter_mut().flat_map(|p| p.round2_packages().unwrap()).collect();
+        deliver(&mut parties, &round2);
+
+        // A false accusation is cleared by revealing the share
+        let complaint = Complaint { accuser: 2, accused: 1 };
+        let answer = parties[0].answer_complaint(&complaint);
+        for party in parties.iter_mut() {
+            party.resolve_complaint(&complaint, answer.as_ref());
+        }
+        assert!(parties.iter().all(|p| p.disqualified().is_empty()));
+        assert!(parties[1].finish().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_round1_rejected() {
+        let (mut parties, mut round1) = setup(2, 3);
+        let p256 = curves::p256();
+        let mut fresh = DkgParticipant::new(p256.params(), 1, 2, 3).unwrap();
+
+        // Replaying party 2's proof for party 3's slot fails: proofs are bound to the sender
+        round1[2].proof_of_knowledge = round1[1].proof_of_knowledge.clone();
+        assert!(fresh.receive_round1(&round1[2]).is_err());
+        assert_eq!(fresh.disqualified(), vec![3]);
+        assert!(parties[0].receive_round1(&round1[0]).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use crate::encoding;
+use crate::{EllipticCurve, Point};
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use hkdf::Hkdf;
+use num_bigint::BigUint;
+use sha2::Sha256;
+
+#[derive(Clone, Debug)]
+pub struct ECDHKeyPair {
+    pub private_key: BigUint,
+    pub public_key: Point,
+}
+
+// The x-coordinate of the shared point at the field size, SEC 1's Z. Z is a field element, not a
+// uniformly random string (the bits aren't independent, and only about half of all x values
+// occur), so it goes through a KDF before it's used as a key
+#[derive(Clone, PartialEq, Eq)]
+pub struct SharedSecret {
+    bytes: Vec<u8>,
+}
+
+impl SharedSecret {
+    // HKDF-SHA256 (RFC 5869) with Z as the input keying material: len bytes, at most 255 * 32.
+    // An empty salt is HKDF's default of zeros; info should name the protocol and purpose
+    pub fn derive_key(&self, hkdf_salt: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, &'static str> {
+        let mut key = vec![0u8; len];
+        Hkdf::<Sha256>::new(Some(hkdf_salt), &self.bytes).expand(info, &mut key).map_err(|_| "HKDF output is at most 8160 bytes")?;
+        Ok(key)
+    }
+
+    // Raw Z, for protocols that specify their own KDF
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+// Keep Z out of logs
+impl std::fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "SharedSecret(..)")
+    }
+}
+
+pub struct ECDH {
+    pub curve: EllipticCurve,
+    pub generator: Point,
+    pub order: BigUint,
+    pub cofactor: BigUint,
+}
+
+impl ECDH {
+    pub fn new(curve: EllipticCurve, generator: Point, order: BigUint) -> Self {
+        Self::with_cofactor(curve, generator, order, BigUint::from(1u32))
+    }
+
+    // Domain parameters for curves whose group order is h * n with h > 1
+    pub fn with_cofactor(curve: EllipticCurve, generator: Point, order: BigUint, cofactor: BigUint) -> Self {
+        ECDH {
+            curve,
+            generator,
+            order,
+            cofactor,
+        }
+    }
+
+    // Generate private key: random in [1, n-1]
+    pub fn generate_private_key(&self) -> BigUint {
+        Scalar::random(&self.order, &mut Entropy).into_value()
+    }
+
+    // Generate public key: Q = d * G
+    // d is secret, so this goes through the complete, rerandomized ladder
+    pub fn generate_public_key(&self, private_key: &BigUint) -> Point {
+        self.curve.scalar_mult_complete(&self.generator, private_key)
+    }
+
+    // Generate keypair
+    pub fn generate_keypair(&self) -> ECDHKeyPair {
+        let private_key = self.generate_private_key();
+        let public_key = self.generate_public_key(&private_key);
+        ECDHKeyPair {
+            private_key,
+            public_key,
+        }
+    }
+
+    // Full public key validation (SEC 1, 3.2.2):
+    // not the identity, on the curve, and in the prime-order subgroup (n * Q = O). The
+    // multiplication runs even for h = 1, since new() assumes that cofactor rather than checking it
+    // The point formulas never use b, so skipping the on-curve check lets an attacker
+    // pick points on weaker curves (invalid-curve attack); skipping the subgroup check
+    // leaks d mod h through small-order points (small-subgroup attack)
+    pub fn validate_public_key(&self, public_key: &Point) -> Result<(), &'static str> {
+        if *public_key == Point::Identity {
+            return Err("public key is the point at infinity");
+        }
+        if !self.curve.is_on_curve(public_key) {
+            return Err("public key is not on the curve");
+        }
+        if !self.curve.is_torsion_point(public_key, &self.order) {
+            return Err("public key is not in the prime-order subgroup");
+        }
+        Ok(())
+    }
+
+    // Shared secret: S = d * Q_peer, after validating Q_peer
+    // Validation puts Q_peer in the odd-order subgroup, where the complete ladder is exact
+    pub fn shared_secret(&self, private_key: &BigUint, peer_public_key: &Point) -> Result<Point, &'static str> {
+        self.validate_public_key(peer_public_key)?;
+
+        match self.curve.scalar_mult_complete(peer_public_key, private_key) {
+            Point::Identity => Err("shared secret is the point at infinity"),
+            secret => Ok(secret),
+        }
+    }
+
+    // shared_secret as the x-coordinate Z, ready for derive_key
+    pub fn agree(&self, private_key: &BigUint, peer_public_key: &Point) -> Result<SharedSecret, &'static str> {
+        let Point::Coordinate(x, _) = self.shared_secret(private_key, peer_public_key)? else {
+            unreachable!()
+        };
+        Ok(SharedSecret { bytes: encoding::to_fixed_bytes(&x, encoding::field_size(&self.curve)) })
+    }
+
+    // One-pass static-ephemeral agreement (SP 800-56A C(1e, 1s), the KEM half of ECIES): the
+    // sender needs only the recipient's static key, and sends the fresh ephemeral public key
+    // along with whatever the secret protects. Returns (secret, ephemeral public key).
+    // The ephemeral key is public and not bound into Z; ECIES-KEM binds it by putting its
+    // encoding in the KDF input, e.g. as derive_key's info
+    pub fn encapsulate(&self, recipient_public_key: &Point) -> Result<(SharedSecret, Point), &'static str> {
+        let ephemeral = self.generate_keypair();
+        Ok((self.agree(&ephemeral.private_key, recipient_public_key)?, ephemeral.public_key))
+    }
+
+    // The recipient's side; the ephemeral key is validated like any peer key
+    pub fn decapsulate(&self, private_key: &BigUint, ephemeral_public_key: &Point) -> Result<SharedSecret, &'static str> {
+        self.agree(private_key, ephemeral_public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use crate::encoding::{from_hex, to_hex};
+
+    fn setup_ecdh() -> ECDH {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        ECDH::new(curve, generator, BigUint::from(19u32))
+    }
+
+    #[test]
+    fn test_shared_secret_agreement() {
+        let ecdh = setup_ecdh();
+        let alice = ecdh.generate_keypair();
+        let bob = ecdh.generate_keypair();
+
+        let alice_secret = ecdh.shared_secret(&alice.private_key, &bob.public_key).unwrap();
+        let bob_secret = ecdh.shared_secret(&bob.private_key, &alice.public_key).unwrap();
+        assert_eq!(alice_secret, bob_secret);
+    }
+
+    #[test]
+    fn test_secret_mults_match_scalar_mult() {
+        // The complete ladder gives the same points as the affine double-and-add
+        let ecdh = setup_ecdh();
+        let peer = ecdh.curve.scalar_mult(&ecdh.generator, &BigUint::from(7u32));
+        for d in 1u32..19 {
+            let d = BigUint::from(d);
+            assert_eq!(ecdh.generate_public_key(&d), ecdh.curve.scalar_mult(&ecdh.generator, &d));
+            assert_eq!(ecdh.shared_secret(&d, &peer), Ok(ecdh.curve.scalar_mult(&peer, &d)));
+        }
+
+        let p256 = curves::p256().ecdh();
+        let alice = p256.generate_keypair();
+        let bob = p256.generate_keypair();
+        assert_eq!(alice.public_key, p256.curve.scalar_mult(&p256.generator, &alice.private_key));
+        assert_eq!(
+            p256.shared_secret(&alice.private_key, &bob.public_key),
+            Ok(p256.curve.scalar_mult(&bob.public_key, &alice.private_key))
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_public_keys() {
+        let ecdh = setup_ecdh();
+        let private_key = BigUint::from(3u32);
+
+        assert!(ecdh.shared_secret(&private_key, &Point::Identity).is_err());
+
+        // (1, 1) is not on y² = x³ + 2x + 2 (mod 17)
+        let off_curve = Point::Coordinate(BigUint::from(1u32), BigUint::from(1u32));
+        assert!(ecdh.shared_secret(&private_key, &off_curve).is_err());
+    }
+
+    #[test]
+    fn test_rejects_small_subgroup_points() {
+        // y² = x³ + x + 1 (mod 23): 28 = 4 * 7 points, (4, 0) has order 2
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(4u32));
+        let ecdh = ECDH::with_cofactor(curve, generator, BigUint::from(7u32), BigUint::from(4u32));
+
+        let order_two = Point::Coordinate(BigUint::from(4u32), BigUint::from(0u32));
+        assert!(ecdh.curve.is_on_curve(&order_two));
+        assert!(ecdh.shared_secret(&BigUint::from(3u32), &order_two).is_err());
+
+        // Same curve through new(), which takes h = 1 on trust
+        let ecdh = ECDH::new(ecdh.curve, ecdh.generator, BigUint::from(7u32));
+        assert_eq!(ecdh.validate_public_key(&order_two), Err("public key is not in the prime-order subgroup"));
+    }
+
+    #[test]
+    fn test_derive_key_rfc5869() {
+        // RFC 5869 A.1, A.2 and A.3 (SHA-256), with the IKM standing in for Z
+        let cases = [
+            ("0b".repeat(22), "000102030405060708090a0b0c".to_string(), "f0f1f2f3f4f5f6f7f8f9".to_string(), "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"),
+            (
+                (0x00..0x50u8).map(|b| format!("{:02x}", b)).collect(),
+                (0x60..0xb0u8).map(|b| format!("{:02x}", b)).collect(),
+                (0xb0..=0xffu8).map(|b| format!("{:02x}", b)).collect(),
+                "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71cc30c58179ec3e87c14c01d5c1f3434f1d87",
+            ),
+            ("0b".repeat(22), String::new(), String::new(), "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"),
+        ];
+        for (ikm, salt, info, okm) in cases {
+            let secret = SharedSecret { bytes: from_hex(&ikm).unwrap() };
+            let key = secret.derive_key(&from_hex(&salt).unwrap(), &from_hex(&info).unwrap(), okm.len() / 2).unwrap();
+            assert_eq!(to_hex(&key), okm);
+        }
+        assert!(SharedSecret { bytes: vec![1] }.derive_key(b"", b"", 255 * 32 + 1).is_err());
+    }
+
+    #[test]
+    fn test_agree() {
+        let ecdh = curves::p256().ecdh();
+        let alice = ecdh.generate_keypair();
+        let bob = ecdh.generate_keypair();
+        let alice_secret = ecdh.agree(&alice.private_key, &bob.public_key).unwrap();
+        let bob_secret = ecdh.agree(&bob.private_key, &alice.public_key).unwrap();
+        assert_eq!(alice_secret, bob_secret);
+        assert_eq!(alice_secret.raw_bytes().len(), 32);
+        assert_eq!(format!("{:?}", alice_secret), "SharedSecret(..)");
+
+        let key = alice_secret.derive_key(b"salt", b"example v1 encryption key", 32).unwrap();
+        assert_eq!(key, bob_secret.derive_key(b"salt", b"example v1 encryption key", 32).unwrap());
+        assert_ne!(key, alice_secret.derive_key(b"salt", b"example v1 mac key", 32).unwrap());
+        assert!(ecdh.agree(&alice.private_key, &Point::Identity).is_err());
+    }
+
+    #[test]
+    fn test_encapsulate() {
+        let p256 = curves::p256();
+        let ecdh = p256.ecdh();
+        let recipient = ecdh.generate_keypair();
+
+        let (secret, ephemeral) = ecdh.encapsulate(&recipient.public_key).unwrap();
+        assert_eq!(ecdh.decapsulate(&recipient.private_key, &ephemeral).unwrap(), secret);
+        let info = encoding::encode_point(&p256.curve, &ephemeral, true);
+        assert_eq!(
+            ecdh.decapsulate(&recipient.private_key, &ephemeral).unwrap().derive_key(b"", &info, 32),
+            secret.derive_key(b"", &info, 32)
+        );
+
+        // Every encapsulation is fresh, and only the recipient's key opens it
+        let (other, other_ephemeral) = ecdh.encapsulate(&recipient.public_key).unwrap();
+        assert_ne!((other, other_ephemeral), (secret.clone(), ephemeral.clone()));
+        assert_ne!(ecdh.decapsulate(&ecdh.generate_private_key(), &ephemeral).unwrap(), secret);
+
+        // Invalid ephemeral or recipient keys
+        assert!(ecdh.decapsulate(&recipient.private_key, &Point::Identity).is_err());
+        let Point::Coordinate(x, y) = &ephemeral else { panic!() };
+        assert!(ecdh.decapsulate(&recipient.private_key, &Point::Coordinate(x.clone(), y + 1u32)).is_err());
+        assert!(ecdh.encapsulate(&Point::Identity).is_err());
+    }
+}
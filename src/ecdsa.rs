@@ -1,7 +1,13 @@
-use crate::{EllipticCurve, Point, FiniteField};
-use num_bigint::{BigUint, RandBigInt};
+use crate::{EllipticCurve, Point};
+use crate::der::{self, DerReader};
+use crate::encoding::to_fixed_bytes;
+use crate::ntheory;
+use crate::recoverable::digest_to_scalar;
+use crate::scalar::Scalar;
+use crate::entropy::Entropy;
+use num_bigint::BigUint;
+use rand::RngCore;
 use sha2::{Sha256, Digest};
-use rand::thread_rng;
 
 #[derive(Clone, Debug)]
 pub struct ECDSAKeyPair {
@@ -9,41 +15,185 @@ pub struct ECDSAKeyPair {
     pub public_key: Point,
 }
 
+// Every value in s = k^(-1) * (z + r * d) mod n, from sign_traced
+#[derive(Clone, Debug, PartialEq)]
+pub struct SigningTrace {
+    pub z: BigUint,
+    pub k: BigUint,
+    pub k_inverse: BigUint,
+    // R = k * G, with r = x(R) mod n
+    pub point: Point,
+    pub signature: ECDSASignature,
+}
+
+// w = s^(-1), u1 = z * w, u2 = r * w and P = u1 * G + u2 * Q, from verify_traced; valid when
+// x(P) mod n = r
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerificationTrace {
+    pub z: BigUint,
+    pub w: BigUint,
+    pub u1: BigUint,
+    pub u2: BigUint,
+    pub point: Point,
+    pub valid: bool,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ECDSASignature {
     pub r: BigUint,
     pub s: BigUint,
 }
 
+// DER: SEQUENCE { INTEGER r, INTEGER s } (X9.62, RFC 3279), decoded strictly (see der.rs)
+impl ECDSASignature {
+    pub fn to_der(&self) -> Vec<u8> {
+        der::sequence(&[der::integer(&self.r), der::integer(&self.s)])
+    }
+
+    pub fn from_der(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut outer = DerReader::new(bytes);
+        let mut sequence = outer.read_sequence()?;
+        outer.finish()?;
+        let r = sequence.read_integer()?;
+        let s = sequence.read_integer()?;
+        sequence.finish()?;
+        Ok(ECDSASignature { r, s })
+    }
+
+    // IEEE P1363: r || s, each big-endian and padded to the byte length of n
+    // (32 for P-256 / ES256, 48 for P-384, 66 for P-521); the raw form JOSE and WebAuthn use
+    pub fn to_fixed_bytes(&self, order_len: usize) -> Vec<u8> {
+        let mut out = to_fixed_bytes(&self.r, order_len);
+        out.extend(to_fixed_bytes(&self.s, order_len));
+        out
+    }
+
+    pub fn from_fixed_bytes(bytes: &[u8], order_len: usize) -> Result<Self, &'static str> {
+        if order_len == 0 || bytes.len() != 2 * order_len {
+            return Err("fixed-width signature must be exactly 2 * order_len bytes");
+        }
+        let (r, s) = bytes.split_at(order_len);
+        Ok(ECDSASignature { r: BigUint::from_bytes_be(r), s: BigUint::from_bytes_be(s) })
+    }
+}
+
 pub struct ECDSA {
     pub curve: EllipticCurve,
     pub generator: Point,
     pub order: BigUint,
+    pub cofactor: BigUint,
+    // Blind the secret scalars in keygen and signing against power analysis (off by default)
+    pub blinding: bool,
 }
 
 impl ECDSA {
     pub fn new(curve: EllipticCurve, generator: Point, order: BigUint) -> Self {
+        Self::with_cofactor(curve, generator, order, BigUint::from(1u32))
+    }
+
+    // Domain parameters for curves whose group order is h * n with h > 1
+    pub fn with_cofactor(curve: EllipticCurve, generator: Point, order: BigUint, cofactor: BigUint) -> Self {
         ECDSA {
             curve,
             generator,
             order,
+            cofactor,
+            blinding: false,
+        }
+    }
+
+    // new() that refuses bad domain parameters, e.g. a custom curve with a mistyped constant
+    pub fn new_validated(curve: EllipticCurve, generator: Point, order: BigUint) -> Result<Self, &'static str> {
+        let ecdsa = Self::new(curve, generator, order);
+        ecdsa.validate()?;
+        Ok(ecdsa)
+    }
+
+    // Domain parameter checks (SEC 1 3.1.1.2.1, without the MOV and anomalous-curve ones):
+    //   G is an affine point on the curve
+    //   n is prime (Miller–Rabin) and n * G = O
+    //   h * n is within the Hasse interval, as the group order must be
+    //   h * n != p + 1: a supersingular curve has embedding degree 2 (see attacks::mov)
+    // p itself isn't tested for primality; every field operation assumes it
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.generator == Point::Identity || !self.curve.is_on_curve(&self.generator) {
+            return Err("generator is not a point on the curve");
+        }
+        if !ntheory::is_probable_prime(&self.order) {
+            return Err("order is not prime");
         }
+        if self.curve.scalar_mult(&self.generator, &self.order) != Point::Identity {
+            return Err("generator does not have the given order");
+        }
+        if !self.curve.within_hasse_bounds(&(&self.cofactor * &self.order)) {
+            return Err("order is outside the Hasse bound");
+        }
+        if &self.cofactor * &self.order == self.curve.p() + 1u32 {
+            return Err("supersingular curve: the MOV reduction applies");
+        }
+        Ok(())
+    }
+
+    // Check that a point lies in the prime-order subgroup: on the curve and n * P = O
+    // Rejects points of small order (or with a small-order component) when h > 1
+    pub fn is_in_prime_subgroup(&self, point: &Point) -> bool {
+        self.curve.is_torsion_point(point, &self.order)
+    }
+
+    // Cofactor clearing: h * P maps any curve point into the prime-order subgroup
+    pub fn clear_cofactor(&self, point: &Point) -> Point {
+        self.curve.scalar_mult(point, &self.cofactor)
     }
 
     // Generate private key: random in [1, n-1]
     pub fn generate_private_key(&self) -> BigUint {
-        let mut rng = thread_rng();
-        rng.gen_biguint_range(&BigUint::from(1u32), &self.order)
+        self.private_key_from_rng(&mut Entropy)
+    }
+
+    // The private key is read from the rng the same way on every platform and crate version, so
+    // a seeded rng (rand_chacha's ChaCha20Rng, say) gives the same keys everywhere:
+    //   candidate = fill_bytes(byte length of n), big-endian, bits above n's bit length cleared
+    // repeated until 1 <= candidate <= n - 1. Only fill_bytes is called, never next_u32/u64
+    fn private_key_from_rng<R: RngCore + ?Sized>(&self, rng: &mut R) -> BigUint {
+        let bits = self.order.bits();
+        let mut bytes = vec![0u8; bits.div_ceil(8) as usize];
+        for _ in 0..256 {
+            rng.fill_bytes(&mut bytes);
+            if !bits.is_multiple_of(8) {
+                bytes[0] &= (1u8 << (bits % 8)) - 1;
+            }
+            let candidate = BigUint::from_bytes_be(&bytes);
+            if candidate > BigUint::from(0u32) && candidate < self.order {
+                return candidate;
+            }
+        }
+        panic!("?rng keeps producing scalars out of range?");
     }
 
     // Generate public key: Q = d * G
+    // d is secret, so this goes through the branch-free complete formulas
     pub fn generate_public_key(&self, private_key: &BigUint) -> Point {
-        self.curve.scalar_mult(&self.generator, private_key)
+        self.secret_mult(private_key)
+    }
+
+    // k * G for a secret k, blinded with a random multiple of n if enabled
+    fn secret_mult(&self, k: &BigUint) -> Point {
+        if self.blinding {
+            self.curve.scalar_mult_blinded(&self.generator, k, &self.order, &mut Entropy)
+        } else {
+            self.curve.scalar_mult_complete(&self.generator, k)
+        }
     }
 
     // Generate keypair
     pub fn generate_keypair(&self) -> ECDSAKeyPair {
-        let private_key = self.generate_private_key();
+        self.generate_keypair_from_rng(&mut Entropy)
+    }
+
+    // Keypair from a caller's rng: with a fixed seed, test suites and reproducible builds get the
+    // same keys on every run. Use generate_keypair for real keys
+    pub fn generate_keypair_from_rng<R: RngCore + ?Sized>(&self, rng: &mut R) -> ECDSAKeyPair {
+        let private_key = self.private_key_from_rng(rng);
         let public_key = self.generate_public_key(&private_key);
         ECDSAKeyPair {
             private_key,
@@ -53,81 +203,247 @@ impl ECDSA {
 
     // Hash message with SHA-256
     fn hash_message(&self, message: &[u8]) -> BigUint {
-        let mut hasher = Sha256::new();
-        hasher.update(message);
-        let hash = hasher.finalize();
-        let hash_int = BigUint::from_bytes_be(&hash);
-        hash_int % &self.order
+        hash_to_scalar(message, &self.order)
     }
 
     // Sign message
     // s = k^(-1) * (z + r * d) mod n
     pub fn sign(&self, message: &[u8], private_key: &BigUint) -> Result<ECDSASignature, &'static str> {
-        let mut rng = thread_rng();
-        let field = FiniteField { p: self.order.clone() };
-        let z = self.hash_message(message);
+        self.sign_z(&self.hash_message(message), private_key)
+    }
+
+    // Sign a digest computed by the caller, for hashes other than SHA-256 (SHA-384 with P-384,
+    // SHA-512 with P-521): z is its leftmost bits up to the order's length
+    pub fn sign_digest(&self, digest: &[u8], private_key: &BigUint) -> Result<ECDSASignature, &'static str> {
+        self.sign_z(&digest_to_scalar(digest, &self.order), private_key)
+    }
+
+    // sign_digest for a digest hashed elsewhere (a TLS transcript hash, an HSM, a streaming
+    // pipeline) and used as is. Stricter about the input: like RustCrypto's ecdsa it rejects
+    // digests shorter than half the order's size, which are a mistake rather than a hash
+    pub fn sign_prehashed(&self, digest: &[u8], private_key: &BigUint) -> Result<ECDSASignature, &'static str> {
+        self.check_prehash(digest)?;
+        self.sign_digest(digest, private_key)
+    }
+
+    fn check_prehash(&self, digest: &[u8]) -> Result<(), &'static str> {
+        if digest.len() < self.order.bits().div_ceil(8) as usize / 2 {
+            return Err("prehash is shorter than half the order's size");
+        }
+        Ok(())
+    }
+
+    fn sign_z(&self, z: &BigUint, private_key: &BigUint) -> Result<ECDSASignature, &'static str> {
+        let mut rng = Entropy;
 
         loop {
-            // Generate random k
-            let k = rng.gen_biguint_range(&BigUint::from(1u32), &self.order);
-            
-            // Compute R = k * G
-            let point = self.curve.scalar_mult(&self.generator, &k);
-            
-            let r = match point {
-                Point::Coordinate(x, _) => x % &self.order,
-                Point::Identity => continue,
-            };
-
-            if r == BigUint::from(0u32) {
-                continue;
+            // Generate random k, retrying on the (unlikely) r = 0 or s = 0
+            let k = Scalar::random(&self.order, &mut rng).into_value();
+
+            if let Ok(signature) = self.sign_z_with_nonce(z, private_key, &k) {
+                return Ok(signature);
             }
+        }
+    }
 
-            // Compute s = k^(-1) * (z + r * d) mod n
-            let r_d = field.mul(&r, private_key);
-            let z_r_d = field.add(&z, &r_d);
-            let k_inv = field.div(&BigUint::from(1u32), &k);
-            let s = field.mul(&k_inv, &z_r_d);
+    // Sign message with a caller-chosen nonce k
+    // k must be secret, uniform and never reused: two signatures sharing k leak the private key
+    // (see attacks::recover_key_from_nonce_reuse, the PlayStation 3 hack)
+    pub fn sign_with_nonce(&self, message: &[u8], private_key: &BigUint, k: &BigUint) -> Result<ECDSASignature, &'static str> {
+        self.sign_z_with_nonce(&self.hash_message(message), private_key, k)
+    }
 
-            if s == BigUint::from(0u32) {
-                continue;
-            }
+    fn sign_z_with_nonce(&self, z: &BigUint, private_key: &BigUint, k: &BigUint) -> Result<ECDSASignature, &'static str> {
+        let z = Scalar::new(z.clone(), &self.order);
+
+        if *k == BigUint::from(0u32) || *k >= self.order {
+            return Err("nonce out of range");
+        }
+
+        // Compute R = k * G, branch-free since k is secret
+        let point = self.secret_mult(k);
+
+        let r = match point {
+            Point::Coordinate(x, _) => x % &self.order,
+            Point::Identity => return Err("nonce gives point at infinity"),
+        };
+
+        if r == BigUint::from(0u32) {
+            return Err("nonce gives r = 0");
+        }
 
-            return Ok(ECDSASignature { r, s });
+        // Compute s = k^(-1) * (z + r * d) mod n
+        let r = Scalar::new(r, &self.order);
+        let d = Scalar::new(private_key.clone(), &self.order);
+        let k = Scalar::new(k.clone(), &self.order);
+        let s = if self.blinding {
+            // With a random mask b: s = (k * b)^(-1) * (z * b + r * (d * b))
+            // k and d are never multiplied or inverted unmasked; b cancels out in the result
+            let b = Scalar::random(&self.order, &mut Entropy);
+            let kb_inv = (&k * &b).inv().ok_or("nonce out of range")?;
+            kb_inv * (&z * &b + &r * (d * &b))
+        } else {
+            k.inv().ok_or("nonce out of range")? * (z + &r * d)
+        };
+
+        if s.is_zero() {
+            return Err("nonce gives s = 0");
         }
+
+        Ok(ECDSASignature { r: r.into_value(), s: s.into_value() })
     }
 
     // Verify signature
     // Check if r == x_p mod n where (x_p, y_p) = u1*G + u2*Q
     pub fn verify(&self, message: &[u8], signature: &ECDSASignature, public_key: &Point) -> bool {
-        let field = FiniteField { p: self.order.clone() };
-        
+        self.verify_z(&self.hash_message(message), signature, public_key)
+    }
+
+    pub fn verify_digest(&self, digest: &[u8], signature: &ECDSASignature, public_key: &Point) -> bool {
+        self.verify_z(&digest_to_scalar(digest, &self.order), signature, public_key)
+    }
+
+    pub fn verify_prehashed(&self, digest: &[u8], signature: &ECDSASignature, public_key: &Point) -> bool {
+        self.check_prehash(digest).is_ok() && self.verify_digest(digest, signature, public_key)
+    }
+
+    fn verify_z(&self, z: &BigUint, signature: &ECDSASignature, public_key: &Point) -> bool {
+        self.verify_z_traced(z, signature, public_key).is_ok_and(|trace| trace.valid)
+    }
+
+    fn verify_z_traced(&self, z: &BigUint, signature: &ECDSASignature, public_key: &Point) -> Result<VerificationTrace, &'static str> {
         // Check r and s in valid range
         if signature.r == BigUint::from(0u32) || signature.r >= self.order ||
            signature.s == BigUint::from(0u32) || signature.s >= self.order {
-            return false;
+            return Err("r or s out of range");
         }
 
-        let z = self.hash_message(message);
+        let z = Scalar::new(z.clone(), &self.order);
 
-        // Compute w = s^(-1) mod n
-        let w = field.div(&BigUint::from(1u32), &signature.s);
+        // Compute w = s^(-1) mod n (s is non-zero, checked above)
+        let w = Scalar::new(signature.s.clone(), &self.order).inv().unwrap();
 
         // Compute u1 = z * w mod n, u2 = r * w mod n
-        let u1 = field.mul(&z, &w);
-        let u2 = field.mul(&signature.r, &w);
+        let u1 = &z * &w;
+        let u2 = Scalar::new(signature.r.clone(), &self.order) * &w;
 
         // Compute point P = u1*G + u2*Q
-        let u1_g = self.curve.scalar_mult(&self.generator, &u1);
-        let u2_q = self.curve.scalar_mult(public_key, &u2);
-        let point = self.curve.add(&u1_g, &u2_q);
+        let g = self.curve.point(self.generator.clone());
+        let q = self.curve.point(public_key.clone());
+        let point = (u1.value() * g + u2.value() * q).into_point();
 
         // Verify r == x_p mod n
-        match point {
+        let valid = match &point {
             Point::Coordinate(x, _) => (x % &self.order) == signature.r,
             Point::Identity => false,
+        };
+        Ok(VerificationTrace {
+            z: z.into_value(),
+            w: w.into_value(),
+            u1: u1.into_value(),
+            u2: u2.into_value(),
+            point,
+            valid,
+        })
+    }
+
+    // sign, keeping the intermediate values for learners and debugging. The trace holds the
+    // nonce k, which gives away the private key to anyone who also sees the signature
+    pub fn sign_traced(&self, message: &[u8], private_key: &BigUint) -> Result<SigningTrace, &'static str> {
+        let z = self.hash_message(message);
+        loop {
+            let k = Scalar::random(&self.order, &mut Entropy);
+            let Ok(signature) = self.sign_z_with_nonce(&z, private_key, k.value()) else { continue };
+            let point = self.curve.scalar_mult(&self.generator, k.value());
+            let k_inverse = k.inv().ok_or("nonce out of range")?.into_value();
+            return Ok(SigningTrace { z, k: k.into_value(), k_inverse, point, signature });
         }
     }
+
+    // verify with the intermediate values; Err only for r or s outside [1, n - 1], where
+    // verification stops before computing anything
+    pub fn verify_traced(&self, message: &[u8], signature: &ECDSASignature, public_key: &Point) -> Result<VerificationTrace, &'static str> {
+        self.verify_z_traced(&self.hash_message(message), signature, public_key)
+    }
+}
+
+// Streaming sign/verify for messages too large to hold in memory: the message goes into the
+// SHA-256 state a chunk at a time (update, or io::copy through the Write impl) and finalize
+// runs the same z as sign/verify, so the signatures are interchangeable with theirs
+pub struct SigningContext<'a> {
+    ecdsa: &'a ECDSA,
+    private_key: &'a BigUint,
+    hasher: Sha256,
+}
+
+pub struct VerifyingContext<'a> {
+    ecdsa: &'a ECDSA,
+    public_key: &'a Point,
+    hasher: Sha256,
+}
+
+impl ECDSA {
+    pub fn signing_context<'a>(&'a self, private_key: &'a BigUint) -> SigningContext<'a> {
+        SigningContext { ecdsa: self, private_key, hasher: Sha256::new() }
+    }
+
+    pub fn verifying_context<'a>(&'a self, public_key: &'a Point) -> VerifyingContext<'a> {
+        VerifyingContext { ecdsa: self, public_key, hasher: Sha256::new() }
+    }
+}
+
+impl SigningContext<'_> {
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    pub fn finalize(self) -> Result<ECDSASignature, &'static str> {
+        let z = finalize_to_scalar(self.hasher, &self.ecdsa.order);
+        self.ecdsa.sign_z(&z, self.private_key)
+    }
+}
+
+impl VerifyingContext<'_> {
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    pub fn finalize(self, signature: &ECDSASignature) -> bool {
+        let z = finalize_to_scalar(self.hasher, &self.ecdsa.order);
+        self.ecdsa.verify_z(&z, signature, self.public_key)
+    }
+}
+
+impl std::io::Write for SigningContext<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl std::io::Write for VerifyingContext<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// SHA-256 digest of the message reduced mod n: the z value in sign/verify
+pub(crate) fn hash_to_scalar(message: &[u8], order: &BigUint) -> BigUint {
+    finalize_to_scalar(Sha256::new_with_prefix(message), order)
+}
+
+// The one digest-to-z reduction, shared by hash_to_scalar and the streaming contexts
+fn finalize_to_scalar(hasher: Sha256, order: &BigUint) -> BigUint {
+    BigUint::from_bytes_be(&hasher.finalize()) % order
 }
 
 #[cfg(test)]
@@ -135,11 +451,7 @@ mod tests {
     use super::*;
 
     fn setup_ecdsa() -> ECDSA {
-        let curve = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(2u32),
-            p: BigUint::from(17u32),
-        };
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
         
         let generator = Point::Coordinate(
             BigUint::from(5u32),
@@ -161,6 +473,56 @@ mod tests {
         assert!(ecdsa.curve.is_on_curve(&keypair.public_key));
     }
 
+    #[test]
+    fn test_keygen_from_rng() {
+        use rand::SeedableRng;
+        use rand::rngs::mock::StepRng;
+
+        // The key is the first fill_bytes output in range, read big-endian
+        let ecdsa = crate::curves::p256().ecdsa();
+        let mut rng = StepRng::new(0x0102030405060708, 0);
+        let keypair = ecdsa.generate_keypair_from_rng(&mut rng);
+        assert_eq!(crate::encoding::to_hex(&keypair.private_key.to_bytes_be()), "0807060504030201".repeat(4));
+        assert_eq!(keypair.public_key, ecdsa.generate_public_key(&keypair.private_key));
+
+        // n = 19 takes one byte with 5 bits kept: 0xff masks to 31 and 0 is out of range, so both retry
+        let mut rng = StepRng::new(0xff, 0x0cu64.wrapping_sub(0xff));
+        assert_eq!(setup_ecdsa().generate_keypair_from_rng(&mut rng).private_key, BigUint::from(12u32));
+        let mut rng = StepRng::new(0, 12);
+        assert_eq!(setup_ecdsa().generate_keypair_from_rng(&mut rng).private_key, BigUint::from(12u32));
+
+        let seeded = |seed| ecdsa.generate_keypair_from_rng(&mut rand::rngs::StdRng::seed_from_u64(seed)).private_key;
+        assert_eq!(seeded(7), seeded(7));
+        assert_ne!(seeded(7), seeded(8));
+    }
+
+    #[test]
+    fn test_domain_parameter_validation() {
+        assert!(setup_ecdsa().validate().is_ok());
+        for curve in [crate::curves::p256(), crate::curves::secp256k1(), crate::curves::secp224k1()] {
+            assert_eq!(curve.ecdsa().validate(), Ok(()));
+        }
+
+        let curve = setup_ecdsa().curve;
+        let g = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        let new = |generator: &Point, order: u32| ECDSA::new_validated(curve.clone(), generator.clone(), BigUint::from(order));
+        assert!(new(&g, 19).is_ok());
+        assert_eq!(new(&Point::Coordinate(BigUint::from(5u32), BigUint::from(2u32)), 19).err(), Some("generator is not a point on the curve"));
+        assert_eq!(new(&Point::Identity, 19).err(), Some("generator is not a point on the curve"));
+        assert_eq!(new(&g, 21).err(), Some("order is not prime"));
+        assert_eq!(new(&g, 17).err(), Some("generator does not have the given order"));
+
+        // 2 * 19 = 38 points can't fit on a curve over F_17, which has 18 ± 8
+        let wrong_cofactor = ECDSA::with_cofactor(curve, g, BigUint::from(19u32), BigUint::from(2u32));
+        assert_eq!(wrong_cofactor.validate(), Err("order is outside the Hasse bound"));
+
+        // y² = x³ + x over F_43 has 44 = 4 * 11 points
+        let supersingular = EllipticCurve::new(BigUint::from(1u32), BigUint::from(0u32), BigUint::from(43u32));
+        let g = Point::Coordinate(BigUint::from(4u32), BigUint::from(5u32));
+        let ecdsa = ECDSA::with_cofactor(supersingular, g, BigUint::from(11u32), BigUint::from(4u32));
+        assert_eq!(ecdsa.validate(), Err("supersingular curve: the MOV reduction applies"));
+    }
+
     #[test]
     fn test_sign_verify() {
         let ecdsa = setup_ecdsa();
@@ -174,6 +536,35 @@ mod tests {
         assert!(!ecdsa.verify(wrong_message, &signature, &keypair.public_key));
     }
 
+    #[test]
+    fn test_sign_verify_traced() {
+        let ecdsa = crate::curves::p256().ecdsa();
+        let keypair = ecdsa.generate_keypair();
+        let message = b"walk me through it";
+
+        let trace = ecdsa.sign_traced(message, &keypair.private_key).unwrap();
+        let n = &ecdsa.order;
+        assert_eq!(trace.point, ecdsa.curve.scalar_mult(&ecdsa.generator, &trace.k));
+        let Point::Coordinate(x, _) = &trace.point else { panic!("R is the identity") };
+        assert_eq!(x % n, trace.signature.r);
+        assert_eq!((&trace.k * &trace.k_inverse) % n, BigUint::from(1u32));
+        assert_eq!((&trace.signature.s * &trace.k) % n, (&trace.z + &trace.signature.r * &keypair.private_key) % n);
+        assert_eq!(ecdsa.sign_with_nonce(message, &keypair.private_key, &trace.k), Ok(trace.signature.clone()));
+
+        // u1 G + u2 Q comes back to R
+        let verification = ecdsa.verify_traced(message, &trace.signature, &keypair.public_key).unwrap();
+        assert!(verification.valid);
+        assert_eq!(verification.point, trace.point);
+        assert_eq!(verification.z, trace.z);
+        assert_eq!((&verification.w * &trace.signature.s) % n, BigUint::from(1u32));
+
+        let wrong = ecdsa.verify_traced(b"something else", &trace.signature, &keypair.public_key).unwrap();
+        assert!(!wrong.valid);
+        assert_ne!(wrong.point, trace.point);
+        let zero = ECDSASignature { r: BigUint::from(0u32), s: trace.signature.s.clone() };
+        assert!(ecdsa.verify_traced(message, &zero, &keypair.public_key).is_err());
+    }
+
     #[test]
     fn test_invalid_signature() {
         let ecdsa = setup_ecdsa();
@@ -187,4 +578,176 @@ mod tests {
         
         assert!(!ecdsa.verify(message, &invalid_sig, &keypair.public_key));
     }
+
+    #[test]
+    fn test_sign_with_nonce() {
+        let ecdsa = setup_ecdsa();
+        let private_key = BigUint::from(7u32);
+        let public_key = ecdsa.generate_public_key(&private_key);
+        let message = b"test message";
+
+        let signature = ecdsa.sign_with_nonce(message, &private_key, &BigUint::from(3u32)).unwrap();
+        assert!(ecdsa.verify(message, &signature, &public_key));
+
+        assert!(ecdsa.sign_with_nonce(message, &private_key, &BigUint::from(0u32)).is_err());
+        assert!(ecdsa.sign_with_nonce(message, &private_key, &ecdsa.order).is_err());
+    }
+
+    #[test]
+    fn test_blinded_signing() {
+        let mut ecdsa = setup_ecdsa();
+        let private_key = BigUint::from(7u32);
+        let message = b"test message";
+        let plain_key = ecdsa.generate_public_key(&private_key);
+        let plain = ecdsa.sign_with_nonce(message, &private_key, &BigUint::from(3u32)).unwrap();
+
+        // Blinding changes the computation, not the result
+        ecdsa.blinding = true;
+        assert_eq!(ecdsa.generate_public_key(&private_key), plain_key);
+        for _ in 0..10 {
+            assert_eq!(ecdsa.sign_with_nonce(message, &private_key, &BigUint::from(3u32)).unwrap(), plain);
+        }
+        let signature = ecdsa.sign(message, &private_key).unwrap();
+        assert!(ecdsa.verify(message, &signature, &plain_key));
+    }
+
+    #[test]
+    fn test_der_roundtrip() {
+        let signature = ECDSASignature { r: BigUint::from(0x80u32), s: BigUint::from(0x7fu32) };
+        let der = signature.to_der();
+        assert_eq!(der, vec![0x30, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x01, 0x7f]);
+        assert_eq!(ECDSASignature::from_der(&der).unwrap(), signature);
+
+        // 256-bit values with the top bit set: 33-byte integers, 70-byte body
+        let big = BigUint::from_bytes_be(&[0xffu8; 32]);
+        let signature = ECDSASignature { r: big.clone(), s: big };
+        assert_eq!(ECDSASignature::from_der(&signature.to_der()).unwrap(), signature);
+
+        // BER-only forms are rejected
+        assert!(ECDSASignature::from_der(&[0x30, 0x81, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]).is_err());
+        assert!(ECDSASignature::from_der(&[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01]).is_err());
+        assert!(ECDSASignature::from_der(&[0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01]).is_err());
+        assert!(ECDSASignature::from_der(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_fixed_bytes() {
+        let signature = ECDSASignature { r: BigUint::from(0x0102u32), s: BigUint::from(0xffu32) };
+        let bytes = signature.to_fixed_bytes(4);
+        assert_eq!(bytes, vec![0, 0, 1, 2, 0, 0, 0, 0xff]);
+        assert_eq!(ECDSASignature::from_fixed_bytes(&bytes, 4).unwrap(), signature);
+        assert!(ECDSASignature::from_fixed_bytes(&bytes, 3).is_err());
+        assert!(ECDSASignature::from_fixed_bytes(&[], 0).is_err());
+
+        // P-256: 64 bytes whatever the size of r and s, and the same signature as the DER form
+        let p256 = crate::curves::p256();
+        let ecdsa = p256.ecdsa();
+        let keypair = ecdsa.generate_keypair();
+        let signature = ecdsa.sign(b"jws payload", &keypair.private_key).unwrap();
+        let raw = signature.to_fixed_bytes(p256.scalar_size());
+        assert_eq!(raw.len(), 64);
+        let decoded = ECDSASignature::from_fixed_bytes(&raw, 32).unwrap();
+        assert_eq!(ECDSASignature::from_der(&decoded.to_der()).unwrap(), signature);
+        assert!(ecdsa.verify(b"jws payload", &decoded, &keypair.public_key));
+    }
+
+    #[test]
+    fn test_sign_digest() {
+        use sha2::Sha384;
+
+        // openssl dgst -sha384 -sign on P-384
+        let named = crate::curves::p384();
+        let ecdsa = named.ecdsa();
+        let public_key = crate::encoding::decode_point(&named.curve, &crate::encoding::from_hex(concat!(
+            "04051f546fc31a7708ed998b1fd444ea294e5910c3eaa957bebb295bdf08d432a33a02a5182ee310bad9840a85a5cee92ac0",
+            "b6c3bc508c841fe793944d138aa683ec84b2f9f088d688525e6d5f770e1679f4585083b2a3d361de51e60b7a704bb8",
+        )).unwrap()).unwrap();
+        let signature = ECDSASignature::from_der(&crate::encoding::from_hex(concat!(
+            "3065023028877a77025e01935aec0c045b34d14541f535d9797bc3fa6c142f340258efed3a58d66eb537fc36ec47e0f9ce1c",
+            "e9ab023100cf102ae5d86f1a8e8707dae006ea8c6ad3f508f88db3e517a669861bd9b614ce72f7879d8405bb5877b5ade7fb7009e1",
+        )).unwrap()).unwrap();
+        let digest = Sha384::digest(b"sign the digest");
+        assert!(ecdsa.verify_digest(&digest, &signature, &public_key));
+        assert!(!ecdsa.verify_digest(&Sha384::digest(b"sign another digest"), &signature, &public_key));
+
+        // Digests longer than the order are cut to its leftmost bits
+        let ecdsa = setup_ecdsa();
+        let keypair = ecdsa.generate_keypair();
+        let signature = ecdsa.sign_digest(&[0xab, 0xcd], &keypair.private_key).unwrap();
+        assert!(ecdsa.verify_digest(&[0xab, 0xcd], &signature, &keypair.public_key));
+        assert!(ecdsa.verify_digest(&[0xab, 0xff], &signature, &keypair.public_key));
+    }
+
+    #[test]
+    fn test_streaming_contexts() {
+        // P-192's order is shorter than SHA-256, so this also checks z is reduced like sign's
+        for named in [crate::curves::p256(), crate::curves::secp192r1()] {
+            let ecdsa = named.ecdsa();
+            let keypair = ecdsa.generate_keypair();
+            let message: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+
+            let mut signer = ecdsa.signing_context(&keypair.private_key);
+            for chunk in message.chunks(4096) {
+                signer.update(chunk);
+            }
+            let signature = signer.finalize().unwrap();
+            assert!(ecdsa.verify(&message, &signature, &keypair.public_key));
+
+            let mut verifier = ecdsa.verifying_context(&keypair.public_key);
+            std::io::copy(&mut &message[..], &mut verifier).unwrap();
+            assert!(verifier.finalize(&ecdsa.sign(&message, &keypair.private_key).unwrap()));
+
+            let mut verifier = ecdsa.verifying_context(&keypair.public_key);
+            verifier.update(&message[1..]);
+            assert!(!verifier.finalize(&signature));
+        }
+    }
+
+    #[test]
+    fn test_sign_prehashed() {
+        use sha2::Sha384;
+
+        // The prehash is signed as is, never rehashed
+        let ecdsa = crate::curves::p256().ecdsa();
+        let keypair = ecdsa.generate_keypair();
+        let prehash = Sha384::digest(b"hashed by the TLS stack");
+        let signature = ecdsa.sign_prehashed(&prehash, &keypair.private_key).unwrap();
+        assert!(ecdsa.verify_prehashed(&prehash, &signature, &keypair.public_key));
+        assert!(ecdsa.verify_digest(&prehash, &signature, &keypair.public_key));
+        assert!(!ecdsa.verify(&prehash, &signature, &keypair.public_key));
+
+        // Under 16 bytes for P-256 is refused both ways
+        assert!(ecdsa.sign_prehashed(&prehash[..15], &keypair.private_key).is_err());
+        let short = ecdsa.sign_digest(&prehash[..15], &keypair.private_key).unwrap();
+        assert!(!ecdsa.verify_prehashed(&prehash[..15], &short, &keypair.public_key));
+        assert!(ecdsa.sign_prehashed(&prehash[..16], &keypair.private_key).is_ok());
+    }
+
+    #[test]
+    fn test_cofactor_subgroup() {
+        // y² = x³ + x + 1 (mod 23) has 28 = 4 * 7 points
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(4u32));
+        let ecdsa = ECDSA::with_cofactor(curve, generator.clone(), BigUint::from(7u32), BigUint::from(4u32));
+
+        assert!(ecdsa.is_in_prime_subgroup(&generator));
+
+        // (4, 0) has order 2 and (11, 3) has order 4: small-subgroup points
+        let order_two = Point::Coordinate(BigUint::from(4u32), BigUint::from(0u32));
+        let order_four = Point::Coordinate(BigUint::from(11u32), BigUint::from(3u32));
+        assert!(!ecdsa.is_in_prime_subgroup(&order_two));
+        assert!(!ecdsa.is_in_prime_subgroup(&order_four));
+        assert_eq!(ecdsa.clear_cofactor(&order_four), Point::Identity);
+
+        // (0, 1) generates the whole group; clearing the cofactor lands in the subgroup
+        let full_order = Point::Coordinate(BigUint::from(0u32), BigUint::from(1u32));
+        assert!(!ecdsa.is_in_prime_subgroup(&full_order));
+        let cleared = ecdsa.clear_cofactor(&full_order);
+        assert_ne!(cleared, Point::Identity);
+        assert!(ecdsa.is_in_prime_subgroup(&cleared));
+
+        // Off-curve points are never in the subgroup
+        let off_curve = Point::Coordinate(BigUint::from(1u32), BigUint::from(1u32));
+        assert!(!ecdsa.is_in_prime_subgroup(&off_curve));
+    }
 }
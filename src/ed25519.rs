@@ -0,0 +1,286 @@
+use crate::edwards::{EdwardsCurve, EdwardsPoint};
+use num_bigint::BigUint;
+use sha2::{Digest, Sha512};
+
+// Ed25519 keys (RFC 8032 5.1) on edwards25519: -x² + y² = 1 + d x² y² over 2^255 - 19
+//
+// A private key is a 32-byte secret, not a scalar: SHA-512(secret) gives
+//   the low half, clamped (bits 0-2 and 255 cleared, bit 254 set), as the scalar s
+//   the high half as a prefix that makes signing nonces deterministic
+// and the public key is the 32-byte encoding of s * B. Clamping makes s a multiple of the
+// cofactor 8, so s * P never leaks s mod 8 through small-order points
+//
+// Signing (5.1.6): r = H(dom || prefix || M) mod L, R = r * B, k = H(dom || R || A || M) mod L,
+// S = r + k s mod L; verification checks 8 S B = 8 R + 8 k A. The three variants differ in dom:
+//   Ed25519     no prefix at all
+//   Ed25519ctx  dom2(0, context) with a 1-255 byte context, separating protocols that share keys
+//   Ed25519ph   dom2(1, context) and M replaced by SHA-512(M), so huge messages can be streamed
+// dom2(f, c) = "SigEd25519 no Ed25519 collisions" || f || len(c) || c, so a signature under one
+// variant or context never verifies under another
+
+const DOM2_PREFIX: &[u8] = b"SigEd25519 no Ed25519 collisions";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ed25519Signature {
+    pub r: [u8; 32],
+    pub s: BigUint,
+}
+
+impl Ed25519Signature {
+    // R || S, S little-endian
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        let s = self.s.to_bytes_le();
+        bytes[32..32 + s.len()].copy_from_slice(&s);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != 64 {
+            return Err("Ed25519 signatures are 64 bytes");
+        }
+        Ok(Ed25519Signature {
+            r: bytes[..32].try_into().unwrap(),
+            s: BigUint::from_bytes_le(&bytes[32..]),
+        })
+    }
+}
+
+fn dom2(prehashed: bool, context: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if context.len() > 255 {
+        return Err("context is longer than 255 bytes");
+    }
+    let mut dom = DOM2_PREFIX.to_vec();
+    dom.push(prehashed as u8);
+    dom.push(context.len() as u8);
+    dom.extend_from_slice(context);
+    Ok(dom)
+}
+
+pub struct Ed25519 {
+    pub curve: EdwardsCurve,
+    pub base: EdwardsPoint,
+    pub order: BigUint,
+}
+
+impl Ed25519 {
+    pub fn new() -> Self {
+        let p = (BigUint::from(1u32) << 255) - BigUint::from(19u32);
+        let dec = |s: &str| s.parse::<BigUint>().unwrap();
+        Ed25519 {
+            curve: EdwardsCurve::new(
+                &p - BigUint::from(1u32),
+                dec("37095705934669439343138083508754565189542113879843219016388785533085940283555"),
+                p,
+            ),
+            base: EdwardsPoint {
+                x: dec("15112221349535400772501151409588531511454012693041857206046113283949847762202"),
+                y: dec("46316835694926478169428394003475163141307993866256225615783033603165251855960"),
+            },
+            // L = 2^252 + 27742317777372353535851937790883648493
+            order: (BigUint::from(1u32) << 252) + dec("27742317777372353535851937790883648493"),
+        }
+    }
+
+    // (clamped scalar s, nonce prefix) from the 32-byte secret
+    pub fn expand_secret(&self, secret: &[u8; 32]) -> (BigUint, [u8; 32]) {
+        let hash = Sha512::digest(secret);
+        let mut scalar_bytes: [u8; 32] = hash[..32].try_into().unwrap();
+        scalar_bytes[0] &= 0xf8;
+        scalar_bytes[31] &= 0x7f;
+        scalar_bytes[31] |= 0x40;
+        (BigUint::from_bytes_le(&scalar_bytes), hash[32..].try_into().unwrap())
+    }
+
+    pub fn public_key(&self, secret: &[u8; 32]) -> [u8; 32] {
+        let (scalar, _) = self.expand_secret(secret);
+        self.encode(&self.curve.scalar_mult(&self.base, &scalar))
+    }
+
+    pub fn encode(&self, point: &EdwardsPoint) -> [u8; 32] {
+        self.curve.encode(point, 32).try_into().unwrap()
+    }
+
+    pub fn decode(&self, bytes: &[u8; 32]) -> Result<EdwardsPoint, &'static str> {
+        self.curve.decode(bytes)
+    }
+
+    // Pure Ed25519
+    pub fn sign(&self, secret: &[u8; 32], message: &[u8]) -> Ed25519Signature {
+        self.sign_with_dom(secret, &[], message)
+    }
+
+    pub fn verify(&self, public_key: &[u8; 32], message: &[u8], signature: &Ed25519Signature) -> bool {
+        self.verify_with_dom(public_key, &[], message, signature)
+    }
+
+    // Ed25519ctx: the context must be non-empty (an empty one would just be pure Ed25519)
+    pub fn sign_ctx(&self, secret: &[u8; 32], message: &[u8], context: &[u8]) -> Result<Ed25519Signature, &'static str> {
+        if context.is_empty() {
+            return Err("Ed25519ctx needs a non-empty context");
+        }
+        Ok(self.sign_with_dom(secret, &dom2(false, context)?, message))
+    }
+
+    pub fn verify_ctx(&self, public_key: &[u8; 32], message: &[u8], context: &[u8], signature: &Ed25519Signature) -> bool {
+        match dom2(false, context) {
+            Ok(dom) if !context.is_empty() => self.verify_with_dom(public_key, &dom, message, signature),
+            _ => false,
+        }
+    }
+
+    // Ed25519ph: signs SHA-512(message); the context may be empty
+    pub fn sign_ph(&self, secret: &[u8; 32], message: &[u8], context: &[u8]) -> Result<Ed25519Signature, &'static str> {
+        self.sign_prehashed(secret, &Sha512::digest(message).into(), context)
+    }
+
+    pub fn verify_ph(&self, public_key: &[u8; 32], message: &[u8], context: &[u8], signature: &Ed25519Signature) -> bool {
+        self.verify_prehashed(public_key, &Sha512::digest(message).into(), context, signature)
+    }
+
+    // Ed25519ph over a SHA-512 digest the caller already computed
+    pub fn sign_prehashed(&self, secret: &[u8; 32], digest: &[u8; 64], context: &[u8]) -> Result<Ed25519Signature, &'static str> {
+        Ok(self.sign_with_dom(secret, &dom2(true, context)?, digest))
+    }
+
+    pub fn verify_prehashed(&self, public_key: &[u8; 32], digest: &[u8; 64], context: &[u8], signature: &Ed25519Signature) -> bool {
+        match dom2(true, context) {
+            Ok(dom) => self.verify_with_dom(public_key, &dom, digest, signature),
+            Err(_) => false,
+        }
+    }
+
+    // H(data...) as a little-endian integer mod L
+    fn hash_to_scalar(&self, parts: &[&[u8]]) -> BigUint {
+        let hash = parts.iter().fold(Sha512::new(), |hasher, part| hasher.chain_update(part)).finalize();
+        BigUint::from_bytes_le(&hash) % &self.order
+    }
+
+    fn sign_with_dom(&self, secret: &[u8; 32], dom: &[u8], message: &[u8]) -> Ed25519Signature {
+        let (scalar, prefix) = self.expand_secret(secret);
+        let public_key = self.encode(&self.curve.scalar_mult(&self.base, &scalar));
+
+        let r = self.hash_to_scalar(&[dom, &prefix, message]);
+        let r_encoded = self.encode(&self.curve.scalar_mult(&self.base, &r));
+        let k = self.hash_to_scalar(&[dom, &r_encoded, &public_key, message]);
+        Ed25519Signature {
+            r: r_encoded,
+            s: (r + k * scalar) % &self.order,
+        }
+    }
+
+    // 8 S B = 8 R + 8 k A, with S < L and R, A valid encodings
+    fn verify_with_dom(&self, public_key: &[u8; 32], dom: &[u8], message: &[u8], signature: &Ed25519Signature) -> bool {
+        if signature.s >= self.order {
+            return false;
+        }
+        let (Ok(a), Ok(r)) = (self.decode(public_key), self.decode(&signature.r)) else {
+            return false;
+        };
+        let k = self.hash_to_scalar(&[dom, &signature.r, public_key, message]);
+        let eight = BigUint::from(8u32);
+        let lhs = self.curve.scalar_mult(&self.curve.scalar_mult(&self.base, &signature.s), &eight);
+        let rhs = self.curve.scalar_mult(&self.curve.add(&r, &self.curve.scalar_mult(&a, &k)), &eight);
+        lhs == rhs
+    }
+}
+
+impl Default for Ed25519 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    #[test]
+    fn test_base_point() {
+        let ed = Ed25519::new();
+        assert!(ed.curve.is_on_curve(&ed.base));
+        assert_eq!(ed.curve.scalar_mult(&ed.base, &ed.order), EdwardsPoint::identity());
+
+        // P + (-P) = O and encodings roundtrip
+        let point = ed.curve.scalar_mult(&ed.base, &BigUint::from(12345u32));
+        assert_eq!(ed.curve.add(&point, &ed.curve.neg(&point)), EdwardsPoint::identity());
+        assert_eq!(ed.decode(&ed.encode(&point)).unwrap(), point);
+    }
+
+    #[test]
+    fn test_public_key_rfc8032() {
+        // RFC 8032 7.1, test 1
+        let ed = Ed25519::new();
+        let secret: [u8; 32] = from_hex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60").unwrap().try_into().unwrap();
+        assert_eq!(
+            to_hex(&ed.public_key(&secret)),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+    }
+
+    fn hex32(s: &str) -> [u8; 32] {
+        from_hex(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_sign_rfc8032() {
+        // RFC 8032 7.1, test 1 (empty message)
+        let ed = Ed25519::new();
+        let secret = hex32("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+        let public_key = ed.public_key(&secret);
+        let signature = ed.sign(&secret, b"");
+        assert_eq!(
+            to_hex(&signature.to_bytes()),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+        assert!(ed.verify(&public_key, b"", &signature));
+        assert!(!ed.verify(&public_key, b"x", &signature));
+        assert_eq!(Ed25519Signature::from_bytes(&signature.to_bytes()).unwrap(), signature);
+    }
+
+    #[test]
+    fn test_ctx_rfc8032() {
+        // RFC 8032 7.2, context "foo"
+        let ed = Ed25519::new();
+        let secret = hex32("0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6");
+        let public_key = ed.public_key(&secret);
+        let message = from_hex("f726936d19c800494e3fdaff20b276a8").unwrap();
+        let signature = ed.sign_ctx(&secret, &message, b"foo").unwrap();
+        assert_eq!(
+            to_hex(&signature.to_bytes()),
+            "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d"
+        );
+        assert!(ed.verify_ctx(&public_key, &message, b"foo", &signature));
+
+        // Another context, or no context at all, rejects it
+        assert!(!ed.verify_ctx(&public_key, &message, b"bar", &signature));
+        assert!(!ed.verify(&public_key, &message, &signature));
+        assert!(ed.sign_ctx(&secret, &message, b"").is_err());
+        assert!(ed.sign_ctx(&secret, &message, &[0u8; 256]).is_err());
+    }
+
+    #[test]
+    fn test_ph_rfc8032() {
+        // RFC 8032 7.3, message "abc"
+        let ed = Ed25519::new();
+        let secret = hex32("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42");
+        let public_key = ed.public_key(&secret);
+        assert_eq!(to_hex(&public_key), "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf");
+        let signature = ed.sign_ph(&secret, b"abc", b"").unwrap();
+        assert_eq!(
+            to_hex(&signature.to_bytes()),
+            "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406"
+        );
+        assert!(ed.verify_ph(&public_key, b"abc", b"", &signature));
+
+        // Not interchangeable with a pure signature over SHA-512(M)
+        assert!(!ed.verify(&public_key, &Sha512::digest(b"abc"), &signature));
+
+        // The same signature from a digest computed elsewhere
+        let digest: [u8; 64] = Sha512::digest(b"abc").into();
+        assert_eq!(ed.sign_prehashed(&secret, &digest, b"").unwrap(), signature);
+        assert!(ed.verify_prehashed(&public_key, &digest, b"", &signature));
+    }
+}
@@ -0,0 +1,239 @@
+use crate::FiniteField;
+use crate::edwards::{EdwardsCurve, EdwardsPoint};
+use crate::montgomery::goldilocks_prime;
+use num_bigint::BigUint;
+use sha3::Shake256;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+// Ed448 (RFC 8032 5.2) on edwards448: x² + y² = 1 - 39081 x² y² over the Goldilocks prime
+//
+// The same scheme as Ed25519 with different parameters:
+//   57-byte secrets, keys and scalars; 114-byte signatures
+//   SHAKE256 with 114 bytes of output in place of SHA-512
+//   clamping clears the low 2 bits (cofactor 4), the whole last byte, and sets bit 447
+//   dom4(f, context) = "SigEd448" || f || len(context) || context prefixes every hash,
+//   even for plain Ed448, so the context (0-255 bytes) is always part of the signature
+// Ed448ph signs SHAKE256(M, 64) with f = 1
+
+const DOM4_PREFIX: &[u8] = b"SigEd448";
+
+pub fn goldilocks() -> FiniteField {
+    FiniteField { p: goldilocks_prime() }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ed448Signature {
+    pub r: [u8; 57],
+    pub s: BigUint,
+}
+
+impl Ed448Signature {
+    pub fn to_bytes(&self) -> [u8; 114] {
+        let mut bytes = [0u8; 114];
+        bytes[..57].copy_from_slice(&self.r);
+        let s = self.s.to_bytes_le();
+        bytes[57..57 + s.len()].copy_from_slice(&s);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != 114 {
+            return Err("Ed448 signatures are 114 bytes");
+        }
+        Ok(Ed448Signature {
+            r: bytes[..57].try_into().unwrap(),
+            s: BigUint::from_bytes_le(&bytes[57..]),
+        })
+    }
+}
+
+fn dom4(prehashed: bool, context: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if context.len() > 255 {
+        return Err("context is longer than 255 bytes");
+    }
+    let mut dom = DOM4_PREFIX.to_vec();
+    dom.push(prehashed as u8);
+    dom.push(context.len() as u8);
+    dom.extend_from_slice(context);
+    Ok(dom)
+}
+
+fn shake256(parts: &[&[u8]], output: &mut [u8]) {
+    let mut hasher = Shake256::default();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize_xof().read(output);
+}
+
+pub struct Ed448 {
+    pub curve: EdwardsCurve,
+    pub base: EdwardsPoint,
+    pub order: BigUint,
+}
+
+impl Ed448 {
+    pub fn new() -> Self {
+        let p = goldilocks_prime();
+        let dec = |s: &str| s.parse::<BigUint>().unwrap();
+        Ed448 {
+            curve: EdwardsCurve::new(BigUint::from(1u32), &p - BigUint::from(39081u32), p),
+            base: EdwardsPoint {
+                x: dec("224580040295924300187604334099896036246789641632564134246125461686950415467406032909029192869357953282578032075146446173674602635247710"),
+                y: dec("298819210078481492676017930443930673437544040154080242095928241372331506189835876003536878655418784733982303233503462500531545062832660"),
+            },
+            // L = 2^446 - 13818066809895115352007386748515426880336692474882178609894547503885
+            order: (BigUint::from(1u32) << 446) - dec("13818066809895115352007386748515426880336692474882178609894547503885"),
+        }
+    }
+
+    // (clamped scalar, nonce prefix) from the 57-byte secret
+    pub fn expand_secret(&self, secret: &[u8; 57]) -> (BigUint, [u8; 57]) {
+        let mut hash = [0u8; 114];
+        shake256(&[secret], &mut hash);
+        let mut scalar_bytes: [u8; 57] = hash[..57].try_into().unwrap();
+        scalar_bytes[0] &= 0xfc;
+        scalar_bytes[56] = 0;
+        scalar_bytes[55] |= 0x80;
+        (BigUint::from_bytes_le(&scalar_bytes), hash[57..].try_into().unwrap())
+    }
+
+    pub fn public_key(&self, secret: &[u8; 57]) -> [u8; 57] {
+        let (scalar, _) = self.expand_secret(secret);
+        self.encode(&self.curve.scalar_mult(&self.base, &scalar))
+    }
+
+    pub fn encode(&self, point: &EdwardsPoint) -> [u8; 57] {
+        self.curve.encode(point, 57).try_into().unwrap()
+    }
+
+    pub fn decode(&self, bytes: &[u8; 57]) -> Result<EdwardsPoint, &'static str> {
+        self.curve.decode(bytes)
+    }
+
+    pub fn sign(&self, secret: &[u8; 57], message: &[u8], context: &[u8]) -> Result<Ed448Signature, &'static str> {
+        Ok(self.sign_with_dom(secret, &dom4(false, context)?, message))
+    }
+
+    pub fn verify(&self, public_key: &[u8; 57], message: &[u8], context: &[u8], signature: &Ed448Signature) -> bool {
+        match dom4(false, context) {
+            Ok(dom) => self.verify_with_dom(public_key, &dom, message, signature),
+            Err(_) => false,
+        }
+    }
+
+    // Ed448ph: signs SHAKE256(message, 64)
+    pub fn sign_ph(&self, secret: &[u8; 57], message: &[u8], context: &[u8]) -> Result<Ed448Signature, &'static str> {
+        Ok(self.sign_with_dom(secret, &dom4(true, context)?, &prehash(message)))
+    }
+
+    pub fn verify_ph(&self, public_key: &[u8; 57], message: &[u8], context: &[u8], signature: &Ed448Signature) -> bool {
+        match dom4(true, context) {
+            Ok(dom) => self.verify_with_dom(public_key, &dom, &prehash(message), signature),
+            Err(_) => false,
+        }
+    }
+
+    // SHAKE256(data..., 114) as a little-endian integer mod L
+    fn hash_to_scalar(&self, parts: &[&[u8]]) -> BigUint {
+        let mut hash = [0u8; 114];
+        shake256(parts, &mut hash);
+        BigUint::from_bytes_le(&hash) % &self.order
+    }
+
+    fn sign_with_dom(&self, secret: &[u8; 57], dom: &[u8], message: &[u8]) -> Ed448Signature {
+        let (scalar, prefix) = self.expand_secret(secret);
+        let public_key = self.encode(&self.curve.scalar_mult(&self.base, &scalar));
+
+        let r = self.hash_to_scalar(&[dom, &prefix, message]);
+        let r_encoded = self.encode(&self.curve.scalar_mult(&self.base, &r));
+        let k = self.hash_to_scalar(&[dom, &r_encoded, &public_key, message]);
+        Ed448Signature {
+            r: r_encoded,
+            s: (r + k * scalar) % &self.order,
+        }
+    }
+
+    // 4 S B = 4 R + 4 k A
+    fn verify_with_dom(&self, public_key: &[u8; 57], dom: &[u8], message: &[u8], signature: &Ed448Signature) -> bool {
+        if signature.s >= self.order {
+            return false;
+        }
+        let (Ok(a), Ok(r)) = (self.decode(public_key), self.decode(&signature.r)) else {
+            return false;
+        };
+        let k = self.hash_to_scalar(&[dom, &signature.r, public_key, message]);
+        let four = BigUint::from(4u32);
+        let lhs = self.curve.scalar_mult(&self.curve.scalar_mult(&self.base, &signature.s), &four);
+        let rhs = self.curve.scalar_mult(&self.curve.add(&r, &self.curve.scalar_mult(&a, &k)), &four);
+        lhs == rhs
+    }
+}
+
+impl Default for Ed448 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn prehash(message: &[u8]) -> [u8; 64] {
+    let mut hash = [0u8; 64];
+    shake256(&[message], &mut hash);
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    fn hex57(s: &str) -> [u8; 57] {
+        from_hex(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_base_point() {
+        let ed = Ed448::new();
+        assert!(ed.curve.is_on_curve(&ed.base));
+        assert_eq!(ed.curve.scalar_mult(&ed.base, &ed.order), EdwardsPoint::identity());
+        assert_eq!(ed.decode(&ed.encode(&ed.base)).unwrap(), ed.base);
+    }
+
+    #[test]
+    fn test_rfc8032_vectors() {
+        let ed = Ed448::new();
+
+        // RFC 8032 7.4 "Blank"
+        let secret = hex57("6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b");
+        let public_key = ed.public_key(&secret);
+        assert_eq!(to_hex(&public_key), "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180");
+        let signature = ed.sign(&secret, b"", b"").unwrap();
+        assert_eq!(
+            to_hex(&signature.to_bytes()),
+            "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823d41a591f2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd3980ff0d2028d4b18a9df63e006c5d1c2d345b925d8dc00b4104852db99ac5c7cdda8530a113a0f4dbb61149f05a7363268c71d95808ff2e652600"
+        );
+        assert!(ed.verify(&public_key, b"", b"", &signature));
+
+        // RFC 8032 7.4 "1 octet (with context)"
+        let secret = hex57("c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e");
+        let public_key = ed.public_key(&secret);
+        let signature = ed.sign(&secret, &[0x03], b"foo").unwrap();
+        assert_eq!(
+            to_hex(&signature.to_bytes()),
+            "d4f8f6131770dd46f40867d6fd5d5055de43541f8c5e35abbcd001b32a89f7d2151f7647f11d8ca2ae279fb842d607217fce6e042f6815ea000c85741de5c8da1144a6a1aba7f96de42505d7a7298524fda538fccbbb754f578c1cad10d54d0d5428407e85dcbc98a49155c13764e66c3c00"
+        );
+        assert!(ed.verify(&public_key, &[0x03], b"foo", &signature));
+        assert!(!ed.verify(&public_key, &[0x03], b"", &signature));
+    }
+
+    #[test]
+    fn test_ph_is_separated() {
+        let ed = Ed448::new();
+        let secret = [0x42u8; 57];
+        let public_key = ed.public_key(&secret);
+        let signature = ed.sign_ph(&secret, b"abc", b"").unwrap();
+        assert!(ed.verify_ph(&public_key, b"abc", b"", &signature));
+        assert!(!ed.verify(&public_key, &prehash(b"abc"), b"", &signature));
+        assert_eq!(Ed448Signature::from_bytes(&signature.to_bytes()).unwrap(), signature);
+    }
+}
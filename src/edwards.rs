@@ -0,0 +1,131 @@
+use crate::FiniteField;
+use num_bigint::BigUint;
+
+// Twisted Edwards curves: a x² + y² = 1 + d x² y² (mod p)
+//
+// With a a square and d a non-square the addition law
+//   (x1, y1) + (x2, y2) = ((x1 y2 + y1 x2) / (1 + d x1 x2 y1 y2), (y1 y2 - a x1 x2) / (1 - d x1 x2 y1 y2))
+// is complete: no special cases for doubling, inverses or the identity (0, 1), and the
+// denominators never vanish. Points are encoded as y little-endian with the low bit of x
+// stored in the top bit of the last byte (RFC 8032 5.1.2 / 5.2.2)
+
+// The field is built once in new(), as for EllipticCurve, so a, d and p are read-only
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdwardsCurve {
+    a: BigUint,
+    d: BigUint,
+    p: BigUint,
+    field: FiniteField,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdwardsPoint {
+    pub x: BigUint,
+    pub y: BigUint,
+}
+
+impl EdwardsPoint {
+    pub fn identity() -> Self {
+        EdwardsPoint {
+            x: BigUint::from(0u32),
+            y: BigUint::from(1u32),
+        }
+    }
+}
+
+impl EdwardsCurve {
+    pub fn new(a: BigUint, d: BigUint, p: BigUint) -> Self {
+        let field = FiniteField { p: p.clone() };
+        EdwardsCurve { a, d, p, field }
+    }
+
+    pub fn a(&self) -> &BigUint {
+        &self.a
+    }
+
+    pub fn d(&self) -> &BigUint {
+        &self.d
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn field(&self) -> &FiniteField {
+        &self.field
+    }
+
+    pub fn is_on_curve(&self, point: &EdwardsPoint) -> bool {
+        let f = self.field();
+        let x2 = f.mul(&point.x, &point.x);
+        let y2 = f.mul(&point.y, &point.y);
+        let lhs = f.add(&f.mul(&self.a, &x2), &y2);
+        let rhs = f.add(&BigUint::from(1u32), &f.mul(&self.d, &f.mul(&x2, &y2)));
+        point.x < self.p && point.y < self.p && lhs == rhs
+    }
+
+    pub fn add(&self, p: &EdwardsPoint, q: &EdwardsPoint) -> EdwardsPoint {
+        let f = self.field();
+        let one = BigUint::from(1u32);
+        let t = f.mul(&self.d, &f.mul(&f.mul(&p.x, &q.x), &f.mul(&p.y, &q.y)));
+        let x = f.div(&f.add(&f.mul(&p.x, &q.y), &f.mul(&p.y, &q.x)), &f.add(&one, &t));
+        let y = f.div(&f.sub(&f.mul(&p.y, &q.y), &f.mul(&self.a, &f.mul(&p.x, &q.x))), &f.sub(&one, &t));
+        EdwardsPoint { x, y }
+    }
+
+    // -(x, y) = (-x, y)
+    pub fn neg(&self, point: &EdwardsPoint) -> EdwardsPoint {
+        EdwardsPoint {
+            x: self.field().sub(&BigUint::from(0u32), &point.x),
+            y: point.y.clone(),
+        }
+    }
+
+    // k * P by double-and-add; the complete formulas make doubling just P + P
+    pub fn scalar_mult(&self, point: &EdwardsPoint, k: &BigUint) -> EdwardsPoint {
+        let mut result = EdwardsPoint::identity();
+        for i in (0..k.bits()).rev() {
+            result = self.add(&result, &result);
+            if k.bit(i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    // len bytes: y little-endian, top bit of the last byte = x mod 2
+    pub fn encode(&self, point: &EdwardsPoint, len: usize) -> Vec<u8> {
+        let mut bytes = point.y.to_bytes_le();
+        bytes.resize(len, 0);
+        if point.x.bit(0) {
+            bytes[len - 1] |= 0x80;
+        }
+        bytes
+    }
+
+    // Recover x from y: x² = (y² - 1) / (d y² - a), then pick the root with the encoded parity
+    pub fn decode(&self, bytes: &[u8]) -> Result<EdwardsPoint, &'static str> {
+        let f = self.field();
+        let mut y_bytes = bytes.to_vec();
+        let last = y_bytes.last_mut().ok_or("empty point encoding")?;
+        let x_odd = *last & 0x80 != 0;
+        *last &= 0x7f;
+
+        let y = BigUint::from_bytes_le(&y_bytes);
+        if y >= self.p {
+            return Err("y is not a field element");
+        }
+        let y2 = f.mul(&y, &y);
+        let denominator = f.sub(&f.mul(&self.d, &y2), &self.a);
+        if denominator == BigUint::from(0u32) {
+            return Err("point is not on the curve");
+        }
+        let x2 = f.div(&f.sub(&y2, &BigUint::from(1u32)), &denominator);
+        let x = f.sqrt(&x2).ok_or("point is not on the curve")?;
+        if x == BigUint::from(0u32) && x_odd {
+            return Err("x = 0 encoded with the sign bit set");
+        }
+        let x = if x.bit(0) != x_odd { f.sub(&BigUint::from(0u32), &x) } else { x };
+        Ok(EdwardsPoint { x, y })
+    }
+}
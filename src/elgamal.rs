@@ -0,0 +1,189 @@
+use crate::Point;
+use crate::curves::DomainParams;
+use num_bigint::BigUint;
+
+// EC ElGamal: a message point M is encrypted under Q = d * G as
+//   (C1, C2) = (k * G, M + k * Q)
+// and decrypted with M = C2 - d * C1. The scheme is IND-CPA but malleable:
+// ciphertexts add componentwise, so encoding m as m * G gives additive homomorphism
+// (the decrypted m * G must then be solved for m, which only works for small m)
+
+#[derive(Clone, Debug)]
+pub struct ElGamalKeyPair {
+    pub private_key: BigUint,
+    pub public_key: Point,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElGamalCiphertext {
+    pub c1: Point,
+    pub c2: Point,
+}
+
+pub struct ElGamal {
+    pub params: DomainParams,
+}
+
+impl ElGamal {
+    pub fn new(params: DomainParams) -> Self {
+        ElGamal { params }
+    }
+
+    // Generate keypair
+    pub fn generate_keypair(&self) -> ElGamalKeyPair {
+        let private_key = self.params.generate_private_key();
+        let public_key = self.params.generate_public_key(&private_key);
+        ElGamalKeyPair {
+            private_key,
+            public_key,
+        }
+    }
+
+    // Encrypt a point with fresh randomness k in [1, n-1]
+    pub fn encrypt(&self, message: &Point, public_key: &Point) -> Result<ElGamalCiphertext, &'static str> {
+        let k = self.params.generate_private_key();
+        self.encrypt_with_randomness(message, public_key, &k)
+    }
+
+    // (C1, C2) = (k * G, M + k * Q) with caller-chosen k
+    // Reusing k across messages reveals M1 - M2 = C2 - C2'
+    pub fn encrypt_with_randomness(&self, message: &Point, public_key: &Point, k: &BigUint) -> Result<ElGamalCiphertext, &'static str> {
+        if !self.params.curve.is_on_curve(message) {
+            return Err("message is not a point on the curve");
+        }
+        self.validate_public_key(public_key)?;
+
+        Ok(ElGamalCiphertext {
+            c1: self.params.curve.scalar_mult(&self.params.generator, k),
+            c2: self.params.curve.add(message, &self.params.curve.scalar_mult(public_key, k)),
+        })
+    }
+
+    // M = C2 - d * C1
+    pub fn decrypt(&self, ciphertext: &ElGamalCiphertext, private_key: &BigUint) -> Result<Point, &'static str> {
+        if !self.params.curve.is_on_curve(&ciphertext.c1) || !self.params.curve.is_on_curve(&ciphertext.c2) {
+            return Err("ciphertext is not on the curve");
+        }
+        let shared = self.params.curve.scalar_mult(&ciphertext.c1, private_key);
+        Ok(self.params.curve.add(&ciphertext.c2, &self.params.curve.neg(&shared)))
+    }
+
+    // Fresh-looking encryption of the same message: add an encryption of O
+    // (C1 + k' * G, C2 + k' * Q), unlinkable to the original without the private key
+    pub fn rerandomize(&self, ciphertext: &ElGamalCiphertext, public_key: &Point) -> Result<ElGamalCiphertext, &'static str> {
+        let zero = self.encrypt(&Point::Identity, public_key)?;
+        Ok(self.add(ciphertext, &zero))
+    }
+
+    // Homomorphic addition: Enc(M1) + Enc(M2) = Enc(M1 + M2)
+    pub fn add(&self, a: &ElGamalCiphertext, b: &ElGamalCiphertext) -> ElGamalCiphertext {
+        ElGamalCiphertext {
+            c1: self.params.curve.add(&a.c1, &b.c1),
+            c2: self.params.curve.add(&a.c2, &b.c2),
+        }
+    }
+
+    // Homomorphic scaling: c * Enc(M) = Enc(c * M)
+    pub fn scale(&self, ciphertext: &ElGamalCiphertext, c: &BigUint) -> ElGamalCiphertext {
+        ElGamalCiphertext {
+            c1: self.params.curve.scalar_mult(&ciphertext.c1, c),
+            c2: self.params.curve.scalar_mult(&ciphertext.c2, c),
+        }
+    }
+
+    // Encrypt a scalar as m * G ("exponential" ElGamal) so ciphertexts add as integers mod n
+    pub fn encrypt_scalar(&self, m: &BigUint, public_key: &Point) -> Result<ElGamalCiphertext, &'static str> {
+        let message = self.params.curve.scalar_mult(&self.params.generator, &(m % &self.params.order));
+        self.encrypt(&message, public_key)
+    }
+
+    // Decrypt to m * G and search m in [0, max_value]
+    // Recovering m is a discrete log, so this only works for small plaintexts (votes, counters)
+    pub fn decrypt_scalar(&self, ciphertext: &ElGamalCiphertext, private_key: &BigUint, max_value: &BigUint) -> Result<BigUint, &'static str> {
+        let target = self.decrypt(ciphertext, private_key)?;
+        let mut candidate = Point::Identity;
+        let mut m = BigUint::from(0u32);
+        while m <= *max_value {
+            if candidate == target {
+                return Ok(m);
+            }
+            candidate = self.params.curve.add(&candidate, &self.params.generator);
+            m += 1u32;
+        }
+        Err("plaintext is larger than max_value")
+    }
+
+    // Public keys get the same checks as ECDH: encrypting to a small-order key leaks the message
+    fn validate_public_key(&self, public_key: &Point) -> Result<(), &'static str> {
+        if *public_key == Point::Identity {
+            return Err("public key is the point at infinity");
+        }
+        if !self.params.curve.is_on_curve(public_key) {
+            return Err("public key is not on the curve");
+        }
+        if self.params.curve.scalar_mult(public_key, &self.params.order) != Point::Identity {
+            return Err("public key is not in the prime-order subgroup");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EllipticCurve;
+
+    fn setup_elgamal() -> ElGamal {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        ElGamal::new(DomainParams::new(curve, generator, BigUint::from(19u32)))
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let elgamal = setup_elgamal();
+        let keypair = elgamal.generate_keypair();
+        let message = elgamal.params.curve.scalar_mult(&elgamal.params.generator, &BigUint::from(6u32));
+
+        let ciphertext = elgamal.encrypt(&message, &keypair.public_key).unwrap();
+        assert_eq!(elgamal.decrypt(&ciphertext, &keypair.private_key).unwrap(), message);
+
+        let off_curve = Point::Coordinate(BigUint::from(1u32), BigUint::from(1u32));
+        assert!(elgamal.encrypt(&off_curve, &keypair.public_key).is_err());
+        assert!(elgamal.encrypt(&message, &Point::Identity).is_err());
+    }
+
+    #[test]
+    fn test_rerandomize() {
+        let elgamal = setup_elgamal();
+        let private_key = BigUint::from(5u32);
+        let public_key = elgamal.params.generate_public_key(&private_key);
+        let message = elgamal.params.curve.scalar_mult(&elgamal.params.generator, &BigUint::from(3u32));
+
+        let ciphertext = elgamal.encrypt_with_randomness(&message, &public_key, &BigUint::from(4u32)).unwrap();
+        let rerandomized = elgamal.rerandomize(&ciphertext, &public_key).unwrap();
+        assert_eq!(elgamal.decrypt(&rerandomized, &private_key).unwrap(), message);
+    }
+
+    #[test]
+    fn test_additive_homomorphism() {
+        let elgamal = setup_elgamal();
+        let keypair = elgamal.generate_keypair();
+        let max = BigUint::from(18u32);
+
+        // Tally three votes without decrypting them individually
+        let votes = [1u32, 0, 1];
+        let mut tally = elgamal.encrypt_scalar(&BigUint::from(0u32), &keypair.public_key).unwrap();
+        for vote in votes {
+            let ballot = elgamal.encrypt_scalar(&BigUint::from(vote), &keypair.public_key).unwrap();
+            tally = elgamal.add(&tally, &ballot);
+        }
+        assert_eq!(elgamal.decrypt_scalar(&tally, &keypair.private_key, &max).unwrap(), BigUint::from(2u32));
+
+        // 3 * Enc(4) = Enc(12)
+        let four = elgamal.encrypt_scalar(&BigUint::from(4u32), &keypair.public_key).unwrap();
+        let twelve = elgamal.scale(&four, &BigUint::from(3u32));
+        assert_eq!(elgamal.decrypt_scalar(&twelve, &keypair.private_key, &max).unwrap(), BigUint::from(12u32));
+        assert!(elgamal.decrypt_scalar(&twelve, &keypair.private_key, &BigUint::from(5u32)).is_err());
+    }
+}
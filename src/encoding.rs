@@ -0,0 +1,492 @@
+use crate::ecdsa::ECDSASignature;
+use crate::{CurvePoint, EllipticCurve, Point};
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+// SEC 1 (2.3.3 / 2.3.4) point encodings:
+//   identity      0x00
+//   compressed    0x02 | 0x03 (parity of y) || x
+//   uncompressed  0x04 || x || y
+// with x and y big-endian, padded to the field size
+
+// Big-endian, left-padded to exactly len bytes (panics if the value doesn't fit)
+pub fn to_fixed_bytes(x: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = x.to_bytes_be();
+    if bytes.len() > len {
+        panic!("?value does not fit in {} bytes?", len);
+    }
+    let mut out = vec![0u8; len - bytes.len()];
+    out.extend_from_slice(&bytes);
+    out
+}
+
+pub fn field_size(curve: &EllipticCurve) -> usize {
+    curve.p().bits().div_ceil(8) as usize
+}
+
+pub fn encode_point(curve: &EllipticCurve, point: &Point, compressed: bool) -> Vec<u8> {
+    let len = field_size(curve);
+    match point {
+        Point::Identity => vec![0x00],
+        Point::Coordinate(x, y) => {
+            let mut out = Vec::with_capacity(1 + 2 * len);
+            if compressed {
+                out.push(if y.bit(0) { 0x03 } else { 0x02 });
+                out.extend(to_fixed_bytes(x, len));
+            } else {
+                out.push(0x04);
+                out.extend(to_fixed_bytes(x, len));
+                out.extend(to_fixed_bytes(y, len));
+            }
+            out
+        }
+    }
+}
+
+// Decode either form; the result is always checked to lie on the curve
+pub fn decode_point(curve: &EllipticCurve, bytes: &[u8]) -> Result<Point, &'static str> {
+    let len = field_size(curve);
+    let field = curve.field();
+
+    let point = match bytes.first() {
+        Some(0x00) if bytes.len() == 1 => return Ok(Point::Identity),
+        Some(0x02) | Some(0x03) if bytes.len() == 1 + len => {
+            let x = BigUint::from_bytes_be(&bytes[1..]);
+            if x >= *curve.p() {
+                return Err("coordinate is not a field element");
+            }
+            // y² = x³ + ax + b, pick the root with the encoded parity
+            let rhs = field.add(&field.add(&field.mul(&field.mul(&x, &x), &x), &field.mul(curve.a(), &x)), curve.b());
+            let y = field.sqrt(&rhs).ok_or("x is not the coordinate of a curve point")?;
+            let y = if y.bit(0) == (bytes[0] == 0x03) { y } else { field.sub(&BigUint::from(0u32), &y) };
+            Point::Coordinate(x, y)
+        }
+        Some(0x04) if bytes.len() == 1 + 2 * len => {
+            let x = BigUint::from_bytes_be(&bytes[1..1 + len]);
+            let y = BigUint::from_bytes_be(&bytes[1 + len..]);
+            if x >= *curve.p() || y >= *curve.p() {
+                return Err("coordinate is not a field element");
+            }
+            Point::Coordinate(x, y)
+        }
+        Some(0x00) | Some(0x02) | Some(0x03) | Some(0x04) => return Err("invalid point encoding length"),
+        _ => return Err("invalid point encoding prefix"),
+    };
+
+    if !curve.is_on_curve(&point) {
+        return Err("point is not on the curve");
+    }
+    Ok(point)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn from_hex(s: &str) -> Result<Vec<u8>, &'static str> {
+    if !s.len().is_multiple_of(2) {
+        return Err("hex string has odd length");
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2).ok_or("invalid hex digit")?, 16).map_err(|_| "invalid hex digit"))
+        .collect()
+}
+
+// Hex text forms for logs, configs and CLIs
+//   points      compressed SEC1 hex ("00" for the identity), written through CurvePoint: a bare
+//               Point doesn't know its field size, and parsing needs the curve to recover y,
+//               so the parser is point_from_hex(curve, s) rather than FromStr
+//   signatures  r || s with both halves the same width; {:.32} pads each half to 32 bytes
+//               (pass the curve's scalar size for the canonical form), plain {} uses the wider
+//               of the two. {:#x} adds a 0x prefix, which parse() accepts
+pub fn point_from_hex(curve: &EllipticCurve, s: &str) -> Result<Point, &'static str> {
+    decode_point(curve, &from_hex(s.strip_prefix("0x").unwrap_or(s))?)
+}
+
+impl fmt::Display for CurvePoint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for CurvePoint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&to_hex(&encode_point(self.curve(), self.point(), true)))
+    }
+}
+
+impl fmt::Display for ECDSASignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for ECDSASignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let needed = self.r.bits().max(self.s.bits()).div_ceil(8).max(1) as usize;
+        let width = f.precision().unwrap_or(0).max(needed);
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&to_hex(&to_fixed_bytes(&self.r, width)))?;
+        f.write_str(&to_hex(&to_fixed_bytes(&self.s, width)))
+    }
+}
+
+impl FromStr for ECDSASignature {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = from_hex(s.strip_prefix("0x").unwrap_or(s))?;
+        if bytes.is_empty() || !bytes.len().is_multiple_of(2) {
+            return Err("signature hex must split into two equal halves");
+        }
+        let (r, s) = bytes.split_at(bytes.len() / 2);
+        Ok(ECDSASignature { r: BigUint::from_bytes_be(r), s: BigUint::from_bytes_be(s) })
+    }
+}
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Bitcoin base58: the bytes as a big-endian number in base 58, one '1' per leading zero byte
+pub fn to_base58(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    let mut digits = BigUint::from_bytes_be(bytes).to_radix_be(58);
+    if digits == [0] {
+        digits.clear();
+    }
+    let mut out = "1".repeat(zeros);
+    out.extend(digits.iter().map(|&d| BASE58_ALPHABET[d as usize] as char));
+    out
+}
+
+pub fn from_base58(s: &str) -> Result<Vec<u8>, &'static str> {
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    let digits = s
+        .bytes()
+        .map(|c| BASE58_ALPHABET.iter().position(|&a| a == c).map(|d| d as u8).ok_or("invalid base58 digit"))
+        .collect::<Result<Vec<u8>, _>>()?;
+    let mut out = vec![0u8; zeros];
+    if digits.len() > zeros {
+        let value = BigUint::from_radix_be(&digits, 58).ok_or("invalid base58 digit")?;
+        out.extend(value.to_bytes_be());
+    }
+    Ok(out)
+}
+
+// Base58Check: payload || first 4 bytes of SHA256(SHA256(payload))
+pub fn to_base58check(payload: &[u8]) -> String {
+    let mut bytes = payload.to_vec();
+    bytes.extend_from_slice(&Sha256::digest(Sha256::digest(payload))[..4]);
+    to_base58(&bytes)
+}
+
+pub fn from_base58check(s: &str) -> Result<Vec<u8>, &'static str> {
+    let mut bytes = from_base58(s)?;
+    if bytes.len() < 4 {
+        return Err("base58check string too short");
+    }
+    let checksum = bytes.split_off(bytes.len() - 4);
+    if Sha256::digest(Sha256::digest(&bytes))[..4] != checksum[..] {
+        return Err("base58check checksum mismatch");
+    }
+    Ok(bytes)
+}
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// RFC 4648 base64: 3 bytes -> 4 characters of 6 bits, '=' padding to a multiple of 4
+pub fn to_base64(bytes: &[u8]) -> String {
+    encode_base64(bytes, BASE64_ALPHABET, true)
+}
+
+// Strict: padding required, no whitespace, and the unused low bits of the last group must be
+// zero so every byte string has exactly one encoding
+pub fn from_base64(s: &str) -> Result<Vec<u8>, &'static str> {
+    if !s.len().is_multiple_of(4) {
+        return Err("base64 length is not a multiple of 4");
+    }
+    let padding = s.bytes().rev().take_while(|&c| c == b'=').count();
+    if padding > 2 {
+        return Err("invalid base64 padding");
+    }
+    decode_base64(&s[..s.len() - padding], BASE64_ALPHABET)
+}
+
+// base64url (RFC 4648 5) without padding, as JOSE uses it
+pub fn to_base64url(bytes: &[u8]) -> String {
+    encode_base64(bytes, BASE64URL_ALPHABET, false)
+}
+
+pub fn from_base64url(s: &str) -> Result<Vec<u8>, &'static str> {
+    decode_base64(s, BASE64URL_ALPHABET)
+}
+
+fn encode_base64(bytes: &[u8], alphabet: &[u8], pad: bool) -> String {
+    let mut out = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(alphabet[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else if pad {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+// Unpadded digits; a lone digit in the last group can't encode a whole byte
+fn decode_base64(s: &str, alphabet: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let digits = s
+        .bytes()
+        .map(|c| alphabet.iter().position(|&a| a == c).map(|d| d as u32).ok_or("invalid base64 character"))
+        .collect::<Result<Vec<u32>, _>>()?;
+    let mut out = Vec::new();
+    for chunk in digits.chunks(4) {
+        if chunk.len() == 1 {
+            return Err("truncated base64");
+        }
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (i, &d)| acc | d << (18 - 6 * i));
+        let len = chunk.len() * 6 / 8;
+        if group & (0xffffff >> (8 * len)) != 0 {
+            return Err("non-canonical base64");
+        }
+        out.extend((0..len).map(|i| (group >> (16 - 8 * i)) as u8));
+    }
+    Ok(out)
+}
+
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+// BIP-173 bech32 and BIP-350 bech32m differ only in the checksum constant
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Bech32Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Bech32Variant {
+    fn constant(&self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+// BCH checksum over GF(32)
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATORS: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    values.iter().fold(1u32, |checksum, &value| {
+        let top = checksum >> 25;
+        let mut checksum = (checksum & 0x1ff_ffff) << 5 ^ value as u32;
+        for (i, generator) in GENERATORS.iter().enumerate() {
+            if top >> i & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+        checksum
+    })
+}
+
+// The human-readable part is mixed into the checksum as high bits, a 0, then low bits
+fn bech32_hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut values: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|c| c & 31));
+    values
+}
+
+// hrp || "1" || data (5-bit values) || 6-character checksum, all lowercase
+pub fn to_bech32(hrp: &str, data: &[u8], variant: Bech32Variant) -> String {
+    let mut values = bech32_hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend([0u8; 6]);
+    let checksum = bech32_polymod(&values) ^ variant.constant();
+
+    let mut out = format!("{}1", hrp);
+    out.extend(data.iter().map(|&d| BECH32_CHARSET[d as usize] as char));
+    out.extend((0..6).map(|i| BECH32_CHARSET[(checksum >> (5 * (5 - i)) & 31) as usize] as char));
+    out
+}
+
+// (hrp, 5-bit data, variant); mixed case is rejected, as BIP-173 requires
+pub fn from_bech32(s: &str) -> Result<(String, Vec<u8>, Bech32Variant), &'static str> {
+    if s.len() > 90 {
+        return Err("bech32 string too long");
+    }
+    if s.chars().any(|c| c.is_ascii_lowercase()) && s.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err("bech32 string has mixed case");
+    }
+    let s = s.to_ascii_lowercase();
+    let separator = s.rfind('1').ok_or("bech32 string has no separator")?;
+    let (hrp, data) = (&s[..separator], &s[separator + 1..]);
+    if hrp.is_empty() || data.len() < 6 || !hrp.bytes().all(|c| (33..=126).contains(&c)) {
+        return Err("malformed bech32 string");
+    }
+    let data = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|&d| d == c).map(|d| d as u8).ok_or("invalid bech32 character"))
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let mut values = bech32_hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    let variant = match bech32_polymod(&values) {
+        1 => Bech32Variant::Bech32,
+        0x2bc8_30a3 => Bech32Variant::Bech32m,
+        _ => return Err("bech32 checksum mismatch"),
+    };
+    Ok((hrp.to_string(), data[..data.len() - 6].to_vec(), variant))
+}
+
+// Regroup bits, e.g. bytes into the 5-bit values bech32 carries; without padding, leftover
+// bits must be fewer than from_bits and all zero
+pub fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Result<Vec<u8>, &'static str> {
+    let mut accumulator = 0u32;
+    let mut bits = 0u32;
+    let mask = (1u32 << to_bits) - 1;
+    let mut out = Vec::new();
+    for &value in data {
+        if (value as u32) >> from_bits != 0 {
+            return Err("value too large for the bit width");
+        }
+        accumulator = accumulator << from_bits | value as u32;
+        bits += from_bits;
+        while bits >= to_bits {
+            bits -= to_bits;
+            out.push((accumulator >> bits & mask) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push((accumulator << (to_bits - bits) & mask) as u8);
+        }
+    } else if bits >= from_bits || accumulator << (to_bits - bits) & mask != 0 {
+        return Err("invalid padding");
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    #[test]
+    fn test_point_roundtrip() {
+        let p256 = curves::p256();
+        let point = p256.curve.scalar_mult(&p256.generator, &BigUint::from(12345u32));
+
+        for compressed in [true, false] {
+            let bytes = encode_point(&p256.curve, &point, compressed);
+            assert_eq!(bytes.len(), if compressed { 33 } else { 65 });
+            assert_eq!(decode_point(&p256.curve, &bytes).unwrap(), point);
+        }
+        assert_eq!(decode_point(&p256.curve, &encode_point(&p256.curve, &Point::Identity, true)).unwrap(), Point::Identity);
+    }
+
+    #[test]
+    fn test_generator_encoding() {
+        let p256 = curves::p256();
+        let expected = "036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296";
+        assert_eq!(to_hex(&encode_point(&p256.curve, &p256.generator, true)), expected);
+        assert_eq!(from_hex(expected).unwrap(), encode_point(&p256.curve, &p256.generator, true));
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid() {
+        let p256 = curves::p256();
+        let mut bytes = encode_point(&p256.curve, &p256.generator, false);
+
+        // Flip a bit of y: off the curve
+        bytes[64] ^= 1;
+        assert!(decode_point(&p256.curve, &bytes).is_err());
+        assert!(decode_point(&p256.curve, &bytes[..40]).is_err());
+        assert!(decode_point(&p256.curve, &[0x05; 33]).is_err());
+        assert!(decode_point(&p256.curve, &[]).is_err());
+        assert!(from_hex("abc").is_err());
+        assert!(from_hex("zz").is_err());
+    }
+
+    #[test]
+    fn test_hex_display_and_parse() {
+        let p256 = curves::p256();
+        let g = p256.curve.point(p256.generator.clone());
+        let expected = "036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296";
+        assert_eq!(g.to_string(), expected);
+        assert_eq!(format!("{:#x}", g), format!("0x{}", expected));
+        assert_eq!(point_from_hex(&p256.curve, expected).unwrap(), p256.generator);
+        assert_eq!(point_from_hex(&p256.curve, &format!("{:#x}", g)).unwrap(), p256.generator);
+        assert_eq!(p256.curve.point(Point::Identity).to_string(), "00");
+        assert!(point_from_hex(&p256.curve, "02ff").is_err());
+
+        let signature = ECDSASignature { r: BigUint::from(0x1234u32), s: BigUint::from(0xabu32) };
+        assert_eq!(signature.to_string(), "123400ab");
+        assert_eq!(format!("{:.4}", signature), "00001234000000ab");
+        assert_eq!(format!("{:#.2x}", signature), "0x123400ab");
+        assert_eq!("00001234000000ab".parse::<ECDSASignature>().unwrap(), signature);
+        assert_eq!("0x123400ab".parse::<ECDSASignature>().unwrap(), signature);
+        assert!("123400a".parse::<ECDSASignature>().is_err());
+        assert!("12340".parse::<ECDSASignature>().is_err());
+        assert!("".parse::<ECDSASignature>().is_err());
+    }
+
+    #[test]
+    fn test_base58() {
+        assert_eq!(to_base58(b""), "");
+        assert_eq!(to_base58(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]), "11233QC4");
+        assert_eq!(from_base58("11233QC4").unwrap(), vec![0, 0, 0x28, 0x7f, 0xb4, 0xcd]);
+        assert!(from_base58("0OIl").is_err());
+
+        let encoded = to_base58check(b"hello");
+        assert_eq!(from_base58check(&encoded).unwrap(), b"hello");
+        let mut tampered = encoded.into_bytes();
+        tampered[0] = if tampered[0] == b'2' { b'3' } else { b'2' };
+        assert!(from_base58check(&String::from_utf8(tampered).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_base64() {
+        // RFC 4648 section 10
+        for (plain, encoded) in [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg=="), ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")] {
+            assert_eq!(to_base64(plain.as_bytes()), encoded);
+            assert_eq!(from_base64(encoded).unwrap(), plain.as_bytes());
+        }
+        assert_eq!(from_base64("+/+/").unwrap(), vec![0xfb, 0xff, 0xbf]);
+        assert!(from_base64("Zg=").is_err());
+        assert!(from_base64("Zh==").is_err());
+        assert!(from_base64("Z===").is_err());
+        assert!(from_base64("Zm9v\n").is_err());
+        assert!(from_base64("Zm-v").is_err());
+
+        assert_eq!(to_base64url(&[0xfb, 0xff, 0xbf, 0x01]), "-_-_AQ");
+        assert_eq!(from_base64url("-_-_AQ").unwrap(), vec![0xfb, 0xff, 0xbf, 0x01]);
+        assert!(from_base64url("-_-_AQ==").is_err());
+        assert!(from_base64url("+/+/").is_err());
+        assert!(from_base64url("Zm9vY").is_err());
+    }
+
+    #[test]
+    fn test_bech32() {
+        // BIP-173 / BIP-350 valid checksums
+        assert_eq!(from_bech32("A12UEL5L").unwrap(), ("a".to_string(), vec![], Bech32Variant::Bech32));
+        assert_eq!(from_bech32("a1lqfn3a").unwrap().2, Bech32Variant::Bech32m);
+        assert!(from_bech32("A12UEl5L").is_err());
+        assert!(from_bech32("a12uel5m").is_err());
+
+        let data = convert_bits(b"hello", 8, 5, true).unwrap();
+        let encoded = to_bech32("test", &data, Bech32Variant::Bech32m);
+        let (hrp, decoded, variant) = from_bech32(&encoded).unwrap();
+        assert_eq!((hrp.as_str(), variant), ("test", Bech32Variant::Bech32m));
+        assert_eq!(convert_bits(&decoded, 5, 8, false).unwrap(), b"hello");
+    }
+}
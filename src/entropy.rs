@@ -0,0 +1,88 @@
+use rand::{CryptoRng, RngCore};
+use std::sync::OnceLock;
+
+// Where the crate's randomness comes from: keys, nonces, salts, IVs, blinding factors
+//
+// By default bytes come straight from the operating system through getrandom (getrandom(2),
+// BCryptGenRandom, SecRandomCopy, ...), with no userspace generator or per-thread state in
+// between. Targets without an OS source (bare metal, enclaves, some wasm hosts) register their
+// own with set_source, once and before the first key is made, e.g. a hardware TRNG driver:
+//   entropy::set_source(|bytes| trng::fill(bytes).map_err(|_| "TRNG fault"))
+// Entropy is a zero-sized CryptoRng over whichever source is active, for the rand APIs that
+// want an Rng (gen_biguint_range, Scalar::random, ...)
+
+pub type EntropySource = fn(&mut [u8]) -> Result<(), &'static str>;
+
+static SOURCE: OnceLock<EntropySource> = OnceLock::new();
+
+fn os_source(bytes: &mut [u8]) -> Result<(), &'static str> {
+    getrandom::getrandom(bytes).map_err(|_| "operating system entropy unavailable")
+}
+
+// Replaces the getrandom default for the rest of the process; only the first call succeeds
+pub fn set_source(source: EntropySource) -> Result<(), &'static str> {
+    SOURCE.set(source).map_err(|_| "entropy source already set")
+}
+
+pub fn fill(bytes: &mut [u8]) -> Result<(), &'static str> {
+    SOURCE.get().copied().unwrap_or(os_source)(bytes)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Entropy;
+
+impl RngCore for Entropy {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    // A failing source is fatal: carrying on would mean predictable keys
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        fill(bytes).expect("?entropy source failed?")
+    }
+
+    fn try_fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), rand::Error> {
+        fill(bytes).map_err(rand::Error::new)
+    }
+}
+
+impl CryptoRng for Entropy {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    // A "hardware" source that still draws from the OS, so other tests keep real randomness
+    fn counting_source(bytes: &mut [u8]) -> Result<(), &'static str> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        os_source(bytes)
+    }
+
+    #[test]
+    fn test_custom_source() {
+        set_source(counting_source).unwrap();
+        assert!(set_source(os_source).is_err());
+
+        let before = CALLS.load(Ordering::SeqCst);
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        fill(&mut a).unwrap();
+        Entropy.fill_bytes(&mut b);
+        assert_ne!(a, b);
+        assert!(Entropy.gen_range(1..=6) <= 6);
+        assert!(CALLS.load(Ordering::SeqCst) >= before + 3);
+        assert!(Entropy.try_fill_bytes(&mut []).is_ok());
+    }
+}
@@ -0,0 +1,319 @@
+use crate::addresses::ethereum::keccak256;
+use crate::curves;
+use crate::encoding::to_fixed_bytes;
+use crate::recoverable::RecoverableSignature;
+use num_bigint::{BigInt, BigUint};
+use std::collections::{BTreeMap, BTreeSet};
+
+// Ethereum message signing: EIP-191 personal messages and EIP-712 typed data
+//
+// Both make sure a signed message can never be a valid transaction:
+//   personal_sign  keccak256("\x19Ethereum Signed Message:\n" || len(m) in decimal || m)
+//   typed data     keccak256(0x19 0x01 || domainSeparator || hashStruct(message))
+// where hashStruct(s) = keccak256(typeHash || encodeData(s)) and typeHash is the hash of the
+// type's signature, e.g. "Mail(Person from,Person to,string contents)Person(string name,address wallet)":
+// the primary type, then every struct it references, sorted by name.
+// encodeData packs each field into 32 bytes: atomic values directly, string/bytes/arrays as
+// their keccak256, and nested structs as their hashStruct
+
+const PERSONAL_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+pub fn hash_personal_message(message: &[u8]) -> [u8; 32] {
+    let mut data = PERSONAL_PREFIX.to_vec();
+    data.extend_from_slice(message.len().to_string().as_bytes());
+    data.extend_from_slice(message);
+    keccak256(&data)
+}
+
+pub fn sign_personal_message(message: &[u8], private_key: &BigUint) -> Result<RecoverableSignature, &'static str> {
+    curves::secp256k1().ecdsa().sign_digest_recoverable(&hash_personal_message(message), private_key)
+}
+
+// The signer's address, as checked against the claimed one by dapps
+pub fn recover_personal_message(message: &[u8], signature: &RecoverableSignature) -> Result<[u8; 20], &'static str> {
+    let public_key = curves::secp256k1().ecdsa().recover_from_digest(&hash_personal_message(message), signature)?;
+    crate::addresses::ethereum::address_bytes(&public_key)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedField {
+    pub name: String,
+    pub type_name: String,
+}
+
+impl TypedField {
+    pub fn new(name: &str, type_name: &str) -> Self {
+        TypedField { name: name.to_string(), type_name: type_name.to_string() }
+    }
+}
+
+// A value of any EIP-712 type; which encoding applies comes from the declared type
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedValue {
+    Uint(BigUint),
+    Int(BigInt),
+    Bool(bool),
+    Address([u8; 20]),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<TypedValue>),
+    Struct(BTreeMap<String, TypedValue>),
+}
+
+// Struct definitions, including EIP712Domain, plus the domain and message to sign
+#[derive(Clone, Debug)]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedField>>,
+    pub primary_type: String,
+    pub domain: TypedValue,
+    pub message: TypedValue,
+}
+
+impl TypedData {
+    pub fn domain_separator(&self) -> Result<[u8; 32], &'static str> {
+        self.hash_struct("EIP712Domain", &self.domain)
+    }
+
+    pub fn message_hash(&self) -> Result<[u8; 32], &'static str> {
+        self.hash_struct(&self.primary_type, &self.message)
+    }
+
+    // keccak256(0x19 0x01 || domainSeparator || hashStruct(message))
+    pub fn signing_hash(&self) -> Result<[u8; 32], &'static str> {
+        let mut data = vec![0x19, 0x01];
+        data.extend_from_slice(&self.domain_separator()?);
+        data.extend_from_slice(&self.message_hash()?);
+        Ok(keccak256(&data))
+    }
+
+    pub fn sign(&self, private_key: &BigUint) -> Result<RecoverableSignature, &'static str> {
+        curves::secp256k1().ecdsa().sign_digest_recoverable(&self.signing_hash()?, private_key)
+    }
+
+    pub fn recover(&self, signature: &RecoverableSignature) -> Result<[u8; 20], &'static str> {
+        let public_key = curves::secp256k1().ecdsa().recover_from_digest(&self.signing_hash()?, signature)?;
+        crate::addresses::ethereum::address_bytes(&public_key)
+    }
+
+    // "Name(type1 field1,type2 field2)" for the type and each struct it references, sorted
+    pub fn encode_type(&self, type_name: &str) -> Result<String, &'static str> {
+        let mut dependencies = BTreeSet::new();
+        self.collect_dependencies(type_name, &mut dependencies)?;
+        dependencies.remove(type_name);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(type_name).chain(dependencies.iter().map(String::as_str)) {
+            let fields = &self.types[name];
+            let members: Vec<String> = fields.iter().map(|f| format!("{} {}", f.type_name, f.name)).collect();
+            encoded.push_str(&format!("{}({})", name, members.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    pub fn type_hash(&self, type_name: &str) -> Result<[u8; 32], &'static str> {
+        Ok(keccak256(self.encode_type(type_name)?.as_bytes()))
+    }
+
+    pub fn hash_struct(&self, type_name: &str, value: &TypedValue) -> Result<[u8; 32], &'static str> {
+        let TypedValue::Struct(members) = value else {
+            return Err("struct type given a non-struct value");
+        };
+        let fields = self.types.get(type_name).ok_or("undefined struct type")?;
+        let mut data = self.type_hash(type_name)?.to_vec();
+        for field in fields {
+            let member = members.get(&field.name).ok_or("struct value is missing a field")?;
+            data.extend_from_slice(&self.encode_value(&field.type_name, member)?);
+        }
+        Ok(keccak256(&data))
+    }
+
+    fn collect_dependencies(&self, type_name: &str, found: &mut BTreeSet<String>) -> Result<(), &'static str> {
+        if found.contains(type_name) {
+            return Ok(());
+        }
+        let fields = self.types.get(type_name).ok_or("undefined struct type")?;
+        found.insert(type_name.to_string());
+        for field in fields {
+            let base = base_type(&field.type_name);
+            if self.types.contains_key(base) {
+                self.collect_dependencies(base, found)?;
+            }
+        }
+        Ok(())
+    }
+
+    // One 32-byte word per value
+    fn encode_value(&self, type_name: &str, value: &TypedValue) -> Result<[u8; 32], &'static str> {
+        if let Some(element_type) = array_element_type(type_name) {
+            let TypedValue::Array(elements) = value else {
+                return Err("array type given a non-array value");
+            };
+            let mut data = Vec::with_capacity(32 * elements.len());
+            for element in elements {
+                data.extend_from_slice(&self.encode_value(element_type, element)?);
+            }
+            return Ok(keccak256(&data));
+        }
+        if self.types.contains_key(type_name) {
+            return self.hash_struct(type_name, value);
+        }
+
+        let word = match (type_name, value) {
+            ("string", TypedValue::String(s)) => keccak256(s.as_bytes()),
+            ("bytes", TypedValue::Bytes(b)) => keccak256(b),
+            ("bool", TypedValue::Bool(b)) => word_from_uint(&BigUint::from(*b as u8)),
+            ("address", TypedValue::Address(a)) => {
+                let mut word = [0u8; 32];
+                word[12..].copy_from_slice(a);
+                word
+            }
+            (t, TypedValue::Bytes(b)) if t.starts_with("bytes") => {
+                let size: usize = t[5..].parse().map_err(|_| "invalid bytesN type")?;
+                if size == 0 || size > 32 || b.len() != size {
+                    return Err("bytesN value has the wrong length");
+                }
+                let mut word = [0u8; 32];
+                word[..size].copy_from_slice(b);
+                word
+            }
+            (t, TypedValue::Uint(u)) if t.starts_with("uint") => {
+                if u.bits() > integer_bits(&t[4..])? {
+                    return Err("uint value too large for its type");
+                }
+                word_from_uint(u)
+            }
+            (t, TypedValue::Int(i)) if t.starts_with("int") => {
+                let bits = integer_bits(&t[3..])?;
+                let bound = BigInt::from(1u32) << (bits - 1);
+                if *i >= bound || *i < -bound {
+                    return Err("int value out of range for its type");
+                }
+                // Two's complement in 256 bits
+                let modulus = BigInt::from(1u32) << 256u32;
+                let (_, magnitude) = ((i % &modulus + &modulus) % &modulus).into_parts();
+                word_from_uint(&magnitude)
+            }
+            _ => return Err("value does not match its declared type"),
+        };
+        Ok(word)
+    }
+}
+
+fn word_from_uint(value: &BigUint) -> [u8; 32] {
+    to_fixed_bytes(value, 32).try_into().unwrap()
+}
+
+// "uint256" -> 256; a bare "uint" is not a valid EIP-712 type
+fn integer_bits(suffix: &str) -> Result<u64, &'static str> {
+    let bits: u64 = suffix.parse().map_err(|_| "integer types need an explicit size")?;
+    if bits == 0 || bits > 256 || !bits.is_multiple_of(8) {
+        return Err("invalid integer size");
+    }
+    Ok(bits)
+}
+
+// "Person[][3]" -> Some("Person[]")
+fn array_element_type(type_name: &str) -> Option<&str> {
+    let stripped = type_name.strip_suffix(']')?;
+    let open = stripped.rfind('[')?;
+    Some(&type_name[..open])
+}
+
+// "Person[][3]" -> "Person"
+fn base_type(type_name: &str) -> &str {
+    type_name.split('[').next().unwrap_or(type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addresses::ethereum::{parse_address, to_checksum_address};
+    use crate::encoding::to_hex;
+
+    fn address(s: &str) -> TypedValue {
+        TypedValue::Address(parse_address(s).unwrap())
+    }
+
+    fn structure(members: &[(&str, TypedValue)]) -> TypedValue {
+        TypedValue::Struct(members.iter().map(|(k, v)| (k.to_string(), v.clone())).collect())
+    }
+
+    // The example from the EIP-712 specification
+    fn mail() -> TypedData {
+        let mut types = BTreeMap::new();
+        types.insert(
+            "EIP712Domain".to_string(),
+            vec![
+                TypedField::new("name", "string"),
+                TypedField::new("version", "string"),
+                TypedField::new("chainId", "uint256"),
+                TypedField::new("verifyingContract", "address"),
+            ],
+        );
+        types.insert("Person".to_string(), vec![TypedField::new("name", "string"), TypedField::new("wallet", "address")]);
+        types.insert(
+            "Mail".to_string(),
+            vec![TypedField::new("from", "Person"), TypedField::new("to", "Person"), TypedField::new("contents", "string")],
+        );
+
+        TypedData {
+            types,
+            primary_type: "Mail".to_string(),
+            domain: structure(&[
+                ("name", TypedValue::String("Ether Mail".to_string())),
+                ("version", TypedValue::String("1".to_string())),
+                ("chainId", TypedValue::Uint(BigUint::from(1u32))),
+                ("verifyingContract", address("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC")),
+            ]),
+            message: structure(&[
+                ("from", structure(&[
+                    ("name", TypedValue::String("Cow".to_string())),
+                    ("wallet", address("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826")),
+                ])),
+                ("to", structure(&[
+                    ("name", TypedValue::String("Bob".to_string())),
+                    ("wallet", address("0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB")),
+                ])),
+                ("contents", TypedValue::String("Hello, Bob!".to_string())),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_eip712_mail_example() {
+        let data = mail();
+        assert_eq!(data.encode_type("Mail").unwrap(), "Mail(Person from,Person to,string contents)Person(string name,address wallet)");
+        assert_eq!(to_hex(&data.domain_separator().unwrap()), "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f");
+        assert_eq!(to_hex(&data.message_hash().unwrap()), "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e");
+        assert_eq!(to_hex(&data.signing_hash().unwrap()), "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2");
+
+        // The spec's signer: private key keccak256("cow"), address 0xCD2a...D826
+        let private_key = BigUint::from_bytes_be(&keccak256(b"cow"));
+        let signature = data.sign(&private_key).unwrap();
+        assert_eq!(to_checksum_address(&data.recover(&signature).unwrap()), "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826");
+    }
+
+    #[test]
+    fn test_personal_message() {
+        assert_eq!(to_hex(&hash_personal_message(b"Hello World")), "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2");
+
+        let private_key = BigUint::from(1u32);
+        let signature = sign_personal_message(b"log in", &private_key).unwrap();
+        let signer = recover_personal_message(b"log in", &signature).unwrap();
+        assert_eq!(to_checksum_address(&signer), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+        assert_ne!(recover_personal_message(b"log out", &signature).ok(), Some(signer));
+    }
+
+    #[test]
+    fn test_eip712_rejects_mismatched_values() {
+        let mut data = mail();
+        data.message = structure(&[("contents", TypedValue::Bool(true))]);
+        assert!(data.message_hash().is_err());
+
+        let data = mail();
+        assert!(data.encode_value("uint8", &TypedValue::Uint(BigUint::from(256u32))).is_err());
+        assert!(data.encode_value("int8", &TypedValue::Int(BigInt::from(-129))).is_err());
+        assert_eq!(data.encode_value("int8", &TypedValue::Int(BigInt::from(-1))).unwrap(), [0xff; 32]);
+        assert!(data.encode_value("bytes4", &TypedValue::Bytes(vec![1, 2, 3])).is_err());
+    }
+}
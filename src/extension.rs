@@ -0,0 +1,636 @@
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+
+// Extension field F_{p^k} = F_p[t] / (f(t)) for a monic irreducible f of degree k
+// Elements are polynomials of degree < k, coefficients stored lowest degree first
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtElement {
+    pub coeffs: Vec<BigUint>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtensionField {
+    pub p: BigUint,
+    // Coefficients of f, lowest first, without the leading 1
+    pub modulus: Vec<BigUint>,
+}
+
+impl ExtensionField {
+    // F_p[t] / (t^k + c_{k-1} t^(k-1) + ... + c_0), modulus = [c_0, ..., c_{k-1}]
+    // The caller is responsible for f being irreducible (see find)
+    pub fn new(p: BigUint, modulus: Vec<BigUint>) -> Self {
+        ExtensionField { p, modulus }
+    }
+
+    // Degree-k extension with the first irreducible monic f found
+    // Candidates are tried in order t^k + c first, so for k = 2 this is t² - d with d a non-residue
+    pub fn find(p: &BigUint, k: usize) -> Self {
+        assert!(k >= 1, "?extension degree must be at least 1?");
+        let zero = BigUint::from(0u32);
+
+        // Counter over (c_0, ..., c_{k-1}), c_0 varying fastest; skip c_0 = 0 (t divides f)
+        let mut candidate = vec![zero.clone(); k];
+        loop {
+            let mut i = 0;
+            loop {
+                candidate[i] += 1u32;
+                if candidate[i] < *p {
+                    break;
+                }
+                candidate[i] = zero.clone();
+                i += 1;
+                assert!(i < k, "?no irreducible polynomial found?");
+            }
+            if candidate[0] == zero {
+                continue;
+            }
+
+            let mut monic = candidate.clone();
+            monic.push(BigUint::from(1u32));
+            if poly::is_irreducible(&monic, p) {
+                return ExtensionField::new(p.clone(), candidate);
+            }
+        }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.modulus.len()
+    }
+
+    // Field size q = p^k
+    pub fn order(&self) -> BigUint {
+        self.p.pow(self.degree() as u32)
+    }
+
+    pub fn zero(&self) -> ExtElement {
+        ExtElement { coeffs: vec![BigUint::from(0u32); self.degree()] }
+    }
+
+    pub fn one(&self) -> ExtElement {
+        self.from_base(&BigUint::from(1u32))
+    }
+
+    // Embed c ∈ F_p as a constant polynomial
+    pub fn from_base(&self, c: &BigUint) -> ExtElement {
+        let mut element = self.zero();
+        element.coeffs[0] = c % &self.p;
+        element
+    }
+
+    // Element from coefficients (lowest first), reduced mod p and padded to length k
+    pub fn from_coeffs(&self, coeffs: &[BigUint]) -> ExtElement {
+        let mut full = coeffs.iter().map(|c| c % &self.p).collect::<Vec<_>>();
+        poly::reduce(&mut full, &self.monic(), &self.p);
+        full.resize(self.degree(), BigUint::from(0u32));
+        ExtElement { coeffs: full }
+    }
+
+    pub fn is_zero(&self, x: &ExtElement) -> bool {
+        x.coeffs.iter().all(|c| *c == BigUint::from(0u32))
+    }
+
+    pub fn add(&self, x: &ExtElement, y: &ExtElement) -> ExtElement {
+        let coeffs = x.coeffs.iter().zip(&y.coeffs).map(|(a, b)| (a + b) % &self.p).collect();
+        ExtElement { coeffs }
+    }
+
+    // c * x for c ∈ F_p: coefficient-wise, no reduction by f needed
+    pub fn scale(&self, x: &ExtElement, c: &BigUint) -> ExtElement {
+        let coeffs = x.coeffs.iter().map(|a| (a * c) % &self.p).collect();
+        ExtElement { coeffs }
+    }
+
+    pub fn neg(&self, x: &ExtElement) -> ExtElement {
+        let coeffs = x.coeffs.iter().map(|c| (&self.p - c) % &self.p).collect();
+        ExtElement { coeffs }
+    }
+
+    pub fn sub(&self, x: &ExtElement, y: &ExtElement) -> ExtElement {
+        self.add(x, &self.neg(y))
+    }
+
+    // Schoolbook product, then reduction by f
+    pub fn mul(&self, x: &ExtElement, y: &ExtElement) -> ExtElement {
+        let product = poly::mul(&x.coeffs, &y.coeffs, &self.p);
+        self.from_coeffs(&product)
+    }
+
+    pub fn pow(&self, x: &ExtElement, exponent: &BigUint) -> ExtElement {
+        let mut result = self.one();
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, x);
+            }
+        }
+        result
+    }
+
+    // x^(-1) via the extended Euclidean algorithm on polynomials: s(t) x(t) + u(t) f(t) = 1
+    // (x^(q-2) would also work, but costs a log(q)-bit exponentiation per inversion)
+    pub fn inv(&self, x: &ExtElement) -> ExtElement {
+        if self.is_zero(x) {
+            panic!("?division by zero in extension field?");
+        }
+        let inverse = poly::inverse_mod(&x.coeffs, &self.monic(), &self.p)
+            .expect("?extension modulus is not irreducible?");
+        self.from_coeffs(&inverse)
+    }
+
+    pub fn div(&self, x: &ExtElement, y: &ExtElement) -> ExtElement {
+        self.mul(x, &self.inv(y))
+    }
+
+    // Square root in F_q (Tonelli–Shanks over the whole field): None for non-squares
+    pub fn sqrt(&self, x: &ExtElement) -> Option<ExtElement> {
+        if self.is_zero(x) {
+            return Some(self.zero());
+        }
+        let one = self.one();
+        let minus_one = self.neg(&one);
+        let q_minus_one = self.order() - BigUint::from(1u32);
+        let euler_exp = &q_minus_one >> 1;
+
+        if self.pow(x, &euler_exp) != one {
+            return None;
+        }
+
+        // q - 1 = odd * 2^s
+        let s = q_minus_one.trailing_zeros().unwrap_or(0);
+        let odd = &q_minus_one >> s;
+
+        // Any non-square z: try c + t for c = 0, 1, ... (c alone when k = 1)
+        // Constants are useless for k >= 2: every element of F_p is a square in F_p²
+        let mut c = BigUint::from(0u32);
+        let z = loop {
+            let candidate = if self.degree() == 1 {
+                self.from_base(&c)
+            } else {
+                self.from_coeffs(&[c.clone(), BigUint::from(1u32)])
+            };
+            if self.pow(&candidate, &euler_exp) == minus_one {
+                break candidate;
+            }
+            c += 1u32;
+        };
+
+        let mut m = s;
+        let mut c = self.pow(&z, &odd);
+        let mut t = self.pow(x, &odd);
+        let mut root = self.pow(x, &((&odd + BigUint::from(1u32)) >> 1));
+
+        while t != one {
+            let mut i = 0u64;
+            let mut t_pow = t.clone();
+            while t_pow != one {
+                t_pow = self.mul(&t_pow, &t_pow);
+                i += 1;
+            }
+
+            let mut b = c.clone();
+            for _ in 0..(m - i - 1) {
+                b = self.mul(&b, &b);
+            }
+
+            m = i;
+            c = self.mul(&b, &b);
+            t = self.mul(&t, &c);
+            root = self.mul(&root, &b);
+        }
+
+        Some(root)
+    }
+
+    // Frobenius table [t^(0·p), t^(1·p), ..., t^((k-1)·p)] for frobenius()
+    pub fn frobenius_table(&self) -> Vec<ExtElement> {
+        let t = self.from_coeffs(&[BigUint::from(0u32), BigUint::from(1u32)]);
+        let t_to_p = self.pow(&t, &self.p);
+        let mut table = vec![self.one()];
+        for i in 1..self.degree() {
+            table.push(self.mul(&table[i - 1], &t_to_p));
+        }
+        table
+    }
+
+    // x^p: since c^p = c for c ∈ F_p, (Σ c_i t^i)^p = Σ c_i (t^p)^i
+    // Costs k² base field products instead of a log(p)-bit exponentiation
+    pub fn frobenius(&self, x: &ExtElement, table: &[ExtElement]) -> ExtElement {
+        let mut result = self.zero();
+        for (c, t_power) in x.coeffs.iter().zip(table) {
+            result = self.add(&result, &self.scale(t_power, c));
+        }
+        result
+    }
+
+    // The n-th element in a fixed enumeration of F_q: base-p digits of n as coefficients
+    pub fn element_from_index(&self, n: &BigUint) -> ExtElement {
+        self.from_coeffs(&self.digits(n))
+    }
+
+    fn digits(&self, n: &BigUint) -> Vec<BigUint> {
+        let mut digits = Vec::with_capacity(self.degree());
+        let mut rest = n.clone();
+        for _ in 0..self.degree() {
+            digits.push(&rest % &self.p);
+            rest /= &self.p;
+        }
+        digits
+    }
+
+    // Base field elements are exactly the constant polynomials
+    pub fn is_in_base_field(&self, x: &ExtElement) -> bool {
+        x.coeffs.iter().skip(1).all(|c| *c == BigUint::from(0u32))
+    }
+
+    fn monic(&self) -> Vec<BigUint> {
+        let mut monic = self.modulus.clone();
+        monic.push(BigUint::from(1u32));
+        monic
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtPoint {
+    Coordinate(ExtElement, ExtElement),
+    Identity,
+}
+
+// The curve y² = x³ + ax + b with points over F_{p^k} (a, b still in F_p)
+#[derive(Clone, Debug)]
+pub struct ExtensionCurve {
+    pub a: ExtElement,
+    pub b: ExtElement,
+    pub field: ExtensionField,
+}
+
+impl ExtensionCurve {
+    pub fn new(curve: &EllipticCurve, field: ExtensionField) -> Self {
+        ExtensionCurve {
+            a: field.from_base(curve.a()),
+            b: field.from_base(curve.b()),
+            field,
+        }
+    }
+
+    // E(F_p) ⊂ E(F_{p^k})
+    pub fn lift(&self, point: &Point) -> ExtPoint {
+        match point {
+            Point::Identity => ExtPoint::Identity,
+            Point::Coordinate(x, y) => ExtPoint::Coordinate(self.field.from_base(x), self.field.from_base(y)),
+        }
+    }
+
+    // y² = x³ + ax + b
+    pub fn is_on_curve(&self, point: &ExtPoint) -> bool {
+        match point {
+            ExtPoint::Identity => true,
+            ExtPoint::Coordinate(x, y) => {
+                let f = &self.field;
+                let y_squared = f.mul(y, y);
+                let x_cubed = f.mul(&f.mul(x, x), x);
+                let right_side = f.add(&f.add(&x_cubed, &f.mul(&self.a, x)), &self.b);
+                y_squared == right_side
+            }
+        }
+    }
+
+    // P is in E[r]: on the curve and r * P = O
+    pub fn is_torsion_point(&self, point: &ExtPoint, r: &BigUint) -> bool {
+        self.is_on_curve(point) && self.scalar_mult(point, r) == ExtPoint::Identity
+    }
+
+    pub fn neg(&self, point: &ExtPoint) -> ExtPoint {
+        match point {
+            ExtPoint::Identity => ExtPoint::Identity,
+            ExtPoint::Coordinate(x, y) => ExtPoint::Coordinate(x.clone(), self.field.neg(y)),
+        }
+    }
+
+    // Same group law as EllipticCurve::add, over F_{p^k}
+    pub fn add(&self, p: &ExtPoint, q: &ExtPoint) -> ExtPoint {
+        match (p, q) {
+            (ExtPoint::Identity, _) => q.clone(),
+            (_, ExtPoint::Identity) => p.clone(),
+            (ExtPoint::Coordinate(x1, y1), ExtPoint::Coordinate(x2, y2)) => {
+                if x1 == x2 {
+                    if y1 == y2 {
+                        self.double(p)
+                    } else {
+                        ExtPoint::Identity
+                    }
+                } else {
+                    let f = &self.field;
+                    let slope = f.div(&f.sub(y2, y1), &f.sub(x2, x1));
+                    self.chord_tangent(x1, y1, x2, &slope)
+                }
+            }
+        }
+    }
+
+    pub fn double(&self, p: &ExtPoint) -> ExtPoint {
+        match p {
+            ExtPoint::Identity => ExtPoint::Identity,
+            ExtPoint::Coordinate(x, y) => {
+                let f = &self.field;
+                if f.is_zero(y) {
+                    return ExtPoint::Identity;
+                }
+                let slope = self.tangent_slope(x, y);
+                self.chord_tangent(x, y, x, &slope)
+            }
+        }
+    }
+
+    pub fn scalar_mult(&self, point: &ExtPoint, k: &BigUint) -> ExtPoint {
+        let mut result = ExtPoint::Identity;
+        for i in (0..k.bits()).rev() {
+            result = self.double(&result);
+            if k.bit(i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    // s = (3x² + a) / 2y
+    pub(crate) fn tangent_slope(&self, x: &ExtElement, y: &ExtElement) -> ExtElement {
+        let f = &self.field;
+        let three_x_squared = f.mul(&f.from_base(&BigUint::from(3u32)), &f.mul(x, x));
+        let two_y = f.add(y, y);
+        f.div(&f.add(&three_x_squared, &self.a), &two_y)
+    }
+
+    // x3 = s² - x1 - x2, y3 = s(x1 - x3) - y1
+    fn chord_tangent(&self, x1: &ExtElement, y1: &ExtElement, x2: &ExtElement, slope: &ExtElement) -> ExtPoint {
+        let f = &self.field;
+        let x3 = f.sub(&f.sub(&f.mul(slope, slope), x1), x2);
+        let y3 = f.sub(&f.mul(slope, &f.sub(x1, &x3)), y1);
+        ExtPoint::Coordinate(x3, y3)
+    }
+}
+
+// Polynomials over F_p, coefficients lowest degree first; the zero polynomial is empty
+pub(crate) mod poly {
+    use crate::ntheory::mod_inverse;
+    use num_bigint::BigUint;
+
+    pub fn trim(a: &mut Vec<BigUint>) {
+        while a.last().is_some_and(|c| *c == BigUint::from(0u32)) {
+            a.pop();
+        }
+    }
+
+    pub fn mul(a: &[BigUint], b: &[BigUint], p: &BigUint) -> Vec<BigUint> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+        // Accumulate unreduced, then one reduction per coefficient
+        let mut product = vec![BigUint::from(0u32); a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                product[i + j] += x * y;
+            }
+        }
+        product.iter().map(|c| c % p).collect()
+    }
+
+    // a = q * m + r with deg r < deg m, returns q and leaves r in a; m must be nonzero
+    pub fn div_rem(a: &mut Vec<BigUint>, m: &[BigUint], p: &BigUint) -> Vec<BigUint> {
+        let mut m = m.to_vec();
+        trim(&mut m);
+        trim(a);
+        let deg_m = m.len() - 1;
+        let lead_inv = mod_inverse(&m[deg_m], p).expect("?leading coefficient not invertible?");
+
+        let mut quotient = vec![BigUint::from(0u32); a.len().saturating_sub(deg_m)];
+        while a.len() > deg_m {
+            let shift = a.len() - 1 - deg_m;
+            let factor = (a.last().unwrap() * &lead_inv) % p;
+            for (i, c) in m.iter().enumerate() {
+                let term = (&factor * c) % p;
+                a[shift + i] = (&a[shift + i] + p - term) % p;
+            }
+            quotient[shift] = factor;
+            trim(a);
+        }
+        quotient
+    }
+
+    // a mod m, in place
+    pub fn reduce(a: &mut Vec<BigUint>, m: &[BigUint], p: &BigUint) {
+        div_rem(a, m, p);
+    }
+
+    pub fn add(a: &[BigUint], b: &[BigUint], p: &BigUint) -> Vec<BigUint> {
+        let mut sum = vec![BigUint::from(0u32); a.len().max(b.len())];
+        for (i, c) in a.iter().enumerate() {
+            sum[i] = c % p;
+        }
+        for (i, c) in b.iter().enumerate() {
+            sum[i] = (&sum[i] + c) % p;
+        }
+        trim(&mut sum);
+        sum
+    }
+
+    pub fn sub(a: &[BigUint], b: &[BigUint], p: &BigUint) -> Vec<BigUint> {
+        let mut diff = vec![BigUint::from(0u32); a.len().max(b.len())];
+        for (i, c) in a.iter().enumerate() {
+            diff[i] = c % p;
+        }
+        for (i, c) in b.iter().enumerate() {
+            diff[i] = (&diff[i] + p - c % p) % p;
+        }
+        trim(&mut diff);
+        diff
+    }
+
+    // a^(-1) mod m via extended Euclid; None when gcd(a, m) isn't constant
+    pub fn inverse_mod(a: &[BigUint], m: &[BigUint], p: &BigUint) -> Option<Vec<BigUint>> {
+        // Invariant: r_i = s_i * a (mod m)
+        let mut old_r = a.to_vec();
+        reduce(&mut old_r, m, p);
+        let mut r = m.to_vec();
+        trim(&mut r);
+        let mut old_s = vec![BigUint::from(1u32)];
+        let mut s = Vec::new();
+
+        while !r.is_empty() {
+            let mut remainder = old_r.clone();
+            let quotient = div_rem(&mut remainder, &r, p);
+            old_r = std::mem::replace(&mut r, remainder);
+            let next_s = sub(&old_s, &mul(&quotient, &s, p), p);
+            old_s = std::mem::replace(&mut s, next_s);
+        }
+
+        // old_r is the gcd: a nonzero constant c means s * a = c, so a^(-1) = s / c
+        if old_r.len() != 1 {
+            return None;
+        }
+        let c_inv = mod_inverse(&old_r[0], p)?;
+        Some(old_s.iter().map(|coeff| (coeff * &c_inv) % p).collect())
+    }
+
+    pub fn gcd(a: &[BigUint], b: &[BigUint], p: &BigUint) -> Vec<BigUint> {
+        let mut a = a.to_vec();
+        let mut b = b.to_vec();
+        trim(&mut a);
+        trim(&mut b);
+        while !b.is_empty() {
+            reduce(&mut a, &b, p);
+            std::mem::swap(&mut a, &mut b);
+        }
+        a
+    }
+
+    // base^e mod m
+    pub fn pow_mod(base: &[BigUint], e: &BigUint, m: &[BigUint], p: &BigUint) -> Vec<BigUint> {
+        let mut result = vec![BigUint::from(1u32)];
+        for i in (0..e.bits()).rev() {
+            result = mul(&result, &result, p);
+            reduce(&mut result, m, p);
+            if e.bit(i) {
+                result = mul(&result, base, p);
+                reduce(&mut result, m, p);
+            }
+        }
+        result
+    }
+
+    // Rabin's test: monic f of degree k is irreducible over F_p iff
+    // t^(p^k) = t (mod f) and gcd(t^(p^(k/q)) - t, f) = 1 for every prime q | k
+    pub fn is_irreducible(f: &[BigUint], p: &BigUint) -> bool {
+        let k = f.len() - 1;
+        let t = vec![BigUint::from(0u32), BigUint::from(1u32)];
+
+        // t^(p^i) mod f by repeated p-th powering
+        let frobenius_power = |i: usize| {
+            let mut x = t.clone();
+            for _ in 0..i {
+                x = pow_mod(&x, p, f, p);
+            }
+            x
+        };
+        let minus_t = |mut x: Vec<BigUint>| {
+            x.resize(x.len().max(2), BigUint::from(0u32));
+            x[1] = (&x[1] + p - BigUint::from(1u32)) % p;
+            reduce(&mut x, f, p);
+            x
+        };
+
+        if !minus_t(frobenius_power(k)).is_empty() {
+            return false;
+        }
+        for q in 2..=k {
+            let is_prime = (2..q).all(|d| !q.is_multiple_of(d));
+            if is_prime && k.is_multiple_of(q) {
+                let g = gcd(&minus_t(frobenius_power(k / q)), f, p);
+                if g.len() != 1 {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_field_arithmetic() {
+        // F_49 = F_7[t] / (t² + 1): -1 is a non-residue mod 7
+        let field = ExtensionField::new(BigUint::from(7u32), vec![BigUint::from(1u32), BigUint::from(0u32)]);
+        let i = field.from_coeffs(&[BigUint::from(0u32), BigUint::from(1u32)]);
+
+        // i² = -1
+        assert_eq!(field.mul(&i, &i), field.neg(&field.one()));
+
+        // (2 + 3i) * (2 + 3i)^(-1) = 1
+        let x = field.from_coeffs(&[BigUint::from(2u32), BigUint::from(3u32)]);
+        assert_eq!(field.mul(&x, &field.inv(&x)), field.one());
+
+        // Multiplicative group has order 48
+        assert_eq!(field.pow(&x, &BigUint::from(48u32)), field.one());
+    }
+
+    #[test]
+    fn test_extension_sqrt() {
+        // F_11² has q - 1 = 120 = 15 * 2³, so Tonelli–Shanks does real work
+        let field = ExtensionField::find(&BigUint::from(11u32), 2);
+        let mut squares = 0;
+        for n in 0u32..121 {
+            let x = field.element_from_index(&BigUint::from(n));
+            if let Some(root) = field.sqrt(&x) {
+                assert_eq!(field.mul(&root, &root), x);
+                squares += 1;
+            }
+        }
+        // Zero plus half of the 120 nonzero elements
+        assert_eq!(squares, 61);
+
+        // Every element of F_11 is a square in F_11²
+        for c in 0u32..11 {
+            assert!(field.sqrt(&field.from_base(&BigUint::from(c))).is_some());
+        }
+    }
+
+    #[test]
+    fn test_frobenius() {
+        let field = ExtensionField::find(&BigUint::from(13u32), 3);
+        let table = field.frobenius_table();
+        let x = field.from_coeffs(&[BigUint::from(3u32), BigUint::from(7u32), BigUint::from(11u32)]);
+        assert_eq!(field.frobenius(&x, &table), field.pow(&x, &field.p));
+
+        // Frobenius fixes exactly F_p
+        let c = field.from_base(&BigUint::from(5u32));
+        assert_eq!(field.frobenius(&c, &table), c);
+    }
+
+    #[test]
+    fn test_find_irreducible() {
+        let p = BigUint::from(7u32);
+        for k in 1..=4 {
+            let field = ExtensionField::find(&p, k);
+            assert_eq!(field.degree(), k);
+
+            // Every nonzero element of F_{7^k} satisfies x^(q-1) = 1 only if f is irreducible
+            let x = field.from_coeffs(&(0..k as u32).map(|c| BigUint::from(c + 2)).collect::<Vec<_>>());
+            assert_eq!(field.pow(&x, &(field.order() - BigUint::from(1u32))), field.one());
+        }
+
+        // t² + 1 is reducible mod 5 (2² = -1), so it must not be picked
+        let field = ExtensionField::find(&BigUint::from(5u32), 2);
+        assert_ne!(field.modulus, vec![BigUint::from(1u32), BigUint::from(0u32)]);
+    }
+
+    #[test]
+    fn test_extension_curve_arithmetic() {
+        // y² = x³ + x over F_43: the distortion map (x, y) -> (-x, iy) lands in E(F_43²)
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(0u32), BigUint::from(43u32));
+        let field = ExtensionField::new(curve.p().clone(), vec![BigUint::from(1u32), BigUint::from(0u32)]);
+        let ext_curve = ExtensionCurve::new(&curve, field);
+
+        let base_point = Point::Coordinate(BigUint::from(2u32), BigUint::from(15u32));
+        assert!(curve.is_on_curve(&base_point));
+
+        // Lifted arithmetic agrees with the base field arithmetic
+        let lifted = ext_curve.lift(&base_point);
+        let five = BigUint::from(5u32);
+        assert_eq!(ext_curve.scalar_mult(&lifted, &five), ext_curve.lift(&curve.scalar_mult(&base_point, &five)));
+
+        let f = &ext_curve.field;
+        let i = f.from_coeffs(&[BigUint::from(0u32), BigUint::from(1u32)]);
+        let distorted = match &lifted {
+            ExtPoint::Coordinate(x, y) => ExtPoint::Coordinate(f.neg(x), f.mul(&i, y)),
+            ExtPoint::Identity => unreachable!(),
+        };
+        assert!(ext_curve.is_on_curve(&distorted));
+        assert!(ext_curve.is_on_curve(&ext_curve.add(&lifted, &distorted)));
+
+        // #E(F_43) = 44 for this supersingular curve
+        assert_eq!(ext_curve.scalar_mult(&lifted, &BigUint::from(44u32)), ExtPoint::Identity);
+        assert_eq!(ext_curve.add(&distorted, &ext_curve.neg(&distorted)), ExtPoint::Identity);
+    }
+}
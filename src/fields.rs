@@ -0,0 +1,2 @@
+// Field constructions beyond the prime field in lib.rs
+pub mod ext;
@@ -0,0 +1,378 @@
+use crate::FiniteField;
+use num_bigint::BigUint;
+
+// The pairing tower F_p² / F_p⁶ / F_p¹² built one step at a time
+//
+//   F_p²  = F_p[u]  / (u² - β)    β a quadratic non-residue in F_p
+//   F_p⁶  = F_p²[v] / (v³ - ξ)    ξ neither a square nor a cube in F_p²
+//   F_p¹² = F_p⁶[w] / (w² - v)    so w⁶ = ξ
+//
+// Unlike extension::ExtensionField (one flat polynomial modulus of any degree), each level
+// has small closed-form multiplication and inversion formulas that reuse the level below,
+// which is how pairing libraries lay out BN and BLS12 arithmetic.
+//
+// Frobenius x -> x^(p^k) is linear over F_p, so it only needs the images of u, v and w:
+//   u^(p^k) = β^((p^k - 1)/2) u = ±u     (conjugation for odd k)
+//   v^(p^k) = ξ^((p^k - 1)/3) v,   w^(p^k) = ξ^((p^k - 1)/6) w
+// The coefficients are precomputed for k = 0..11 as γ_k = φ(γ_{k-1}) γ_1, φ the F_p² Frobenius:
+//   ξ^((p^k - 1)/n) = (ξ^((p^(k-1) - 1)/n))^p ξ^((p - 1)/n)
+// Requires p ≡ 1 (mod 6) so those exponents are integers (true for BN and BLS12 primes)
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fp2Element {
+    pub c0: BigUint,
+    pub c1: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fp6Element {
+    pub c0: Fp2Element,
+    pub c1: Fp2Element,
+    pub c2: Fp2Element,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fp12Element {
+    pub c0: Fp6Element,
+    pub c1: Fp6Element,
+}
+
+#[derive(Clone, Debug)]
+pub struct Fp2 {
+    pub fp: FiniteField,
+    pub non_residue: BigUint,
+}
+
+impl Fp2 {
+    pub fn new(p: BigUint, non_residue: BigUint) -> Self {
+        Fp2 { fp: FiniteField { p }, non_residue }
+    }
+
+    pub fn element(&self, c0: &BigUint, c1: &BigUint) -> Fp2Element {
+        Fp2Element { c0: c0 % &self.fp.p, c1: c1 % &self.fp.p }
+    }
+
+    pub fn zero(&self) -> Fp2Element {
+        self.element(&BigUint::from(0u32), &BigUint::from(0u32))
+    }
+
+    pub fn one(&self) -> Fp2Element {
+        self.element(&BigUint::from(1u32), &BigUint::from(0u32))
+    }
+
+    pub fn is_zero(&self, x: &Fp2Element) -> bool {
+        *x == self.zero()
+    }
+
+    pub fn add(&self, x: &Fp2Element, y: &Fp2Element) -> Fp2Element {
+        Fp2Element { c0: self.fp.add(&x.c0, &y.c0), c1: self.fp.add(&x.c1, &y.c1) }
+    }
+
+    pub fn sub(&self, x: &Fp2Element, y: &Fp2Element) -> Fp2Element {
+        Fp2Element { c0: self.fp.sub(&x.c0, &y.c0), c1: self.fp.sub(&x.c1, &y.c1) }
+    }
+
+    pub fn neg(&self, x: &Fp2Element) -> Fp2Element {
+        self.sub(&self.zero(), x)
+    }
+
+    // (a0 + a1 u)(b0 + b1 u) = a0 b0 + β a1 b1 + (a0 b1 + a1 b0) u
+    pub fn mul(&self, x: &Fp2Element, y: &Fp2Element) -> Fp2Element {
+        let f = &self.fp;
+        Fp2Element {
+            c0: f.add(&f.mul(&x.c0, &y.c0), &f.mul(&self.non_residue, &f.mul(&x.c1, &y.c1))),
+            c1: f.add(&f.mul(&x.c0, &y.c1), &f.mul(&x.c1, &y.c0)),
+        }
+    }
+
+    pub fn scale(&self, x: &Fp2Element, c: &BigUint) -> Fp2Element {
+        Fp2Element { c0: self.fp.mul(&x.c0, c), c1: self.fp.mul(&x.c1, c) }
+    }
+
+    pub fn conjugate(&self, x: &Fp2Element) -> Fp2Element {
+        Fp2Element { c0: x.c0.clone(), c1: self.fp.sub(&BigUint::from(0u32), &x.c1) }
+    }
+
+    // 1 / (a0 + a1 u) = (a0 - a1 u) / (a0² - β a1²), the denominator being the norm
+    pub fn inv(&self, x: &Fp2Element) -> Fp2Element {
+        let f = &self.fp;
+        let norm = f.sub(&f.mul(&x.c0, &x.c0), &f.mul(&self.non_residue, &f.mul(&x.c1, &x.c1)));
+        if norm == BigUint::from(0u32) {
+            panic!("?division by zero in extension field?");
+        }
+        self.scale(&self.conjugate(x), &f.div(&BigUint::from(1u32), &norm))
+    }
+
+    pub fn div(&self, x: &Fp2Element, y: &Fp2Element) -> Fp2Element {
+        self.mul(x, &self.inv(y))
+    }
+
+    pub fn pow(&self, x: &Fp2Element, exponent: &BigUint) -> Fp2Element {
+        let mut result = self.one();
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, x);
+            }
+        }
+        result
+    }
+
+    // x^(p^power): conjugation an odd number of times
+    pub fn frobenius(&self, x: &Fp2Element, power: usize) -> Fp2Element {
+        if power % 2 == 1 { self.conjugate(x) } else { x.clone() }
+    }
+
+    // ξ^((p^k - 1)/n) for k = 0..count
+    fn frobenius_coefficients(&self, xi: &Fp2Element, n: u32, count: usize) -> Vec<Fp2Element> {
+        let p = &self.fp.p;
+        let first = self.pow(xi, &((p - BigUint::from(1u32)) / BigUint::from(n)));
+        let mut coefficients = vec![self.one()];
+        for k in 1..count {
+            let previous = self.frobenius(&coefficients[k - 1], 1);
+            coefficients.push(self.mul(&previous, &first));
+        }
+        coefficients
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Fp6 {
+    pub fp2: Fp2,
+    pub non_residue: Fp2Element,
+    // v^(p^k) = frobenius_v[k] v, (v²)^(p^k) = frobenius_v2[k] v²
+    frobenius_v: Vec<Fp2Element>,
+    frobenius_v2: Vec<Fp2Element>,
+}
+
+impl Fp6 {
+    pub fn new(fp2: Fp2, non_residue: Fp2Element) -> Self {
+        let frobenius_v = fp2.frobenius_coefficients(&non_residue, 3, 12);
+        let frobenius_v2 = frobenius_v.iter().map(|c| fp2.mul(c, c)).collect();
+        Fp6 { fp2, non_residue, frobenius_v, frobenius_v2 }
+    }
+
+    pub fn zero(&self) -> Fp6Element {
+        Fp6Element { c0: self.fp2.zero(), c1: self.fp2.zero(), c2: self.fp2.zero() }
+    }
+
+    pub fn one(&self) -> Fp6Element {
+        Fp6Element { c0: self.fp2.one(), c1: self.fp2.zero(), c2: self.fp2.zero() }
+    }
+
+    pub fn add(&self, x: &Fp6Element, y: &Fp6Element) -> Fp6Element {
+        let f = &self.fp2;
+        Fp6Element { c0: f.add(&x.c0, &y.c0), c1: f.add(&x.c1, &y.c1), c2: f.add(&x.c2, &y.c2) }
+    }
+
+    pub fn sub(&self, x: &Fp6Element, y: &Fp6Element) -> Fp6Element {
+        let f = &self.fp2;
+        Fp6Element { c0: f.sub(&x.c0, &y.c0), c1: f.sub(&x.c1, &y.c1), c2: f.sub(&x.c2, &y.c2) }
+    }
+
+    pub fn neg(&self, x: &Fp6Element) -> Fp6Element {
+        self.sub(&self.zero(), x)
+    }
+
+    fn mul_by_xi(&self, x: &Fp2Element) -> Fp2Element {
+        self.fp2.mul(x, &self.non_residue)
+    }
+
+    // Schoolbook product with v³ = ξ folding the high terms back down
+    pub fn mul(&self, x: &Fp6Element, y: &Fp6Element) -> Fp6Element {
+        let f = &self.fp2;
+        let (a0b0, a1b1, a2b2) = (f.mul(&x.c0, &y.c0), f.mul(&x.c1, &y.c1), f.mul(&x.c2, &y.c2));
+        let a1b2_a2b1 = f.add(&f.mul(&x.c1, &y.c2), &f.mul(&x.c2, &y.c1));
+        let a0b1_a1b0 = f.add(&f.mul(&x.c0, &y.c1), &f.mul(&x.c1, &y.c0));
+        let a0b2_a2b0 = f.add(&f.mul(&x.c0, &y.c2), &f.mul(&x.c2, &y.c0));
+        Fp6Element {
+            c0: f.add(&a0b0, &self.mul_by_xi(&a1b2_a2b1)),
+            c1: f.add(&a0b1_a1b0, &self.mul_by_xi(&a2b2)),
+            c2: f.add(&a0b2_a2b0, &a1b1),
+        }
+    }
+
+    // x v = ξ a2 + a0 v + a1 v²
+    pub fn mul_by_v(&self, x: &Fp6Element) -> Fp6Element {
+        Fp6Element { c0: self.mul_by_xi(&x.c2), c1: x.c0.clone(), c2: x.c1.clone() }
+    }
+
+    // Adjugate over the norm to F_p²:
+    //   A = a0² - ξ a1 a2,  B = ξ a2² - a0 a1,  C = a1² - a0 a2,  t = a0 A + ξ (a2 B + a1 C)
+    pub fn inv(&self, x: &Fp6Element) -> Fp6Element {
+        let f = &self.fp2;
+        let a = f.sub(&f.mul(&x.c0, &x.c0), &self.mul_by_xi(&f.mul(&x.c1, &x.c2)));
+        let b = f.sub(&self.mul_by_xi(&f.mul(&x.c2, &x.c2)), &f.mul(&x.c0, &x.c1));
+        let c = f.sub(&f.mul(&x.c1, &x.c1), &f.mul(&x.c0, &x.c2));
+        let t = f.add(&f.mul(&x.c0, &a), &self.mul_by_xi(&f.add(&f.mul(&x.c2, &b), &f.mul(&x.c1, &c))));
+        let t_inv = f.inv(&t);
+        Fp6Element { c0: f.mul(&a, &t_inv), c1: f.mul(&b, &t_inv), c2: f.mul(&c, &t_inv) }
+    }
+
+    pub fn frobenius(&self, x: &Fp6Element, power: usize) -> Fp6Element {
+        let f = &self.fp2;
+        let k = power % 6;
+        Fp6Element {
+            c0: f.frobenius(&x.c0, power),
+            c1: f.mul(&f.frobenius(&x.c1, power), &self.frobenius_v[k]),
+            c2: f.mul(&f.frobenius(&x.c2, power), &self.frobenius_v2[k]),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Fp12 {
+    pub fp6: Fp6,
+    // w^(p^k) = frobenius_w[k] w
+    frobenius_w: Vec<Fp2Element>,
+}
+
+impl Fp12 {
+    pub fn new(fp6: Fp6) -> Self {
+        let frobenius_w = fp6.fp2.frobenius_coefficients(&fp6.non_residue, 6, 12);
+        Fp12 { fp6, frobenius_w }
+    }
+
+    // The BLS12-381 tower: β = -1, ξ = 1 + u
+    pub fn bls12_381() -> Self {
+        let p = crate::bls12_381::fp().p;
+        let fp2 = Fp2::new(p.clone(), &p - BigUint::from(1u32));
+        let xi = fp2.one();
+        let xi = fp2.add(&xi, &fp2.element(&BigUint::from(0u32), &BigUint::from(1u32)));
+        Fp12::new(Fp6::new(fp2, xi))
+    }
+
+    pub fn zero(&self) -> Fp12Element {
+        Fp12Element { c0: self.fp6.zero(), c1: self.fp6.zero() }
+    }
+
+    pub fn one(&self) -> Fp12Element {
+        Fp12Element { c0: self.fp6.one(), c1: self.fp6.zero() }
+    }
+
+    pub fn add(&self, x: &Fp12Element, y: &Fp12Element) -> Fp12Element {
+        Fp12Element { c0: self.fp6.add(&x.c0, &y.c0), c1: self.fp6.add(&x.c1, &y.c1) }
+    }
+
+    pub fn sub(&self, x: &Fp12Element, y: &Fp12Element) -> Fp12Element {
+        Fp12Element { c0: self.fp6.sub(&x.c0, &y.c0), c1: self.fp6.sub(&x.c1, &y.c1) }
+    }
+
+    pub fn neg(&self, x: &Fp12Element) -> Fp12Element {
+        self.sub(&self.zero(), x)
+    }
+
+    // (a0 + a1 w)(b0 + b1 w) = a0 b0 + a1 b1 v + (a0 b1 + a1 b0) w
+    pub fn mul(&self, x: &Fp12Element, y: &Fp12Element) -> Fp12Element {
+        let f = &self.fp6;
+        Fp12Element {
+            c0: f.add(&f.mul(&x.c0, &y.c0), &f.mul_by_v(&f.mul(&x.c1, &y.c1))),
+            c1: f.add(&f.mul(&x.c0, &y.c1), &f.mul(&x.c1, &y.c0)),
+        }
+    }
+
+    pub fn conjugate(&self, x: &Fp12Element) -> Fp12Element {
+        Fp12Element { c0: x.c0.clone(), c1: self.fp6.neg(&x.c1) }
+    }
+
+    // 1 / (a0 + a1 w) = (a0 - a1 w) / (a0² - v a1²)
+    pub fn inv(&self, x: &Fp12Element) -> Fp12Element {
+        let f = &self.fp6;
+        let norm = f.sub(&f.mul(&x.c0, &x.c0), &f.mul_by_v(&f.mul(&x.c1, &x.c1)));
+        let norm_inv = f.inv(&norm);
+        Fp12Element { c0: f.mul(&x.c0, &norm_inv), c1: f.neg(&f.mul(&x.c1, &norm_inv)) }
+    }
+
+    pub fn div(&self, x: &Fp12Element, y: &Fp12Element) -> Fp12Element {
+        self.mul(x, &self.inv(y))
+    }
+
+    pub fn pow(&self, x: &Fp12Element, exponent: &BigUint) -> Fp12Element {
+        let mut result = self.one();
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, x);
+            }
+        }
+        result
+    }
+
+    pub fn frobenius(&self, x: &Fp12Element, power: usize) -> Fp12Element {
+        let f = &self.fp6;
+        let c1 = f.frobenius(&x.c1, power);
+        let gamma = &self.frobenius_w[power % 12];
+        let fp2 = &f.fp2;
+        Fp12Element {
+            c0: f.frobenius(&x.c0, power),
+            c1: Fp6Element { c0: fp2.mul(&c1.c0, gamma), c1: fp2.mul(&c1.c1, gamma), c2: fp2.mul(&c1.c2, gamma) },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bls12_381;
+    use crate::extension::ExtElement;
+    use num_bigint::RandBigInt;
+
+    fn random_fp12(field: &Fp12) -> Fp12Element {
+        let mut rng = crate::entropy::Entropy;
+        let fp2 = &field.fp6.fp2;
+        let p = &fp2.fp.p;
+        let mut random_fp2 = || fp2.element(&rng.gen_biguint_below(p), &rng.gen_biguint_below(p));
+        Fp12Element {
+            c0: Fp6Element { c0: random_fp2(), c1: random_fp2(), c2: random_fp2() },
+            c1: Fp6Element { c0: random_fp2(), c1: random_fp2(), c2: random_fp2() },
+        }
+    }
+
+    // The tower element in bls12_381's flat F_p[w] / (w¹² - 2w⁶ + 2), where u = w⁶ - 1 and v = w²
+    fn flatten(field: &Fp12, x: &Fp12Element) -> ExtElement {
+        let flat = bls12_381::fp12();
+        let p = &field.fp6.fp2.fp.p;
+        let mut coeffs = vec![BigUint::from(0u32); 12];
+        let parts = [&x.c0.c0, &x.c1.c0, &x.c0.c1, &x.c1.c1, &x.c0.c2, &x.c1.c2];
+        for (degree, part) in parts.iter().enumerate() {
+            coeffs[degree] += &part.c0 + p - &part.c1;
+            coeffs[degree + 6] += &part.c1;
+        }
+        let coeffs: Vec<BigUint> = coeffs.iter().map(|c| c % p).collect();
+        flat.from_coeffs(&coeffs)
+    }
+
+    #[test]
+    fn test_tower_matches_flat_field() {
+        let field = Fp12::bls12_381();
+        let flat = bls12_381::fp12();
+        let (x, y) = (random_fp12(&field), random_fp12(&field));
+        assert_eq!(flatten(&field, &field.mul(&x, &y)), flat.mul(&flatten(&field, &x), &flatten(&field, &y)));
+        assert_eq!(flatten(&field, &field.inv(&x)), flat.inv(&flatten(&field, &x)));
+    }
+
+    #[test]
+    fn test_inversion() {
+        let field = Fp12::bls12_381();
+        let x = random_fp12(&field);
+        assert_eq!(field.mul(&x, &field.inv(&x)), field.one());
+        let fp6 = &field.fp6;
+        assert_eq!(fp6.mul(&x.c0, &fp6.inv(&x.c0)), fp6.one());
+        let fp2 = &fp6.fp2;
+        assert_eq!(fp2.div(&x.c0.c0, &x.c0.c0), fp2.one());
+    }
+
+    #[test]
+    fn test_frobenius() {
+        let field = Fp12::bls12_381();
+        let p = field.fp6.fp2.fp.p.clone();
+        let x = random_fp12(&field);
+        assert_eq!(field.frobenius(&x, 1), field.pow(&x, &p));
+        assert_eq!(field.frobenius(&field.frobenius(&x, 5), 7), x);
+        assert_eq!(field.frobenius(&field.frobenius(&x, 2), 3), field.frobenius(&x, 5));
+        // The sixth power is conjugation over F_p⁶
+        assert_eq!(field.frobenius(&x, 6), field.conjugate(&x));
+        let fp6 = &field.fp6;
+        assert_eq!(fp6.frobenius(&x.c0, 1), field.pow(&Fp12Element { c0: x.c0.clone(), c1: fp6.zero() }, &p).c0);
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{EllipticCurve, FiniteField, Point};
+use crate::encoding::{decode_point, encode_point, to_fixed_bytes};
+use crate::schnorr::{Schnorr, SchnorrSignature};
+use crate::secret_sharing::{self, Share};
+use crate::zkp::Transcript;
+use num_bigint::BigUint;
+
+// FROST: flexible round-optimized threshold Schnorr signatures (Komlo–Goldberg, RFC 9591 shape)
+//
+// Any t of n share-holders produce an ordinary Schnorr signature (verified by Schnorr::verify)
+// under the group key Y = s * G, without anyone reconstructing s
+//   keygen:    s is Shamir-shared; party i holds s_i = f(i) and everyone knows Y_i = s_i * G
+//   round 1:   each signer commits to two nonces, D_i = d_i * G (hiding), E_i = e_i * G (binding)
+//   round 2:   ρ_i = H(i, Y, m, all commitments), R = Σ (D_i + ρ_i E_i), c = H(R || Y || m)
+//              z_i = d_i + e_i ρ_i + λ_i s_i c        (λ_i the Lagrange coefficient)
+//   aggregate: z = Σ z_i, signature (R, z), since z * G = R + c * Σ λ_i Y_i = R + c * Y
+// The binding factors tie every nonce to the full commitment list, which is what stops
+// a coalition from steering R by choosing its nonces after seeing the others'
+
+const BINDING_DOMAIN: &[u8] = b"FROST-binding-factor";
+
+// A participant's long-term key material
+#[derive(Clone, Debug)]
+pub struct KeyPackage {
+    pub identifier: u32,
+    pub signing_share: BigUint,
+    pub verifying_share: Point,
+    pub group_public_key: Point,
+    pub threshold: usize,
+}
+
+// What everyone (including the aggregator) knows about the keys
+#[derive(Clone, Debug)]
+pub struct PublicKeyPackage {
+    pub verifying_shares: Vec<(u32, Point)>,
+    pub group_public_key: Point,
+    pub commitments: Vec<Point>,
+}
+
+impl PublicKeyPackage {
+    pub fn verifying_share(&self, identifier: u32) -> Option<&Point> {
+        self.verifying_shares.iter().find(|(i, _)| *i == identifier).map(|(_, p)| p)
+    }
+}
+
+// Round-1 secret; consumed by sign so the nonces are never reused
+pub struct SigningNonces {
+    hiding: BigUint,
+    binding: BigUint,
+    commitments: SigningCommitments,
+}
+
+// Round-1 message: (i, D_i, E_i)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SigningCommitments {
+    pub identifier: u32,
+    pub hiding: Point,
+    pub binding: Point,
+}
+
+impl SigningCommitments {
+    // i (4 bytes) || D_i || E_i, points SEC1-compressed
+    pub fn to_bytes(&self, curve: &EllipticCurve) -> Vec<u8> {
+        let mut bytes = self.identifier.to_be_bytes().to_vec();
+        bytes.extend(encode_point(curve, &self.hiding, true));
+        bytes.extend(encode_point(curve, &self.binding, true));
+        bytes
+    }
+
+    pub fn from_bytes(curve: &EllipticCurve, bytes: &[u8]) -> Result<Self, &'static str> {
+        let point_len = 1 + curve.p().bits().div_ceil(8) as usize;
+        if bytes.len() != 4 + 2 * point_len {
+            return Err("invalid signing commitments length");
+        }
+        let commitments = SigningCommitments {
+            identifier: u32::from_be_bytes(bytes[..4].try_into().expect("?slice has length 4?")),
+            hiding: decode_point(curve, &bytes[4..4 + point_len])?,
+            binding: decode_point(curve, &bytes[4 + point_len..])?,
+        };
+        if commitments.hiding == Point::Identity || commitments.binding == Point::Identity {
+            return Err("nonce commitment is the point at infinity");
+        }
+        Ok(commitments)
+    }
+}
+
+// The coordinator's round-2 request: which signers, their commitments, and the message
+#[derive(Clone, Debug)]
+pub struct SigningPackage {
+    pub commitments: Vec<SigningCommitments>,
+    pub message: Vec<u8>,
+}
+
+// Round-2 message: (i, z_i)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignatureShare {
+    pub identifier: u32,
+    pub share: BigUint,
+}
+
+impl SignatureShare {
+    pub fn to_bytes(&self, order: &BigUint) -> Vec<u8> {
+        let mut bytes = self.identifier.to_be_bytes().to_vec();
+        bytes.extend(to_fixed_bytes(&self.share, order.bits().div_ceil(8) as usize));
+        bytes
+    }
+
+    pub fn from_bytes(order: &BigUint, bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != 4 + order.bits().div_ceil(8) as usize {
+            return Err("invalid signature share length");
+        }
+        let share = BigUint::from_bytes_be(&bytes[4..]);
+        if share >= *order {
+            return Err("signature share out of range");
+        }
+        Ok(SignatureShare {
+            identifier: u32::from_be_bytes(bytes[..4].try_into().expect("?slice has length 4?")),
+            share,
+        })
+    }
+}
+
+// Trusted dealer: picks s, Shamir-shares it and publishes Feldman commitments
+// The dealer sees s; use the DKG instead when no single party may know it
+pub fn trusted_dealer_keygen(scheme: &Schnorr, threshold: usize, max_signers: usize) -> Result<(Vec<KeyPackage>, PublicKeyPackage), &'static str> {
+    let secret = scheme.params.generate_private_key();
+    let (shares, coefficients) = secret_sharing::split(&secret, threshold, max_signers, &scheme.params.order)?;
+    let commitments = secret_sharing::commit_polynomial(&scheme.params.curve, &scheme.params.generator, &coefficients);
+    Ok(key_packages_from_shares(scheme, &shares, commitments, threshold))
+}
+
+// Key packages for shares of a polynomial with the given Feldman commitments
+pub fn key_packages_from_shares(scheme: &Schnorr, shares: &[Share], commitments: Vec<Point>, threshold: usize) -> (Vec<KeyPackage>, PublicKeyPackage) {
+    let group_public_key = commitments[0].clone();
+    let key_packages: Vec<KeyPackage> = shares
+        .iter()
+        .map(|share| KeyPackage {
+            identifier: share.identifier,
+            verifying_share: scheme.params.generate_public_key(&share.value),
+            signing_share: share.value.clone(),
+            group_public_key: group_public_key.clone(),
+            threshold,
+        })
+        .collect();
+    let public_package = PublicKeyPackage {
+        verifying_shares: key_packages.iter().map(|k| (k.identifier, k.verifying_share.clone())).collect(),
+        group_public_key,
+        commitments,
+    };
+    (key_packages, public_package)
+}
+
+// A participant checks its share against the dealer's commitments before using it
+pub fn verify_key_package(scheme: &Schnorr, key_package: &KeyPackage, public_package: &PublicKeyPackage) -> bool {
+    let share = Share { identifier: key_package.identifier, value: key_package.signing_share.clone() };
+    secret_sharing::verify_share(&scheme.params.curve, &scheme.params.generator, &share, &public_package.commitments, &scheme.params.order)
+        && key_package.group_public_key == public_package.group_public_key
+}
+
+// Round 1: fresh hiding and binding nonces
+pub fn commit(scheme: &Schnorr, key_package: &KeyPackage) -> (SigningNonces, SigningCommitments) {
+    let hiding = scheme.params.generate_private_key();
+    let binding = scheme.params.generate_private_key();
+    let commitments = SigningCommitments {
+        identifier: key_package.identifier,
+        hiding: scheme.params.generate_public_key(&hiding),
+        binding: scheme.params.generate_public_key(&binding),
+    };
+    (SigningNonces { hiding, binding, commitments: commitments.clone() }, commitments)
+}
+
+// Round 2: z_i = d_i + e_i ρ_i + λ_i s_i c
+pub fn sign(scheme: &Schnorr, package: &SigningPackage, nonces: SigningNonces, key_package: &KeyPackage) -> Result<SignatureShare, &'static str> {
+    if package.commitments.len() < key_package.threshold {
+        return Err("not enough signers");
+    }
+    let own = package
+        .commitments
+        .iter()
+        .find(|c| c.identifier == key_package.identifier)
+        .ok_or("signer is not part of the signing package")?;
+    if *own != nonces.commitments {
+        return Err("signing package has the wrong commitments for this signer");
+    }
+
+    let field = FiniteField { p: scheme.params.order.clone() };
+    let (group_commitment, binding_factors) = group_commitment(scheme, package, &key_package.group_public_key)?;
+    let challenge = scheme.challenge(&group_commitment, &key_package.group_public_key, &package.message);
+    let rho = binding_factor_for(&binding_factors, key_package.identifier);
+    let lambda = secret_sharing::lagrange_coefficient(key_package.identifier, &identifiers(package), &scheme.params.order)?;
+
+    let share = field.add(
+        &field.add(&nonces.hiding, &field.mul(&nonces.binding, rho)),
+        &field.mul(&field.mul(&lambda, &key_package.signing_share), &challenge),
+    );
+    Ok(SignatureShare { identifier: key_package.identifier, share })
+}
+
+// Check every share (z_i G = D_i + ρ_i E_i + λ_i c Y_i, so a cheater is identified), then z = Σ z_i
+pub fn aggregate(scheme: &Schnorr, package: &SigningPackage, shares: &[SignatureShare], public_package: &PublicKeyPackage) -> Result<SchnorrSignature, &'static str> {
+    if shares.len() != package.commitments.len() {
+        return Err("need exactly one share per committed signer");
+    }
+    let field = FiniteField { p: scheme.params.order.clone() };
+    let (group_commitment, binding_factors) = group_commitment(scheme, package, &public_package.group_public_key)?;
+    let challenge = scheme.challenge(&group_commitment, &public_package.group_public_key, &package.message);
+    let participants = identifiers(package);
+
+    let mut z = BigUint::from(0u32);
+    for share in shares {
+        let commitments = package
+            .commitments
+            .iter()
+            .find(|c| c.identifier == share.identifier)
+            .ok_or("share from a signer outside the signing package")?;
+        let verifying_share = public_package.verifying_share(share.identifier).ok_or("unknown signer")?;
+        let rho = binding_factor_for(&binding_factors, share.identifier);
+        let lambda = secret_sharing::lagrange_coefficient(share.identifier, &participants, &scheme.params.order)?;
+
+        let commitment_share = scheme.params.curve.add(&commitments.hiding, &scheme.params.curve.scalar_mult(&commitments.binding, rho));
+        let expected = scheme.params.curve.add(&commitment_share, &scheme.params.curve.scalar_mult(verifying_share, &field.mul(&lambda, &challenge)));
+        if scheme.params.generate_public_key(&share.share) != expected {
+            return Err("invalid signature share");
+        }
+        z = field.add(&z, &share.share);
+    }
+
+    Ok(SchnorrSignature { r: group_commitment, s: z })
+}
+
+// (i, ρ_i)
+type BindingFactor = (u32, BigUint);
+
+// R = Σ (D_i + ρ_i E_i) with ρ_i = H(domain, Y, m, commitment list, i)
+fn group_commitment(scheme: &Schnorr, package: &SigningPackage, group_public_key: &Point) -> Result<(Point, Vec<BindingFactor>), &'static str> {
+    let mut sorted = package.commitments.clone();
+    sorted.sort_by_key(|c| c.identifier);
+    if sorted.windows(2).any(|w| w[0].identifier == w[1].identifier) {
+        return Err("duplicate signer in signing package");
+    }
+
+    let mut transcript = Transcript::new(BINDING_DOMAIN);
+    transcript.append_point(b"Y", &scheme.params.curve, group_public_key);
+    transcript.append_message(b"message", &package.message);
+    for c in &sorted {
+        transcript.append_message(b"commitment", &c.to_bytes(&scheme.params.curve));
+    }
+
+    let mut r = Point::Identity;
+    let mut binding_factors = Vec::with_capacity(sorted.len());
+    for c in &sorted {
+        let mut per_signer = transcript.clone();
+        per_signer.append_message(b"identifier", &c.identifier.to_be_bytes());
+        let rho = per_signer.challenge_scalar(b"rho", &scheme.params.order);
+        r = scheme.params.curve.add(&r, &scheme.params.curve.add(&c.hiding, &scheme.params.curve.scalar_mult(&c.binding, &rho)));
+        binding_factors.push((c.identifier, rho));
+    }
+    if r == Point::Identity {
+        return Err("group commitment is the point at infinity");
+    }
+    Ok((r, binding_factors))
+}
+
+fn binding_factor_for(binding_factors: &[BindingFactor], identifier: u32) -> &BigUint {
+    &binding_factors.iter().find(|(i, _)| *i == identifier).expect("?binding factor for every signer?").1
+}
+
+fn identifiers(package: &SigningPackage) -> Vec<u32> {
+    package.commitments.iter().map(|c| c.identifier).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    fn run_signing(scheme: &Schnorr, signers: &[&KeyPackage], message: &[u8]) -> (SigningPackage, Vec<SignatureShare>) {
+        let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|k| commit(scheme, k)).unzip();
+        let package = SigningPackage { commitments, message: message.to_vec() };
+        let shares = nonces
+            .into_iter()
+            .zip(signers)
+            .map(|(n, k)| sign(scheme, &package, n, k).unwrap())
+            .collect();
+        (package, shares)
+    }
+
+    #[test]
+    fn test_frost_2_of_3() {
+        let scheme = curves::p256().schnorr();
+        let (keys, public_package) = trusted_dealer_keygen(&scheme, 2, 3).unwrap();
+        for key in &keys {
+            assert!(verify_key_package(&scheme, key, &public_package));
+        }
+
+        let message = b"release build v1.2.3";
+        for signers in [[&keys[0], &keys[1]], [&keys[2], &keys[0]]] {
+            let (package, shares) = run_signing(&scheme, &signers, message);
+            let signature = aggregate(&scheme, &package, &shares, &public_package).unwrap();
+            assert!(scheme.verify(message, &signature, &public_package.group_public_key));
+        }
+    }
+
+    #[test]
+    fn test_frost_detects_bad_share() {
+        let scheme = curves::p256().schnorr();
+        let (keys, public_package) = trusted_dealer_keygen(&scheme, 2, 3).unwrap();
+        let (package, mut shares) = run_signing(&scheme, &[&keys[0], &keys[1]], b"msg");
+
+        shares[1].share = (&shares[1].share + 1u32) % &scheme.params.order;
+        assert_eq!(aggregate(&scheme, &package, &shares, &public_package), Err("invalid signature share"));
+
+        // Below threshold, signing refuses
+        let (nonces, commitments) = commit(&scheme, &keys[0]);
+        let lonely = SigningPackage { commitments: vec![commitments], message: b"msg".to_vec() };
+        assert!(sign(&scheme, &lonely, nonces, &keys[0]).is_err());
+    }
+
+    #[test]
+    fn test_round_message_serialization() {
+        let scheme = curves::p256().schnorr();
+        let (keys, _) = trusted_dealer_keygen(&scheme, 2, 2).unwrap();
+        let (_, commitments) = commit(&scheme, &keys[1]);
+        let bytes = commitments.to_bytes(&scheme.params.curve);
+        assert_eq!(bytes.len(), 4 + 33 + 33);
+        assert_eq!(SigningCommitments::from_bytes(&scheme.params.curve, &bytes).unwrap(), commitments);
+
+        let share = SignatureShare { identifier: 2, share: BigUint::from(77u32) };
+        assert_eq!(SignatureShare::from_bytes(&scheme.params.order, &share.to_bytes(&scheme.params.order)).unwrap(), share);
+        assert!(SignatureShare::from_bytes(&scheme.params.order, &[0u8; 5]).is_err());
+    }
+}
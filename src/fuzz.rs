@@ -0,0 +1,28 @@
+use crate::ecdsa::ECDSASignature;
+use crate::Point;
+use arbitrary::{Arbitrary, Result, Unstructured};
+use num_bigint::BigUint;
+
+// Arbitrary impls (feature "arbitrary") for cargo-fuzz / libFuzzer targets
+// Coordinates and signature values are raw big-endian byte strings of any length, so most
+// points are off every curve and most signatures out of range: the structured-invalid inputs
+// a verifier or encoder has to survive. For valid values use the proptest strategies
+
+fn biguint(u: &mut Unstructured) -> Result<BigUint> {
+    Ok(BigUint::from_bytes_be(u.arbitrary::<&[u8]>()?))
+}
+
+impl<'a> Arbitrary<'a> for Point {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 16)? {
+            return Ok(Point::Identity);
+        }
+        Ok(Point::Coordinate(biguint(u)?, biguint(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ECDSASignature {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ECDSASignature { r: biguint(u)?, s: biguint(u)? })
+    }
+}
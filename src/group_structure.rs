@@ -0,0 +1,164 @@
+use crate::ntheory;
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+use std::collections::HashSet;
+
+// The abelian group E(F_p) is always Z_m × Z_n with m | n, and m | p - 1 as well: the Weil
+// pairing maps E[m] onto the m-th roots of unity, which only lie in F_p when m | p - 1. So most
+// curves are cyclic (m = 1) and the rest are close to it. Here n is the group's exponent, the
+// largest order of any point, found by running through every point, and m = #E / n. A basis is
+// returned too: P of order n and Q of order m with <P> ∩ <Q> = {O}, so every point is
+// uniquely aP + bQ. Point orders come from dividing the factored #E, so each point costs a few
+// scalar multiplications and fields up to 2^16 are fine. torsion_points(r) lists E(F_p)[r], the
+// points killed by r, the same way; it's a subgroup of Z_gcd(r, m) × Z_gcd(r, n), all of E[r]
+// only when r | m
+
+pub const MAX_MODULUS_BITS: u64 = 16;
+
+// E(F_p) ≅ Z_m × Z_n, generated by p (order n) and q (order m)
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupStructure {
+    pub m: BigUint,
+    pub n: BigUint,
+    pub p: Point,
+    pub q: Point,
+}
+
+impl GroupStructure {
+    pub fn is_cyclic(&self) -> bool {
+        self.m == BigUint::from(1u32)
+    }
+}
+
+impl EllipticCurve {
+    // Smallest divisor of the group order killing the point
+    fn order_dividing(&self, point: &Point, group_order: &BigUint, factors: &[(BigUint, u32)]) -> BigUint {
+        let mut order = group_order.clone();
+        for (prime, _) in factors {
+            while (&order % prime) == BigUint::from(0u32) && self.scalar_mult(point, &(&order / prime)) == Point::Identity {
+                order /= prime;
+            }
+        }
+        order
+    }
+
+    // E(F_p)[r]: O and every point with r * P = O, in points() order
+    pub fn torsion_points(&self, r: &BigUint) -> Result<Vec<Point>, &'static str> {
+        if self.p().bits() > MAX_MODULUS_BITS {
+            return Err("field too large to enumerate");
+        }
+        Ok(self.points().filter(|point| self.scalar_mult(point, r) == Point::Identity).collect())
+    }
+
+    pub fn group_structure(&self) -> Result<GroupStructure, &'static str> {
+        if self.p().bits() > MAX_MODULUS_BITS {
+            return Err("field too large to enumerate");
+        }
+        let group_order = self.count_points()?;
+        let factors = ntheory::factorize(&group_order);
+
+        let (mut p, mut n) = (Point::Identity, BigUint::from(1u32));
+        for point in self.points() {
+            let order = self.order_dividing(&point, &group_order, &factors);
+            if order > n {
+                (p, n) = (point, order);
+            }
+            if n == group_order {
+                break;
+            }
+        }
+        let m = &group_order / &n;
+
+        // Q: order m, with no multiple but O in <P>
+        let mut multiples = HashSet::new();
+        let mut multiple = Point::Identity;
+        loop {
+            multiple = self.add(&multiple, &p);
+            multiples.insert(multiple.clone());
+            if multiple == Point::Identity {
+                break;
+            }
+        }
+        let independent = |q: &Point| {
+            let mut multiple = q.clone();
+            while multiple != Point::Identity {
+                if multiples.contains(&multiple) {
+                    return false;
+                }
+                multiple = self.add(&multiple, q);
+            }
+            true
+        };
+        let q = self
+            .torsion_points(&m)?
+            .into_iter()
+            .find(|q| self.order_dividing(q, &group_order, &factors) == m && independent(q))
+            .ok_or("?no point completes the basis?")?;
+        Ok(GroupStructure { m, n, p, q })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_structure() {
+        // y² = x³ - x over F_103 has 104 points and full 2-torsion: Z_2 × Z_52
+        let p = BigUint::from(103u32);
+        let curve = EllipticCurve::new(&p - 1u32, BigUint::from(0u32), p.clone());
+        let structure = curve.group_structure().unwrap();
+        assert_eq!((structure.m.clone(), structure.n.clone()), (BigUint::from(2u32), BigUint::from(52u32)));
+        assert!(!structure.is_cyclic());
+
+        // Every point is exactly one aP + bQ
+        let mut combinations = HashSet::new();
+        let mut a_p = Point::Identity;
+        for _ in 0..52 {
+            combinations.insert(a_p.clone());
+            combinations.insert(curve.add(&a_p, &structure.q));
+            a_p = curve.add(&a_p, &structure.p);
+        }
+        assert_eq!(combinations.len(), 104);
+        assert!(curve.points().all(|point| combinations.contains(&point)));
+
+        // Prime order means cyclic, with Q = O
+        let demo = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let structure = demo.group_structure().unwrap();
+        assert!(structure.is_cyclic());
+        assert_eq!((structure.n, structure.q), (BigUint::from(19u32), Point::Identity));
+    }
+
+    #[test]
+    fn test_torsion_points() {
+        // y² = x³ - x over F_103, Z_2 × Z_52: E[2] is all four 2-torsion points, and E(F_p)[4]
+        // is Z_2 × Z_4, as 4 divides n but not m
+        let p = BigUint::from(103u32);
+        let curve = EllipticCurve::new(&p - 1u32, BigUint::from(0u32), p.clone());
+        let two_torsion = curve.torsion_points(&BigUint::from(2u32)).unwrap();
+        let expected: Vec<Point> = [0u32, 1, 102].iter().map(|x| Point::Coordinate(BigUint::from(*x), BigUint::from(0u32))).collect();
+        assert_eq!(two_torsion[0], Point::Identity);
+        assert_eq!(&two_torsion[1..], &expected[..]);
+        assert_eq!(curve.torsion_points(&BigUint::from(4u32)).unwrap().len(), 8);
+        assert_eq!(curve.torsion_points(&BigUint::from(13u32)).unwrap().len(), 13);
+        assert_eq!(curve.torsion_points(&BigUint::from(5u32)).unwrap(), vec![Point::Identity]);
+        assert!(crate::curves::p256().curve.torsion_points(&BigUint::from(2u32)).is_err());
+    }
+
+    #[test]
+    fn test_m_divides_p_minus_one() {
+        for p in [13u32, 31, 37, 61] {
+            let field = BigUint::from(p);
+            for a in 0..p {
+                for b in [1u32, 2, 5] {
+                    let curve = EllipticCurve::new(BigUint::from(a), BigUint::from(b), field.clone());
+                    let Ok(structure) = curve.group_structure() else { continue };
+                    assert_eq!(&structure.m * &structure.n, curve.count_points().unwrap());
+                    assert_eq!(&structure.n % &structure.m, BigUint::from(0u32));
+                    assert_eq!(BigUint::from(p - 1) % &structure.m, BigUint::from(0u32));
+                }
+            }
+        }
+        assert!(crate::curves::p256().curve.group_structure().is_err());
+    }
+}
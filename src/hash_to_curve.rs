@@ -0,0 +1,208 @@
+use crate::{EllipticCurve, Point};
+use crate::curves::{self, NamedCurve};
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+// Hashing to elliptic curves (RFC 9380) with expand_message_xmd(SHA-256)
+// and the simplified SWU map, for curves with a, b != 0 and cofactor 1 (P-256)
+//
+//   hash_to_curve(msg)   = map(u0) + map(u1)   uniform in the group (random oracle)
+//   encode_to_curve(msg) = map(u0)             cheaper, but only covers about half the points
+//
+// Unlike try-and-increment the number of field operations doesn't depend on the input
+
+const SHA256_BLOCK: usize = 64;
+const SHA256_OUTPUT: usize = 32;
+
+// expand_message_xmd: stretch msg into len_in_bytes pseudorandom bytes bound to the DST
+//   b0 = H(Z_pad || msg || l_i_b_str || 0 || DST'),  b1 = H(b0 || 1 || DST')
+//   b_i = H((b0 xor b_(i-1)) || i || DST')
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, &'static str> {
+    let ell = len_in_bytes.div_ceil(SHA256_OUTPUT);
+    if ell > 255 || len_in_bytes > 65535 {
+        return Err("requested output too long for expand_message_xmd");
+    }
+    if dst.len() > 255 {
+        return Err("domain separation tag longer than 255 bytes");
+    }
+
+    let mut dst_prime = dst.to_vec();
+    dst_prime.push(dst.len() as u8);
+
+    let b0 = Sha256::new()
+        .chain_update([0u8; SHA256_BLOCK])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    let mut b_i = Sha256::new().chain_update(b0).chain_update([1u8]).chain_update(&dst_prime).finalize();
+    let mut uniform_bytes = b_i.to_vec();
+    for i in 2..=ell {
+        let mixed: Vec<u8> = b0.iter().zip(b_i.iter()).map(|(x, y)| x ^ y).collect();
+        b_i = Sha256::new().chain_update(mixed).chain_update([i as u8]).chain_update(&dst_prime).finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+// hash_to_field: count elements of Z/m, each from L bytes (L = ceil((bits(m) + k) / 8)
+// with k = 128 bits of security) so the bias of the reduction is negligible
+pub fn hash_to_field(msg: &[u8], dst: &[u8], count: usize, modulus: &BigUint, l: usize) -> Result<Vec<BigUint>, &'static str> {
+    let uniform_bytes = expand_message_xmd(msg, dst, count * l)?;
+    Ok(uniform_bytes.chunks(l).map(|chunk| BigUint::from_bytes_be(chunk) % modulus).collect())
+}
+
+// Try-and-increment: x = H(dst || counter || msg) mod p until x³ + ax + b is a square,
+// then clear the cofactor. Works on any short Weierstrass curve (including toy ones), but the
+// running time depends on the input and the output doesn't match RFC 9380 suites
+pub fn try_and_increment(curve: &EllipticCurve, cofactor: &BigUint, msg: &[u8], dst: &[u8]) -> Point {
+    let field = curve.field();
+    let mut counter = 0u32;
+    loop {
+        // 512 bits of hash output reduced mod p: negligible bias for p up to 384 bits
+        let mut wide = Vec::with_capacity(64);
+        for block in 0u8..2 {
+            let digest = Sha256::new()
+                .chain_update(dst)
+                .chain_update(counter.to_be_bytes())
+                .chain_update([block])
+                .chain_update(msg)
+                .finalize();
+            wide.extend_from_slice(&digest);
+        }
+        let x = BigUint::from_bytes_be(&wide) % &field.p;
+        counter += 1;
+
+        let rhs = field.add(&field.add(&field.mul(&field.mul(&x, &x), &x), &field.mul(curve.a(), &x)), curve.b());
+        if let Some(y) = field.sqrt(&rhs) {
+            // Pick one of ±y from a hash bit so both roots are reachable
+            let y = if wide[63] & 1 == 1 { field.sub(&BigUint::from(0u32), &y) } else { y };
+            let point = curve.scalar_mult(&Point::Coordinate(x, y), cofactor);
+            if point != Point::Identity {
+                return point;
+            }
+        }
+    }
+}
+
+// Simplified SWU parameters for one curve
+pub struct HashToCurve {
+    pub curve: EllipticCurve,
+    pub z: BigUint,
+    pub l: usize,
+}
+
+impl HashToCurve {
+    // P256_XMD:SHA-256_SSWU_RO_ / _NU_ (Z = -10, L = 48)
+    pub fn p256() -> Self {
+        let NamedCurve { curve, .. } = curves::p256();
+        HashToCurve {
+            z: curve.p() - BigUint::from(10u32),
+            curve,
+            l: 48,
+        }
+    }
+
+    // Uniform hash to the curve group
+    pub fn hash_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<Point, &'static str> {
+        let u = hash_to_field(msg, dst, 2, self.curve.p(), self.l)?;
+        Ok(self.curve.add(&self.map_to_curve(&u[0]), &self.map_to_curve(&u[1])))
+    }
+
+    // Nonuniform encoding: a single map evaluation
+    pub fn encode_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<Point, &'static str> {
+        let u = hash_to_field(msg, dst, 1, self.curve.p(), self.l)?;
+        Ok(self.map_to_curve(&u[0]))
+    }
+
+    // Simplified SWU (RFC 9380, 6.6.2)
+    //   tv1 = 1 / (Z² u⁴ + Z u²)   (0 if the denominator vanishes)
+    //   x1 = (-B / A)(1 + tv1),  or B / (Z A) when tv1 = 0
+    //   if g(x1) is square: (x1, √g(x1)), else x2 = Z u² x1 and (x2, √g(x2))
+    // and y takes the sign of u
+    pub fn map_to_curve(&self, u: &BigUint) -> Point {
+        let field = self.curve.field();
+        let zero = BigUint::from(0u32);
+        let one = BigUint::from(1u32);
+        let (a, b, z) = (self.curve.a(), self.curve.b(), &self.z);
+
+        let u_squared = field.mul(u, u);
+        let z_u_squared = field.mul(z, &u_squared);
+        let denominator = field.add(&field.mul(&z_u_squared, &z_u_squared), &z_u_squared);
+
+        let x1 = if denominator == zero {
+            field.div(b, &field.mul(z, a))
+        } else {
+            let tv1 = field.div(&one, &denominator);
+            field.mul(&field.div(&field.sub(&zero, b), a), &field.add(&one, &tv1))
+        };
+
+        let g = |x: &BigUint| field.add(&field.add(&field.mul(&field.mul(x, x), x), &field.mul(a, x)), b);
+        let x = if field.is_quadratic_residue(&g(&x1)) { x1 } else { field.mul(&z_u_squared, &x1) };
+        let y = field.sqrt(&g(&x)).expect("?Z is not a valid SSWU parameter for this curve?");
+
+        // sgn0: parity of the canonical representative
+        let y = if u.bit(0) == y.bit(0) { y } else { field.sub(&zero, &y) };
+        Point::Coordinate(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_hex;
+
+    fn hex(s: &str) -> BigUint {
+        BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+    }
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // RFC 9380, K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let out = expand_message_xmd(b"", dst, 0x20).unwrap();
+        assert_eq!(to_hex(&out), "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235");
+        assert_eq!(expand_message_xmd(b"abc", dst, 100).unwrap().len(), 100);
+        assert!(expand_message_xmd(b"", dst, 256 * 32).is_err());
+    }
+
+    #[test]
+    fn test_hash_to_curve_p256() {
+        // RFC 9380, J.1.1: P256_XMD:SHA-256_SSWU_RO_, msg = ""
+        let h2c = HashToCurve::p256();
+        let point = h2c.hash_to_curve(b"", b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_").unwrap();
+        assert_eq!(
+            point,
+            Point::Coordinate(
+                hex("2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4"),
+                hex("8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415"),
+            )
+        );
+    }
+
+    #[test]
+    fn test_map_to_curve_lands_on_curve() {
+        let h2c = HashToCurve::p256();
+        for u in [0u32, 1, 2, 12345] {
+            assert!(h2c.curve.is_on_curve(&h2c.map_to_curve(&BigUint::from(u))));
+        }
+        let encoded = h2c.encode_to_curve(b"abc", b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_NU_").unwrap();
+        assert!(h2c.curve.is_on_curve(&encoded));
+    }
+
+    #[test]
+    fn test_try_and_increment() {
+        // y² = x³ + x + 1 (mod 23): 28 = 4 * 7 points, cofactor 4
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let cofactor = BigUint::from(4u32);
+        for msg in [&b"a"[..], b"b", b"c"] {
+            let point = try_and_increment(&curve, &cofactor, msg, b"TEST");
+            assert!(curve.is_on_curve(&point));
+            assert_eq!(curve.scalar_mult(&point, &BigUint::from(7u32)), Point::Identity);
+        }
+        assert_eq!(try_and_increment(&curve, &cofactor, b"a", b"TEST"), try_and_increment(&curve, &cofactor, b"a", b"TEST"));
+    }
+}
@@ -0,0 +1,603 @@
+use crate::Point;
+use crate::curves::{self, DomainParams, NamedCurve};
+use crate::ed25519::Ed25519;
+use crate::encoding::{self, to_fixed_bytes};
+use hmac::{Hmac, Mac};
+use num_bigint::BigUint;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+
+// BIP-32 hierarchical deterministic keys on secp256k1, and SLIP-0010 for P-256 and Ed25519
+//
+// An extended key is a key plus a 32-byte chain code. Children are derived with
+//   I = HMAC-SHA512(chain code, data || index),  child key = IL + k (mod n), child chain = IR
+// where data is 0x00 || k for hardened indices (≥ 2³¹) and the compressed public key otherwise.
+// Since IL * G + K = (IL + k) * G, normal children of a public key can be derived without the
+// private key; hardened ones can't, and a leaked normal child private key plus the parent
+// xpub reveals the parent private key, which is why accounts sit behind hardened levels
+
+pub const HARDENED: u32 = 0x8000_0000;
+
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtendedPrivateKey {
+    pub private_key: BigUint,
+    pub chain_code: [u8; 32],
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtendedPublicKey {
+    pub public_key: Point,
+    pub chain_code: [u8; 32],
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+}
+
+// "m/44'/0'/0'/0/0" -> [44 + 2³¹, 2³¹, 2³¹, 0, 0]; h and H also mark hardened indices
+pub fn parse_path(path: &str) -> Result<Vec<u32>, &'static str> {
+    let mut parts = path.split('/');
+    if !matches!(parts.next(), Some("m") | Some("M")) {
+        return Err("derivation path must start with m");
+    }
+    parts
+        .map(|part| {
+            let (digits, hardened) = match part.strip_suffix(['\'', 'h', 'H']) {
+                Some(digits) => (digits, true),
+                None => (part, false),
+            };
+            if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+                return Err("invalid derivation path index");
+            }
+            let index: u32 = digits.parse().map_err(|_| "derivation path index too large")?;
+            if index >= HARDENED {
+                return Err("derivation path index too large");
+            }
+            Ok(if hardened { index + HARDENED } else { index })
+        })
+        .collect()
+}
+
+// Inverse of parse_path, using ' for hardened indices
+pub fn format_path(indices: &[u32]) -> String {
+    let mut path = String::from("m");
+    for &index in indices {
+        if index >= HARDENED {
+            path.push_str(&format!("/{}'", index - HARDENED));
+        } else {
+            path.push_str(&format!("/{}", index));
+        }
+    }
+    path
+}
+
+// HASH160 = RIPEMD160(SHA256(data)); a key's fingerprint is the first 4 bytes of its HASH160
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("?HMAC accepts keys of any length?");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+pub struct Bip32 {
+    pub params: DomainParams,
+}
+
+impl Bip32 {
+    pub fn new() -> Self {
+        Bip32 { params: curves::secp256k1().params() }
+    }
+
+    // Master key from a seed (16 to 64 bytes): I = HMAC-SHA512("Bitcoin seed", seed)
+    pub fn master_key(&self, seed: &[u8]) -> Result<ExtendedPrivateKey, &'static str> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err("seed must be 16 to 64 bytes");
+        }
+        let i = hmac_sha512(b"Bitcoin seed", seed);
+        let private_key = BigUint::from_bytes_be(&i[..32]);
+        if private_key == BigUint::from(0u32) || private_key >= self.params.order {
+            return Err("seed gives an invalid master key");
+        }
+        Ok(ExtendedPrivateKey {
+            private_key,
+            chain_code: i[32..].try_into().unwrap(),
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+        })
+    }
+
+    // CKDpriv: hardened or normal child of a private key
+    // An invalid IL (probability ~2⁻¹²⁷) is an error; BIP-32 says to move on to the next index
+    pub fn derive_private_child(&self, parent: &ExtendedPrivateKey, index: u32) -> Result<ExtendedPrivateKey, &'static str> {
+        let depth = parent.depth.checked_add(1).ok_or("maximum derivation depth reached")?;
+        let parent_public = self.params.generate_public_key(&parent.private_key);
+        let mut data = if index >= HARDENED {
+            let mut data = vec![0u8];
+            data.extend(to_fixed_bytes(&parent.private_key, 32));
+            data
+        } else {
+            encoding::encode_point(&self.params.curve, &parent_public, true)
+        };
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let i = hmac_sha512(&parent.chain_code, &data);
+        let tweak = BigUint::from_bytes_be(&i[..32]);
+        if tweak >= self.params.order {
+            return Err("derived tweak out of range; use the next index");
+        }
+        let private_key = (tweak + &parent.private_key) % &self.params.order;
+        if private_key == BigUint::from(0u32) {
+            return Err("derived key is zero; use the next index");
+        }
+        Ok(ExtendedPrivateKey {
+            private_key,
+            chain_code: i[32..].try_into().unwrap(),
+            depth,
+            parent_fingerprint: self.fingerprint(&parent_public),
+            child_number: index,
+        })
+    }
+
+    // CKDpub: normal child of a public key, K_child = IL * G + K
+    pub fn derive_public_child(&self, parent: &ExtendedPublicKey, index: u32) -> Result<ExtendedPublicKey, &'static str> {
+        if index >= HARDENED {
+            return Err("hardened children need the private key");
+        }
+        let depth = parent.depth.checked_add(1).ok_or("maximum derivation depth reached")?;
+        let mut data = encoding::encode_point(&self.params.curve, &parent.public_key, true);
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let i = hmac_sha512(&parent.chain_code, &data);
+        let tweak = BigUint::from_bytes_be(&i[..32]);
+        if tweak >= self.params.order {
+            return Err("derived tweak out of range; use the next index");
+        }
+        let public_key = self.params.curve.add(&self.params.generate_public_key(&tweak), &parent.public_key);
+        if public_key == Point::Identity {
+            return Err("derived key is the point at infinity; use the next index");
+        }
+        Ok(ExtendedPublicKey {
+            public_key,
+            chain_code: i[32..].try_into().unwrap(),
+            depth,
+            parent_fingerprint: self.fingerprint(&parent.public_key),
+            child_number: index,
+        })
+    }
+
+    // N(k): the extended public key with the same chain code and metadata
+    pub fn neuter(&self, key: &ExtendedPrivateKey) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            public_key: self.params.generate_public_key(&key.private_key),
+            chain_code: key.chain_code,
+            depth: key.depth,
+            parent_fingerprint: key.parent_fingerprint,
+            child_number: key.child_number,
+        }
+    }
+
+    pub fn derive_path(&self, key: &ExtendedPrivateKey, path: &str) -> Result<ExtendedPrivateKey, &'static str> {
+        parse_path(path)?
+            .into_iter()
+            .try_fold(key.clone(), |key, index| self.derive_private_child(&key, index))
+    }
+
+    // Public derivation along a path with no hardened steps
+    pub fn derive_public_path(&self, key: &ExtendedPublicKey, path: &str) -> Result<ExtendedPublicKey, &'static str> {
+        parse_path(path)?
+            .into_iter()
+            .try_fold(key.clone(), |key, index| self.derive_public_child(&key, index))
+    }
+
+    pub fn fingerprint(&self, public_key: &Point) -> [u8; 4] {
+        let hash = hash160(&encoding::encode_point(&self.params.curve, public_key, true));
+        hash[..4].try_into().unwrap()
+    }
+
+    // 78 bytes: version || depth || parent fingerprint || child number || chain code || key
+    pub fn serialize_private(&self, key: &ExtendedPrivateKey) -> String {
+        let mut key_data = vec![0u8];
+        key_data.extend(to_fixed_bytes(&key.private_key, 32));
+        encoding::to_base58check(&serialize(XPRV_VERSION, key.depth, key.parent_fingerprint, key.child_number, &key.chain_code, &key_data))
+    }
+
+    pub fn serialize_public(&self, key: &ExtendedPublicKey) -> String {
+        let key_data = encoding::encode_point(&self.params.curve, &key.public_key, true);
+        encoding::to_base58check(&serialize(XPUB_VERSION, key.depth, key.parent_fingerprint, key.child_number, &key.chain_code, &key_data))
+    }
+
+    pub fn parse_private(&self, s: &str) -> Result<ExtendedPrivateKey, &'static str> {
+        let (version, fields) = deserialize(s)?;
+        if version != XPRV_VERSION {
+            return Err("not an xprv");
+        }
+        if fields.key_data[0] != 0 {
+            return Err("private key data must start with 0x00");
+        }
+        let private_key = BigUint::from_bytes_be(&fields.key_data[1..]);
+        if private_key == BigUint::from(0u32) || private_key >= self.params.order {
+            return Err("private key out of range");
+        }
+        Ok(ExtendedPrivateKey {
+            private_key,
+            chain_code: fields.chain_code,
+            depth: fields.depth,
+            parent_fingerprint: fields.parent_fingerprint,
+            child_number: fields.child_number,
+        })
+    }
+
+    pub fn parse_public(&self, s: &str) -> Result<ExtendedPublicKey, &'static str> {
+        let (version, fields) = deserialize(s)?;
+        if version != XPUB_VERSION {
+            return Err("not an xpub");
+        }
+        if !matches!(fields.key_data[0], 0x02 | 0x03) {
+            return Err("public key must be compressed");
+        }
+        Ok(ExtendedPublicKey {
+            public_key: encoding::decode_point(&self.params.curve, &fields.key_data)?,
+            chain_code: fields.chain_code,
+            depth: fields.depth,
+            parent_fingerprint: fields.parent_fingerprint,
+            child_number: fields.child_number,
+        })
+    }
+}
+
+impl Default for Bip32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SLIP-0010: the same derivation for other curves
+//   each curve has its own master HMAC key ("Bitcoin seed", "Nist256p1 seed", "ed25519 seed")
+//   an invalid IL is retried instead of failing: the master rehashes I, a child uses
+//   I = HMAC-SHA512(c, 0x01 || IR || index) until IL is a valid key
+//   Ed25519 keys are the 32-byte secret IL itself, not IL + k, so only hardened children exist
+//   public keys are 33 bytes: a compressed point, or 0x00 || the Ed25519 key
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Slip10Curve {
+    Secp256k1,
+    Nist256p1,
+    Ed25519,
+}
+
+enum Slip10Group {
+    Weierstrass(NamedCurve),
+    Edwards(Ed25519),
+}
+
+pub struct Slip10 {
+    pub curve: Slip10Curve,
+    group: Slip10Group,
+}
+
+impl Slip10 {
+    pub fn new(curve: Slip10Curve) -> Self {
+        let group = match curve {
+            Slip10Curve::Secp256k1 => Slip10Group::Weierstrass(curves::secp256k1()),
+            Slip10Curve::Nist256p1 => Slip10Group::Weierstrass(curves::p256()),
+            Slip10Curve::Ed25519 => Slip10Group::Edwards(Ed25519::new()),
+        };
+        Slip10 { curve, group }
+    }
+
+    fn seed_key(&self) -> &'static [u8] {
+        match self.curve {
+            Slip10Curve::Secp256k1 => b"Bitcoin seed",
+            Slip10Curve::Nist256p1 => b"Nist256p1 seed",
+            Slip10Curve::Ed25519 => b"ed25519 seed",
+        }
+    }
+
+    pub fn master_key(&self, seed: &[u8]) -> Result<ExtendedPrivateKey, &'static str> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err("seed must be 16 to 64 bytes");
+        }
+        let mut i = hmac_sha512(self.seed_key(), seed);
+        if let Slip10Group::Weierstrass(named) = &self.group {
+            loop {
+                let candidate = BigUint::from_bytes_be(&i[..32]);
+                if candidate != BigUint::from(0u32) && candidate < named.order {
+                    break;
+                }
+                i = hmac_sha512(self.seed_key(), &i);
+            }
+        }
+        Ok(ExtendedPrivateKey {
+            private_key: BigUint::from_bytes_be(&i[..32]),
+            chain_code: i[32..].try_into().unwrap(),
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+        })
+    }
+
+    pub fn derive_private_child(&self, parent: &ExtendedPrivateKey, index: u32) -> Result<ExtendedPrivateKey, &'static str> {
+        let depth = parent.depth.checked_add(1).ok_or("maximum derivation depth reached")?;
+        let parent_public = self.public_key_bytes(&parent.private_key);
+        let mut data = if index >= HARDENED {
+            let mut data = vec![0u8];
+            data.extend(to_fixed_bytes(&parent.private_key, 32));
+            data
+        } else {
+            if self.curve == Slip10Curve::Ed25519 {
+                return Err("ed25519 only supports hardened derivation");
+            }
+            parent_public.clone()
+        };
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let (private_key, chain_code) = loop {
+            let i = hmac_sha512(&parent.chain_code, &data);
+            let tweak = BigUint::from_bytes_be(&i[..32]);
+            let chain_code: [u8; 32] = i[32..].try_into().unwrap();
+            match &self.group {
+                Slip10Group::Edwards(_) => break (tweak, chain_code),
+                Slip10Group::Weierstrass(named) => {
+                    let child = (&tweak + &parent.private_key) % &named.order;
+                    if tweak < named.order && child != BigUint::from(0u32) {
+                        break (child, chain_code);
+                    }
+                }
+            }
+            data = retry_data(&chain_code, index);
+        };
+        Ok(ExtendedPrivateKey {
+            private_key,
+            chain_code,
+            depth,
+            parent_fingerprint: hash160(&parent_public)[..4].try_into().unwrap(),
+            child_number: index,
+        })
+    }
+
+    // Public derivation, Weierstrass curves only
+    pub fn derive_public_child(&self, parent: &ExtendedPublicKey, index: u32) -> Result<ExtendedPublicKey, &'static str> {
+        let Slip10Group::Weierstrass(named) = &self.group else {
+            return Err("ed25519 has no public derivation");
+        };
+        if index >= HARDENED {
+            return Err("hardened children need the private key");
+        }
+        let depth = parent.depth.checked_add(1).ok_or("maximum derivation depth reached")?;
+        let parent_public = encoding::encode_point(&named.curve, &parent.public_key, true);
+        let mut data = parent_public.clone();
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let (public_key, chain_code) = loop {
+            let i = hmac_sha512(&parent.chain_code, &data);
+            let tweak = BigUint::from_bytes_be(&i[..32]);
+            let chain_code: [u8; 32] = i[32..].try_into().unwrap();
+            if tweak < named.order {
+                let child = named.curve.add(&named.curve.scalar_mult(&named.generator, &tweak), &parent.public_key);
+                if child != Point::Identity {
+                    break (child, chain_code);
+                }
+            }
+            data = retry_data(&chain_code, index);
+        };
+        Ok(ExtendedPublicKey {
+            public_key,
+            chain_code,
+            depth,
+            parent_fingerprint: hash160(&parent_public)[..4].try_into().unwrap(),
+            child_number: index,
+        })
+    }
+
+    pub fn neuter(&self, key: &ExtendedPrivateKey) -> Result<ExtendedPublicKey, &'static str> {
+        let Slip10Group::Weierstrass(named) = &self.group else {
+            return Err("ed25519 keys have no extended public form");
+        };
+        Ok(ExtendedPublicKey {
+            public_key: named.curve.scalar_mult(&named.generator, &key.private_key),
+            chain_code: key.chain_code,
+            depth: key.depth,
+            parent_fingerprint: key.parent_fingerprint,
+            child_number: key.child_number,
+        })
+    }
+
+    pub fn derive_path(&self, key: &ExtendedPrivateKey, path: &str) -> Result<ExtendedPrivateKey, &'static str> {
+        parse_path(path)?
+            .into_iter()
+            .try_fold(key.clone(), |key, index| self.derive_private_child(&key, index))
+    }
+
+    // 33-byte public key: compressed point, or 0x00 || Ed25519 public key
+    pub fn public_key_bytes(&self, private_key: &BigUint) -> Vec<u8> {
+        match &self.group {
+            Slip10Group::Weierstrass(named) => {
+                encoding::encode_point(&named.curve, &named.curve.scalar_mult(&named.generator, private_key), true)
+            }
+            Slip10Group::Edwards(ed) => {
+                let secret: [u8; 32] = to_fixed_bytes(private_key, 32).try_into().unwrap();
+                let mut bytes = vec![0u8];
+                bytes.extend_from_slice(&ed.public_key(&secret));
+                bytes
+            }
+        }
+    }
+}
+
+// Retry input after an invalid IL: 0x01 || IR || index
+fn retry_data(chain_code: &[u8; 32], index: u32) -> Vec<u8> {
+    let mut data = vec![1u8];
+    data.extend_from_slice(chain_code);
+    data.extend_from_slice(&index.to_be_bytes());
+    data
+}
+
+// The version-independent part of a serialized extended key
+struct SerializedFields {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    key_data: Vec<u8>,
+}
+
+fn serialize(version: [u8; 4], depth: u8, parent_fingerprint: [u8; 4], child_number: u32, chain_code: &[u8; 32], key_data: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(78);
+    bytes.extend_from_slice(&version);
+    bytes.push(depth);
+    bytes.extend_from_slice(&parent_fingerprint);
+    bytes.extend_from_slice(&child_number.to_be_bytes());
+    bytes.extend_from_slice(chain_code);
+    bytes.extend_from_slice(key_data);
+    bytes
+}
+
+fn deserialize(s: &str) -> Result<([u8; 4], SerializedFields), &'static str> {
+    let bytes = encoding::from_base58check(s)?;
+    if bytes.len() != 78 {
+        return Err("extended keys are 78 bytes");
+    }
+    let fields = SerializedFields {
+        depth: bytes[4],
+        parent_fingerprint: bytes[5..9].try_into().unwrap(),
+        child_number: u32::from_be_bytes(bytes[9..13].try_into().unwrap()),
+        chain_code: bytes[13..45].try_into().unwrap(),
+        key_data: bytes[45..].to_vec(),
+    };
+    // A master key has no parent and is child 0
+    if fields.depth == 0 && (fields.parent_fingerprint != [0; 4] || fields.child_number != 0) {
+        return Err("depth-0 key with a parent");
+    }
+    Ok((bytes[..4].try_into().unwrap(), fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_hex;
+
+    // BIP-32 test vector 1
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn test_vector_1() {
+        let bip32 = Bip32::new();
+        let master = bip32.master_key(&encoding::from_hex(SEED).unwrap()).unwrap();
+        assert_eq!(
+            bip32.serialize_private(&master),
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"
+        );
+        assert_eq!(
+            bip32.serialize_public(&bip32.neuter(&master)),
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+        );
+
+        let leaf = bip32.derive_path(&master, "m/0'/1/2'/2/1000000000").unwrap();
+        assert_eq!(
+            bip32.serialize_private(&leaf),
+            "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76"
+        );
+        assert_eq!(
+            bip32.serialize_public(&bip32.neuter(&leaf)),
+            "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy"
+        );
+    }
+
+    #[test]
+    fn test_public_derivation_matches_private() {
+        let bip32 = Bip32::new();
+        let master = bip32.master_key(&encoding::from_hex(SEED).unwrap()).unwrap();
+        let account = bip32.derive_path(&master, "m/44'/0'/0'").unwrap();
+        let account_xpub = bip32.neuter(&account);
+
+        // Normal children of the account can be watched from the xpub alone
+        let from_private = bip32.neuter(&bip32.derive_path(&account, "m/0/5").unwrap());
+        let from_public = bip32.derive_public_path(&account_xpub, "m/0/5").unwrap();
+        assert_eq!(from_private, from_public);
+        assert!(bip32.derive_public_child(&account_xpub, HARDENED).is_err());
+    }
+
+    #[test]
+    fn test_path_and_serialization_roundtrip() {
+        let bip32 = Bip32::new();
+        let path = parse_path("m/44'/0'/0'/0/0").unwrap();
+        assert_eq!(path, vec![44 + HARDENED, HARDENED, HARDENED, 0, 0]);
+        assert_eq!(format_path(&path), "m/44'/0'/0'/0/0");
+        assert_eq!(parse_path("m/1h/2H").unwrap(), vec![1 + HARDENED, 2 + HARDENED]);
+        assert_eq!(parse_path("m").unwrap(), Vec::<u32>::new());
+        for bad in ["44'/0", "m/", "m/x", "m/-1", "m/2147483648"] {
+            assert!(parse_path(bad).is_err(), "{}", bad);
+        }
+
+        let master = bip32.master_key(&[7u8; 32]).unwrap();
+        let child = bip32.derive_path(&master, "m/3'/1").unwrap();
+        assert_eq!(bip32.parse_private(&bip32.serialize_private(&child)).unwrap(), child);
+        let xpub = bip32.serialize_public(&bip32.neuter(&child));
+        assert_eq!(bip32.parse_public(&xpub).unwrap(), bip32.neuter(&child));
+        assert!(bip32.parse_private(&xpub).is_err());
+        assert!(bip32.master_key(&[1u8; 8]).is_err());
+    }
+
+    #[test]
+    fn test_slip10_vectors() {
+        let seed = encoding::from_hex(SEED).unwrap();
+
+        // SLIP-0010 test vector 1 for ed25519, m/0H/1H/2H/2H/1000000000H
+        let ed = Slip10::new(Slip10Curve::Ed25519);
+        let master = ed.master_key(&seed).unwrap();
+        assert_eq!(to_hex(&master.chain_code), "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb");
+        assert_eq!(
+            to_hex(&ed.public_key_bytes(&master.private_key)),
+            "00a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"
+        );
+        let leaf = ed.derive_path(&master, "m/0'/1'/2'/2'/1000000000'").unwrap();
+        assert_eq!(to_hex(&leaf.parent_fingerprint), "d6322ccd");
+        assert_eq!(to_hex(&to_fixed_bytes(&leaf.private_key, 32)), "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793");
+        assert_eq!(
+            to_hex(&ed.public_key_bytes(&leaf.private_key)),
+            "003c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a"
+        );
+        assert!(ed.derive_private_child(&master, 0).is_err());
+
+        // ...and for nist256p1, m/0H/1/2H/2/1000000000
+        let nist = Slip10::new(Slip10Curve::Nist256p1);
+        let master = nist.master_key(&seed).unwrap();
+        assert_eq!(to_hex(&to_fixed_bytes(&master.private_key, 32)), "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2");
+        let leaf = nist.derive_path(&master, "m/0'/1/2'/2/1000000000").unwrap();
+        assert_eq!(to_hex(&leaf.chain_code), "b9b7b82d326bb9cb5b5b121066feea4eb93d5241103c9e7a18aad40f1dde8059");
+        assert_eq!(to_hex(&to_fixed_bytes(&leaf.private_key, 32)), "21c4f269ef0a5fd1badf47eeacebeeaa3de22eb8e5b0adcd0f27dd99d34d0119");
+    }
+
+    #[test]
+    fn test_slip10_retry_and_public_derivation() {
+        let seed = encoding::from_hex(SEED).unwrap();
+        let nist = Slip10::new(Slip10Curve::Nist256p1);
+        let master = nist.master_key(&seed).unwrap();
+
+        // SLIP-0010 "derivation retry" vector: m/28578H/33941 hits an IL ≥ n
+        let leaf = nist.derive_path(&master, "m/28578'/33941").unwrap();
+        assert_eq!(to_hex(&to_fixed_bytes(&leaf.private_key, 32)), "092154eed4af83e078ff9b84322015aefe5769e31270f62c3f66c33888335f3a");
+
+        // Public derivation takes the same retry
+        let parent = nist.derive_path(&master, "m/28578'").unwrap();
+        let public_leaf = nist.derive_public_child(&nist.neuter(&parent).unwrap(), 33941).unwrap();
+        assert_eq!(public_leaf, nist.neuter(&leaf).unwrap());
+
+        // secp256k1 under SLIP-0010 is plain BIP-32
+        let bip32 = Bip32::new();
+        let slip = Slip10::new(Slip10Curve::Secp256k1);
+        let path = "m/0'/1/2'";
+        assert_eq!(
+            slip.derive_path(&slip.master_key(&seed).unwrap(), path).unwrap(),
+            bip32.derive_path(&bip32.master_key(&seed).unwrap(), path).unwrap()
+        );
+    }
+}
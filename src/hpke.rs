@@ -0,0 +1,390 @@
+use crate::curves;
+use crate::encoding;
+use crate::montgomery::{x25519, x25519_public_key};
+use crate::entropy::Entropy;
+use aes_gcm::aead::{Aead as AeadCipher, KeyInit, Payload};
+use aes_gcm::{Aes128Gcm, Aes256Gcm};
+use chacha20poly1305::ChaCha20Poly1305;
+use hkdf::Hkdf;
+use num_bigint::BigUint;
+use rand::RngCore;
+use sha2::Sha256;
+
+// HPKE (RFC 9180) in base mode, with DHKEM(P-256, HKDF-SHA256) or DHKEM(X25519, HKDF-SHA256),
+// HKDF-SHA256 as the KDF and AES-128-GCM, AES-256-GCM or ChaCha20-Poly1305 as the AEAD
+//
+// The KEM turns a Diffie-Hellman exchange with a fresh ephemeral key into a shared secret:
+//   Encap(pkR):       dh = DH(skE, pkR), enc = pkE
+//   Decap(enc, skR):  dh = DH(skR, enc)
+//   shared_secret = LabeledExpand(LabeledExtract("", "eae_prk", dh), "shared_secret", enc || pkR, 32)
+// and the key schedule expands it, together with the application's info string, into an AEAD
+// key, a base nonce and an exporter secret. Message i is sealed under base_nonce XOR i, so both
+// sides have to process messages in the same order. Every HKDF call is labeled with
+// "HPKE-v1", the suite and a purpose, so nothing derived for one use can stand in for another.
+// Keys are their serialized forms: P-256 public keys are uncompressed SEC1 (65 bytes), private
+// keys 32-byte big-endian scalars; X25519 keys are the RFC 7748 32-byte strings
+
+const VERSION_LABEL: &[u8] = b"HPKE-v1";
+const MODE_BASE: u8 = 0x00;
+const KDF_HKDF_SHA256: u16 = 0x0001;
+const NONCE_LEN: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kem {
+    DhkemP256HkdfSha256,
+    DhkemX25519HkdfSha256,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aead {
+    Aes128Gcm,
+    Aes256Gcm,
+    ChaCha20Poly1305,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Suite {
+    pub kem: Kem,
+    pub aead: Aead,
+}
+
+#[derive(Clone, Debug)]
+pub struct HpkeKeyPair {
+    pub private_key: Vec<u8>,
+    pub public_key: Vec<u8>,
+}
+
+fn labeled_extract(suite_id: &[u8], salt: &[u8], label: &[u8], ikm: &[u8]) -> Vec<u8> {
+    let (prk, _) = Hkdf::<Sha256>::extract(Some(salt), &[VERSION_LABEL, suite_id, label, ikm].concat());
+    prk.to_vec()
+}
+
+fn labeled_expand(suite_id: &[u8], prk: &[u8], label: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    let length = (len as u16).to_be_bytes();
+    let mut okm = vec![0u8; len];
+    Hkdf::<Sha256>::from_prk(prk)
+        .expect("?PRK is a SHA-256 output?")
+        .expand(&[&length[..], VERSION_LABEL, suite_id, label, info].concat(), &mut okm)
+        .expect("?HKDF output too long?");
+    okm
+}
+
+impl Kem {
+    pub fn id(self) -> u16 {
+        match self {
+            Kem::DhkemP256HkdfSha256 => 0x0010,
+            Kem::DhkemX25519HkdfSha256 => 0x0020,
+        }
+    }
+
+    fn suite_id(self) -> Vec<u8> {
+        [&b"KEM"[..], &self.id().to_be_bytes()].concat()
+    }
+
+    pub fn public_key_len(self) -> usize {
+        match self {
+            Kem::DhkemP256HkdfSha256 => 65,
+            Kem::DhkemX25519HkdfSha256 => 32,
+        }
+    }
+
+    pub fn generate_key_pair(self) -> HpkeKeyPair {
+        let mut ikm = [0u8; 32];
+        Entropy.fill_bytes(&mut ikm);
+        self.derive_key_pair(&ikm).expect("?random key derivation failed?")
+    }
+
+    // DeriveKeyPair: deterministic keys from at least 32 bytes of input keying material.
+    // P-256 retries candidate scalars until one is in [1, n-1]
+    pub fn derive_key_pair(self, ikm: &[u8]) -> Result<HpkeKeyPair, &'static str> {
+        let suite_id = self.suite_id();
+        let dkp_prk = labeled_extract(&suite_id, b"", b"dkp_prk", ikm);
+        let private_key = match self {
+            Kem::DhkemP256HkdfSha256 => {
+                let order = curves::p256().order;
+                (0..=255u8)
+                    .map(|counter| labeled_expand(&suite_id, &dkp_prk, b"candidate", &[counter], 32))
+                    .find(|candidate| {
+                        let sk = BigUint::from_bytes_be(candidate);
+                        sk != BigUint::from(0u32) && sk < order
+                    })
+                    .ok_or("DeriveKeyPair found no valid scalar")?
+            }
+            Kem::DhkemX25519HkdfSha256 => labeled_expand(&suite_id, &dkp_prk, b"sk", b"", 32),
+        };
+        let public_key = self.public_key(&private_key)?;
+        Ok(HpkeKeyPair { private_key, public_key })
+    }
+
+    pub fn public_key(self, private_key: &[u8]) -> Result<Vec<u8>, &'static str> {
+        match self {
+            Kem::DhkemP256HkdfSha256 => {
+                let curve = curves::p256();
+                let sk = self.p256_scalar(private_key)?;
+                Ok(encoding::encode_point(&curve.curve, &curve.ecdsa().generate_public_key(&sk), false))
+            }
+            Kem::DhkemX25519HkdfSha256 => Ok(x25519_public_key(&x25519_key(private_key)?).to_vec()),
+        }
+    }
+
+    fn p256_scalar(self, private_key: &[u8]) -> Result<BigUint, &'static str> {
+        let sk = BigUint::from_bytes_be(private_key);
+        if private_key.len() != 32 || sk == BigUint::from(0u32) || sk >= curves::p256().order {
+            return Err("invalid P-256 private key");
+        }
+        Ok(sk)
+    }
+
+    // The raw DH output: the x-coordinate for P-256, the X25519 output for X25519
+    fn dh(self, private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, &'static str> {
+        match self {
+            Kem::DhkemP256HkdfSha256 => {
+                let curve = curves::p256();
+                if public_key.len() != 65 || public_key[0] != 0x04 {
+                    return Err("P-256 HPKE public keys are uncompressed points");
+                }
+                let peer = encoding::decode_point(&curve.curve, public_key)?;
+                Ok(curve.ecdh().agree(&self.p256_scalar(private_key)?, &peer)?.raw_bytes().to_vec())
+            }
+            Kem::DhkemX25519HkdfSha256 => {
+                let dh = x25519(&x25519_key(private_key)?, &x25519_key(public_key)?);
+                // A small-order peer key forces the all-zero output
+                if dh == [0u8; 32] {
+                    return Err("X25519 shared secret is all zero");
+                }
+                Ok(dh.to_vec())
+            }
+        }
+    }
+
+    fn extract_and_expand(self, dh: &[u8], kem_context: &[u8]) -> Vec<u8> {
+        let suite_id = self.suite_id();
+        let eae_prk = labeled_extract(&suite_id, b"", b"eae_prk", dh);
+        labeled_expand(&suite_id, &eae_prk, b"shared_secret", kem_context, 32)
+    }
+
+    // (shared secret, enc) for the recipient's public key
+    pub fn encap(self, public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
+        self.encap_with_ephemeral(public_key, &self.generate_key_pair())
+    }
+
+    // Encap with a chosen ephemeral key pair, for test vectors
+    pub fn encap_with_ephemeral(self, public_key: &[u8], ephemeral: &HpkeKeyPair) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
+        let dh = self.dh(&ephemeral.private_key, public_key)?;
+        let enc = ephemeral.public_key.clone();
+        let shared_secret = self.extract_and_expand(&dh, &[&enc[..], public_key].concat());
+        Ok((shared_secret, enc))
+    }
+
+    pub fn decap(self, enc: &[u8], private_key: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let dh = self.dh(private_key, enc)?;
+        Ok(self.extract_and_expand(&dh, &[enc, &self.public_key(private_key)?].concat()))
+    }
+}
+
+fn x25519_key(bytes: &[u8]) -> Result<[u8; 32], &'static str> {
+    bytes.try_into().map_err(|_| "X25519 keys are 32 bytes")
+}
+
+impl Aead {
+    pub fn id(self) -> u16 {
+        match self {
+            Aead::Aes128Gcm => 0x0001,
+            Aead::Aes256Gcm => 0x0002,
+            Aead::ChaCha20Poly1305 => 0x0003,
+        }
+    }
+
+    pub fn key_len(self) -> usize {
+        match self {
+            Aead::Aes128Gcm => 16,
+            Aead::Aes256Gcm | Aead::ChaCha20Poly1305 => 32,
+        }
+    }
+
+    fn seal(self, key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let payload = Payload { msg: plaintext, aad };
+        match self {
+            Aead::Aes128Gcm => Aes128Gcm::new_from_slice(key).unwrap().encrypt(nonce.into(), payload),
+            Aead::Aes256Gcm => Aes256Gcm::new_from_slice(key).unwrap().encrypt(nonce.into(), payload),
+            Aead::ChaCha20Poly1305 => ChaCha20Poly1305::new_from_slice(key).unwrap().encrypt(nonce.into(), payload),
+        }
+        .expect("?AEAD encryption failed?")
+    }
+
+    fn open(self, key: &[u8], nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let payload = Payload { msg: ciphertext, aad };
+        match self {
+            Aead::Aes128Gcm => Aes128Gcm::new_from_slice(key).unwrap().decrypt(nonce.into(), payload),
+            Aead::Aes256Gcm => Aes256Gcm::new_from_slice(key).unwrap().decrypt(nonce.into(), payload),
+            Aead::ChaCha20Poly1305 => ChaCha20Poly1305::new_from_slice(key).unwrap().decrypt(nonce.into(), payload),
+        }
+        .map_err(|_| "HPKE decryption failed")
+    }
+}
+
+// An encryption context from SetupBaseS or SetupBaseR. The sender only seals and the
+// recipient only opens; the sequence number advances with each message
+pub struct Context {
+    suite: Suite,
+    key: Vec<u8>,
+    base_nonce: Vec<u8>,
+    exporter_secret: Vec<u8>,
+    seq: u64,
+}
+
+impl Suite {
+    pub fn new(kem: Kem, aead: Aead) -> Self {
+        Suite { kem, aead }
+    }
+
+    fn suite_id(self) -> Vec<u8> {
+        [&b"HPKE"[..], &self.kem.id().to_be_bytes(), &KDF_HKDF_SHA256.to_be_bytes(), &self.aead.id().to_be_bytes()].concat()
+    }
+
+    fn key_schedule(self, shared_secret: &[u8], info: &[u8]) -> Context {
+        let suite_id = self.suite_id();
+        let psk_id_hash = labeled_extract(&suite_id, b"", b"psk_id_hash", b"");
+        let info_hash = labeled_extract(&suite_id, b"", b"info_hash", info);
+        let context = [&[MODE_BASE][..], &psk_id_hash, &info_hash].concat();
+        let secret = labeled_extract(&suite_id, shared_secret, b"secret", b"");
+        Context {
+            suite: self,
+            key: labeled_expand(&suite_id, &secret, b"key", &context, self.aead.key_len()),
+            base_nonce: labeled_expand(&suite_id, &secret, b"base_nonce", &context, NONCE_LEN),
+            exporter_secret: labeled_expand(&suite_id, &secret, b"exp", &context, 32),
+            seq: 0,
+        }
+    }
+
+    // (enc, sender context) for the recipient's public key
+    pub fn setup_base_sender(self, public_key: &[u8], info: &[u8]) -> Result<(Vec<u8>, Context), &'static str> {
+        let (shared_secret, enc) = self.kem.encap(public_key)?;
+        Ok((enc, self.key_schedule(&shared_secret, info)))
+    }
+
+    // SetupBaseS with a chosen ephemeral key pair, for test vectors
+    pub fn setup_base_sender_with_ephemeral(self, public_key: &[u8], info: &[u8], ephemeral: &HpkeKeyPair) -> Result<(Vec<u8>, Context), &'static str> {
+        let (shared_secret, enc) = self.kem.encap_with_ephemeral(public_key, ephemeral)?;
+        Ok((enc, self.key_schedule(&shared_secret, info)))
+    }
+
+    pub fn setup_base_recipient(self, enc: &[u8], private_key: &[u8], info: &[u8]) -> Result<Context, &'static str> {
+        Ok(self.key_schedule(&self.kem.decap(enc, private_key)?, info))
+    }
+
+    // Single-shot encryption: (enc, ciphertext)
+    pub fn seal(self, public_key: &[u8], info: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
+        let (enc, mut context) = self.setup_base_sender(public_key, info)?;
+        Ok((enc, context.seal(aad, plaintext)?))
+    }
+
+    pub fn open(self, enc: &[u8], private_key: &[u8], info: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        self.setup_base_recipient(enc, private_key, info)?.open(aad, ciphertext)
+    }
+}
+
+impl Context {
+    fn nonce(&self) -> Vec<u8> {
+        let mut nonce = self.base_nonce.clone();
+        for (n, s) in nonce[NONCE_LEN - 8..].iter_mut().zip(self.seq.to_be_bytes()) {
+            *n ^= s;
+        }
+        nonce
+    }
+
+    fn increment_seq(&mut self) -> Result<(), &'static str> {
+        self.seq = self.seq.checked_add(1).ok_or("HPKE message limit reached")?;
+        Ok(())
+    }
+
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let ciphertext = self.suite.aead.seal(&self.key, &self.nonce(), aad, plaintext);
+        self.increment_seq()?;
+        Ok(ciphertext)
+    }
+
+    // A failed open leaves the sequence number alone, so the next message can still be opened
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let plaintext = self.suite.aead.open(&self.key, &self.nonce(), aad, ciphertext)?;
+        self.increment_seq()?;
+        Ok(plaintext)
+    }
+
+    // Secret export: len bytes bound to exporter_context, the same on both sides
+    pub fn export(&self, exporter_context: &[u8], len: usize) -> Vec<u8> {
+        labeled_expand(&self.suite.suite_id(), &self.exporter_secret, b"sec", exporter_context, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    #[test]
+    fn test_rfc9180_x25519() {
+        // A.1.1: DHKEM(X25519, HKDF-SHA256), HKDF-SHA256, AES-128-GCM, base mode
+        let kem = Kem::DhkemX25519HkdfSha256;
+        let ephemeral = kem.derive_key_pair(&from_hex("7268600d403fce431561aef583ee1613527cff655c1343f29812e66706df3234").unwrap()).unwrap();
+        assert_eq!(to_hex(&ephemeral.private_key), "52c4a758a802cd8b936eceea314432798d5baf2d7e9235dc084ab1b9cfa2f736");
+        assert_eq!(to_hex(&ephemeral.public_key), "37fda3567bdbd628e88668c3c8d7e97d1d1253b6d4ea6d44c150f741f1bf4431");
+        let recipient = kem.derive_key_pair(&from_hex("6db9df30aa07dd42ee5e8181afdb977e538f5e1fec8a06223f33f7013e525037").unwrap()).unwrap();
+        assert_eq!(to_hex(&recipient.private_key), "4612c550263fc8ad58375df3f557aac531d26850903e55a9f23f21d8534e8ac8");
+
+        let suite = Suite::new(kem, Aead::Aes128Gcm);
+        let info = from_hex("4f6465206f6e2061204772656369616e2055726e").unwrap();
+        let (enc, mut sender) = suite.setup_base_sender_with_ephemeral(&recipient.public_key, &info, &ephemeral).unwrap();
+        assert_eq!(enc, ephemeral.public_key);
+        assert_eq!(to_hex(&kem.decap(&enc, &recipient.private_key).unwrap()), "fe0e18c9f024ce43799ae393c7e8fe8fce9d218875e8227b0187c04e7d2ea1fc");
+        assert_eq!(to_hex(&sender.key), "4531685d41d65f03dc48f6b8302c05b0");
+        assert_eq!(to_hex(&sender.base_nonce), "56d890e5accaaf011cff4b7d");
+        assert_eq!(to_hex(&sender.exporter_secret), "45ff1c2e220db587171952c0592d5f5ebe103f1561a2614e38f2ffd47e99e3f8");
+
+        let mut recipient_context = suite.setup_base_recipient(&enc, &recipient.private_key, &info).unwrap();
+        let plaintext = b"Beauty is truth, truth beauty";
+        let ciphertext = sender.seal(b"Count-0", plaintext).unwrap();
+        assert_eq!(to_hex(&ciphertext), "f938558b5d72f1a23810b4be2ab4f84331acc02fc97babc53a52ae8218a355a96d8770ac83d07bea87e13c512a");
+        assert_eq!(recipient_context.open(b"Count-0", &ciphertext).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_seal_open() {
+        for kem in [Kem::DhkemP256HkdfSha256, Kem::DhkemX25519HkdfSha256] {
+            for aead in [Aead::Aes128Gcm, Aead::Aes256Gcm, Aead::ChaCha20Poly1305] {
+                let suite = Suite::new(kem, aead);
+                let recipient = kem.generate_key_pair();
+                assert_eq!(recipient.public_key.len(), kem.public_key_len());
+
+                let (enc, ciphertext) = suite.seal(&recipient.public_key, b"app info", b"header", b"attack at dawn").unwrap();
+                assert_eq!(suite.open(&enc, &recipient.private_key, b"app info", b"header", &ciphertext).unwrap(), b"attack at dawn");
+                assert!(suite.open(&enc, &recipient.private_key, b"other info", b"header", &ciphertext).is_err());
+                assert!(suite.open(&enc, &recipient.private_key, b"app info", b"", &ciphertext).is_err());
+                assert!(suite.open(&enc, &kem.generate_key_pair().private_key, b"app info", b"header", &ciphertext).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_context_sequence_and_export() {
+        let suite = Suite::new(Kem::DhkemP256HkdfSha256, Aead::Aes128Gcm);
+        let recipient = suite.kem.generate_key_pair();
+        let (enc, mut sender) = suite.setup_base_sender(&recipient.public_key, b"").unwrap();
+        let mut receiver = suite.setup_base_recipient(&enc, &recipient.private_key, b"").unwrap();
+        assert_eq!(sender.export(b"channel binding", 48), receiver.export(b"channel binding", 48));
+
+        let first = sender.seal(b"", b"one").unwrap();
+        let second = sender.seal(b"", b"two").unwrap();
+        // Out of order fails without consuming a sequence number
+        assert!(receiver.open(b"", &second).is_err());
+        assert_eq!(receiver.open(b"", &first).unwrap(), b"one");
+        assert_eq!(receiver.open(b"", &second).unwrap(), b"two");
+
+        // Compressed or identity P-256 keys, and a small-order X25519 key
+        let curve = curves::p256();
+        let compressed = encoding::encode_point(&curve.curve, &curve.generator, true);
+        assert!(suite.seal(&compressed, b"", b"", b"x").is_err());
+        assert!(suite.seal(&[0x00], b"", b"", b"x").is_err());
+        assert!(Suite::new(Kem::DhkemX25519HkdfSha256, Aead::Aes128Gcm).seal(&[0u8; 32], b"", b"", b"x").is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+use std::collections::HashSet;
+
+// Separable isogenies from their kernels by Vélu's formulas
+//
+// An isogeny φ: E -> E' is a rational map that is also a group homomorphism; for every finite
+// subgroup G of E there is one with kernel G and degree |G|, and Vélu writes it down. Split
+// G \ {O} into the points of order 2 and one representative of each pair ±Q of the rest (the
+// set S), and for Q = (x_Q, y_Q) in S:
+//   g^x_Q = 3x_Q² + a,  g^y_Q = -2y_Q,  u_Q = (g^y_Q)²
+//   v_Q = g^x_Q if Q has order 2, else 2g^x_Q
+//   v = Σ v_Q,  w = Σ (u_Q + x_Q v_Q)
+// Then E': y² = x³ + (a - 5v)x + (b - 7w) and
+//   φ(x, y) = (x + Σ [v_Q / (x - x_Q) + u_Q / (x - x_Q)²],
+//              y - Σ [2y u_Q / (x - x_Q)³ + v_Q (y - y_Q) / (x - x_Q)² - g^x_Q g^y_Q / (x - x_Q)²])
+// Isogenous curves over F_p have the same number of points, which is what makes isogeny graphs
+// (curves as vertices, l-isogenies as edges) interesting: walking them changes the curve but
+// not the group order. Only kernels made of F_p-points are handled, so small curves and small
+// degrees
+
+// Kernel subgroups are built up to this many points
+pub const MAX_KERNEL_SIZE: usize = 10_000;
+
+// One of Vélu's terms: (x_Q, y_Q, g^x_Q, g^y_Q, u_Q, v_Q)
+#[derive(Clone, Debug, PartialEq)]
+struct KernelTerm {
+    x: BigUint,
+    y: BigUint,
+    gx: BigUint,
+    gy: BigUint,
+    u: BigUint,
+    v: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Isogeny {
+    pub domain: EllipticCurve,
+    pub codomain: EllipticCurve,
+    pub kernel: Vec<Point>,
+    terms: Vec<KernelTerm>,
+}
+
+impl Isogeny {
+    // The isogeny with kernel generated by a point of finite order
+    pub fn from_generator(curve: &EllipticCurve, generator: &Point) -> Result<Self, &'static str> {
+        if !curve.is_on_curve(generator) {
+            return Err("kernel generator is not on the curve");
+        }
+        let mut kernel = vec![Point::Identity];
+        let mut multiple = generator.clone();
+        while multiple != Point::Identity {
+            if kernel.len() == MAX_KERNEL_SIZE {
+                return Err("kernel too large");
+            }
+            kernel.push(multiple.clone());
+            multiple = curve.add(&multiple, generator);
+        }
+        // The multiples of a point are a subgroup already
+        Ok(Self::from_subgroup(curve, kernel))
+    }
+
+    // The isogeny with the given kernel, which must be a subgroup (the identity may be left out)
+    pub fn from_kernel(curve: &EllipticCurve, kernel: &[Point]) -> Result<Self, &'static str> {
+        let mut group: Vec<Point> = vec![Point::Identity];
+        let mut members: HashSet<Point> = HashSet::from([Point::Identity]);
+        for point in kernel {
+            if !curve.is_on_curve(point) {
+                return Err("kernel point is not on the curve");
+            }
+            if members.insert(point.clone()) {
+                group.push(point.clone());
+            }
+        }
+        if group.len() > MAX_KERNEL_SIZE {
+            return Err("kernel too large");
+        }
+
+        // Build the subgroup the points generate, one generator at a time: if H is a subgroup
+        // and g is new, <H, g> is the cosets H + jg up to the first jg back in H. The points are
+        // a subgroup when nothing generated falls outside them, and each point is added once
+        let mut span: HashSet<Point> = HashSet::from([Point::Identity]);
+        for generator in &group {
+            let base: Vec<Point> = span.iter().cloned().collect();
+            let mut multiple = generator.clone();
+            while !span.contains(&multiple) {
+                for point in &base {
+                    let sum = curve.add(point, &multiple);
+                    if !members.contains(&sum) {
+                        return Err("kernel is not a subgroup");
+                    }
+                    span.insert(sum);
+                }
+                multiple = curve.add(&multiple, generator);
+            }
+        }
+        Ok(Self::from_subgroup(curve, group))
+    }
+
+    // Vélu's formulas over a subgroup that starts with the identity and has no repeats
+    fn from_subgroup(curve: &EllipticCurve, group: Vec<Point>) -> Self {
+        let field = curve.field();
+        let (mut v, mut w) = (BigUint::from(0u32), BigUint::from(0u32));
+        let mut terms: Vec<KernelTerm> = Vec::new();
+        for point in &group {
+            let Point::Coordinate(x, y) = point else { continue };
+            // One of ±Q: the negative has the same x
+            if terms.iter().any(|term| term.x == *x) {
+                continue;
+            }
+            let gx = field.add(&field.mul(&BigUint::from(3u32), &field.mul(x, x)), curve.a());
+            let gy = field.sub(&BigUint::from(0u32), &field.add(y, y));
+            let u = field.mul(&gy, &gy);
+            let order_two = *y == BigUint::from(0u32);
+            let v_q = if order_two { gx.clone() } else { field.add(&gx, &gx) };
+            v = field.add(&v, &v_q);
+            w = field.add(&w, &field.add(&u, &field.mul(x, &v_q)));
+            terms.push(KernelTerm { x: x.clone(), y: y.clone(), gx, gy, u, v: v_q });
+        }
+
+        let codomain = EllipticCurve::new(
+            field.sub(curve.a(), &field.mul(&BigUint::from(5u32), &v)),
+            field.sub(curve.b(), &field.mul(&BigUint::from(7u32), &w)),
+            curve.p().clone(),
+        );
+        Isogeny { domain: curve.clone(), codomain, kernel: group, terms }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.kernel.len()
+    }
+
+    // φ(P), the identity for P in the kernel
+    pub fn evaluate(&self, point: &Point) -> Point {
+        let Point::Coordinate(x, y) = point else { return Point::Identity };
+        let field = self.domain.field();
+        let (mut image_x, mut image_y) = (x.clone(), y.clone());
+        for term in &self.terms {
+            // x = x_Q only for ±Q, which is in the kernel
+            let Some(inverse) = field.invert(&field.sub(x, &term.x)) else { return Point::Identity };
+            let inverse_2 = field.mul(&inverse, &inverse);
+            let inverse_3 = field.mul(&inverse_2, &inverse);
+
+            image_x = field.add(&image_x, &field.add(&field.mul(&term.v, &inverse), &field.mul(&term.u, &inverse_2)));
+            let cubic = field.mul(&field.mul(&field.add(y, y), &term.u), &inverse_3);
+            let square = field.mul(&field.sub(&field.mul(&term.v, &field.sub(y, &term.y)), &field.mul(&term.gx, &term.gy)), &inverse_2);
+            image_y = field.sub(&image_y, &field.add(&cubic, &square));
+        }
+        Point::Coordinate(image_x, image_y)
+    }
+}
+
+// Every l-isogeny out of the curve whose kernel is generated by an F_p-point, one per cyclic
+// subgroup of order l; l is prime. The kernels come from torsion_points, which enumerates the
+// curve, so p must fit in MAX_MODULUS_BITS (16) bits or this errors
+pub fn rational_isogenies(curve: &EllipticCurve, l: u32) -> Result<Vec<Isogeny>, &'static str> {
+    let l = BigUint::from(l);
+    let mut isogenies: Vec<Isogeny> = Vec::new();
+    for point in curve.torsion_points(&l)?.into_iter().skip(1) {
+        if !isogenies.iter().any(|isogeny| isogeny.kernel.contains(&point)) {
+            isogenies.push(Isogeny::from_generator(curve, &point)?);
+        }
+    }
+    Ok(isogenies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_curve() -> EllipticCurve {
+        // y² = x³ + x + 1 over F_101 has 105 = 3 · 5 · 7 points
+        EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(101u32))
+    }
+
+    #[test]
+    fn test_velu_homomorphism() {
+        let curve = small_curve();
+        assert_eq!(curve.count_points(), Ok(BigUint::from(105u32)));
+        let all: Vec<Point> = curve.points().collect();
+        for l in [3u32, 5, 7] {
+            let isogenies = rational_isogenies(&curve, l).unwrap();
+            assert_eq!(isogenies.len(), 1);
+            let isogeny = &isogenies[0];
+            assert_eq!(isogeny.degree(), l as usize);
+            // Same order on both sides, the kernel vanishes, and φ(P + Q) = φ(P) + φ(Q)
+            assert_eq!(isogeny.codomain.count_points(), curve.count_points());
+            for point in &isogeny.kernel {
+                assert_eq!(isogeny.evaluate(point), Point::Identity);
+            }
+            for (i, p) in all.iter().enumerate().step_by(7) {
+                let q = &all[(i * 3 + 1) % all.len()];
+                let image = isogeny.evaluate(p);
+                assert!(isogeny.codomain.is_on_curve(&image));
+                assert_eq!(isogeny.evaluate(&curve.add(p, q)), isogeny.codomain.add(&image, &isogeny.evaluate(q)));
+            }
+            // |E| / l points are in the kernel, so the image has 105 / l points
+            let mut images: Vec<Point> = all.iter().map(|p| isogeny.evaluate(p)).collect();
+            images.sort();
+            images.dedup();
+            assert_eq!(images.len(), 105 / l as usize);
+        }
+    }
+
+    #[test]
+    fn test_two_isogenies() {
+        // y² = x³ - x has full 2-torsion over F_103: (0, 0), (1, 0), (-1, 0)
+        let p = BigUint::from(103u32);
+        let curve = EllipticCurve::new(&p - 1u32, BigUint::from(0u32), p.clone());
+        let isogenies = rational_isogenies(&curve, 2).unwrap();
+        assert_eq!(isogenies.len(), 3);
+        for isogeny in &isogenies {
+            assert_eq!(isogeny.degree(), 2);
+            assert_eq!(isogeny.codomain.count_points(), curve.count_points());
+        }
+
+        // The whole 2-torsion gives a degree-4 isogeny: multiplication by 2 up to isomorphism
+        let two_torsion: Vec<Point> = [0u32, 1, 102].iter().map(|x| Point::Coordinate(BigUint::from(*x), BigUint::from(0u32))).collect();
+        let isogeny = Isogeny::from_kernel(&curve, &two_torsion).unwrap();
+        assert_eq!(isogeny.degree(), 4);
+        assert_eq!(isogeny.codomain.count_points(), curve.count_points());
+        assert!(Isogeny::from_kernel(&curve, &two_torsion[..2]).is_err());
+
+        // E(F_p)[4] ≅ Z_2 × Z_4 needs two generators; dropping a point breaks it
+        let four_torsion = curve.torsion_points(&BigUint::from(4u32)).unwrap();
+        let isogeny = Isogeny::from_kernel(&curve, &four_torsion).unwrap();
+        assert_eq!(isogeny.degree(), 8);
+        assert_eq!(isogeny.codomain.count_points(), curve.count_points());
+        assert!(Isogeny::from_kernel(&curve, &four_torsion[..7]).is_err());
+
+        // Too big to enumerate
+        assert!(rational_isogenies(&crate::curves::p256().curve, 2).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ntheory;
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+
+// Isomorphisms between short Weierstrass curves over F_p
+//
+// The only changes of variables that keep the form y² = x³ + ax + b are (x, y) -> (u²x, u³y),
+// which take the curve to y² = x³ + u⁴ax + u⁶b. The j-invariant
+//   j = 1728 · 4a³ / (4a³ + 27b²)
+// is unchanged by them, and two curves with the same j are isomorphic over the algebraic
+// closure. Over F_p they may only be twists: u exists in some extension but not in F_p. So a
+// u with u⁴ = a'/a and u⁶ = b'/b is looked for:
+//   a, b != 0       u² = (a b') / (a' b), a single candidate
+//   j = 1728, b = 0 u² = ±√(a'/a)           (quartic twists)
+//   j = 0, a = 0    u² = ∛(b'/b), up to three (sextic twists)
+// and the curves are isomorphic over F_p iff one of the candidates is a square. Equal
+// parameters up to this scaling are common in the wild (a = -3 forms, SEC vs. other encodings);
+// the map lets points be moved across to the normalized curve
+
+// (x, y) -> (u²x, u³y) from domain to codomain
+#[derive(Clone, Debug, PartialEq)]
+pub struct Isomorphism {
+    pub domain: EllipticCurve,
+    pub codomain: EllipticCurve,
+    pub u: BigUint,
+}
+
+impl Isomorphism {
+    pub fn map(&self, point: &Point) -> Point {
+        let Point::Coordinate(x, y) = point else { return Point::Identity };
+        let field = self.domain.field();
+        let u_squared = field.mul(&self.u, &self.u);
+        Point::Coordinate(field.mul(&u_squared, x), field.mul(&field.mul(&u_squared, &self.u), y))
+    }
+
+    pub fn inverse(&self) -> Isomorphism {
+        let u = self.domain.field().invert(&self.u).expect("?u is nonzero?");
+        Isomorphism { domain: self.codomain.clone(), codomain: self.domain.clone(), u }
+    }
+}
+
+impl EllipticCurve {
+    // j = 1728 · 4a³ / (4a³ + 27b²), defined for nonsingular curves
+    pub fn j_invariant(&self) -> Result<BigUint, &'static str> {
+        let field = self.field();
+        let four_a_cubed = field.mul(&BigUint::from(4u32), &field.mul(&field.mul(self.a(), self.a()), self.a()));
+        let denominator = field.add(&four_a_cubed, &field.mul(&BigUint::from(27u32), &field.mul(self.b(), self.b())));
+        let inverse = field.invert(&denominator).ok_or("singular curve")?;
+        Ok(field.mul(&field.mul(&BigUint::from(1728u32), &four_a_cubed), &inverse))
+    }
+
+    pub fn is_isomorphic_to(&self, other: &EllipticCurve) -> bool {
+        self.isomorphism_to(other).is_ok()
+    }
+
+    // The isomorphism onto other, if there is one over F_p
+    pub fn isomorphism_to(&self, other: &EllipticCurve) -> Result<Isomorphism, &'static str> {
+        if self.p() != other.p() {
+            return Err("curves are over different fields");
+        }
+        if self.j_invariant()? != other.j_invariant()? {
+            return Err("j-invariants differ");
+        }
+        let field = self.field();
+        let zero = BigUint::from(0u32);
+
+        // Candidates for u²; with equal j, a = 0 iff a' = 0 and b = 0 iff b' = 0
+        let candidates = if *self.a() == zero {
+            ntheory::cube_roots(&field.div(other.b(), self.b()), self.p())
+        } else if *self.b() == zero {
+            let root = field.sqrt(&field.div(other.a(), self.a())).ok_or("curves are quartic twists of each other")?;
+            vec![field.sub(&zero, &root), root]
+        } else {
+            vec![field.div(&field.mul(self.a(), other.b()), &field.mul(other.a(), self.b()))]
+        };
+
+        let u = candidates.iter().filter_map(|u_squared| field.sqrt(u_squared)).find(|u| {
+            let u_squared = field.mul(u, u);
+            let u_fourth = field.mul(&u_squared, &u_squared);
+            field.mul(&u_fourth, self.a()) == *other.a() && field.mul(&field.mul(&u_fourth, &u_squared), self.b()) == *other.b()
+        });
+        let u = u.ok_or("curves are twists of each other")?;
+        Ok(Isomorphism { domain: self.clone(), codomain: other.clone(), u })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use crate::entropy::Entropy;
+
+    // y² = x³ + u⁴ax + u⁶b
+    fn scaled(curve: &EllipticCurve, u: u32) -> EllipticCurve {
+        let field = curve.field();
+        let u = BigUint::from(u);
+        let u_squared = field.mul(&u, &u);
+        let u_fourth = field.mul(&u_squared, &u_squared);
+        EllipticCurve::new(field.mul(&u_fourth, curve.a()), field.mul(&field.mul(&u_fourth, &u_squared), curve.b()), curve.p().clone())
+    }
+
+    #[test]
+    fn test_j_invariant() {
+        assert_eq!(curves::secp256k1().curve.j_invariant(), Ok(BigUint::from(0u32)));
+        let p = BigUint::from(1019u32);
+        assert_eq!(EllipticCurve::new(BigUint::from(1u32), BigUint::from(0u32), p.clone()).j_invariant(), Ok(BigUint::from(1728u32 % 1019)));
+        assert!(EllipticCurve::new(BigUint::from(0u32), BigUint::from(0u32), p).j_invariant().is_err());
+    }
+
+    #[test]
+    fn test_isomorphisms() {
+        let p256 = curves::p256();
+        let secp256k1 = curves::secp256k1();
+        let small = EllipticCurve::new(BigUint::from(1u32), BigUint::from(0u32), BigUint::from(1021u32));
+        for (curve, u) in [(&p256.curve, 12345u32), (&secp256k1.curve, 3), (&small, 10)] {
+            let image = scaled(curve, u);
+            let isomorphism = curve.isomorphism_to(&image).unwrap();
+            assert!(curve.is_isomorphic_to(&image) && image.is_isomorphic_to(curve));
+
+            // Points go across and back, and addition is preserved
+            let p = curve.random_point(&mut Entropy).unwrap();
+            let q = curve.random_point(&mut Entropy).unwrap();
+            let mapped = isomorphism.map(&p);
+            assert!(image.is_on_curve(&mapped));
+            assert_eq!(isomorphism.inverse().map(&mapped), p);
+            assert_eq!(isomorphism.map(&curve.add(&p, &q)), image.add(&mapped, &isomorphism.map(&q)));
+            assert_eq!(isomorphism.map(&Point::Identity), Point::Identity);
+        }
+        assert!(p256.curve.is_isomorphic_to(&p256.curve));
+
+        // Twists share j but not F_p-points
+        let twist = p256.curve.quadratic_twist().unwrap();
+        assert_eq!(twist.j_invariant(), p256.curve.j_invariant());
+        assert_eq!(p256.curve.isomorphism_to(&twist).err(), Some("curves are twists of each other"));
+        // b = 7 and b = 7 · 3 on secp256k1's field: 3 isn't a sixth power there
+        let sextic = EllipticCurve::new(BigUint::from(0u32), BigUint::from(21u32), secp256k1.curve.p().clone());
+        assert!(!secp256k1.curve.is_isomorphic_to(&sextic));
+        assert_eq!(secp256k1.curve.isomorphism_to(&p256.curve).err(), Some("curves are over different fields"));
+        let other = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(1021u32));
+        assert_eq!(small.isomorphism_to(&other).err(), Some("j-invariants differ"));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::curves::{self, NamedCurve};
+use crate::ecdsa::ECDSASignature;
+use crate::encoding::{self, from_base64url, to_base64url};
+use crate::Point;
+use num_bigint::BigUint;
+use serde_json::Value;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+// JWS (RFC 7515) compact serialization with the ECDSA algorithms of RFC 7518 3.4:
+//   ES256  P-256 with SHA-256
+//   ES384  P-384 with SHA-384
+//   ES512  P-521 with SHA-512
+//
+// A token is BASE64URL(header) . BASE64URL(payload) . BASE64URL(signature), signed over the
+// first two parts exactly as they appear. The signature is r || s, each half padded to the
+// order's length (32, 48 or 66 bytes); DER signatures are not valid JWS
+//
+// The verifier says which algorithm it expects and the header has to agree. Taking "alg" from
+// the token is how JWT libraries came to accept "none" or HMAC keyed with the public key.
+// Headers that mark extensions critical ("crit") are rejected, since none are understood here
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    ES256,
+    ES384,
+    ES512,
+}
+
+impl Algorithm {
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::ES256 => "ES256",
+            Algorithm::ES384 => "ES384",
+            Algorithm::ES512 => "ES512",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Algorithm::ES256, Algorithm::ES384, Algorithm::ES512].into_iter().find(|alg| alg.name() == name)
+    }
+
+    pub fn curve(self) -> NamedCurve {
+        match self {
+            Algorithm::ES256 => curves::p256(),
+            Algorithm::ES384 => curves::p384(),
+            Algorithm::ES512 => curves::p521(),
+        }
+    }
+
+    // JWK "crv" value
+    pub fn curve_name(self) -> &'static str {
+        match self {
+            Algorithm::ES256 => "P-256",
+            Algorithm::ES384 => "P-384",
+            Algorithm::ES512 => "P-521",
+        }
+    }
+
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::ES256 => Sha256::digest(data).to_vec(),
+            Algorithm::ES384 => Sha384::digest(data).to_vec(),
+            Algorithm::ES512 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+// header holds any extra parameters ({"typ": "JWT", "kid": "..."}) and may be null; "alg" is
+// always set from alg
+pub fn sign(alg: Algorithm, private_key: &BigUint, header: &Value, payload: &[u8]) -> Result<String, &'static str> {
+    let mut header = match header {
+        Value::Null => serde_json::Map::new(),
+        Value::Object(params) => params.clone(),
+        _ => return Err("JWS header must be a JSON object"),
+    };
+    header.insert("alg".to_string(), Value::from(alg.name()));
+
+    let signing_input = format!("{}.{}", to_base64url(Value::Object(header).to_string().as_bytes()), to_base64url(payload));
+    let curve = alg.curve();
+    let signature = curve.ecdsa().sign_digest(&alg.digest(signing_input.as_bytes()), private_key)?;
+    Ok(format!("{}.{}", signing_input, to_base64url(&signature.to_fixed_bytes(curve.scalar_size()))))
+}
+
+// (header, payload) of a token signed with alg by public_key
+pub fn verify(alg: Algorithm, public_key: &Point, token: &str) -> Result<(Value, Vec<u8>), &'static str> {
+    let parts: Vec<&str> = token.split('.').collect();
+    let [header_part, payload_part, signature_part] = parts[..] else {
+        return Err("JWS compact serialization has three parts");
+    };
+
+    let header: Value = serde_json::from_slice(&from_base64url(header_part)?).map_err(|_| "JWS header is not JSON")?;
+    if !header.is_object() {
+        return Err("JWS header must be a JSON object");
+    }
+    if header["alg"] != alg.name() {
+        return Err("unexpected JWS algorithm");
+    }
+    if header.get("crit").is_some() {
+        return Err("unsupported critical JWS header");
+    }
+
+    let curve = alg.curve();
+    let signature = ECDSASignature::from_fixed_bytes(&from_base64url(signature_part)?, curve.scalar_size())?;
+    let signing_input = &token[..header_part.len() + 1 + payload_part.len()];
+    if !curve.ecdsa().verify_digest(&alg.digest(signing_input.as_bytes()), &signature, public_key) {
+        return Err("invalid JWS signature");
+    }
+    Ok((header, from_base64url(payload_part)?))
+}
+
+// Public key as a JWK (RFC 7518 6.2): {"kty": "EC", "crv": ..., "x": ..., "y": ...}
+pub fn to_jwk(alg: Algorithm, public_key: &Point) -> Result<Value, &'static str> {
+    let Point::Coordinate(x, y) = public_key else {
+        return Err("the identity has no JWK");
+    };
+    let len = alg.curve().field_size();
+    Ok(serde_json::json!({
+        "kty": "EC",
+        "crv": alg.curve_name(),
+        "x": to_base64url(&encoding::to_fixed_bytes(x, len)),
+        "y": to_base64url(&encoding::to_fixed_bytes(y, len)),
+    }))
+}
+
+// The algorithm for the key's curve and the validated point
+pub fn from_jwk(jwk: &Value) -> Result<(Algorithm, Point), &'static str> {
+    if jwk["kty"] != "EC" {
+        return Err("not an EC JWK");
+    }
+    let alg = [Algorithm::ES256, Algorithm::ES384, Algorithm::ES512]
+        .into_iter()
+        .find(|alg| jwk["crv"] == alg.curve_name())
+        .ok_or("unsupported JWK curve")?;
+    let coordinate = |name: &str| -> Result<Vec<u8>, &'static str> {
+        let bytes = from_base64url(jwk[name].as_str().ok_or("missing JWK coordinate")?)?;
+        // Coordinates are exactly the field size (RFC 7518 6.2.1.2)
+        if bytes.len() != alg.curve().field_size() {
+            return Err("JWK coordinate has the wrong length");
+        }
+        Ok(bytes)
+    };
+    let encoded = [&[0x04][..], &coordinate("x")?, &coordinate("y")?].concat();
+    Ok((alg, encoding::decode_point(&alg.curve().curve, &encoded)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rfc7515_es256() {
+        // RFC 7515 A.3
+        let jwk = json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "x": "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU",
+            "y": "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0",
+        });
+        let token = concat!(
+            "eyJhbGciOiJFUzI1NiJ9",
+            ".eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ",
+            ".DtEhU3ljbEg8L38VWAfUAqOyKAM6-Xx-F4GawxaepmXFCgfTjDxw5djxLa8ISlSApmWQxfKTUJqPP3-Kg6NU1Q",
+        );
+        let (alg, public_key) = from_jwk(&jwk).unwrap();
+        assert_eq!(alg, Algorithm::ES256);
+        assert_eq!(to_jwk(alg, &public_key), Ok(jwk));
+        assert!(to_jwk(alg, &Point::Identity).is_err());
+
+        let (header, payload) = verify(Algorithm::ES256, &public_key, token).unwrap();
+        assert_eq!(header, json!({"alg": "ES256"}));
+        assert_eq!(payload, b"{\"iss\":\"joe\",\r\n \"exp\":1300819380,\r\n \"http://example.com/is_root\":true}");
+
+        // The same token checked as ES384, and with a byte of the payload changed
+        assert!(verify(Algorithm::ES384, &public_key, token).is_err());
+        assert!(verify(Algorithm::ES256, &public_key, &token.replacen(".eyJpc3", ".eyJpd3", 1)).is_err());
+    }
+
+    #[test]
+    fn test_es512_vector() {
+        // Signed with pyca/cryptography on P-521
+        let jwk = json!({
+            "kty": "EC",
+            "crv": "P-521",
+            "x": "AIGJnA1_EmsdorIIX0EcZIHd5yow1vD11uR2fydMTV9FT2856kPCkvlUEx9lmsHPvGCzp4Dc_YixcVmeGUrQRtyi",
+            "y": "AaSrVAlQOZgOcavYg-YcIgEn6wXtmZkdLw5wMaia0d2Fvrgm7Ni5NfJ6edbbhlnVLOWxcVYwgV0E4QPO5fo4NJ9q",
+        });
+        let token = concat!(
+            "eyJhbGciOiJFUzUxMiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiJhbGljZSIsImF1ZCI6ImNyYXRlIn0.",
+            "AP1H7v7Z1mnU2opgwlWDXkw_HGqa3FPobB_NLl8sX-wAJrKh4ZZ2yOd2OJRfwNSo9q5FpblZyWk0QC2PJFqlxqTZ",
+            "AbJEyF5DK8_i53BfQhZp5-VAZDvmomSTJvEqWPi7NLKEbpHzFUb0dgdPTUeGzyYDxn_tgyeccLY99enIw0_nMDjZ",
+        );
+        let (alg, public_key) = from_jwk(&jwk).unwrap();
+        assert_eq!(alg, Algorithm::ES512);
+        let (_, payload) = verify(alg, &public_key, token).unwrap();
+        assert_eq!(payload, br#"{"sub":"alice","aud":"crate"}"#);
+
+        // A coordinate without its leading zero byte
+        let mut short = jwk.clone();
+        short["x"] = Value::from(to_base64url(&from_base64url(jwk["x"].as_str().unwrap()).unwrap()[1..]));
+        assert!(from_jwk(&short).is_err());
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        for alg in [Algorithm::ES256, Algorithm::ES384, Algorithm::ES512] {
+            let ecdsa = alg.curve().ecdsa();
+            let keypair = ecdsa.generate_keypair();
+            let token = sign(alg, &keypair.private_key, &json!({"typ": "JWT", "kid": "k1"}), b"{\"sub\":\"1234\"}").unwrap();
+            let (header, payload) = verify(alg, &keypair.public_key, &token).unwrap();
+            assert_eq!(header, json!({"alg": alg.name(), "typ": "JWT", "kid": "k1"}));
+            assert_eq!(payload, b"{\"sub\":\"1234\"}");
+
+            // r || s is twice the scalar size, 64, 96 or 132 bytes
+            let signature = from_base64url(token.rsplit('.').next().unwrap()).unwrap();
+            assert_eq!(signature.len(), 2 * alg.curve().scalar_size());
+        }
+    }
+
+    #[test]
+    fn test_rejected_tokens() {
+        let ecdsa = Algorithm::ES256.curve().ecdsa();
+        let keypair = ecdsa.generate_keypair();
+        let token = sign(Algorithm::ES256, &keypair.private_key, &Value::Null, b"payload").unwrap();
+        let [_, payload, signature] = token.split('.').collect::<Vec<_>>()[..] else { panic!() };
+
+        // alg none, a critical extension, a DER signature and a missing part
+        let none = format!("{}.{}.", to_base64url(br#"{"alg":"none"}"#), payload);
+        assert_eq!(verify(Algorithm::ES256, &keypair.public_key, &none), Err("unexpected JWS algorithm"));
+        let crit = sign(Algorithm::ES256, &keypair.private_key, &json!({"crit": ["exp"], "exp": 1}), b"payload").unwrap();
+        assert_eq!(verify(Algorithm::ES256, &keypair.public_key, &crit), Err("unsupported critical JWS header"));
+        let der = ECDSASignature::from_fixed_bytes(&from_base64url(signature).unwrap(), 32).unwrap().to_der();
+        let der_token = format!("{}.{}", token.rsplit_once('.').unwrap().0, to_base64url(&der));
+        assert!(verify(Algorithm::ES256, &keypair.public_key, &der_token).is_err());
+        assert!(verify(Algorithm::ES256, &keypair.public_key, token.rsplit_once('.').unwrap().0).is_err());
+        assert!(sign(Algorithm::ES256, &keypair.private_key, &json!([1, 2]), b"payload").is_err());
+    }
+}
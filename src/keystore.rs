@@ -0,0 +1,192 @@
+use crate::addresses::ethereum::{address_bytes, keccak256};
+use crate::curves;
+use crate::encoding::{from_hex, to_fixed_bytes, to_hex};
+use crate::pkcs8::{derive_key, Kdf};
+use crate::secret_key::SecretKey;
+use crate::entropy::Entropy;
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use num_bigint::BigUint;
+use rand::RngCore;
+use serde_json::{json, Value};
+use subtle::ConstantTimeEq;
+
+// Web3 Secret Storage (keystore v3), the encrypted JSON wallet file of geth, MetaMask & co:
+//   derived key  dk = scrypt or PBKDF2-HMAC-SHA256 (password, salt), 32 bytes
+//   ciphertext   AES-128-CTR (key dk[0..16], iv) over the 32-byte secp256k1 private key
+//   mac          keccak256(dk[16..32] || ciphertext), checked before decrypting
+// {"crypto": {"cipher", "cipherparams": {"iv"}, "ciphertext", "kdf", "kdfparams", "mac"},
+//  "id": UUID, "version": 3, "address": optional, hex without 0x}, all binary fields lowercase
+// hex. Some wallets write "Crypto". The address, when present, must match the key.
+// KDF costs are capped as for PKCS#8 (see pkcs8.rs); geth's standard and light scrypt are within
+
+// geth's defaults: 256 MiB and about a second, or 4 MiB for constrained devices
+pub const STANDARD_SCRYPT: Kdf = Kdf::Scrypt { log_n: 18, r: 8, p: 1 };
+pub const LIGHT_SCRYPT: Kdf = Kdf::Scrypt { log_n: 12, r: 8, p: 6 };
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    Entropy.fill_bytes(&mut bytes);
+    bytes
+}
+
+// Random (version 4) UUID in its 8-4-4-4-12 form
+fn uuid_v4() -> String {
+    let mut bytes = random_bytes::<16>();
+    bytes[6] = bytes[6] & 0x0f | 0x40;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+    let hex = to_hex(&bytes);
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+fn apply_keystream(key: &[u8], iv: &[u8; 16], data: &mut [u8]) {
+    ctr::Ctr128BE::<Aes128>::new(key.into(), iv.into()).apply_keystream(data);
+}
+
+fn mac(derived_key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    keccak256(&[&derived_key[16..32], ciphertext].concat())
+}
+
+// A keystore file for a secp256k1 key, with a fresh salt and IV
+pub fn encrypt(key: &SecretKey, password: &str, kdf: &Kdf) -> Result<String, &'static str> {
+    if key.curve().name != "secp256k1" {
+        return Err("Ethereum keystores hold secp256k1 keys");
+    }
+    let (salt, iv) = (random_bytes::<32>(), random_bytes::<16>());
+    let derived_key = derive_key(password, &salt, kdf, 32)?;
+    let mut ciphertext = to_fixed_bytes(key.scalar(), 32);
+    apply_keystream(&derived_key[..16], &iv, &mut ciphertext);
+
+    let kdfparams = match *kdf {
+        Kdf::Scrypt { log_n, r, p } => json!({"dklen": 32, "n": 1u64 << log_n, "r": r, "p": p, "salt": to_hex(&salt)}),
+        Kdf::Pbkdf2Sha256 { iterations } => json!({"c": iterations, "dklen": 32, "prf": "hmac-sha256", "salt": to_hex(&salt)}),
+    };
+    let keystore = json!({
+        "address": to_hex(&address_bytes(&key.public_key())?),
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": to_hex(&iv)},
+            "ciphertext": to_hex(&ciphertext),
+            "kdf": if matches!(kdf, Kdf::Scrypt { .. }) { "scrypt" } else { "pbkdf2" },
+            "kdfparams": kdfparams,
+            "mac": to_hex(&mac(&derived_key, &ciphertext)),
+        },
+        "id": uuid_v4(),
+        "version": 3,
+    });
+    Ok(serde_json::to_string_pretty(&keystore).unwrap())
+}
+
+fn field<'a>(value: &'a Value, name: &str) -> Result<&'a Value, &'static str> {
+    value.get(name).ok_or("keystore is missing a field")
+}
+
+fn hex_field(value: &Value, name: &str) -> Result<Vec<u8>, &'static str> {
+    from_hex(field(value, name)?.as_str().ok_or("keystore field is not a string")?)
+}
+
+fn u32_field(value: &Value, name: &str) -> Result<u32, &'static str> {
+    field(value, name)?.as_u64().and_then(|n| u32::try_from(n).ok()).ok_or("keystore KDF parameter out of range")
+}
+
+pub fn decrypt(json: &str, password: &str) -> Result<SecretKey, &'static str> {
+    let keystore: Value = serde_json::from_str(json).map_err(|_| "keystore is not JSON")?;
+    if keystore.get("version").and_then(Value::as_u64) != Some(3) {
+        return Err("unsupported keystore version");
+    }
+    let crypto = keystore.get("crypto").or(keystore.get("Crypto")).ok_or("keystore is missing a field")?;
+    if field(crypto, "cipher")?.as_str() != Some("aes-128-ctr") {
+        return Err("unsupported keystore cipher");
+    }
+    let kdfparams = field(crypto, "kdfparams")?;
+    if u32_field(kdfparams, "dklen")? != 32 {
+        return Err("unsupported keystore dklen");
+    }
+    let kdf = match field(crypto, "kdf")?.as_str() {
+        Some("scrypt") => {
+            let n = u32_field(kdfparams, "n")?;
+            if !n.is_power_of_two() {
+                return Err("scrypt n must be a power of two");
+            }
+            Kdf::Scrypt { log_n: n.trailing_zeros() as u8, r: u32_field(kdfparams, "r")?, p: u32_field(kdfparams, "p")? }
+        }
+        Some("pbkdf2") => {
+            if field(kdfparams, "prf")?.as_str() != Some("hmac-sha256") {
+                return Err("unsupported keystore PBKDF2 PRF");
+            }
+            Kdf::Pbkdf2Sha256 { iterations: u32_field(kdfparams, "c")? }
+        }
+        _ => return Err("unsupported keystore KDF"),
+    };
+    let iv: [u8; 16] = hex_field(field(crypto, "cipherparams")?, "iv")?.try_into().map_err(|_| "keystore IV must be 16 bytes")?;
+    let mut ciphertext = hex_field(crypto, "ciphertext")?;
+    if ciphertext.len() != 32 {
+        return Err("keystore ciphertext must be 32 bytes");
+    }
+
+    let derived_key = derive_key(password, &hex_field(kdfparams, "salt")?, &kdf, 32)?;
+    if !bool::from(mac(&derived_key, &ciphertext).ct_eq(&hex_field(crypto, "mac")?[..])) {
+        return Err("wrong password or corrupt keystore");
+    }
+    apply_keystream(&derived_key[..16], &iv, &mut ciphertext);
+    let key = SecretKey::new(&curves::secp256k1(), BigUint::from_bytes_be(&ciphertext))?;
+
+    if let Some(address) = keystore.get("address").and_then(Value::as_str) {
+        let address = from_hex(&address.trim_start_matches("0x").to_ascii_lowercase())?;
+        if address != address_bytes(&key.public_key())? {
+            return Err("keystore address doesn't match the key");
+        }
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Web3 Secret Storage definition, PBKDF2-SHA-256 test vector
+    const PBKDF2_VECTOR: &str = r#"{
+        "crypto" : {
+            "cipher" : "aes-128-ctr",
+            "cipherparams" : {"iv" : "6087dab2f9fdbbfaddc31a909735c1e6"},
+            "ciphertext" : "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf" : "pbkdf2",
+            "kdfparams" : {
+                "c" : 262144,
+                "dklen" : 32,
+                "prf" : "hmac-sha256",
+                "salt" : "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac" : "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id" : "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version" : 3
+    }"#;
+
+    #[test]
+    fn test_spec_vector() {
+        let key = decrypt(PBKDF2_VECTOR, "testpassword").unwrap();
+        assert_eq!(to_hex(&key.scalar().to_bytes_be()), "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d");
+        assert_eq!(decrypt(PBKDF2_VECTOR, "testpassworD"), Err("wrong password or corrupt keystore"));
+        assert!(decrypt(&PBKDF2_VECTOR.replace("\"version\" : 3", "\"version\" : 2"), "testpassword").is_err());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let key = SecretKey::random(&curves::secp256k1());
+        for kdf in [Kdf::Scrypt { log_n: 10, r: 8, p: 1 }, Kdf::Pbkdf2Sha256 { iterations: 1000 }] {
+            let json = encrypt(&key, "hunter2", &kdf).unwrap();
+            assert_eq!(decrypt(&json, "hunter2").unwrap(), key);
+            assert!(decrypt(&json, "hunter3").is_err());
+
+            // MyEtherWallet-style "Crypto", and an address that doesn't belong to the key
+            assert_eq!(decrypt(&json.replace("\"crypto\"", "\"Crypto\""), "hunter2").unwrap(), key);
+            let value: Value = serde_json::from_str(&json).unwrap();
+            let address = value["address"].as_str().unwrap();
+            let other = address_bytes(&SecretKey::random(&curves::secp256k1()).public_key()).unwrap();
+            assert!(decrypt(&json.replace(address, &to_hex(&other)), "hunter2").is_err());
+            assert_eq!(value["id"].as_str().unwrap().as_bytes()[14], b'4');
+        }
+        assert!(encrypt(&SecretKey::random(&curves::p256()), "pw", &LIGHT_SCRYPT).is_err());
+    }
+}
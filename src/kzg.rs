@@ -0,0 +1,226 @@
+use crate::{EllipticCurve, FiniteField, Point};
+use crate::bls12_381::{self, Bls12Pairing};
+use crate::extension::{ExtPoint, ExtensionCurve};
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use num_bigint::BigUint;
+
+// KZG polynomial commitments (Kate–Zaverucha–Goldberg) over BLS12-381
+//
+// Setup publishes [τ^i]G1 and [τ]G2 for a secret τ nobody may keep ("toxic waste").
+// A polynomial p over F_r is committed as C = [p(τ)]G1 = Σ c_i [τ^i]G1.
+// To open at z with y = p(z): (x - z) divides p(x) - y, so q(x) = (p(x) - y) / (x - z)
+// is a polynomial and the proof is π = [q(τ)]G1. The verifier checks
+//   e(C - [y]G1, G2) = e(π, [τ]G2 - [z]G2)     i.e. p(τ) - y = q(τ)(τ - z)
+// without learning τ. Anyone who knows τ can open C to any value.
+
+// Structured reference string: powers of τ in G1 up to the maximum degree, and [τ]G2
+#[derive(Clone, Debug)]
+pub struct TrustedSetup {
+    pub g1_powers: Vec<Point>,
+    pub g2_generator: ExtPoint,
+    pub g2_tau: ExtPoint,
+}
+
+impl TrustedSetup {
+    // Single-party setup: τ is sampled and dropped before returning
+    // Real deployments run a multi-party ceremony so no one ever learns τ
+    pub fn generate(max_degree: usize) -> Self {
+        let tau = Scalar::random(&bls12_381::scalar_order(), &mut Entropy).into_value();
+        Self::from_secret(&tau, max_degree)
+    }
+
+    // Setup from a known τ: insecure, for tests and demonstrations only
+    pub fn from_secret(tau: &BigUint, max_degree: usize) -> Self {
+        let g1 = bls12_381::g1_curve();
+        let g2 = bls12_381::g2_curve();
+        let order = bls12_381::scalar_order();
+        let generator = bls12_381::g1_generator();
+
+        let mut g1_powers = Vec::with_capacity(max_degree + 1);
+        let mut tau_power = BigUint::from(1u32);
+        for _ in 0..=max_degree {
+            g1_powers.push(g1.scalar_mult(&generator, &tau_power));
+            tau_power = (tau_power * tau) % &order;
+        }
+
+        let g2_generator = bls12_381::g2_generator();
+        TrustedSetup {
+            g1_powers,
+            g2_tau: g2.scalar_mult(&g2_generator, tau),
+            g2_generator,
+        }
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.g1_powers.len() - 1
+    }
+}
+
+// An opening of a commitment at some point z: y = p(z) and the proof π = [q(τ)]G1
+#[derive(Clone, Debug, PartialEq)]
+pub struct KZGOpening {
+    pub value: BigUint,
+    pub proof: Point,
+}
+
+pub struct KZG {
+    pub setup: TrustedSetup,
+    pub g1: EllipticCurve,
+    pub g2: ExtensionCurve,
+    pub order: BigUint,
+    pub pairing: Bls12Pairing,
+}
+
+impl KZG {
+    pub fn new(setup: TrustedSetup) -> Self {
+        KZG {
+            setup,
+            g1: bls12_381::g1_curve(),
+            g2: bls12_381::g2_curve(),
+            order: bls12_381::scalar_order(),
+            pairing: Bls12Pairing::new(),
+        }
+    }
+
+    // C = Σ c_i [τ^i]G1 for p(x) = Σ c_i x^i (coefficients lowest degree first, mod r)
+    pub fn commit(&self, polynomial: &[BigUint]) -> Result<Point, &'static str> {
+        if polynomial.len() > self.setup.g1_powers.len() {
+            return Err("polynomial degree exceeds the trusted setup");
+        }
+        let mut commitment = Point::Identity;
+        for (coeff, power) in polynomial.iter().zip(&self.setup.g1_powers) {
+            let term = self.g1.scalar_mult(power, &(coeff % &self.order));
+            commitment = self.g1.add(&commitment, &term);
+        }
+        Ok(commitment)
+    }
+
+    // y = p(z) and π = commit(q) for q(x) = (p(x) - y) / (x - z)
+    pub fn open(&self, polynomial: &[BigUint], z: &BigUint) -> Result<KZGOpening, &'static str> {
+        let field = FiniteField { p: self.order.clone() };
+        let (quotient, value) = divide_by_linear(polynomial, z, &field);
+        Ok(KZGOpening {
+            value,
+            proof: self.commit(&quotient)?,
+        })
+    }
+
+    // e(C - [y]G1, G2) = e(π, [τ]G2 - [z]G2), checked as
+    // e(C - [y]G1, -G2) * e(π, [τ - z]G2) = 1 with a single final exponentiation
+    pub fn verify(&self, commitment: &Point, z: &BigUint, opening: &KZGOpening) -> bool {
+        if !self.g1.is_on_curve(commitment) || !self.g1.is_on_curve(&opening.proof) {
+            return false;
+        }
+
+        let generator = &self.setup.g1_powers[0];
+        let y_g1 = self.g1.scalar_mult(generator, &(&opening.value % &self.order));
+        let commitment_minus_y = self.g1.add(commitment, &self.g1.neg(&y_g1));
+
+        let z_g2 = self.g2.scalar_mult(&self.setup.g2_generator, &(z % &self.order));
+        let tau_minus_z = self.g2.add(&self.setup.g2_tau, &self.g2.neg(&z_g2));
+
+        // Identity inputs contribute e = 1 and have no Miller function: drop them
+        let mut pairs = Vec::new();
+        if commitment_minus_y != Point::Identity {
+            pairs.push((commitment_minus_y, self.g2.neg(&self.setup.g2_generator)));
+        }
+        if opening.proof != Point::Identity && tau_minus_z != ExtPoint::Identity {
+            pairs.push((opening.proof.clone(), tau_minus_z));
+        }
+        if pairs.is_empty() {
+            return true;
+        }
+
+        match self.pairing.pairing_product(&pairs) {
+            Ok(value) => value == self.pairing.tate.curve.field.one(),
+            Err(_) => false,
+        }
+    }
+}
+
+// Synthetic division by (x - z): returns (q, p(z)) with p(x) = q(x)(x - z) + p(z)
+fn divide_by_linear(polynomial: &[BigUint], z: &BigUint, field: &FiniteField) -> (Vec<BigUint>, BigUint) {
+    if polynomial.is_empty() {
+        return (Vec::new(), BigUint::from(0u32));
+    }
+    // Horner from the top: each running value is the next quotient coefficient
+    let mut quotient = vec![BigUint::from(0u32); polynomial.len() - 1];
+    let mut running = BigUint::from(0u32);
+    for i in (0..polynomial.len()).rev() {
+        running = field.add(&field.mul(&running, z), &(&polynomial[i] % &field.p));
+        if i > 0 {
+            quotient[i - 1] = running.clone();
+        }
+    }
+    (quotient, running)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(coeffs: &[u32]) -> Vec<BigUint> {
+        coeffs.iter().map(|&c| BigUint::from(c)).collect()
+    }
+
+    #[test]
+    fn test_divide_by_linear() {
+        // x² + 2x + 3 = (x - 1)(x + 3) + 6
+        let field = FiniteField { p: bls12_381::scalar_order() };
+        let (quotient, remainder) = divide_by_linear(&poly(&[3, 2, 1]), &BigUint::from(1u32), &field);
+        assert_eq!(quotient, poly(&[3, 1]));
+        assert_eq!(remainder, BigUint::from(6u32));
+    }
+
+    #[test]
+    fn test_commit_is_evaluation_at_tau() {
+        // With a known τ, C = [p(τ)]G1
+        let tau = BigUint::from(5u32);
+        let kzg = KZG::new(TrustedSetup::from_secret(&tau, 3));
+        let commitment = kzg.commit(&poly(&[1, 2, 3])).unwrap();
+
+        // p(5) = 1 + 10 + 75 = 86
+        let expected = kzg.g1.scalar_mult(&bls12_381::g1_generator(), &BigUint::from(86u32));
+        assert_eq!(commitment, expected);
+
+        assert!(kzg.commit(&poly(&[1, 2, 3, 4, 5])).is_err());
+    }
+
+    #[test]
+    fn test_open_verify() {
+        let kzg = KZG::new(TrustedSetup::generate(3));
+        let polynomial = poly(&[7, 0, 4, 1]);
+        let commitment = kzg.commit(&polynomial).unwrap();
+
+        // p(3) = 7 + 36 + 27 = 70
+        let z = BigUint::from(3u32);
+        let opening = kzg.open(&polynomial, &z).unwrap();
+        assert_eq!(opening.value, BigUint::from(70u32));
+        assert!(kzg.verify(&commitment, &z, &opening));
+
+        // Claiming a different value with the same proof fails
+        let lie = KZGOpening { value: BigUint::from(71u32), proof: opening.proof.clone() };
+        assert!(!kzg.verify(&commitment, &z, &lie));
+    }
+
+    #[test]
+    fn test_knowing_tau_breaks_binding() {
+        // π = [(p(τ) - y') / (τ - z)]G1 opens C to any y' once τ is known
+        let tau = BigUint::from(11u32);
+        let kzg = KZG::new(TrustedSetup::from_secret(&tau, 2));
+        let field = FiniteField { p: kzg.order.clone() };
+        let polynomial = poly(&[1, 1, 1]);
+        let commitment = kzg.commit(&polynomial).unwrap();
+
+        let z = BigUint::from(2u32);
+        let fake_value = BigUint::from(1000u32);
+        let p_tau = BigUint::from(1u32 + 11 + 121);
+        let q_tau = field.div(&field.sub(&p_tau, &fake_value), &field.sub(&tau, &z));
+        let forged = KZGOpening {
+            value: fake_value,
+            proof: kzg.g1.scalar_mult(&bls12_381::g1_generator(), &q_tau),
+        };
+        assert!(kzg.verify(&commitment, &z, &forged));
+    }
+}
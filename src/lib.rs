@@ -1,39 +1,338 @@
 use num_bigint::BigUint;
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 pub mod ecdsa;
+pub mod ecdh;
+pub mod attacks;
+pub mod ntheory;
+pub mod extension;
+pub mod pairing;
+pub mod bls12_381;
+pub mod bls;
+pub mod kzg;
+pub mod elgamal;
+pub mod curves;
+pub mod encoding;
+pub mod hash_to_curve;
+pub mod oprf;
+pub mod zkp;
+pub mod ring_sig;
+pub mod schnorr;
+pub mod blind_schnorr;
+pub mod adaptor;
+pub mod bip340;
+pub mod musig2;
+pub mod secret_sharing;
+pub mod frost;
+pub mod dkg;
+pub mod threshold_ecdsa;
+pub mod hd;
+pub mod edwards;
+pub mod ed25519;
+pub mod montgomery;
+pub mod ed448;
+pub mod ristretto;
+pub mod birational;
+pub mod binary;
+pub mod const_curve;
+pub mod fields;
+pub mod scalar;
+pub mod bip39;
+pub mod wif;
+pub mod addresses;
+pub mod recoverable;
+pub mod eth;
+pub mod projective;
+pub mod ct;
+pub mod der;
+pub mod ssh;
+pub mod sshsig;
+pub mod sigfile;
+pub mod jose;
+pub mod cbor;
+pub mod cose;
+pub mod hpke;
+pub mod noise;
+pub mod x3dh;
+pub mod signcryption;
+pub mod secret_key;
+pub mod public_key;
+pub mod signer;
+pub mod pkcs8;
+pub mod keystore;
+pub mod entropy;
+pub mod schoof;
+pub mod cm;
+pub mod verifiably_random;
+pub mod security;
+pub mod isogeny;
+pub mod isomorphism;
+pub mod visualize;
+pub mod trace;
+pub mod group_structure;
+#[cfg(test)]
+mod wycheproof;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+#[cfg(feature = "cbor")]
+mod cbor_types;
+#[cfg(feature = "async")]
+pub mod async_signer;
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
 
 // y^2 = x^3 + ax + b (mod p)
-#[derive(Clone)]
+// The base field is built once in new() and borrowed by every operation, instead of cloning p
+// into a fresh FiniteField per add/double. The parameters are read through a(), b() and p() so
+// the cached field can't go stale; a different curve is a new EllipticCurve
+#[derive(Clone, Debug, PartialEq)]
 pub struct EllipticCurve {
-    pub a: BigUint,
-    pub b: BigUint,
-    pub p: BigUint,
+    a: BigUint,
+    b: BigUint,
+    p: BigUint,
+    field: FiniteField,
 }
 
+// Twist order and its factorization, for judging twist security
+// x-only ladders accept any x, and half of all x values land on the twist:
+// a twist order with only small factors lets an attacker learn d mod each factor
 #[derive(Clone, Debug, PartialEq)]
+pub struct TwistSecurity {
+    pub twist_order: BigUint,
+    pub factors: Vec<(BigUint, u32)>,
+    pub largest_prime_factor: BigUint,
+}
+
+// Eq, Hash and Ord work on the affine coordinates, which are canonical as long as they're
+// reduced mod p (is_on_curve and decode_point reject anything else), so points can key
+// HashMaps and BTreeMaps. Ordering matches the SEC1 uncompressed encoding: the identity (0x00)
+// first, then by x, then by y
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Point {
     Coordinate(BigUint, BigUint),  // Regular point (x, y)
     Identity,                      // Point at infinity (identity element)
 }
 
+impl Ord for Point {
+    fn cmp(&self, other: &Point) -> std::cmp::Ordering {
+        match (self, other) {
+            (Point::Identity, Point::Identity) => std::cmp::Ordering::Equal,
+            (Point::Identity, _) => std::cmp::Ordering::Less,
+            (_, Point::Identity) => std::cmp::Ordering::Greater,
+            (Point::Coordinate(x1, y1), Point::Coordinate(x2, y2)) => (x1, y1).cmp(&(x2, y2)),
+        }
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other: &Point) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+// A point together with the curve it lives on, so group operations can be written as
+// p + q, -p, p - q and k * p. Combining points from different curves panics
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurvePoint<'a> {
+    curve: &'a EllipticCurve,
+    point: Point,
+}
+
+impl<'a> CurvePoint<'a> {
+    pub fn curve(&self) -> &'a EllipticCurve {
+        self.curve
+    }
+
+    pub fn point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn into_point(self) -> Point {
+        self.point
+    }
+
+    fn same_curve(&self, other: &CurvePoint) {
+        if !std::ptr::eq(self.curve, other.curve) && self.curve != other.curve {
+            panic!("?cannot combine points on different curves?");
+        }
+    }
+}
+
+impl<'a> std::ops::Add<&CurvePoint<'a>> for &CurvePoint<'a> {
+    type Output = CurvePoint<'a>;
+    fn add(self, other: &CurvePoint<'a>) -> CurvePoint<'a> {
+        self.same_curve(other);
+        CurvePoint { curve: self.curve, point: self.curve.add(&self.point, &other.point) }
+    }
+}
+
+impl<'a> std::ops::Add for CurvePoint<'a> {
+    type Output = CurvePoint<'a>;
+    fn add(self, other: CurvePoint<'a>) -> CurvePoint<'a> {
+        &self + &other
+    }
+}
+
+impl<'a> std::ops::AddAssign<&CurvePoint<'a>> for CurvePoint<'a> {
+    fn add_assign(&mut self, other: &CurvePoint<'a>) {
+        *self = &*self + other;
+    }
+}
+
+impl<'a> std::ops::AddAssign for CurvePoint<'a> {
+    fn add_assign(&mut self, other: CurvePoint<'a>) {
+        *self += &other;
+    }
+}
+
+impl<'a> std::ops::Neg for &CurvePoint<'a> {
+    type Output = CurvePoint<'a>;
+    fn neg(self) -> CurvePoint<'a> {
+        CurvePoint { curve: self.curve, point: self.curve.neg(&self.point) }
+    }
+}
+
+impl<'a> std::ops::Neg for CurvePoint<'a> {
+    type Output = CurvePoint<'a>;
+    fn neg(self) -> CurvePoint<'a> {
+        -&self
+    }
+}
+
+impl<'a> std::ops::Sub<&CurvePoint<'a>> for &CurvePoint<'a> {
+    type Output = CurvePoint<'a>;
+    fn sub(self, other: &CurvePoint<'a>) -> CurvePoint<'a> {
+        self + &-other
+    }
+}
+
+impl<'a> std::ops::Sub for CurvePoint<'a> {
+    type Output = CurvePoint<'a>;
+    fn sub(self, other: CurvePoint<'a>) -> CurvePoint<'a> {
+        &self - &other
+    }
+}
+
+// Scalar multiplication in both orders: p * k and k * p
+impl<'a> std::ops::Mul<&BigUint> for &CurvePoint<'a> {
+    type Output = CurvePoint<'a>;
+    fn mul(self, k: &BigUint) -> CurvePoint<'a> {
+        CurvePoint { curve: self.curve, point: self.curve.scalar_mult(&self.point, k) }
+    }
+}
+
+impl<'a> std::ops::Mul<&BigUint> for CurvePoint<'a> {
+    type Output = CurvePoint<'a>;
+    fn mul(self, k: &BigUint) -> CurvePoint<'a> {
+        &self * k
+    }
+}
+
+impl<'a> std::ops::Mul<&CurvePoint<'a>> for &BigUint {
+    type Output = CurvePoint<'a>;
+    fn mul(self, point: &CurvePoint<'a>) -> CurvePoint<'a> {
+        point * self
+    }
+}
+
+impl<'a> std::ops::Mul<CurvePoint<'a>> for &BigUint {
+    type Output = CurvePoint<'a>;
+    fn mul(self, point: CurvePoint<'a>) -> CurvePoint<'a> {
+        &point * self
+    }
+}
+
+// Every point of the curve, from EllipticCurve::points: the identity, then the affine points in
+// Ord order (by x, then y). O(p) like count_points, so small curves only
+#[derive(Clone, Debug)]
+pub struct Points<'a> {
+    curve: &'a EllipticCurve,
+    x: BigUint,
+    // -P, after P for the same x
+    pending: Option<Point>,
+    started: bool,
+}
+
+impl Iterator for Points<'_> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if !self.started {
+            self.started = true;
+            return Some(Point::Identity);
+        }
+        if let Some(point) = self.pending.take() {
+            return Some(point);
+        }
+        while self.x < self.curve.p {
+            let x = self.x.clone();
+            self.x += 1u32;
+            if let Some((Point::Coordinate(_, y), _)) = self.curve.lift_x(&x) {
+                let neg_y = self.curve.field.sub(&BigUint::from(0u32), &y);
+                if neg_y != y {
+                    self.pending = Some(Point::Coordinate(x.clone(), neg_y.clone().max(y.clone())));
+                }
+                return Some(Point::Coordinate(x, neg_y.min(y)));
+            }
+        }
+        None
+    }
+}
 
 impl EllipticCurve {
+    pub fn new(a: BigUint, b: BigUint, p: BigUint) -> Self {
+        let field = FiniteField { p: p.clone() };
+        EllipticCurve { a, b, p, field }
+    }
+
+    pub fn a(&self) -> &BigUint {
+        &self.a
+    }
+
+    pub fn b(&self) -> &BigUint {
+        &self.b
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    // The base field F_p
+    pub fn field(&self) -> &FiniteField {
+        &self.field
+    }
+
+    // Attach this curve to a point for operator arithmetic
+    pub fn point(&self, point: Point) -> CurvePoint<'_> {
+        CurvePoint { curve: self, point }
+    }
+
     // Check if a point is on the elliptic curve: y² = x³ + ax + b (mod p)
     pub fn is_on_curve(&self, point: &Point) -> bool {
         match point {
             Point::Identity => true,  // Point at infinity is always on the curve
             Point::Coordinate(x, y) => {
-                let field = FiniteField { p: self.p.clone() };
-                
+                let f = &self.field;
+
+                // Coordinates must be field elements: x + p would satisfy the equation too,
+                // but the point formulas expect values below p
+                if *x >= self.p || *y >= self.p {
+                    return false;
+                }
+
+                let (x, y) = (f.element(x), f.element(y));
+
                 // Left side: y²
-                let y_squared = field.mul(y, y);
-                
+                let y_squared = &y * &y;
+
                 // Right side: x³ + ax + b
-                let x_squared = field.mul(x, x);
-                let x_cubed = field.mul(&x_squared, x);
-                let ax = field.mul(&self.a, x);
-                let right_side = field.add(&field.add(&x_cubed, &ax), &self.b);
-                
+                let x_cubed = &x * &x * &x;
+                let right_side = x_cubed + f.element(&self.a) * &x + f.element(&self.b);
+
                 y_squared == right_side
             }
         }
@@ -45,10 +344,10 @@ impl EllipticCurve {
             // Identity cases: P + O = P, O + P = P
             (Point::Identity, _) => q.clone(),
             (_, Point::Identity) => p.clone(),
-            
+
             (Point::Coordinate(x1, y1), Point::Coordinate(x2, y2)) => {
-                let field = FiniteField { p: self.p.clone() };
-                
+                let f = &self.field;
+
                 // Case 1: Same x-coordinate
                 if x1 == x2 {
                     if y1 == y2 {
@@ -60,20 +359,24 @@ impl EllipticCurve {
                     }
                 } else {
                     // Case 2: Different points
+                    let (x1, y1) = (f.element(x1), f.element(y1));
+                    let (x2, y2) = (f.element(x2), f.element(y2));
+
                     // Slope: s = (y2 - y1) / (x2 - x1)
-                    let dy = field.sub(y2, y1);
-                    let dx = field.sub(x2, x1);
-                    let slope = field.div(&dy, &dx);
-                    
+                    let slope = (&y2 - &y1) / (&x2 - &x1);
+
                     // x3 = s² - x1 - x2
-                    let slope_squared = field.mul(&slope, &slope);
-                    let x3 = field.sub(&field.sub(&slope_squared, x1), x2);
-                    
+                    let mut x3 = slope.clone();
+                    x3.square_in_place();
+                    x3 -= &x1;
+                    x3 -= &x2;
+
                     // y3 = s(x1 - x3) - y1
-                    let dx1_x3 = field.sub(x1, &x3);
-                    let y3 = field.sub(&field.mul(&slope, &dx1_x3), y1);
-                    
-                    Point::Coordinate(x3, y3)
+                    let mut y3 = &x1 - &x3;
+                    y3 *= &slope;
+                    y3 -= &y1;
+
+                    Point::Coordinate(x3.into_value(), y3.into_value())
                 }
             }
         }
@@ -84,37 +387,48 @@ impl EllipticCurve {
         match p {
             Point::Identity => Point::Identity,
             Point::Coordinate(x, y) => {
-                let field = FiniteField { p: self.p.clone() };
-                
+                let f = &self.field;
+
                 // Check if y = 0 (point has order 2)
                 if *y == BigUint::from(0u32) {
                     return Point::Identity;
                 }
-                
+
+                let (x, y) = (f.element(x), f.element(y));
+
                 // Slope: s = (3x² + a) / (2y)
-                let three = BigUint::from(3u32);
-                let two = BigUint::from(2u32);
-                
-                let x_squared = field.mul(x, x);
-                let three_x_squared = field.mul(&three, &x_squared);
-                let numerator = field.add(&three_x_squared, &self.a);
-                let denominator = field.mul(&two, y);
-                let slope = field.div(&numerator, &denominator);
-                
+                let mut x_squared = x.clone();
+                x_squared.square_in_place();
+                let mut numerator = x_squared.clone();
+                numerator += &x_squared;
+                numerator += &x_squared;
+                numerator += &f.element(&self.a);
+                let slope = numerator / (&y + &y);
+
                 // x3 = s² - 2x
-                let slope_squared = field.mul(&slope, &slope);
-                let two_x = field.mul(&two, x);
-                let x3 = field.sub(&slope_squared, &two_x);
-                
+                let mut x3 = slope.clone();
+                x3.square_in_place();
+                x3 -= &x;
+                x3 -= &x;
+
                 // y3 = s(x - x3) - y
-                let dx_x3 = field.sub(x, &x3);
-                let y3 = field.sub(&field.mul(&slope, &dx_x3), y);
-                
-                Point::Coordinate(x3, y3)
+                let mut y3 = &x - &x3;
+                y3 *= &slope;
+                y3 -= &y;
+
+                Point::Coordinate(x3.into_value(), y3.into_value())
             }
         }
     }
 
+    // Point negation: -(x, y) = (x, -y)
+    pub fn neg(&self, point: &Point) -> Point {
+        match point {
+            Point::Identity => Point::Identity,
+            Point::Coordinate(x, y) => Point::Coordinate(x.clone(), (&self.p - y % &self.p) % &self.p),
+        }
+    }
+
     // Scalar multiplication: k * P (double-and-add algorithm)
     pub fn scalar_mult(&self, point: &Point, k: &BigUint) -> Point {
         // Handle edge cases
@@ -140,11 +454,212 @@ impl EllipticCurve {
             addend = self.double(&addend);
             scalar /= BigUint::from(2u32);
         }
-        
+
         result
     }
+
+    // Both points with a given x: (x, y) and (x, -y), the same point when y = 0
+    // None if x³ + ax + b is not a square, i.e. x is not the coordinate of a curve point
+    pub fn lift_x(&self, x: &BigUint) -> Option<(Point, Point)> {
+        let field = &self.field;
+        let x = x % &self.p;
+        let rhs = field.add(&field.add(&field.mul(&field.mul(&x, &x), &x), &field.mul(&self.a, &x)), &self.b);
+        let y = field.sqrt(&rhs)?;
+        let neg_y = field.sub(&BigUint::from(0u32), &y);
+        Some((Point::Coordinate(x.clone(), y), Point::Coordinate(x, neg_y)))
+    }
+
+    // Uniform random x until one lies on the curve (about half do), then a random choice of y
+    // Says nothing about the point's order: clear the cofactor if a subgroup element is needed.
+    // Gives up after 1000 tries, which only happens on tiny curves with almost no affine points
+    pub fn random_point<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Result<Point, &'static str> {
+        use num_bigint::RandBigInt;
+        for _ in 0..1000 {
+            let x = rng.gen_biguint_below(&self.p);
+            if let Some((positive, negative)) = self.lift_x(&x) {
+                return Ok(if rng.gen_bool(0.5) { positive } else { negative });
+            }
+        }
+        Err("no curve point found")
+    }
+
+    // A point of prime order n, for deriving a generator instead of hard-coding one
+    // With #E = h * n, h * P lands in the order-n subgroup for any point P; it's a generator
+    // unless it's O. group_order is #E if known (e.g. from the curve's published parameters),
+    // otherwise it's counted, which only works for small p. n must be prime
+    pub fn find_generator_of_order<R: rand::Rng + ?Sized>(
+        &self,
+        n: &BigUint,
+        group_order: Option<&BigUint>,
+        rng: &mut R,
+    ) -> Result<Point, &'static str> {
+        let group_order = match group_order {
+            Some(order) if !self.within_hasse_bounds(order) => return Err("group order is outside the Hasse bound"),
+            Some(order) => order.clone(),
+            None => self.count_points()?,
+        };
+        if *n <= BigUint::from(1u32) || &group_order % n != BigUint::from(0u32) {
+            return Err("n does not divide the group order");
+        }
+        let cofactor = &group_order / n;
+
+        for _ in 0..1000 {
+            let candidate = self.scalar_mult(&self.random_point(rng)?, &cofactor);
+            if candidate == Point::Identity {
+                continue;
+            }
+            // Catches a wrong group_order: a true order-n point always passes
+            if self.scalar_mult(&candidate, n) != Point::Identity {
+                return Err("group order is wrong for this curve");
+            }
+            return Ok(candidate);
+        }
+        Err("no point of order n found")
+    }
+
+    // P is in E[r]: on the curve and r * P = O
+    pub fn is_torsion_point(&self, point: &Point, r: &BigUint) -> bool {
+        self.is_on_curve(point) && self.scalar_mult(point, r) == Point::Identity
+    }
+
+    // Order of a point: smallest k > 0 with k * P = O (baby-step giant-step)
+    // group_order_bound is any upper limit on the order, e.g. the group order or p + 1 + 2√p
+    // Needs O(√bound) time and memory, so it's meant for small and medium curves
+    // Errors if no order ≤ bound is found (bound too small, or the point isn't on the curve)
+    pub fn point_order(&self, point: &Point, group_order_bound: &BigUint) -> Result<BigUint, &'static str> {
+        let one = BigUint::from(1u32);
+        if *point == Point::Identity {
+            return Ok(one);
+        }
+
+        // m = ⌈√bound⌉ (or one more), so every order ≤ bound is i*m - j with 0 <= j < m
+        let m = group_order_bound.sqrt() + &one;
+
+        // Baby steps: store j * P for 1 <= j < m
+        // Hitting the identity here means the order is small and found directly
+        let mut baby_steps = HashMap::new();
+        let mut current = point.clone();
+        let mut j = one.clone();
+        while j < m {
+            if current == Point::Identity {
+                return Ok(j);
+            }
+            baby_steps.insert(current.clone(), j.clone());
+            current = self.add(&current, point);
+            j += 1u32;
+        }
+
+        // Giant steps: i * (m * P); the first match i*m*P = j*P gives order = i*m - j
+        // (any smaller i with a match would give a smaller positive multiple hitting O)
+        let giant_step = current;
+        let mut giant = giant_step.clone();
+        let mut i = one;
+        while &i * &m <= group_order_bound + &m {
+            if giant == Point::Identity {
+                return Ok(&i * &m);
+            }
+            if let Some(j) = baby_steps.get(&giant) {
+                return Ok(&i * &m - j);
+            }
+            giant = self.add(&giant, &giant_step);
+            i += 1u32;
+        }
+
+        Err("point order exceeds the given bound")
+    }
+
+    // Hasse: |#E - (p + 1)| <= 2√p, so #E lies in [p + 1 - 2√p, p + 1 + 2√p]
+    // #E is an integer, so the bounds are p + 1 ∓ ⌊2√p⌋ = p + 1 ∓ ⌊√(4p)⌋, both inclusive
+    pub fn hasse_bounds(&self) -> (BigUint, BigUint) {
+        let p_plus_one = &self.p + 1u32;
+        let width = (&self.p * 4u32).sqrt();
+        let low = if p_plus_one > width { &p_plus_one - &width } else { BigUint::from(0u32) };
+        (low, p_plus_one + width)
+    }
+
+    // Whether a claimed group order #E = h * n is possible for this curve
+    pub fn within_hasse_bounds(&self, group_order: &BigUint) -> bool {
+        let (low, high) = self.hasse_bounds();
+        low <= *group_order && *group_order <= high
+    }
+
+    // Group order #E by exhaustive counting: O(p) work, so only for small primes
+    // (count_points_schoof in schoof.rs goes further)
+    // Each x gives 2 points if x³ + ax + b is a nonzero square, 1 if it's zero, else none
+    pub fn count_points(&self) -> Result<BigUint, &'static str> {
+        let field = &self.field;
+        let zero = BigUint::from(0u32);
+        let one = BigUint::from(1u32);
+
+        let mut count = one.clone(); // point at infinity
+        let mut x = zero.clone();
+        while x < self.p {
+            let x_cubed = field.mul(&field.mul(&x, &x), &x);
+            let rhs = field.add(&field.add(&x_cubed, &field.mul(&self.a, &x)), &self.b);
+
+            // 1 + (rhs / p) points with this x
+            match field.legendre(&rhs) {
+                0 => count += 1u32,
+                1 => count += 2u32,
+                _ => {}
+            }
+            x += 1u32;
+        }
+
+        // A count outside the Hasse interval means p isn't prime (or the curve is malformed)
+        if !self.within_hasse_bounds(&count) {
+            return Err("point count outside the Hasse bound");
+        }
+
+        Ok(count)
+    }
+
+    // Enumerates every point, for exercises on toy curves (subgroup tables, order histograms)
+    // that don't want to repeat lift_x over every x: curve.points().count() is count_points
+    pub fn points(&self) -> Points<'_> {
+        Points { curve: self, x: BigUint::from(0u32), pending: None, started: false }
+    }
+
+    // Quadratic twist: y² = x³ + ad²x + bd³ for a quadratic non-residue d
+    // Isomorphic to E over F_p², but not over F_p; #E + #E' = 2(p + 1)
+    // Every element of F_2 is a square, so there's no twist of this form there
+    pub fn quadratic_twist(&self) -> Result<EllipticCurve, &'static str> {
+        let field = &self.field;
+
+        // Smallest non-residue: no square root mod p
+        let mut d = BigUint::from(2u32);
+        while field.sqrt(&d).is_some() {
+            d += 1u32;
+            if d >= self.p {
+                return Err("no quadratic non-residue mod p");
+            }
+        }
+
+        let d_squared = field.mul(&d, &d);
+        let d_cubed = field.mul(&d_squared, &d);
+        Ok(EllipticCurve::new(field.mul(&self.a, &d_squared), field.mul(&self.b, &d_cubed), self.p.clone()))
+    }
+
+    // Twist order 2(p + 1) - #E and its factorization (counts points, so small curves only)
+    pub fn twist_security(&self) -> Result<TwistSecurity, &'static str> {
+        let group_order = self.count_points()?;
+        let twist_order = BigUint::from(2u32) * (&self.p + BigUint::from(1u32)) - group_order;
+
+        let factors = ntheory::factorize(&twist_order);
+        let largest_prime_factor = factors
+            .last()
+            .map(|(prime, _)| prime.clone())
+            .unwrap_or_else(|| BigUint::from(1u32));
+
+        Ok(TwistSecurity {
+            twist_order,
+            factors,
+            largest_prime_factor,
+        })
+    }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FiniteField {
     pub p: BigUint,
 }
@@ -166,6 +681,32 @@ impl FiniteField {
         (x * y) % &self.p
     }
 
+    // In-place variants: overwrite x instead of returning a fresh BigUint, so a formula can
+    // keep reusing a handful of temporaries. Operands must already be reduced (< p)
+    pub fn add_assign(&self, x: &mut BigUint, y: &BigUint) {
+        *x += y;
+        if *x >= self.p {
+            *x -= &self.p;
+        }
+    }
+
+    pub fn sub_assign(&self, x: &mut BigUint, y: &BigUint) {
+        if *y > *x {
+            *x += &self.p;
+        }
+        *x -= y;
+    }
+
+    pub fn mul_assign(&self, x: &mut BigUint, y: &BigUint) {
+        *x *= y;
+        *x %= &self.p;
+    }
+
+    pub fn square_in_place(&self, x: &mut BigUint) {
+        let square = &*x * &*x;
+        *x = square % &self.p;
+    }
+
     // multiplication by multiplicative inverse (division)
     pub fn div(&self, x: &BigUint, y: &BigUint) -> BigUint {
         // division in finite field: x / y = x * y^(-1) mod p
@@ -175,17 +716,254 @@ impl FiniteField {
         }
         
         // Using Fermat's little theorem: y^(-1) = y^(p-2) mod p (when p is prime)
+        // One multiplication confirms it; for composite p fall back to the extended Euclidean algorithm
         let p_minus_2 = &self.p - BigUint::from(2u32);
-        let y_inverse = y.modpow(&p_minus_2, &self.p);
+        let mut y_inverse = y.modpow(&p_minus_2, &self.p);
+        if self.mul(y, &y_inverse) != BigUint::from(1u32) {
+            y_inverse = self.invert(y).expect("?divisor is not invertible modulo p?");
+        }
         self.mul(x, &y_inverse)
     }
 
+    // Multiplicative inverse by the extended Euclidean algorithm, None when gcd(x, p) ≠ 1
+    // Fermat's x^(p-2) only inverts for prime p; for composite p it quietly returns garbage
+    pub fn invert(&self, x: &BigUint) -> Option<BigUint> {
+        ntheory::mod_inverse(x, &self.p)
+    }
+
+    // Legendre symbol (x / p) for odd prime p: 0 if x ≡ 0, 1 for a nonzero square, -1 otherwise
+    // Euler's criterion: x^((p-1)/2) is 1 for squares and p - 1 for non-squares
+    pub fn legendre(&self, x: &BigUint) -> i8 {
+        let x = x % &self.p;
+        if x == BigUint::from(0u32) {
+            return 0;
+        }
+        let euler_exp = (&self.p - BigUint::from(1u32)) >> 1;
+        if x.modpow(&euler_exp, &self.p) == BigUint::from(1u32) { 1 } else { -1 }
+    }
+
+    // Squares mod p, counting 0 as a square (it has the root 0)
+    pub fn is_quadratic_residue(&self, x: &BigUint) -> bool {
+        self.legendre(x) >= 0
+    }
+
+    // Square root mod p (Tonelli–Shanks): Some(y) with y² = x, or None if x is a non-residue
+    // The other root is p - y
+    pub fn sqrt(&self, x: &BigUint) -> Option<BigUint> {
+        let zero = BigUint::from(0u32);
+        let one = BigUint::from(1u32);
+        let two = BigUint::from(2u32);
+        let x = x % &self.p;
+
+        if x == zero {
+            return Some(zero);
+        }
+        if self.p == two {
+            return Some(x);
+        }
+
+        if !self.is_quadratic_residue(&x) {
+            return None;
+        }
+        let p_minus_one = &self.p - &one;
+
+        // Write p - 1 = q * 2^s with q odd
+        let mut q = p_minus_one.clone();
+        let mut s = 0u32;
+        while &q % &two == zero {
+            q /= &two;
+            s += 1;
+        }
+
+        // Any quadratic non-residue z
+        let mut z = two.clone();
+        while self.legendre(&z) != -1 {
+            z += 1u32;
+        }
+
+        let mut m = s;
+        let mut c = z.modpow(&q, &self.p);
+        let mut t = x.modpow(&q, &self.p);
+        let mut root = x.modpow(&((&q + &one) / &two), &self.p);
+
+        // Invariant: root² = x * t, with t of order dividing 2^(m-1)
+        while t != one {
+            // Least i with t^(2^i) = 1
+            let mut i = 0u32;
+            let mut t_pow = t.clone();
+            while t_pow != one {
+                t_pow = self.mul(&t_pow, &t_pow);
+                i += 1;
+            }
+
+            let mut b = c.clone();
+            for _ in 0..(m - i - 1) {
+                b = self.mul(&b, &b);
+            }
+
+            m = i;
+            c = self.mul(&b, &b);
+            t = self.mul(&t, &c);
+            root = self.mul(&root, &b);
+        }
+
+        Some(root)
+    }
+
     // a^(p-1) ≡ 1 (mod p)
     // a^(p-2) × a ≡ 1 (mod p) i.e a^(p-2) is the multiplicative inverse of a
 
     // x ÷ y = x × y^(-1) = x × y^(p-2) (mod p)
 
     
+    // x mod p as an element that borrows this field
+    pub fn element(&self, x: &BigUint) -> FieldElement<'_> {
+        let value = if *x < self.p { x.clone() } else { x % &self.p };
+        FieldElement { value, field: Cow::Borrowed(self) }
+    }
+}
+
+// A value mod p that carries its field, so arithmetic reads (x * y + z) / w
+// Combining elements of different fields panics instead of silently reducing by one of the moduli.
+// Elements from FiniteField::element borrow the field (an EllipticCurve's cached one in the point
+// formulas), so no operation clones p; FieldElement::new owns its field instead
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldElement<'a> {
+    value: BigUint,
+    field: Cow<'a, FiniteField>,
+}
+
+impl FieldElement<'static> {
+    pub fn new(value: BigUint, modulus: BigUint) -> Self {
+        if modulus == BigUint::from(0u32) {
+            panic!("?field modulus cannot be zero?");
+        }
+        FieldElement { value: value % &modulus, field: Cow::Owned(FiniteField { p: modulus }) }
+    }
+}
+
+impl<'a> FieldElement<'a> {
+    // Always reduced, 0 <= value < p
+    pub fn value(&self) -> &BigUint {
+        &self.value
+    }
+
+    pub fn into_value(self) -> BigUint {
+        self.value
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.field.p
+    }
+
+    pub fn field(&self) -> &FiniteField {
+        &self.field
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == BigUint::from(0u32)
+    }
+
+    fn with_value(&self, value: BigUint) -> FieldElement<'a> {
+        FieldElement { value, field: self.field.clone() }
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> FieldElement<'a> {
+        self.with_value(self.value.modpow(exponent, &self.field.p))
+    }
+
+    pub fn inv(&self) -> FieldElement<'a> {
+        self.with_value(BigUint::from(1u32)) / self
+    }
+
+    pub fn sqrt(&self) -> Option<FieldElement<'a>> {
+        let root = self.field.sqrt(&self.value)?;
+        Some(self.with_value(root))
+    }
+
+    // x² without a separate result
+    pub fn square_in_place(&mut self) {
+        self.field.square_in_place(&mut self.value);
+    }
+
+    fn same_field(&self, other: &FieldElement) {
+        if !std::ptr::eq(self.field(), other.field()) && self.field.p != other.field.p {
+            panic!("?cannot combine elements of different fields?");
+        }
+    }
+
+    fn combine(&self, other: &FieldElement, op: fn(&FiniteField, &BigUint, &BigUint) -> BigUint) -> FieldElement<'a> {
+        self.same_field(other);
+        self.with_value(op(&self.field, &self.value, &other.value))
+    }
+}
+
+// Each operator for every mix of owned and borrowed operands
+macro_rules! field_element_op {
+    ($op:ident, $method:ident) => {
+        impl<'a> std::ops::$op<&FieldElement<'_>> for &FieldElement<'a> {
+            type Output = FieldElement<'a>;
+            fn $method(self, other: &FieldElement) -> FieldElement<'a> {
+                self.combine(other, FiniteField::$method)
+            }
+        }
+
+        impl<'a> std::ops::$op<FieldElement<'_>> for &FieldElement<'a> {
+            type Output = FieldElement<'a>;
+            fn $method(self, other: FieldElement) -> FieldElement<'a> {
+                self.combine(&other, FiniteField::$method)
+            }
+        }
+
+        impl<'a> std::ops::$op<&FieldElement<'_>> for FieldElement<'a> {
+            type Output = FieldElement<'a>;
+            fn $method(self, other: &FieldElement) -> FieldElement<'a> {
+                self.combine(other, FiniteField::$method)
+            }
+        }
+
+        impl<'a> std::ops::$op<FieldElement<'_>> for FieldElement<'a> {
+            type Output = FieldElement<'a>;
+            fn $method(self, other: FieldElement) -> FieldElement<'a> {
+                self.combine(&other, FiniteField::$method)
+            }
+        }
+    };
+}
+
+field_element_op!(Add, add);
+field_element_op!(Sub, sub);
+field_element_op!(Mul, mul);
+field_element_op!(Div, div);
+
+// x += y and friends update x's value in place, with FiniteField's in-place ops
+macro_rules! field_element_assign_op {
+    ($op:ident, $method:ident) => {
+        impl std::ops::$op<&FieldElement<'_>> for FieldElement<'_> {
+            fn $method(&mut self, other: &FieldElement) {
+                self.same_field(other);
+                self.field.$method(&mut self.value, &other.value);
+            }
+        }
+    };
+}
+
+field_element_assign_op!(AddAssign, add_assign);
+field_element_assign_op!(SubAssign, sub_assign);
+field_element_assign_op!(MulAssign, mul_assign);
+
+impl<'a> std::ops::Neg for &FieldElement<'a> {
+    type Output = FieldElement<'a>;
+    fn neg(self) -> FieldElement<'a> {
+        self.with_value((&self.field.p - &self.value) % &self.field.p)
+    }
+}
+
+impl<'a> std::ops::Neg for FieldElement<'a> {
+    type Output = FieldElement<'a>;
+    fn neg(self) -> FieldElement<'a> {
+        -&self
+    }
 }
 
 
@@ -248,6 +1026,84 @@ mod tests {
         assert_eq!(field.div(&x, &y), BigUint::from(0u32));
     }
 
+    #[test]
+    fn test_invert() {
+        let field = FiniteField { p: BigUint::from(11u32) };
+        assert_eq!(field.invert(&BigUint::from(3u32)), Some(BigUint::from(4u32)));
+        assert_eq!(field.invert(&BigUint::from(0u32)), None);
+
+        // Mod 12 only the units 1, 5, 7, 11 have inverses (each is its own)
+        let composite = FiniteField { p: BigUint::from(12u32) };
+        assert_eq!(composite.invert(&BigUint::from(5u32)), Some(BigUint::from(5u32)));
+        assert_eq!(composite.invert(&BigUint::from(19u32)), Some(BigUint::from(7u32)));
+        assert_eq!(composite.invert(&BigUint::from(4u32)), None);
+        // Fermat would give 5^10 mod 12 = 1, which is not an inverse
+        assert_eq!(composite.div(&BigUint::from(1u32), &BigUint::from(5u32)), BigUint::from(5u32));
+    }
+
+    #[test]
+    fn test_point_as_map_key() {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let g = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+
+        // 40 multiples of a generator of order 19: 19 distinct points, identity included
+        let multiples: Vec<Point> = (0u32..40).map(|k| curve.scalar_mult(&g, &BigUint::from(k))).collect();
+        let distinct: std::collections::HashSet<&Point> = multiples.iter().collect();
+        assert_eq!(distinct.len(), 19);
+
+        let sorted: std::collections::BTreeSet<&Point> = multiples.iter().collect();
+        let sorted: Vec<&Point> = sorted.into_iter().collect();
+        assert_eq!(sorted[0], &Point::Identity);
+        assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(Point::Coordinate(BigUint::from(3u32), BigUint::from(16u32)) < Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32)));
+    }
+
+    #[test]
+    fn test_in_place_arithmetic() {
+        let field = FiniteField { p: BigUint::from(17u32) };
+        let mut x = BigUint::from(12u32);
+        field.add_assign(&mut x, &BigUint::from(9u32));
+        assert_eq!(x, BigUint::from(4u32));
+        field.sub_assign(&mut x, &BigUint::from(6u32));
+        assert_eq!(x, BigUint::from(15u32));
+        field.mul_assign(&mut x, &BigUint::from(3u32));
+        assert_eq!(x, BigUint::from(11u32));
+        field.square_in_place(&mut x);
+        assert_eq!(x, BigUint::from(2u32));
+    }
+
+    #[test]
+    fn test_legendre() {
+        // The squares mod 11 are 1, 3, 4, 5, 9
+        let field = FiniteField { p: BigUint::from(11u32) };
+        let symbols: Vec<i8> = (0u32..11).map(|x| field.legendre(&BigUint::from(x))).collect();
+        assert_eq!(symbols, vec![0, 1, -1, 1, 1, 1, -1, -1, -1, 1, -1]);
+        assert!(field.is_quadratic_residue(&BigUint::from(0u32)));
+        assert!(field.is_quadratic_residue(&BigUint::from(14u32)));
+        assert!(!field.is_quadratic_residue(&BigUint::from(2u32)));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        // 5² = 25 ≡ 3 (mod 11)
+        let field = FiniteField { p: BigUint::from(11u32) };
+        let root = field.sqrt(&BigUint::from(3u32)).unwrap();
+        assert!(root == BigUint::from(5u32) || root == BigUint::from(6u32));
+
+        // 2 is a non-residue mod 11
+        assert_eq!(field.sqrt(&BigUint::from(2u32)), None);
+        assert_eq!(field.sqrt(&BigUint::from(0u32)), Some(BigUint::from(0u32)));
+
+        // p = 97 ≡ 1 (mod 32) exercises the full Tonelli–Shanks loop
+        let field = FiniteField { p: BigUint::from(97u32) };
+        for x in 1u32..97 {
+            let x = BigUint::from(x);
+            if let Some(root) = field.sqrt(&x) {
+                assert_eq!(field.mul(&root, &root), x);
+            }
+        }
+    }
+
     #[test]
     fn test_multiplicative_identity() {
         let x = BigUint::from(4u32);
@@ -273,14 +1129,47 @@ mod tests {
         assert_eq!(field.add(&x, &neg_x), zero);
     }
 
+    #[test]
+    fn test_field_element_operators() {
+        let field = FiniteField { p: BigUint::from(11u32) };
+        let x = field.element(&BigUint::from(17u32));
+        let y = field.element(&BigUint::from(3u32));
+
+        assert_eq!(x.value(), &BigUint::from(6u32));
+        assert_eq!((&x + &y).into_value(), BigUint::from(9u32));
+        assert_eq!((&x - &y).into_value(), BigUint::from(3u32));
+        assert_eq!((&x * &y).into_value(), BigUint::from(7u32));
+        assert_eq!((&x / &y).into_value(), BigUint::from(2u32));
+        assert_eq!(-&x + &x, field.element(&BigUint::from(0u32)));
+        assert_eq!(&y * y.inv(), field.element(&BigUint::from(1u32)));
+        assert_eq!(x.pow(&BigUint::from(10u32)), field.element(&BigUint::from(1u32)));
+        let root = field.element(&BigUint::from(5u32)).sqrt().unwrap();
+        assert_eq!(&root * &root, field.element(&BigUint::from(5u32)));
+
+        // In place: ((6 + 3) * 3 - 6)² = 21² = 1 (mod 11)
+        let mut z = x.clone();
+        z += &y;
+        z *= &y;
+        z -= &x;
+        z.square_in_place();
+        assert_eq!(z.into_value(), BigUint::from(1u32));
+        assert!(std::ptr::eq(x.field(), &field));
+    }
+
+    #[test]
+    #[should_panic(expected = "different fields")]
+    fn test_field_element_mixed_moduli() {
+        let x = FieldElement::new(BigUint::from(3u32), BigUint::from(11u32));
+        let y = FieldElement::new(BigUint::from(3u32), BigUint::from(13u32));
+        let _ = x + y;
+    }
+
     #[test]
     fn test_point_on_curve() {
         // Test curve: y² = x³ + 2x + 3 (mod 11)
-        let curve = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(3u32),
-            p: BigUint::from(11u32),
-        };
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(11u32));
+        assert_eq!((curve.a(), curve.b(), curve.p()), (&BigUint::from(2u32), &BigUint::from(3u32), &BigUint::from(11u32)));
+        assert_eq!(&curve.field().p, curve.p());
 
         // Test point at infinity
         assert!(curve.is_on_curve(&Point::Identity));
@@ -298,14 +1187,25 @@ mod tests {
         assert!(!curve.is_on_curve(&invalid_point));
     }
 
+    #[test]
+    fn test_unreduced_coordinates_rejected() {
+        // (0 + 11, 5) and (0, 5 + 11) satisfy the equation mod 11, but aren't the canonical (0, 5):
+        // they'd hash and compare differently and slip through key validation
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(11u32));
+        assert!(curve.is_on_curve(&Point::Coordinate(BigUint::from(0u32), BigUint::from(5u32))));
+        assert!(!curve.is_on_curve(&Point::Coordinate(BigUint::from(11u32), BigUint::from(5u32))));
+        assert!(!curve.is_on_curve(&Point::Coordinate(BigUint::from(0u32), BigUint::from(16u32))));
+
+        let demo_curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let ecdh = ecdh::ECDH::new(demo_curve, Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32)), BigUint::from(19u32));
+        let unreduced = Point::Coordinate(BigUint::from(5u32 + 17), BigUint::from(1u32));
+        assert_eq!(ecdh.validate_public_key(&unreduced), Err("public key is not on the curve"));
+    }
+
     #[test]
     fn test_ec_point_addition() {
         // Test curve: y² = x³ + 2x + 3 (mod 11)
-        let curve = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(3u32),
-            p: BigUint::from(11u32),
-        };
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(11u32));
 
         // Test P + O = P (identity)
         let p = Point::Coordinate(BigUint::from(0u32), BigUint::from(5u32));
@@ -329,11 +1229,7 @@ mod tests {
 
     #[test]
     fn test_ec_point_doubling() {
-        let curve = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(3u32),
-            p: BigUint::from(11u32),
-        };
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(11u32));
 
         // Test 2O = O
         let result = curve.double(&Point::Identity);
@@ -347,14 +1243,38 @@ mod tests {
         assert!(curve.is_on_curve(&doubled));
     }
 
+    #[test]
+    fn test_point_operators() {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(11u32));
+        let p = curve.point(Point::Coordinate(BigUint::from(0u32), BigUint::from(5u32)));
+        let q = curve.point(curve.double(p.point()));
+
+        assert_eq!((&p + &q).into_point(), curve.add(p.point(), q.point()));
+        assert_eq!(&p + &p, q);
+        assert_eq!((&q - &p), p);
+        assert_eq!((&p - &p).into_point(), Point::Identity);
+        assert_eq!(-(-&p), p);
+        assert_eq!(&BigUint::from(2u32) * &p, q);
+        assert_eq!(&p * &BigUint::from(3u32), &p + &q);
+
+        let mut sum = p.clone();
+        sum += &p;
+        sum += p.clone();
+        assert_eq!(sum, &BigUint::from(3u32) * p);
+    }
+
+    #[test]
+    #[should_panic(expected = "different curves")]
+    fn test_point_operators_mixed_curves() {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(11u32));
+        let other = EllipticCurve::new(BigUint::from(1u32), BigUint::from(3u32), BigUint::from(11u32));
+        let _ = curve.point(Point::Identity) + other.point(Point::Identity);
+    }
+
     #[test]
     // h/w [homework] : test y = 0 special case
     fn test_y_zero_special_case() {
-        let curve = EllipticCurve {
-            a: BigUint::from(0u32),
-            b: BigUint::from(7u32),
-            p: BigUint::from(11u32),
-        };
+        let curve = EllipticCurve::new(BigUint::from(0u32), BigUint::from(7u32), BigUint::from(11u32));
 
         // Find a point with y = 0: x³ + 7 ≡ 0 (mod 11)
         // x³ ≡ -7 ≡ 4 (mod 11)
@@ -372,11 +1292,7 @@ mod tests {
 
     #[test]
     fn test_scalar_multiplication() {
-        let curve = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(3u32),
-            p: BigUint::from(11u32),
-        };
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(11u32));
 
         let p = Point::Coordinate(BigUint::from(0u32), BigUint::from(5u32));
 
@@ -404,11 +1320,11 @@ mod tests {
     fn test_secp256k1_like_curve() {
         // Simplified version of secp256k1: y² = x³ + 7 (mod small_prime)
         // Using small prime for testing: p = 23
-        let curve = EllipticCurve {
-            a: BigUint::from(0u32),  // secp256k1 has a = 0
-            b: BigUint::from(7u32),  // secp256k1 has b = 7
-            p: BigUint::from(23u32), // Small prime for testing
-        };
+        let curve = EllipticCurve::new(
+            BigUint::from(0u32),  // secp256k1 has a = 0
+            BigUint::from(7u32),  // secp256k1 has b = 7
+            BigUint::from(23u32), // Small prime for testing
+        );
 
         // Find a valid point on y² = x³ + 7 (mod 23)
         // For x = 1: y² = 1 + 7 = 8 (mod 23)
@@ -433,11 +1349,7 @@ mod tests {
 
     #[test]
     fn test_group_properties() {
-        let curve = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(3u32),
-            p: BigUint::from(11u32),
-        };
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(11u32));
 
         let p1 = Point::Coordinate(BigUint::from(0u32), BigUint::from(5u32));
         let p2 = Point::Coordinate(BigUint::from(0u32), BigUint::from(6u32)); // -p1
@@ -458,6 +1370,197 @@ mod tests {
         // Test inverse: P + (-P) = O
         let sum = curve.add(&p1, &p2);
         assert_eq!(sum, Point::Identity);
+        assert_eq!(curve.neg(&p1), p2);
+        assert_eq!(curve.neg(&Point::Identity), Point::Identity);
+    }
+
+    #[test]
+    fn test_point_order() {
+        // y² = x³ + x + 1 (mod 23) has 28 points
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let bound = BigUint::from(28u32);
+
+        let order_of = |x: u32, y: u32| {
+            curve.point_order(&Point::Coordinate(BigUint::from(x), BigUint::from(y)), &bound).unwrap()
+        };
+        assert_eq!(order_of(4, 0), BigUint::from(2u32));
+        assert_eq!(order_of(11, 3), BigUint::from(4u32));
+        assert_eq!(order_of(5, 4), BigUint::from(7u32));
+        assert_eq!(order_of(6, 4), BigUint::from(14u32));
+        assert_eq!(order_of(0, 1), BigUint::from(28u32));
+        assert_eq!(curve.point_order(&Point::Identity, &bound), Ok(BigUint::from(1u32)));
+
+        // The ECDSA demo generator (5, 1) on y² = x³ + 2x + 2 (mod 17) has order 19
+        // A loose bound (Hasse: 17 + 1 + 2√17 < 27) works just as well
+        let demo_curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        assert_eq!(demo_curve.point_order(&generator, &BigUint::from(27u32)), Ok(BigUint::from(19u32)));
+    }
+
+    #[test]
+    fn test_point_order_bound_too_small() {
+        // (0, 1) has order 28, which a bound of 5 can't reach
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let point = Point::Coordinate(BigUint::from(0u32), BigUint::from(1u32));
+        assert_eq!(curve.point_order(&point, &BigUint::from(5u32)), Err("point order exceeds the given bound"));
+        assert_eq!(curve.point_order(&point, &BigUint::from(28u32)), Ok(BigUint::from(28u32)));
+    }
+
+    #[test]
+    fn test_count_points() {
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        assert_eq!(curve.count_points(), Ok(BigUint::from(28u32)));
+
+        // The ECDSA demo curve: #E = 19 is where the hard-coded order comes from
+        let demo_curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        assert_eq!(demo_curve.count_points(), Ok(BigUint::from(19u32)));
+
+        let secp_like = EllipticCurve::new(BigUint::from(0u32), BigUint::from(7u32), BigUint::from(23u32));
+        assert_eq!(secp_like.count_points(), Ok(BigUint::from(24u32)));
+    }
+
+    #[test]
+    fn test_points() {
+        // y² = x³ + x + 1 (mod 23): 28 points, sorted, distinct and all on the curve
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let points: Vec<Point> = curve.points().collect();
+        assert_eq!(points.len(), 28);
+        assert_eq!(points[0], Point::Identity);
+        assert!(points.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(points.iter().all(|point| curve.is_on_curve(point)));
+
+        // y² = x³ + 7 (mod 23): (9, 0) has y = -y and is listed once
+        let two_torsion = EllipticCurve::new(BigUint::from(0u32), BigUint::from(7u32), BigUint::from(23u32));
+        let points: Vec<Point> = two_torsion.points().collect();
+        assert_eq!(BigUint::from(points.len()), two_torsion.count_points().unwrap());
+        assert_eq!(points.iter().filter(|point| matches!(point, Point::Coordinate(_, y) if *y == BigUint::from(0u32))).count(), 1);
+        assert!(points.contains(&Point::Coordinate(BigUint::from(9u32), BigUint::from(0u32))));
+
+        // Order histogram of the ECDSA demo curve: prime order 19, so all but O have order 19
+        let demo_curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        let orders: Vec<BigUint> = demo_curve.points().map(|point| demo_curve.point_order(&point, &BigUint::from(19u32)).unwrap()).collect();
+        assert_eq!(orders.iter().filter(|order| **order == BigUint::from(19u32)).count(), 18);
+    }
+
+    #[test]
+    fn test_subgroup_membership() {
+        // y² = x³ + x + 1 (mod 23): 28 = 4 * 7 points; (4, 0) has order 2, 4 * (5, 4) has order 7
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let (order, cofactor) = (BigUint::from(7u32), BigUint::from(4u32));
+        let order_two = Point::Coordinate(BigUint::from(4u32), BigUint::from(0u32));
+        let generator = curve.scalar_mult(&Point::Coordinate(BigUint::from(5u32), BigUint::from(4u32)), &cofactor);
+        assert!(curve.is_torsion_point(&order_two, &BigUint::from(2u32)));
+        assert!(!curve.is_torsion_point(&order_two, &order));
+        assert!(curve.is_torsion_point(&generator, &order));
+        assert!(!curve.is_torsion_point(&curve.add(&generator, &order_two), &order));
+        assert!(curve.is_torsion_point(&Point::Identity, &order));
+
+        // Off the curve is never torsion, whatever r * P comes out as
+        let off_curve = Point::Coordinate(BigUint::from(1u32), BigUint::from(1u32));
+        assert!(!curve.is_torsion_point(&off_curve, &BigUint::from(0u32)));
+
+        // Prime order: every point on the curve is in the subgroup
+        let demo_curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        assert!(demo_curve.points().all(|point| demo_curve.is_torsion_point(&point, &BigUint::from(19u32))));
+        assert!(!demo_curve.is_torsion_point(&off_curve, &BigUint::from(19u32)));
+    }
+
+    #[test]
+    fn test_hasse_bounds() {
+        // p = 23: ⌊2√23⌋ = 9, so 15 <= #E <= 33, and 28 is in there
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        assert_eq!(curve.hasse_bounds(), (BigUint::from(15u32), BigUint::from(33u32)));
+        assert!(curve.within_hasse_bounds(&BigUint::from(28u32)));
+        assert!(!curve.within_hasse_bounds(&BigUint::from(14u32)) && !curve.within_hasse_bounds(&BigUint::from(34u32)));
+
+        // Every small curve's count lands inside
+        for p in [5u32, 7, 11, 13] {
+            for a in 0..p {
+                for b in 0..p {
+                    let curve = EllipticCurve::new(BigUint::from(a), BigUint::from(b), BigUint::from(p));
+                    let (low, high) = curve.hasse_bounds();
+                    let count = curve.count_points().unwrap();
+                    assert!(low <= count && count <= high);
+                }
+            }
+        }
+
+        // The published P-256 order, and the same order with a typo
+        let p256 = curves::p256();
+        assert!(p256.curve.within_hasse_bounds(&p256.order));
+        let typo = &p256.order - (BigUint::from(1u32) << 200);
+        assert!(!p256.curve.within_hasse_bounds(&typo));
+    }
+
+    #[test]
+    fn test_quadratic_twist() {
+        // y² = x³ + x + 1 (mod 23): #E = 28, so the twist has 2 * 24 - 28 = 20 points
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let twist = curve.quadratic_twist().unwrap();
+        assert_ne!(twist, curve);
+        assert_eq!(twist.count_points(), Ok(BigUint::from(20u32)));
+
+        let report = curve.twist_security().unwrap();
+        assert_eq!(report.twist_order, BigUint::from(20u32));
+        assert_eq!(report.factors, vec![(BigUint::from(2u32), 2), (BigUint::from(5u32), 1)]);
+        assert_eq!(report.largest_prime_factor, BigUint::from(5u32));
+
+        // The twist of the twist is isomorphic to the original: same point count
+        assert_eq!(twist.quadratic_twist().unwrap().count_points(), Ok(BigUint::from(28u32)));
+
+        // F_2 has no non-residues: an error, not an endless search
+        let binary = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(2u32));
+        assert!(binary.quadratic_twist().is_err());
+    }
+
+    #[test]
+    fn test_count_points_composite_modulus() {
+        // p = 91 = 7 * 13 isn't prime; the "count" lands outside the Hasse interval
+        let curve = EllipticCurve::new(BigUint::from(0u32), BigUint::from(7u32), BigUint::from(91u32));
+        assert!(curve.count_points().is_err());
+    }
+
+    #[test]
+    fn test_random_point() {
+        // y² = x³ + x + 1 (mod 23): 27 affine points
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let mut rng = crate::entropy::Entropy;
+        for _ in 0..50 {
+            let point = curve.random_point(&mut rng).unwrap();
+            assert!(curve.is_on_curve(&point));
+        }
+
+        // x = 3: rhs = 31 = 8, roots 10 and 13
+        let (positive, negative) = curve.lift_x(&BigUint::from(3u32)).unwrap();
+        assert!(curve.is_on_curve(&positive) && curve.is_on_curve(&negative));
+        assert_eq!(curve.add(&positive, &negative), Point::Identity);
+        // x = 2: rhs = 11, a non-residue mod 23
+        assert_eq!(curve.lift_x(&BigUint::from(2u32)), None);
+    }
+
+    #[test]
+    fn test_find_generator_of_order() {
+        // y² = x³ + x + 1 (mod 23) has 28 = 4 * 7 points
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let mut rng = crate::entropy::Entropy;
+        let seven = BigUint::from(7u32);
+
+        // Counting the group order
+        let generator = curve.find_generator_of_order(&seven, None, &mut rng).unwrap();
+        assert_eq!(curve.point_order(&generator, &BigUint::from(28u32)), Ok(seven));
+
+        // Known group order, and the order-2 subgroup
+        let order = BigUint::from(28u32);
+        let generator = curve.find_generator_of_order(&BigUint::from(2u32), Some(&order), &mut rng).unwrap();
+        assert_eq!(generator, Point::Coordinate(BigUint::from(4u32), BigUint::from(0u32)));
+
+        assert!(curve.find_generator_of_order(&BigUint::from(5u32), None, &mut rng).is_err());
+        // A wrong group order: 6 * P has order dividing 14, so 5 * (6 * P) is never O unless 6 * P is
+        let wrong = curve.find_generator_of_order(&BigUint::from(5u32), Some(&BigUint::from(30u32)), &mut rng);
+        assert_eq!(wrong, Err("group order is wrong for this curve"));
+        // and one that no curve over F_23 can have
+        let impossible = curve.find_generator_of_order(&BigUint::from(5u32), Some(&BigUint::from(35u32)), &mut rng);
+        assert_eq!(impossible, Err("group order is outside the Hasse bound"));
     }
 
 }
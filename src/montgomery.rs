@@ -0,0 +1,199 @@
+use crate::{ct, FiniteField};
+use num_bigint::BigUint;
+use subtle::Choice;
+
+// Montgomery curves B v² = u³ + A u² + u (mod p) and the X25519 / X448 functions (RFC 7748)
+//
+// Only u-coordinates are used: the ladder keeps (x2 : z2) = k * P and (x3 : z3) = (k + 1) * P,
+// whose difference is always P, so the differential addition needs just u(P). Every step does
+// the same work whatever the key bit, and every u maps to a point on the curve or its twist,
+// so there is nothing to validate; both twists are chosen to have large prime-order subgroups.
+// Scalars are clamped: low bits cleared (a multiple of the cofactor 8 or 4) and a fixed top bit
+
+// The field is built once in new(), as for EllipticCurve, so a, b and p are read-only
+#[derive(Clone, Debug, PartialEq)]
+pub struct MontgomeryCurve {
+    a: BigUint,
+    b: BigUint,
+    p: BigUint,
+    field: FiniteField,
+}
+
+// Curve25519: v² = u³ + 486662 u² + u over 2^255 - 19
+pub fn curve25519() -> MontgomeryCurve {
+    MontgomeryCurve::new(BigUint::from(486662u32), BigUint::from(1u32), (BigUint::from(1u32) << 255) - BigUint::from(19u32))
+}
+
+// Curve448: v² = u³ + 156326 u² + u over the Goldilocks prime 2^448 - 2^224 - 1
+pub fn curve448() -> MontgomeryCurve {
+    MontgomeryCurve::new(BigUint::from(156326u32), BigUint::from(1u32), goldilocks_prime())
+}
+
+pub fn goldilocks_prime() -> BigUint {
+    (BigUint::from(1u32) << 448) - (BigUint::from(1u32) << 224) - BigUint::from(1u32)
+}
+
+impl MontgomeryCurve {
+    pub fn new(a: BigUint, b: BigUint, p: BigUint) -> Self {
+        let field = FiniteField { p: p.clone() };
+        MontgomeryCurve { a, b, p, field }
+    }
+
+    pub fn a(&self) -> &BigUint {
+        &self.a
+    }
+
+    pub fn b(&self) -> &BigUint {
+        &self.b
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn field(&self) -> &FiniteField {
+        &self.field
+    }
+
+    // u(k * P) from u(P), using the low `bits` bits of k; u = 0 is returned for the identity
+    pub fn ladder(&self, k: &BigUint, u: &BigUint, bits: u64) -> BigUint {
+        let f = &self.field;
+        let a24 = f.div(&f.sub(&self.a, &BigUint::from(2u32)), &BigUint::from(4u32));
+        let x1 = u % &self.p;
+        let (mut x2, mut z2) = (BigUint::from(1u32), BigUint::from(0u32));
+        let (mut x3, mut z3) = (x1.clone(), BigUint::from(1u32));
+        let limbs = self.p.to_u64_digits().len();
+        let mut swap = Choice::from(0);
+
+        for t in (0..bits).rev() {
+            let bit = Choice::from(k.bit(t) as u8);
+            // Conditional swap so the same formulas serve both bit values, masked rather than
+            // branching on the key bit
+            ct::swap_padded(&mut x2, &mut x3, swap ^ bit, limbs);
+            ct::swap_padded(&mut z2, &mut z3, swap ^ bit, limbs);
+            swap = bit;
+
+            let a = f.add(&x2, &z2);
+            let aa = f.mul(&a, &a);
+            let b = f.sub(&x2, &z2);
+            let bb = f.mul(&b, &b);
+            let e = f.sub(&aa, &bb);
+            let c = f.add(&x3, &z3);
+            let d = f.sub(&x3, &z3);
+            let da = f.mul(&d, &a);
+            let cb = f.mul(&c, &b);
+            let sum = f.add(&da, &cb);
+            let difference = f.sub(&da, &cb);
+            x3 = f.mul(&sum, &sum);
+            z3 = f.mul(&x1, &f.mul(&difference, &difference));
+            x2 = f.mul(&aa, &bb);
+            z2 = f.mul(&e, &f.add(&aa, &f.mul(&a24, &e)));
+        }
+        ct::swap_padded(&mut x2, &mut x3, swap, limbs);
+        ct::swap_padded(&mut z2, &mut z3, swap, limbs);
+
+        // x2 / z2, with z2 = 0 (the identity) giving 0 as RFC 7748's z^(p-2) does
+        if z2 == BigUint::from(0u32) {
+            return z2;
+        }
+        f.div(&x2, &z2)
+    }
+}
+
+fn le_bytes<const N: usize>(x: &BigUint) -> [u8; N] {
+    let mut bytes = x.to_bytes_le();
+    bytes.resize(N, 0);
+    bytes.try_into().unwrap()
+}
+
+// X25519(k, u): clamp k, mask the top bit of u
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let mut k = *scalar;
+    k[0] &= 0xf8;
+    k[31] &= 0x7f;
+    k[31] |= 0x40;
+    let mut u = *u;
+    u[31] &= 0x7f;
+    let curve = curve25519();
+    le_bytes(&curve.ladder(&BigUint::from_bytes_le(&k), &BigUint::from_bytes_le(&u), 255))
+}
+
+// X448(k, u): clear the low 2 bits of k and set its top bit
+pub fn x448(scalar: &[u8; 56], u: &[u8; 56]) -> [u8; 56] {
+    let mut k = *scalar;
+    k[0] &= 0xfc;
+    k[55] |= 0x80;
+    let curve = curve448();
+    le_bytes(&curve.ladder(&BigUint::from_bytes_le(&k), &BigUint::from_bytes_le(u), 448))
+}
+
+// Public keys: the base point is u = 9 for X25519 and u = 5 for X448
+pub fn x25519_public_key(private_key: &[u8; 32]) -> [u8; 32] {
+    let mut base = [0u8; 32];
+    base[0] = 9;
+    x25519(private_key, &base)
+}
+
+pub fn x448_public_key(private_key: &[u8; 56]) -> [u8; 56] {
+    let mut base = [0u8; 56];
+    base[0] = 5;
+    x448(private_key, &base)
+}
+
+// Shared secrets, rejecting the all-zero output a small-order peer key produces
+pub fn x25519_shared_secret(private_key: &[u8; 32], peer_public_key: &[u8; 32]) -> Result<[u8; 32], &'static str> {
+    let secret = x25519(private_key, peer_public_key);
+    if secret == [0u8; 32] {
+        return Err("peer public key has small order");
+    }
+    Ok(secret)
+}
+
+pub fn x448_shared_secret(private_key: &[u8; 56], peer_public_key: &[u8; 56]) -> Result<[u8; 56], &'static str> {
+    let secret = x448(private_key, peer_public_key);
+    if secret == [0u8; 56] {
+        return Err("peer public key has small order");
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    #[test]
+    fn test_x25519_rfc7748() {
+        let scalar = from_hex("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4").unwrap();
+        let u = from_hex("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c").unwrap();
+        assert_eq!(
+            to_hex(&x25519(&scalar.try_into().unwrap(), &u.try_into().unwrap())),
+            "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"
+        );
+    }
+
+    #[test]
+    fn test_x448_rfc7748() {
+        let scalar = from_hex("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3").unwrap();
+        let u = from_hex("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086").unwrap();
+        assert_eq!(
+            to_hex(&x448(&scalar.try_into().unwrap(), &u.try_into().unwrap())),
+            "ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f"
+        );
+    }
+
+    #[test]
+    fn test_x448_key_agreement() {
+        let alice = [0x11u8; 56];
+        let bob = [0x22u8; 56];
+        let alice_secret = x448_shared_secret(&alice, &x448_public_key(&bob)).unwrap();
+        let bob_secret = x448_shared_secret(&bob, &x448_public_key(&alice)).unwrap();
+        assert_eq!(alice_secret, bob_secret);
+
+        // u = 0 and u = 1 are small-order points
+        assert!(x448_shared_secret(&alice, &[0u8; 56]).is_err());
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        assert!(x25519_shared_secret(&[0x33; 32], &one).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::{EllipticCurve, Point};
+use crate::bip340::{tagged_hash, Bip340, Bip340Signature};
+use crate::encoding::{decode_point, encode_point, to_fixed_bytes};
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use num_bigint::BigUint;
+
+// MuSig2 (BIP-327): n signers produce one BIP-340 signature valid under an aggregate key
+//
+//   key aggregation:  a_i = H(L || P_i) with L = H(P_1 || ... || P_n), Q = Σ a_i P_i
+//                     (the coefficients stop rogue-key attacks; the second distinct key gets a = 1)
+//   round 1:          each signer publishes two nonces R1_i = k1_i G, R2_i = k2_i G
+//   round 2:          R1 = Σ R1_i, R2 = Σ R2_i, b = H(R1 || R2 || Q || m), R = R1 + b R2,
+//                     e = H_BIP0340(R || Q || m), s_i = k1_i + b k2_i + e a_i d_i
+//   aggregation:      s = Σ s_i, signature (x(R), s)
+// with the BIP-340 even-y negations applied to Q, R and the secrets
+// Two nonces per signer are what allow round 1 to run before the message is known
+// without opening up the Wagner/ROS attacks that break single-nonce two-round schemes
+
+pub struct KeyAggContext {
+    pub public_keys: Vec<Point>,
+    pub aggregate_key: Point,
+    key_list_hash: [u8; 32],
+    second_key: Option<Point>,
+}
+
+impl KeyAggContext {
+    // Keys are aggregated in the given order; sort them first for an order-independent Q
+    pub fn new(bip340: &Bip340, public_keys: &[Point]) -> Result<Self, &'static str> {
+        if public_keys.is_empty() {
+            return Err("no public keys to aggregate");
+        }
+        if public_keys.iter().any(|p| *p == Point::Identity || !bip340.params.curve.is_on_curve(p)) {
+            return Err("invalid public key");
+        }
+
+        let encoded: Vec<u8> = public_keys.iter().flat_map(|p| encode_point(&bip340.params.curve, p, true)).collect();
+        let key_list_hash = tagged_hash("KeyAgg list", &encoded);
+        let second_key = public_keys.iter().find(|p| **p != public_keys[0]).cloned();
+
+        let mut context = KeyAggContext {
+            public_keys: public_keys.to_vec(),
+            aggregate_key: Point::Identity,
+            key_list_hash,
+            second_key,
+        };
+        let mut aggregate = Point::Identity;
+        for key in public_keys {
+            let a = context.coefficient(bip340, key);
+            aggregate = bip340.params.curve.add(&aggregate, &bip340.params.curve.scalar_mult(key, &a));
+        }
+        if aggregate == Point::Identity {
+            return Err("aggregate key is the point at infinity");
+        }
+        context.aggregate_key = aggregate;
+        Ok(context)
+    }
+
+    // a_i = H_KeyAgg coefficient(L || P_i), or 1 for the second distinct key
+    pub fn coefficient(&self, bip340: &Bip340, public_key: &Point) -> BigUint {
+        if Some(public_key) == self.second_key.as_ref() {
+            return BigUint::from(1u32);
+        }
+        let mut data = self.key_list_hash.to_vec();
+        data.extend(encode_point(&bip340.params.curve, public_key, true));
+        BigUint::from_bytes_be(&tagged_hash("KeyAgg coefficient", &data)) % &bip340.params.order
+    }
+
+    // x-only aggregate key, what BIP-340 verifiers see
+    pub fn x_only_public_key(&self) -> BigUint {
+        match &self.aggregate_key {
+            Point::Coordinate(x, _) => x.clone(),
+            Point::Identity => unreachable!("checked in new"),
+        }
+    }
+}
+
+// A signer's round-1 secret; not Clone, and consumed by signing so it can't be reused
+pub struct SecretNonce {
+    k1: Scalar,
+    k2: Scalar,
+    public_key: Point,
+}
+
+// Round-1 message: (R1, R2), 66 bytes on the wire
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicNonce {
+    pub r1: Point,
+    pub r2: Point,
+}
+
+impl PublicNonce {
+    // cbytes(R1) || cbytes(R2), with the identity (possible only in aggregates) as 33 zero bytes
+    pub fn to_bytes(&self, curve: &EllipticCurve) -> Vec<u8> {
+        let mut bytes = encode_nonce_point(curve, &self.r1);
+        bytes.extend(encode_nonce_point(curve, &self.r2));
+        bytes
+    }
+
+    pub fn from_bytes(curve: &EllipticCurve, bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != 66 {
+            return Err("public nonces are 66 bytes");
+        }
+        Ok(PublicNonce {
+            r1: decode_nonce_point(curve, &bytes[..33])?,
+            r2: decode_nonce_point(curve, &bytes[33..])?,
+        })
+    }
+}
+
+// Round 2 message
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartialSignature {
+    pub s: BigUint,
+}
+
+// Round 1: fresh random nonces for the signer holding public_key
+pub fn nonce_gen(bip340: &Bip340, public_key: &Point) -> (SecretNonce, PublicNonce) {
+    let k1 = Scalar::random(&bip340.params.order, &mut Entropy);
+    let k2 = Scalar::random(&bip340.params.order, &mut Entropy);
+    let public_nonce = PublicNonce {
+        r1: bip340.params.generate_public_key(k1.value()),
+        r2: bip340.params.generate_public_key(k2.value()),
+    };
+    (SecretNonce { k1, k2, public_key: public_key.clone() }, public_nonce)
+}
+
+// Sum of everyone's nonces, computed by any participant or a coordinator
+pub fn nonce_agg(bip340: &Bip340, nonces: &[PublicNonce]) -> PublicNonce {
+    let mut aggregate = PublicNonce { r1: Point::Identity, r2: Point::Identity };
+    for nonce in nonces {
+        aggregate.r1 = bip340.params.curve.add(&aggregate.r1, &nonce.r1);
+        aggregate.r2 = bip340.params.curve.add(&aggregate.r2, &nonce.r2);
+    }
+    aggregate
+}
+
+// Everything fixed once the aggregate nonce and message are known
+pub struct Session<'a> {
+    bip340: &'a Bip340,
+    key_agg: &'a KeyAggContext,
+    pub b: BigUint,
+    pub r: Point,
+    pub e: BigUint,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(bip340: &'a Bip340, key_agg: &'a KeyAggContext, aggregate_nonce: &PublicNonce, message: &[u8]) -> Self {
+        let curve = &bip340.params.curve;
+        let q = key_agg.x_only_public_key();
+
+        // b = H_MuSig/noncecoef(aggnonce || x(Q) || m)
+        let mut data = aggregate_nonce.to_bytes(curve);
+        data.extend(to_fixed_bytes(&q, 32));
+        data.extend_from_slice(message);
+        let b = BigUint::from_bytes_be(&tagged_hash("MuSig/noncecoef", &data)) % &bip340.params.order;
+
+        // R = R1 + b R2, replaced by G in the (negligible) infinity case
+        let r = match curve.add(&aggregate_nonce.r1, &curve.scalar_mult(&aggregate_nonce.r2, &b)) {
+            Point::Identity => bip340.params.generator.clone(),
+            r => r,
+        };
+        let e = bip340.challenge(&x_of(&r), &q, message);
+        Session { bip340, key_agg, b, r, e }
+    }
+
+    // s_i = k1' + b k2' + e a_i d', with k negated if R has odd y and d negated if Q does
+    pub fn sign(&self, secret_nonce: SecretNonce, private_key: &BigUint) -> Result<PartialSignature, &'static str> {
+        let bip340 = self.bip340;
+        let order = &bip340.params.order;
+        let public_key = bip340.params.generate_public_key(private_key);
+        if public_key != secret_nonce.public_key {
+            return Err("secret nonce was generated for a different key");
+        }
+        if !self.key_agg.public_keys.contains(&public_key) {
+            return Err("signer is not part of the aggregate key");
+        }
+
+        let (k1, k2) = if has_even_y(&self.r) {
+            (secret_nonce.k1, secret_nonce.k2)
+        } else {
+            (-&secret_nonce.k1, -&secret_nonce.k2)
+        };
+        let d = Scalar::new(private_key.clone(), order);
+        let d = if has_even_y(&self.key_agg.aggregate_key) { d } else { -&d };
+        let a = Scalar::new(self.key_agg.coefficient(bip340, &public_key), order);
+        let (b, e) = (Scalar::new(self.b.clone(), order), Scalar::new(self.e.clone(), order));
+
+        let s = k1 + &b * &k2 + &e * &(&a * &d);
+        Ok(PartialSignature { s: s.into_value() })
+    }
+
+    // s_i G = ±(R1_i + b R2_i) + e a_i (±P_i): identifies which signer sent a bad share
+    pub fn verify_partial(&self, partial: &PartialSignature, public_nonce: &PublicNonce, public_key: &Point) -> bool {
+        let curve = &self.bip340.params.curve;
+        if partial.s >= self.bip340.params.order {
+            return false;
+        }
+        let mut r_i = curve.add(&public_nonce.r1, &curve.scalar_mult(&public_nonce.r2, &self.b));
+        if !has_even_y(&self.r) {
+            r_i = curve.neg(&r_i);
+        }
+        let mut p_i = curve.scalar_mult(public_key, &self.key_agg.coefficient(self.bip340, public_key));
+        if !has_even_y(&self.key_agg.aggregate_key) {
+            p_i = curve.neg(&p_i);
+        }
+        let lhs = curve.scalar_mult(&self.bip340.params.generator, &partial.s);
+        lhs == curve.add(&r_i, &curve.scalar_mult(&p_i, &self.e))
+    }
+
+    // s = Σ s_i, signature (x(R), s)
+    pub fn aggregate(&self, partials: &[PartialSignature]) -> Bip340Signature {
+        let order = &self.bip340.params.order;
+        let s = partials.iter().fold(Scalar::zero(order), |acc, partial| acc + Scalar::new(partial.s.clone(), order));
+        Bip340Signature { r: x_of(&self.r), s: s.into_value() }
+    }
+}
+
+fn has_even_y(point: &Point) -> bool {
+    matches!(point, Point::Coordinate(_, y) if !y.bit(0))
+}
+
+fn x_of(point: &Point) -> BigUint {
+    match point {
+        Point::Coordinate(x, _) => x.clone(),
+        Point::Identity => BigUint::from(0u32),
+    }
+}
+
+fn encode_nonce_point(curve: &EllipticCurve, point: &Point) -> Vec<u8> {
+    match point {
+        Point::Identity => vec![0u8; 33],
+        _ => encode_point(curve, point, true),
+    }
+}
+
+fn decode_nonce_point(curve: &EllipticCurve, bytes: &[u8]) -> Result<Point, &'static str> {
+    if bytes.iter().all(|b| *b == 0) {
+        return Ok(Point::Identity);
+    }
+    decode_point(curve, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign_with(bip340: &Bip340, private_keys: &[BigUint], message: &[u8]) -> (KeyAggContext, Bip340Signature) {
+        let public_keys: Vec<Point> = private_keys.iter().map(|d| bip340.params.curve.scalar_mult(&bip340.params.generator, d)).collect();
+        let key_agg = KeyAggContext::new(bip340, &public_keys).unwrap();
+
+        // Round 1
+        let (secret_nonces, public_nonces): (Vec<_>, Vec<_>) = public_keys.iter().map(|p| nonce_gen(bip340, p)).unzip();
+        let aggregate_nonce = nonce_agg(bip340, &public_nonces);
+
+        // Round 2
+        let session = Session::new(bip340, &key_agg, &aggregate_nonce, message);
+        let partials: Vec<PartialSignature> = secret_nonces
+            .into_iter()
+            .zip(private_keys)
+            .map(|(nonce, d)| session.sign(nonce, d).unwrap())
+            .collect();
+        for ((partial, nonce), key) in partials.iter().zip(&public_nonces).zip(&public_keys) {
+            assert!(session.verify_partial(partial, nonce, key));
+        }
+        let signature = session.aggregate(&partials);
+        (key_agg, signature)
+    }
+
+    #[test]
+    fn test_musig2_produces_bip340_signature() {
+        let bip340 = Bip340::new();
+        let private_keys: Vec<BigUint> = (1u32..=3).map(|i| BigUint::from(1000u32 * i + 7)).collect();
+        let message = b"2-of-2 channel close, 3 parties for good measure";
+
+        let (key_agg, signature) = sign_with(&bip340, &private_keys, message);
+        let q = key_agg.x_only_public_key();
+        assert!(bip340.verify(message, &signature, &q));
+        assert!(!bip340.verify(b"other message", &signature, &q));
+    }
+
+    #[test]
+    fn test_key_aggregation_coefficients() {
+        let bip340 = Bip340::new();
+        let p1 = bip340.params.curve.scalar_mult(&bip340.params.generator, &BigUint::from(11u32));
+        let p2 = bip340.params.curve.scalar_mult(&bip340.params.generator, &BigUint::from(22u32));
+
+        // The second distinct key gets coefficient 1; order matters
+        let context = KeyAggContext::new(&bip340, &[p1.clone(), p2.clone()]).unwrap();
+        assert_eq!(context.coefficient(&bip340, &p2), BigUint::from(1u32));
+        assert_ne!(context.coefficient(&bip340, &p1), BigUint::from(1u32));
+        let swapped = KeyAggContext::new(&bip340, &[p2.clone(), p1.clone()]).unwrap();
+        assert_ne!(context.aggregate_key, swapped.aggregate_key);
+
+        // Q isn't simply P1 + P2, so a rogue key P2' = X - P1 doesn't give control of Q = X
+        assert_ne!(context.aggregate_key, bip340.params.curve.add(&p1, &p2));
+        assert!(KeyAggContext::new(&bip340, &[]).is_err());
+    }
+
+    #[test]
+    fn test_bad_partial_signature_detected() {
+        let bip340 = Bip340::new();
+        let d1 = BigUint::from(5u32);
+        let d2 = BigUint::from(6u32);
+        let p1 = bip340.params.curve.scalar_mult(&bip340.params.generator, &d1);
+        let p2 = bip340.params.curve.scalar_mult(&bip340.params.generator, &d2);
+        let key_agg = KeyAggContext::new(&bip340, &[p1.clone(), p2.clone()]).unwrap();
+
+        let (sec1, pub1) = nonce_gen(&bip340, &p1);
+        let (sec2, pub2) = nonce_gen(&bip340, &p2);
+        let aggregate_nonce = nonce_agg(&bip340, &[pub1.clone(), pub2.clone()]);
+        let roundtrip = PublicNonce::from_bytes(&bip340.params.curve, &aggregate_nonce.to_bytes(&bip340.params.curve)).unwrap();
+        assert_eq!(roundtrip, aggregate_nonce);
+
+        let session = Session::new(&bip340, &key_agg, &aggregate_nonce, b"msg");
+        let good = session.sign(sec1, &d1).unwrap();
+        assert!(session.verify_partial(&good, &pub1, &p1));
+        assert!(!session.verify_partial(&good, &pub2, &p2));
+
+        // A nonce generated for one key can't be used with another
+        assert!(session.sign(sec2, &d1).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::curves;
+use crate::encoding;
+use crate::montgomery::{x25519_public_key, x25519_shared_secret, x448_public_key, x448_shared_secret};
+use crate::entropy::Entropy;
+use num_bigint::BigUint;
+use rand::RngCore;
+
+// Noise protocol framework DH functions (noiseprotocol.org, section 4.1): GENERATE_KEYPAIR,
+// DH and DHLEN, behind a trait so a Noise implementation can take this crate as its backend
+//   25519  X25519, 32-byte keys and outputs
+//   448    X448, 56-byte keys and outputs
+//   P256   not in the spec but a common extension: 65-byte uncompressed public keys, and the
+//          32-byte x-coordinate of the shared point as the DH output
+// Noise lets DH either reject invalid public keys or produce some output; here they're
+// rejected: small-order 25519/448 keys (all-zero output) and P-256 points that fail
+// validation. DH names go into protocol names, e.g. Noise_XX_25519_ChaChaPoly_SHA256
+
+#[derive(Clone, Debug)]
+pub struct NoiseKeyPair {
+    pub private_key: Vec<u8>,
+    pub public_key: Vec<u8>,
+}
+
+pub trait NoiseDh {
+    // The DH name in Noise protocol names
+    fn name(&self) -> &'static str;
+    // DHLEN, the size of DH outputs
+    fn dh_len(&self) -> usize;
+    // Equal to DHLEN for the spec's functions, larger for P256
+    fn public_key_len(&self) -> usize;
+    fn generate_keypair(&self) -> NoiseKeyPair;
+    // The key pair for a stored private key
+    fn keypair_from_private(&self, private_key: &[u8]) -> Result<NoiseKeyPair, &'static str>;
+    fn dh(&self, keypair: &NoiseKeyPair, public_key: &[u8]) -> Result<Vec<u8>, &'static str>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dh25519;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dh448;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DhP256;
+
+fn fixed<const N: usize>(bytes: &[u8], error: &'static str) -> Result<[u8; N], &'static str> {
+    bytes.try_into().map_err(|_| error)
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    Entropy.fill_bytes(&mut bytes);
+    bytes
+}
+
+impl NoiseDh for Dh25519 {
+    fn name(&self) -> &'static str {
+        "25519"
+    }
+
+    fn dh_len(&self) -> usize {
+        32
+    }
+
+    fn public_key_len(&self) -> usize {
+        32
+    }
+
+    fn generate_keypair(&self) -> NoiseKeyPair {
+        self.keypair_from_private(&random_bytes(32)).unwrap()
+    }
+
+    fn keypair_from_private(&self, private_key: &[u8]) -> Result<NoiseKeyPair, &'static str> {
+        let public_key = x25519_public_key(&fixed(private_key, "X25519 keys are 32 bytes")?);
+        Ok(NoiseKeyPair { private_key: private_key.to_vec(), public_key: public_key.to_vec() })
+    }
+
+    fn dh(&self, keypair: &NoiseKeyPair, public_key: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let private_key = fixed(&keypair.private_key, "X25519 keys are 32 bytes")?;
+        Ok(x25519_shared_secret(&private_key, &fixed(public_key, "X25519 keys are 32 bytes")?)?.to_vec())
+    }
+}
+
+impl NoiseDh for Dh448 {
+    fn name(&self) -> &'static str {
+        "448"
+    }
+
+    fn dh_len(&self) -> usize {
+        56
+    }
+
+    fn public_key_len(&self) -> usize {
+        56
+    }
+
+    fn generate_keypair(&self) -> NoiseKeyPair {
+        self.keypair_from_private(&random_bytes(56)).unwrap()
+    }
+
+    fn keypair_from_private(&self, private_key: &[u8]) -> Result<NoiseKeyPair, &'static str> {
+        let public_key = x448_public_key(&fixed(private_key, "X448 keys are 56 bytes")?);
+        Ok(NoiseKeyPair { private_key: private_key.to_vec(), public_key: public_key.to_vec() })
+    }
+
+    fn dh(&self, keypair: &NoiseKeyPair, public_key: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let private_key = fixed(&keypair.private_key, "X448 keys are 56 bytes")?;
+        Ok(x448_shared_secret(&private_key, &fixed(public_key, "X448 keys are 56 bytes")?)?.to_vec())
+    }
+}
+
+impl NoiseDh for DhP256 {
+    fn name(&self) -> &'static str {
+        "P256"
+    }
+
+    fn dh_len(&self) -> usize {
+        32
+    }
+
+    fn public_key_len(&self) -> usize {
+        65
+    }
+
+    fn generate_keypair(&self) -> NoiseKeyPair {
+        let private_key = curves::p256().ecdh().generate_private_key();
+        self.keypair_from_private(&encoding::to_fixed_bytes(&private_key, 32)).unwrap()
+    }
+
+    // A 32-byte big-endian scalar in [1, n-1]
+    fn keypair_from_private(&self, private_key: &[u8]) -> Result<NoiseKeyPair, &'static str> {
+        let curve = curves::p256();
+        let d = BigUint::from_bytes_be(private_key);
+        if private_key.len() != 32 || d == BigUint::from(0u32) || d >= curve.order {
+            return Err("invalid P-256 private key");
+        }
+        let public_key = encoding::encode_point(&curve.curve, &curve.ecdh().generate_public_key(&d), false);
+        Ok(NoiseKeyPair { private_key: private_key.to_vec(), public_key })
+    }
+
+    fn dh(&self, keypair: &NoiseKeyPair, public_key: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let curve = curves::p256();
+        if public_key.len() != 65 {
+            return Err("P256 public keys are 65-byte uncompressed points");
+        }
+        let peer = encoding::decode_point(&curve.curve, public_key)?;
+        let d = BigUint::from_bytes_be(&keypair.private_key);
+        Ok(curve.ecdh().agree(&d, &peer)?.raw_bytes().to_vec())
+    }
+}
+
+// The DH function for a name from a Noise protocol name
+pub fn by_name(name: &str) -> Option<Box<dyn NoiseDh>> {
+    match name {
+        "25519" => Some(Box::new(Dh25519)),
+        "448" => Some(Box::new(Dh448)),
+        "P256" => Some(Box::new(DhP256)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    #[test]
+    fn test_25519_rfc7748() {
+        // RFC 7748 6.1
+        let alice = Dh25519.keypair_from_private(&from_hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a").unwrap()).unwrap();
+        let bob = Dh25519.keypair_from_private(&from_hex("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb").unwrap()).unwrap();
+        assert_eq!(to_hex(&alice.public_key), "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+        assert_eq!(to_hex(&bob.public_key), "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+        let shared = Dh25519.dh(&alice, &bob.public_key).unwrap();
+        assert_eq!(to_hex(&shared), "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(Dh25519.dh(&bob, &alice.public_key).unwrap(), shared);
+
+        // u = 0 and u = 1 have small order
+        assert!(Dh25519.dh(&alice, &[0u8; 32]).is_err());
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        assert!(Dh25519.dh(&alice, &one).is_err());
+    }
+
+    #[test]
+    fn test_dh_functions() {
+        for name in ["25519", "448", "P256"] {
+            let dh = by_name(name).unwrap();
+            assert_eq!(dh.name(), name);
+            let (a, b) = (dh.generate_keypair(), dh.generate_keypair());
+            assert_eq!(a.public_key.len(), dh.public_key_len());
+            let shared = dh.dh(&a, &b.public_key).unwrap();
+            assert_eq!(shared.len(), dh.dh_len());
+            assert_eq!(dh.dh(&b, &a.public_key).unwrap(), shared);
+            assert_eq!(dh.keypair_from_private(&a.private_key).unwrap().public_key, a.public_key);
+            assert!(dh.dh(&a, &b.public_key[1..]).is_err());
+        }
+        assert!(by_name("secp256k1").is_none());
+    }
+
+    #[test]
+    fn test_p256_rejects_invalid_keys() {
+        let curve = curves::p256();
+        let keypair = DhP256.generate_keypair();
+
+        // Compressed, off-curve and out-of-range private keys
+        let compressed = encoding::encode_point(&curve.curve, &curve.generator, true);
+        assert!(DhP256.dh(&keypair, &compressed).is_err());
+        let mut off_curve = keypair.public_key.clone();
+        off_curve[64] ^= 1;
+        assert!(DhP256.dh(&keypair, &off_curve).is_err());
+        assert!(DhP256.keypair_from_private(&[0u8; 32]).is_err());
+        assert!(DhP256.keypair_from_private(&encoding::to_fixed_bytes(&curve.order, 32)).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+use crate::entropy::Entropy;
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+
+// Trial-division factorization: [(prime, exponent)] in ascending order
+// O(√n) divisions, so only for the small numbers that come up with toy curves
+pub fn factorize(n: &BigUint) -> Vec<(BigUint, u32)> {
+    let mut factors = Vec::new();
+    let mut remaining = n.clone();
+    let mut divisor = BigUint::from(2u32);
+
+    while &divisor * &divisor <= remaining {
+        let mut exponent = 0u32;
+        while &remaining % &divisor == BigUint::from(0u32) {
+            remaining /= &divisor;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((divisor.clone(), exponent));
+        }
+        divisor += 1u32;
+    }
+
+    // Whatever is left (if > 1) is a prime larger than √n
+    if remaining > BigUint::from(1u32) {
+        factors.push((remaining, 1));
+    }
+
+    factors
+}
+
+// Modular inverse via the extended Euclidean algorithm, for any modulus
+// None when gcd(x, m) != 1
+pub fn mod_inverse(x: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let m_int = BigInt::from_biguint(Sign::Plus, m.clone());
+
+    // Invariant: old_r = old_s * x (mod m), r = s * x (mod m)
+    let mut old_r = BigInt::from_biguint(Sign::Plus, x % m);
+    let mut r = m_int.clone();
+    let mut old_s = BigInt::from(1);
+    let mut s = BigInt::from(0);
+
+    while r != BigInt::from(0) {
+        let quotient = &old_r / &r;
+        let next_r = &old_r - &quotient * &r;
+        old_r = std::mem::replace(&mut r, next_r);
+        let next_s = &old_s - &quotient * &s;
+        old_s = std::mem::replace(&mut s, next_s);
+    }
+
+    if old_r != BigInt::from(1) {
+        return None;
+    }
+
+    // Bring the Bézout coefficient into [0, m)
+    let inverse = ((old_s % &m_int) + &m_int) % &m_int;
+    inverse.to_biguint()
+}
+
+// Chinese remainder theorem: x ≡ r_i (mod m_i) for pairwise coprime m_i
+// Returns (x, M) with M = ∏ m_i and 0 <= x < M
+pub fn crt(congruences: &[(BigUint, BigUint)]) -> Result<(BigUint, BigUint), &'static str> {
+    let mut x = BigUint::from(0u32);
+    let mut modulus = BigUint::from(1u32);
+
+    // Fold one congruence at a time: x' = x + M * ((r - x) * M^(-1) mod m)
+    for (residue, m) in congruences {
+        let m_inverse = mod_inverse(&modulus, m).ok_or("moduli are not pairwise coprime")?;
+        let x_mod_m = &x % m;
+        let diff = ((residue % m) + m - x_mod_m) % m;
+        let t = (diff * m_inverse) % m;
+        x += &modulus * t;
+        modulus *= m;
+    }
+
+    Ok((x, modulus))
+}
+
+// Miller–Rabin with 40 random bases: a composite passes with probability below 4^-40
+// n - 1 = 2^s * d with d odd; n is a probable prime if each base a has a^d = 1 or
+// a^(2^r * d) = n - 1 for some r < s
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    let one = BigUint::from(1u32);
+    let two = BigUint::from(2u32);
+    if *n < two {
+        return false;
+    }
+    for small in [2u32, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if *n == BigUint::from(small) {
+            return true;
+        }
+        if (n % small) == BigUint::from(0u32) {
+            return false;
+        }
+    }
+
+    let n_minus_one = n - &one;
+    let s = n_minus_one.trailing_zeros().expect("?n - 1 is even and nonzero?");
+    let d = &n_minus_one >> s;
+    let mut rng = Entropy;
+    'bases: for _ in 0..40 {
+        let mut x = rng.gen_biguint_range(&two, &n_minus_one).modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+// All cube roots of c mod a prime p: one when p = 2 mod 3 (cubing is a bijection), none or
+// three when p = 1 mod 3. For the latter, the Tonelli–Shanks idea with 3 in place of 2:
+// p - 1 = 3^e * m with 3 ∤ m, x = c^(3^-1 mod m) is a root up to an error ε = x³/c in the
+// subgroup S of order 3^e, generated by h = g^m for a non-cube g. ε is a cube in S, so ε = h^3i
+// with i found digit by digit in base 3, and x h^-i is a root; the others differ by h^(3^(e-1))
+pub fn cube_roots(c: &BigUint, p: &BigUint) -> Vec<BigUint> {
+    let one = BigUint::from(1u32);
+    let c = c % p;
+    if c == BigUint::from(0u32) {
+        return vec![c];
+    }
+    let p_minus_one = p - &one;
+    if &p_minus_one % 3u32 != BigUint::from(0u32) {
+        let exponent = (&p_minus_one * 2u32 + 1u32) / 3u32;
+        return vec![c.modpow(&exponent, p)];
+    }
+    if c.modpow(&(&p_minus_one / 3u32), p) != one {
+        return Vec::new();
+    }
+
+    let mut e = 0u32;
+    let mut m = p_minus_one.clone();
+    while &m % 3u32 == BigUint::from(0u32) {
+        m /= 3u32;
+        e += 1;
+    }
+    let mut g = BigUint::from(2u32);
+    while g.modpow(&(&p_minus_one / 3u32), p) == one {
+        g += 1u32;
+    }
+    let h = g.modpow(&m, p);
+    let h_inverse = mod_inverse(&h, p).expect("?p is prime?");
+    let three_power = |k: u32| BigUint::from(3u32).pow(k);
+    // ω, a primitive cube root of unity
+    let omega = h.modpow(&three_power(e - 1), p);
+
+    let x = c.modpow(&mod_inverse(&BigUint::from(3u32), &m).unwrap_or_default(), p);
+    let error = (x.modpow(&BigUint::from(3u32), p) * mod_inverse(&c, p).expect("?c is nonzero?")) % p;
+
+    // ε = h^k with k = Σ k_j 3^j
+    let mut k = BigUint::from(0u32);
+    for j in 0..e {
+        let rest = (&error * h_inverse.modpow(&k, p)) % p;
+        let digit = rest.modpow(&three_power(e - 1 - j), p);
+        if digit != one {
+            let step = if digit == omega { 1u32 } else { 2u32 };
+            k += three_power(j) * step;
+        }
+    }
+    let root = (x * h_inverse.modpow(&(k / 3u32), p)) % p;
+    let root_omega = (&root * &omega) % p;
+    let root_omega_squared = (&root_omega * &omega) % p;
+    vec![root, root_omega, root_omega_squared]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factorize() {
+        let factors = factorize(&BigUint::from(28u32));
+        assert_eq!(factors, vec![(BigUint::from(2u32), 2), (BigUint::from(7u32), 1)]);
+
+        let factors = factorize(&BigUint::from(97u32));
+        assert_eq!(factors, vec![(BigUint::from(97u32), 1)]);
+
+        assert!(factorize(&BigUint::from(1u32)).is_empty());
+    }
+
+    #[test]
+    fn test_is_probable_prime() {
+        let primes: Vec<u32> = (0..200u32).filter(|&n| is_probable_prime(&BigUint::from(n))).collect();
+        let expected: Vec<u32> = (2..200u32).filter(|&n| (2..n).all(|d| n % d != 0)).collect();
+        assert_eq!(primes, expected);
+
+        // Carmichael numbers fool the Fermat test but not Miller–Rabin
+        for carmichael in [561u32, 41041, 825265] {
+            assert!(!is_probable_prime(&BigUint::from(carmichael)));
+        }
+        // 2^127 - 1 is prime, 2^128 + 1 = 59649589127497217 * 5704689200685129054721 isn't
+        assert!(is_probable_prime(&((BigUint::from(1u32) << 127) - 1u32)));
+        assert!(!is_probable_prime(&((BigUint::from(1u32) << 128) + 1u32)));
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        // 4 * 3 = 12 ≡ 1 (mod 11)
+        assert_eq!(mod_inverse(&BigUint::from(4u32), &BigUint::from(11u32)), Some(BigUint::from(3u32)));
+
+        // Composite modulus: 7 * 13 = 91 ≡ 1 (mod 18)
+        assert_eq!(mod_inverse(&BigUint::from(7u32), &BigUint::from(18u32)), Some(BigUint::from(13u32)));
+
+        // gcd(6, 18) != 1
+        assert_eq!(mod_inverse(&BigUint::from(6u32), &BigUint::from(18u32)), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        // x ≡ 2 (mod 3), x ≡ 3 (mod 5), x ≡ 2 (mod 7) => x = 23 (mod 105)
+        let congruences = [
+            (BigUint::from(2u32), BigUint::from(3u32)),
+            (BigUint::from(3u32), BigUint::from(5u32)),
+            (BigUint::from(2u32), BigUint::from(7u32)),
+        ];
+        assert_eq!(crt(&congruences), Ok((BigUint::from(23u32), BigUint::from(105u32))));
+
+        let not_coprime = [
+            (BigUint::from(1u32), BigUint::from(4u32)),
+            (BigUint::from(3u32), BigUint::from(6u32)),
+        ];
+        assert!(crt(&not_coprime).is_err());
+    }
+
+    #[test]
+    fn test_cube_roots() {
+        for p in [101u32, 103, 109, 163, 487, 1459] {
+            let p = BigUint::from(p);
+            for c in 0..60u32 {
+                let c = BigUint::from(c);
+                let roots = cube_roots(&c, &p);
+                let expected = (0..p.to_u64_digits().first().copied().unwrap_or(0))
+                    .filter(|x| BigUint::from(*x).modpow(&BigUint::from(3u32), &p) == c)
+                    .count();
+                assert_eq!(roots.len(), expected, "c = {}, p = {}", c, p);
+                for root in roots {
+                    assert_eq!(root.modpow(&BigUint::from(3u32), &p), c);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{FiniteField, Point};
+use crate::curves::{self, NamedCurve};
+use crate::encoding::{decode_point, encode_point};
+use crate::hash_to_curve::{hash_to_field, HashToCurve};
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+// Oblivious pseudorandom function, base mode (RFC 9497), ciphersuite P256-SHA256
+//
+// The server holds a key k; the client learns F(k, input) = H(input, k * H2C(input))
+// while the server learns nothing about the input and the client nothing about k:
+//   client:  r random, B = r * H2C(input)        (blind)
+//   server:  Z = k * B                           (blind_evaluate)
+//   client:  N = r⁻¹ * Z = k * H2C(input), output = H(input, N)   (finalize)
+// Used for password hardening (OPAQUE), private set intersection and anonymous tokens
+
+pub const MODE_OPRF: u8 = 0x00;
+const SUITE_ID: &[u8] = b"P256-SHA256";
+
+// Client state between blind and finalize: the blind must stay secret
+#[derive(Clone, Debug)]
+pub struct BlindedInput {
+    pub blind: BigUint,
+    pub blinded_element: Point,
+}
+
+#[derive(Clone, Debug)]
+pub struct OPRFKeyPair {
+    pub private_key: BigUint,
+    pub public_key: Point,
+}
+
+pub struct OPRF {
+    pub suite: NamedCurve,
+    pub hasher: HashToCurve,
+    context_string: Vec<u8>,
+}
+
+impl OPRF {
+    pub fn p256_sha256() -> Self {
+        // contextString = "OPRFV1-" || mode || "-" || identifier
+        let mut context_string = b"OPRFV1-".to_vec();
+        context_string.push(MODE_OPRF);
+        context_string.push(b'-');
+        context_string.extend_from_slice(SUITE_ID);
+
+        OPRF {
+            suite: curves::p256(),
+            hasher: HashToCurve::p256(),
+            context_string,
+        }
+    }
+
+    // Deterministic server key from a 32-byte seed and public info (DeriveKeyPair)
+    pub fn derive_keypair(&self, seed: &[u8], info: &[u8]) -> Result<OPRFKeyPair, &'static str> {
+        if info.len() > u16::MAX as usize {
+            return Err("key info too long");
+        }
+        let mut derive_input = seed.to_vec();
+        derive_input.extend_from_slice(&(info.len() as u16).to_be_bytes());
+        derive_input.extend_from_slice(info);
+
+        let dst = self.dst(b"DeriveKeyPair");
+        for counter in 0..=255u8 {
+            let mut input = derive_input.clone();
+            input.push(counter);
+            let private_key = self.hash_to_scalar(&input, &dst)?;
+            if private_key != BigUint::from(0u32) {
+                let public_key = self.suite.curve.scalar_mult(&self.suite.generator, &private_key);
+                return Ok(OPRFKeyPair { private_key, public_key });
+            }
+        }
+        Err("key derivation failed")
+    }
+
+    // Random server key
+    pub fn generate_keypair(&self) -> OPRFKeyPair {
+        let private_key = Scalar::random(&self.suite.order, &mut Entropy).into_value();
+        let public_key = self.suite.curve.scalar_mult(&self.suite.generator, &private_key);
+        OPRFKeyPair { private_key, public_key }
+    }
+
+    // Client: B = r * H2C(input) with a fresh random blind r
+    pub fn blind(&self, input: &[u8]) -> Result<BlindedInput, &'static str> {
+        let blind = Scalar::random(&self.suite.order, &mut Entropy).into_value();
+        self.blind_with(input, &blind)
+    }
+
+    // Client: blinding with a caller-chosen r (test vectors)
+    pub fn blind_with(&self, input: &[u8], blind: &BigUint) -> Result<BlindedInput, &'static str> {
+        if *blind == BigUint::from(0u32) || *blind >= self.suite.order {
+            return Err("blind out of range");
+        }
+        let input_element = self.hash_to_group(input)?;
+        Ok(BlindedInput {
+            blind: blind.clone(),
+            blinded_element: self.suite.curve.scalar_mult(&input_element, blind),
+        })
+    }
+
+    // Server: Z = k * B, after checking B is a valid non-identity group element
+    pub fn blind_evaluate(&self, private_key: &BigUint, blinded_element: &Point) -> Result<Point, &'static str> {
+        self.validate_element(blinded_element)?;
+        Ok(self.suite.curve.scalar_mult(blinded_element, private_key))
+    }
+
+    // Client: N = r⁻¹ * Z, output = H(len(input) || input || len(N) || N || "Finalize")
+    pub fn finalize(&self, input: &[u8], blinded: &BlindedInput, evaluated_element: &Point) -> Result<Vec<u8>, &'static str> {
+        self.validate_element(evaluated_element)?;
+        let scalar_field = FiniteField { p: self.suite.order.clone() };
+        let blind_inverse = scalar_field.div(&BigUint::from(1u32), &blinded.blind);
+        let unblinded = self.suite.curve.scalar_mult(evaluated_element, &blind_inverse);
+        self.finalize_hash(input, &unblinded)
+    }
+
+    // Server-side evaluation without blinding: F(k, input), e.g. to precompute a lookup table
+    pub fn evaluate(&self, private_key: &BigUint, input: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let input_element = self.hash_to_group(input)?;
+        let evaluated = self.suite.curve.scalar_mult(&input_element, private_key);
+        self.finalize_hash(input, &evaluated)
+    }
+
+    // SerializeElement: compressed SEC1
+    pub fn serialize_element(&self, element: &Point) -> Vec<u8> {
+        encode_point(&self.suite.curve, element, true)
+    }
+
+    // DeserializeElement: rejects the identity and anything off the curve
+    pub fn deserialize_element(&self, bytes: &[u8]) -> Result<Point, &'static str> {
+        let element = decode_point(&self.suite.curve, bytes)?;
+        self.validate_element(&element)?;
+        Ok(element)
+    }
+
+    fn hash_to_group(&self, input: &[u8]) -> Result<Point, &'static str> {
+        let element = self.hasher.hash_to_curve(input, &self.dst(b"HashToGroup-"))?;
+        if element == Point::Identity {
+            return Err("input hashes to the identity");
+        }
+        Ok(element)
+    }
+
+    fn hash_to_scalar(&self, input: &[u8], dst: &[u8]) -> Result<BigUint, &'static str> {
+        Ok(hash_to_field(input, dst, 1, &self.suite.order, self.hasher.l)?.remove(0))
+    }
+
+    fn finalize_hash(&self, input: &[u8], element: &Point) -> Result<Vec<u8>, &'static str> {
+        if input.len() > u16::MAX as usize {
+            return Err("input too long");
+        }
+        let serialized = self.serialize_element(element);
+        let output = Sha256::new()
+            .chain_update((input.len() as u16).to_be_bytes())
+            .chain_update(input)
+            .chain_update((serialized.len() as u16).to_be_bytes())
+            .chain_update(&serialized)
+            .chain_update(b"Finalize")
+            .finalize();
+        Ok(output.to_vec())
+    }
+
+    fn validate_element(&self, element: &Point) -> Result<(), &'static str> {
+        if *element == Point::Identity {
+            return Err("element is the identity");
+        }
+        if !self.suite.curve.is_on_curve(element) {
+            return Err("element is not on the curve");
+        }
+        Ok(())
+    }
+
+    fn dst(&self, prefix: &[u8]) -> Vec<u8> {
+        let mut dst = prefix.to_vec();
+        dst.extend_from_slice(&self.context_string);
+        dst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    fn hex(s: &str) -> BigUint {
+        BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+    }
+
+    #[test]
+    fn test_rfc9497_vector() {
+        // RFC 9497, A.3.1: P256-SHA256, OPRF mode, test vector 1
+        let oprf = OPRF::p256_sha256();
+        let keypair = oprf.derive_keypair(&[0xa3; 32], b"test key").unwrap();
+        assert_eq!(keypair.private_key, hex("159749d750713afe245d2d39ccfaae8381c53ce92d098a9375ee70739c7ac0bf"));
+
+        let input = [0x00];
+        let blind = hex("3338fa65ec36e0290022b48eb562889d89dbfa691d1cde91517fa222ed7ad364");
+        let blinded = oprf.blind_with(&input, &blind).unwrap();
+        assert_eq!(
+            to_hex(&oprf.serialize_element(&blinded.blinded_element)),
+            "03723a1e5c09b8b9c18d1dcbca29e8007e95f14f4732d9346d490ffc195110368d"
+        );
+
+        let evaluated = oprf.blind_evaluate(&keypair.private_key, &blinded.blinded_element).unwrap();
+        assert_eq!(
+            to_hex(&oprf.serialize_element(&evaluated)),
+            "030de02ffec47a1fd53efcdd1c6faf5bdc270912b8749e783c7ca75bb412958832"
+        );
+
+        let output = oprf.finalize(&input, &blinded, &evaluated).unwrap();
+        assert_eq!(output, from_hex("a0b34de5fa4c5b6da07e72af73cc507cceeb48981b97b7285fc375345fe495dd").unwrap());
+        assert_eq!(oprf.evaluate(&keypair.private_key, &input).unwrap(), output);
+    }
+
+    #[test]
+    fn test_blinding_hides_input() {
+        let oprf = OPRF::p256_sha256();
+        let keypair = oprf.generate_keypair();
+        let input = b"correct horse battery staple";
+
+        // Two sessions on the same input: different blinded elements, same output
+        let first = oprf.blind(input).unwrap();
+        let second = oprf.blind(input).unwrap();
+        assert_ne!(first.blinded_element, second.blinded_element);
+
+        let out1 = oprf.finalize(input, &first, &oprf.blind_evaluate(&keypair.private_key, &first.blinded_element).unwrap()).unwrap();
+        let out2 = oprf.finalize(input, &second, &oprf.blind_evaluate(&keypair.private_key, &second.blinded_element).unwrap()).unwrap();
+        assert_eq!(out1, out2);
+
+        // Output depends on the key
+        let other = oprf.generate_keypair();
+        assert_ne!(oprf.evaluate(&other.private_key, input).unwrap(), out1);
+    }
+
+    #[test]
+    fn test_rejects_invalid_elements() {
+        let oprf = OPRF::p256_sha256();
+        let keypair = oprf.generate_keypair();
+        assert!(oprf.blind_evaluate(&keypair.private_key, &Point::Identity).is_err());
+
+        let off_curve = Point::Coordinate(BigUint::from(1u32), BigUint::from(1u32));
+        assert!(oprf.blind_evaluate(&keypair.private_key, &off_curve).is_err());
+        assert!(oprf.deserialize_element(&[0x00]).is_err());
+        assert!(oprf.blind_with(b"x", &BigUint::from(0u32)).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{EllipticCurve, Point};
+use crate::extension::{ExtElement, ExtPoint, ExtensionCurve, ExtensionField};
+use num_bigint::BigUint;
+
+// Embedding degree: smallest k with r | p^k - 1, searched up to max_k
+// E[r] lives in E(F_{p^k}) and the pairing lands in the order-r subgroup of F_{p^k}*
+pub fn embedding_degree(p: &BigUint, r: &BigUint, max_k: u32) -> Option<u32> {
+    let one = BigUint::from(1u32) % r;
+    let p_mod_r = p % r;
+    let mut p_power = p_mod_r.clone();
+    for k in 1..=max_k {
+        // p^k ≡ 1 (mod r)
+        if p_power == one {
+            return Some(k);
+        }
+        p_power = (&p_power * &p_mod_r) % r;
+    }
+    None
+}
+
+// Reduced Tate pairing e_r: E(F_p)[r] × E(F_{p^k}) -> μ_r ⊂ F_{p^k}*
+pub struct TatePairing {
+    pub base_curve: EllipticCurve,
+    pub curve: ExtensionCurve,
+    pub order: BigUint,
+    frobenius_table: Vec<ExtElement>,
+}
+
+impl TatePairing {
+    // r must divide #E(F_p) and p^k - 1, with k the degree of the field
+    pub fn new(curve: &EllipticCurve, order: BigUint, field: ExtensionField) -> Self {
+        TatePairing {
+            base_curve: curve.clone(),
+            frobenius_table: field.frobenius_table(),
+            curve: ExtensionCurve::new(curve, field),
+            order,
+        }
+    }
+
+    // Miller's algorithm: f_{r,P}(Q), where div(f_{r,P}) = r(P) - r(O)
+    //
+    // Builds rP by double-and-add; each step multiplies in the line through the points
+    // being added, divided by the vertical line through their sum:
+    //   f_{2i} = f_i² * l_{T,T} / v_{2T},   f_{i+1} = f_i * l_{T,P} / v_{T+P}
+    // T stays in E(F_p), so only the line evaluations at Q touch F_{p^k}
+    // Numerator and denominator are kept apart so only one inversion is needed
+    pub fn miller(&self, p: &Point, q: &ExtPoint) -> Result<ExtElement, &'static str> {
+        let f = &self.curve.field;
+        if *p == Point::Identity || *q == ExtPoint::Identity {
+            return Err("pairing input is the point at infinity");
+        }
+        if !self.base_curve.is_torsion_point(p, &self.order) {
+            return Err("first pairing input is not an r-torsion point");
+        }
+
+        let mut numerator = f.one();
+        let mut denominator = f.one();
+        let mut t = p.clone();
+
+        for i in (0..self.order.bits() - 1).rev() {
+            let (line, vertical, doubled) = self.line_function(&t, &t, q)?;
+            numerator = f.mul(&f.mul(&numerator, &numerator), &line);
+            denominator = f.mul(&f.mul(&denominator, &denominator), &vertical);
+            t = doubled;
+
+            if self.order.bit(i) {
+                let (line, vertical, sum) = self.line_function(&t, p, q)?;
+                numerator = f.mul(&numerator, &line);
+                denominator = f.mul(&denominator, &vertical);
+                t = sum;
+            }
+        }
+
+        Ok(f.div(&numerator, &denominator))
+    }
+
+    // f^((p^k - 1) / r): kills the r-th powers, making the Miller value well defined
+    //
+    // For even k with r ∤ p^(k/2) - 1 the exponent splits as (p^(k/2) - 1) * (p^(k/2) + 1) / r:
+    //   easy part: f^(p^(k/2) - 1) = frob^(k/2)(f) / f, a few Frobenius maps and one inversion
+    //   hard part: write (p^(k/2) + 1) / r in base p, f^(Σ d_i p^i) = ∏ frob^i(f)^(d_i),
+    //              a multi-exponentiation sharing log(p) squarings
+    pub fn final_exponentiation(&self, value: &ExtElement) -> Result<ExtElement, &'static str> {
+        let f = &self.curve.field;
+        let zero = BigUint::from(0u32);
+        let one = BigUint::from(1u32);
+        let q_minus_one = f.order() - &one;
+        if &q_minus_one % &self.order != zero {
+            return Err("r does not divide p^k - 1: field degree below the embedding degree");
+        }
+
+        let k = f.degree();
+        let half_power = f.p.pow((k / 2) as u32);
+        if !k.is_multiple_of(2) || (&half_power - &one) % &self.order == zero {
+            return Ok(f.pow(value, &(q_minus_one / &self.order)));
+        }
+
+        let mut conjugate = value.clone();
+        for _ in 0..k / 2 {
+            conjugate = f.frobenius(&conjugate, &self.frobenius_table);
+        }
+        let easy = f.div(&conjugate, value);
+
+        let mut hard_exponent = (half_power + &one) / &self.order;
+        let mut digits = Vec::new();
+        let mut bases = Vec::new();
+        let mut base = easy;
+        while hard_exponent > zero {
+            digits.push(&hard_exponent % &f.p);
+            hard_exponent /= &f.p;
+            bases.push(base.clone());
+            base = f.frobenius(&base, &self.frobenius_table);
+        }
+
+        let max_bits = digits.iter().map(|d| d.bits()).max().unwrap_or(0);
+        let mut result = f.one();
+        for bit in (0..max_bits).rev() {
+            result = f.mul(&result, &result);
+            for (digit, base) in digits.iter().zip(&bases) {
+                if digit.bit(bit) {
+                    result = f.mul(&result, base);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    // e(P, Q) = f_{r,P}(Q)^((p^k - 1) / r)
+    pub fn pair(&self, p: &Point, q: &ExtPoint) -> Result<ExtElement, &'static str> {
+        self.final_exponentiation(&self.miller(p, q)?)
+    }
+
+    // ∏ e(P_i, Q_i) with a single final exponentiation, the expensive part of a pairing
+    pub fn pairing_product(&self, pairs: &[(Point, ExtPoint)]) -> Result<ExtElement, &'static str> {
+        let f = &self.curve.field;
+        let mut product = f.one();
+        for (p, q) in pairs {
+            product = f.mul(&product, &self.miller(p, q)?);
+        }
+        self.final_exponentiation(&product)
+    }
+
+    // Line through T and U evaluated at Q, the vertical at T + U evaluated at Q, and T + U
+    fn line_function(&self, t: &Point, u: &Point, q: &ExtPoint) -> Result<(ExtElement, ExtElement, Point), &'static str> {
+        let f = &self.curve.field;
+        let base = self.base_curve.field();
+        let (Point::Coordinate(xt, yt), Point::Coordinate(xu, yu), ExtPoint::Coordinate(xq, yq)) = (t, u, q) else {
+            return Err("line function through the point at infinity");
+        };
+
+        let sum = self.base_curve.add(t, u);
+        let xq_minus_xt = f.sub(xq, &f.from_base(xt));
+
+        // T = -U (including 2-torsion doubling): the line is vertical, x - x_T
+        // and T + U = O contributes no vertical of its own
+        let (line, vertical) = if xt == xu && (yt != yu || *yt == BigUint::from(0u32)) {
+            (xq_minus_xt, f.one())
+        } else {
+            // Slope in F_p: tangent (3x² + a) / 2y or chord (y_U - y_T) / (x_U - x_T)
+            let slope = if xt == xu {
+                let three_x_squared = base.mul(&BigUint::from(3u32), &base.mul(xt, xt));
+                base.div(&base.add(&three_x_squared, self.base_curve.a()), &base.add(yt, yt))
+            } else {
+                base.div(&base.sub(yu, yt), &base.sub(xu, xt))
+            };
+            // l(Q) = y_Q - y_T - s(x_Q - x_T)
+            let line = f.sub(&f.sub(yq, &f.from_base(yt)), &f.scale(&xq_minus_xt, &slope));
+            let Point::Coordinate(x_sum, _) = &sum else {
+                unreachable!("a non-vertical line meets the curve in a third affine point")
+            };
+            (line, f.sub(xq, &f.from_base(x_sum)))
+        };
+
+        // Q on one of the lines: the divisors aren't disjoint, choose another Q
+        if f.is_zero(&line) || f.is_zero(&vertical) {
+            return Err("degenerate pairing input: Q lies on a Miller line");
+        }
+
+        Ok((line, vertical, sum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // y² = x³ + x over F_43 is supersingular: #E = 44 = 4 * 11, embedding degree 2
+    // F_43² = F_43[i] / (i² + 1), and ψ(x, y) = (-x, iy) is a distortion map
+    fn setup() -> (EllipticCurve, TatePairing, Point) {
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(0u32), BigUint::from(43u32));
+        let field = ExtensionField::new(curve.p().clone(), vec![BigUint::from(1u32), BigUint::from(0u32)]);
+        let pairing = TatePairing::new(&curve, BigUint::from(11u32), field);
+        let point = Point::Coordinate(BigUint::from(4u32), BigUint::from(5u32));
+        (curve, pairing, point)
+    }
+
+    fn distort(pairing: &TatePairing, point: &Point) -> ExtPoint {
+        let f = &pairing.curve.field;
+        let i = f.from_coeffs(&[BigUint::from(0u32), BigUint::from(1u32)]);
+        match pairing.curve.lift(point) {
+            ExtPoint::Coordinate(x, y) => ExtPoint::Coordinate(f.neg(&x), f.mul(&i, &y)),
+            ExtPoint::Identity => ExtPoint::Identity,
+        }
+    }
+
+    #[test]
+    fn test_embedding_degree() {
+        assert_eq!(embedding_degree(&BigUint::from(43u32), &BigUint::from(11u32), 10), Some(2));
+
+        // The ECDSA demo curve: 17 has order 9 mod 19, far from pairing-friendly
+        assert_eq!(embedding_degree(&BigUint::from(17u32), &BigUint::from(19u32), 20), Some(9));
+        assert_eq!(embedding_degree(&BigUint::from(17u32), &BigUint::from(19u32), 5), None);
+    }
+
+    #[test]
+    fn test_tate_pairing_non_degenerate() {
+        let (curve, pairing, point) = setup();
+        let value = pairing.pair(&point, &distort(&pairing, &point)).unwrap();
+
+        let f = &pairing.curve.field;
+        assert_ne!(value, f.one());
+        assert_eq!(f.pow(&value, &pairing.order), f.one());
+
+        // The same for all of E(F_43)[11], which is <P>
+        let torsion = curve.torsion_points(&pairing.order).unwrap();
+        assert_eq!(torsion.len(), 11);
+        for point in torsion.iter().skip(1) {
+            assert_ne!(pairing.pair(point, &distort(&pairing, point)).unwrap(), f.one());
+        }
+    }
+
+    #[test]
+    fn test_tate_pairing_bilinear() {
+        let (curve, pairing, point) = setup();
+        let f = &pairing.curve.field;
+        let base = pairing.pair(&point, &distort(&pairing, &point)).unwrap();
+
+        // e(aP, ψ(bP)) = e(P, ψ(P))^(ab)
+        for (a, b) in [(2u32, 3u32), (5, 7), (10, 4)] {
+            let a_p = curve.scalar_mult(&point, &BigUint::from(a));
+            let b_p = curve.scalar_mult(&point, &BigUint::from(b));
+            let value = pairing.pair(&a_p, &distort(&pairing, &b_p)).unwrap();
+            assert_eq!(value, f.pow(&base, &BigUint::from(a * b)));
+        }
+
+        // Linearity in the second argument: e(P, Q1 + Q2) = e(P, Q1) * e(P, Q2)
+        let q1 = distort(&pairing, &curve.scalar_mult(&point, &BigUint::from(2u32)));
+        let q2 = distort(&pairing, &curve.scalar_mult(&point, &BigUint::from(3u32)));
+        let lhs = pairing.pair(&point, &pairing.curve.add(&q1, &q2)).unwrap();
+        let rhs = f.mul(&pairing.pair(&point, &q1).unwrap(), &pairing.pair(&point, &q2).unwrap());
+        assert_eq!(lhs, rhs);
+    }
+
+    #[test]
+    fn test_final_exponentiation_matches_plain_power() {
+        let (_, pairing, point) = setup();
+        let f = &pairing.curve.field;
+        let value = pairing.miller(&point, &distort(&pairing, &point)).unwrap();
+
+        let exponent = (f.order() - BigUint::from(1u32)) / &pairing.order;
+        assert_eq!(pairing.final_exponentiation(&value).unwrap(), f.pow(&value, &exponent));
+    }
+
+    #[test]
+    fn test_pairing_rejects_bad_inputs() {
+        let (_, pairing, point) = setup();
+        let q = distort(&pairing, &point);
+
+        // (2, 15) has order 44, not 11
+        let not_torsion = Point::Coordinate(BigUint::from(2u32), BigUint::from(15u32));
+        assert!(pairing.pair(&not_torsion, &q).is_err());
+        assert!(pairing.pair(&Point::Identity, &q).is_err());
+    }
+}
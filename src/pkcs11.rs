@@ -0,0 +1,237 @@
+use crate::curves::{self, NamedCurve};
+use crate::der::DerReader;
+use crate::ecdsa::ECDSASignature;
+use crate::encoding;
+use crate::signer::{Error, Keypair, PrehashSigner, Signer, VerifyingKey};
+use crate::Point;
+use sha2::{Digest, Sha256};
+
+// ECDSA signing with a key that never leaves a PKCS#11 token (HSM, smart card, SoftHSM)
+//
+// The backend needs four Cryptoki calls, behind Pkcs11Session so it works over whichever
+// binding the application already links (the cryptoki crate, a vendor SDK):
+//   C_FindObjects        locate the private and public EC key objects by CKA_LABEL
+//   C_GetAttributeValue  CKA_EC_PARAMS (curve OID or explicit parameters), CKA_EC_POINT
+//   C_GetMechanismList   pick CKM_ECDSA_SHA256, or CKM_ECDSA with SHA-256 on the host
+//   C_Sign               returns r || s, each padded to the order's size (PKCS#11 2.3.1)
+// CKA_EC_POINT is a DER OCTET STRING around the SEC1 point; some tokens store the bare point,
+// which is accepted too. The session must already be logged in as the user
+
+pub const CKO_PUBLIC_KEY: u64 = 2;
+pub const CKO_PRIVATE_KEY: u64 = 3;
+pub const CKK_EC: u64 = 3;
+pub const CKA_EC_PARAMS: u64 = 0x180;
+pub const CKA_EC_POINT: u64 = 0x181;
+pub const CKM_ECDSA: u64 = 0x1041;
+pub const CKM_ECDSA_SHA256: u64 = 0x1044;
+
+pub type ObjectHandle = u64;
+
+// Search template entries for C_FindObjects
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    Class(u64),
+    KeyType(u64),
+    Label(Vec<u8>),
+}
+
+pub trait Pkcs11Session {
+    fn find_objects(&self, template: &[Attribute]) -> Result<Vec<ObjectHandle>, &'static str>;
+    fn get_attribute(&self, object: ObjectHandle, attribute: u64) -> Result<Vec<u8>, &'static str>;
+    fn mechanisms(&self) -> Result<Vec<u64>, &'static str>;
+    fn sign(&self, mechanism: u64, key: ObjectHandle, data: &[u8]) -> Result<Vec<u8>, &'static str>;
+}
+
+pub struct Pkcs11Signer<S: Pkcs11Session> {
+    session: S,
+    key: ObjectHandle,
+    mechanism: u64,
+    curve: NamedCurve,
+    public_key: Point,
+}
+
+// The one object of this class with this label
+fn find_one<S: Pkcs11Session>(session: &S, class: u64, label: &str) -> Result<ObjectHandle, &'static str> {
+    let template = [Attribute::Class(class), Attribute::KeyType(CKK_EC), Attribute::Label(label.as_bytes().to_vec())];
+    match session.find_objects(&template)?[..] {
+        [object] => Ok(object),
+        [] => Err("no EC key with that label on the token"),
+        _ => Err("label matches more than one key"),
+    }
+}
+
+fn decode_ec_point(curve: &NamedCurve, bytes: &[u8]) -> Result<Point, &'static str> {
+    let mut reader = DerReader::new(bytes);
+    let point = match reader.read_octet_string() {
+        Ok(inner) if reader.finish().is_ok() => encoding::decode_point(&curve.curve, inner),
+        _ => encoding::decode_point(&curve.curve, bytes),
+    }?;
+    curve.ecdh().validate_public_key(&point)?;
+    Ok(point)
+}
+
+impl<S: Pkcs11Session> Pkcs11Signer<S> {
+    pub fn find_by_label(session: S, label: &str) -> Result<Self, &'static str> {
+        let key = find_one(&session, CKO_PRIVATE_KEY, label)?;
+        let curve = curves::from_der_parameters(&session.get_attribute(key, CKA_EC_PARAMS)?)?;
+        let public_object = find_one(&session, CKO_PUBLIC_KEY, label)?;
+        let public_key = decode_ec_point(&curve, &session.get_attribute(public_object, CKA_EC_POINT)?)?;
+
+        let mechanisms = session.mechanisms()?;
+        let mechanism = [CKM_ECDSA_SHA256, CKM_ECDSA]
+            .into_iter()
+            .find(|m| mechanisms.contains(m))
+            .ok_or("token supports neither CKM_ECDSA_SHA256 nor CKM_ECDSA")?;
+        Ok(Pkcs11Signer { session, key, mechanism, curve, public_key })
+    }
+
+    pub fn curve(&self) -> &NamedCurve {
+        &self.curve
+    }
+
+    pub fn mechanism(&self) -> u64 {
+        self.mechanism
+    }
+
+    pub fn session(&self) -> &S {
+        &self.session
+    }
+
+    // C_Sign's r || s as an ECDSASignature
+    fn sign_raw(&self, mechanism: u64, data: &[u8]) -> Result<ECDSASignature, &'static str> {
+        let raw = self.session.sign(mechanism, self.key, data)?;
+        ECDSASignature::from_fixed_bytes(&raw, self.curve.scalar_size())
+    }
+}
+
+impl<S: Pkcs11Session> Keypair for Pkcs11Signer<S> {
+    type VerifyingKey = VerifyingKey;
+
+    fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey::new(&self.curve, self.public_key.clone()).expect("?validated when the key was found?")
+    }
+}
+
+impl<S: Pkcs11Session> Signer<ECDSASignature> for Pkcs11Signer<S> {
+    fn try_sign(&self, message: &[u8]) -> Result<ECDSASignature, Error> {
+        let signature = match self.mechanism {
+            CKM_ECDSA_SHA256 => self.sign_raw(CKM_ECDSA_SHA256, message),
+            _ => self.sign_raw(CKM_ECDSA, &Sha256::digest(message)),
+        };
+        signature.map_err(Error::from_source)
+    }
+}
+
+// CKM_ECDSA signs the digest as given, so prehashes need the token to offer it
+impl<S: Pkcs11Session> PrehashSigner<ECDSASignature> for Pkcs11Signer<S> {
+    fn sign_prehash(&self, prehash: &[u8]) -> Result<ECDSASignature, Error> {
+        if !self.session.mechanisms().map_err(Error::from_source)?.contains(&CKM_ECDSA) {
+            return Err(Error::from_source("token doesn't support CKM_ECDSA"));
+        }
+        self.sign_raw(CKM_ECDSA, prehash).map_err(Error::from_source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::der;
+    use crate::secret_key::SecretKey;
+    use crate::signer::{PrehashVerifier, Verifier};
+
+    // An in-memory token: (class, label, key) objects, handles are indices
+    struct MockToken {
+        objects: Vec<(u64, &'static str, SecretKey)>,
+        mechanisms: Vec<u64>,
+        bare_ec_point: bool,
+    }
+
+    impl MockToken {
+        fn new(keys: &[(&'static str, SecretKey)], mechanisms: &[u64]) -> Self {
+            let objects = keys
+                .iter()
+                .flat_map(|(label, key)| [(CKO_PRIVATE_KEY, *label, key.clone()), (CKO_PUBLIC_KEY, *label, key.clone())])
+                .collect();
+            MockToken { objects, mechanisms: mechanisms.to_vec(), bare_ec_point: false }
+        }
+    }
+
+    impl Pkcs11Session for MockToken {
+        fn find_objects(&self, template: &[Attribute]) -> Result<Vec<ObjectHandle>, &'static str> {
+            let matches = |(class, label, _): &(u64, &str, SecretKey)| {
+                template.iter().all(|attribute| match attribute {
+                    Attribute::Class(c) => c == class,
+                    Attribute::KeyType(k) => *k == CKK_EC,
+                    Attribute::Label(l) => l == label.as_bytes(),
+                })
+            };
+            Ok((0..self.objects.len() as u64).filter(|&i| matches(&self.objects[i as usize])).collect())
+        }
+
+        fn get_attribute(&self, object: ObjectHandle, attribute: u64) -> Result<Vec<u8>, &'static str> {
+            let (_, _, key) = self.objects.get(object as usize).ok_or("CKR_OBJECT_HANDLE_INVALID")?;
+            let point = encoding::encode_point(&key.curve().curve, &key.public_key(), false);
+            match attribute {
+                CKA_EC_PARAMS => Ok(key.curve().to_der_parameters()),
+                CKA_EC_POINT if self.bare_ec_point => Ok(point),
+                CKA_EC_POINT => Ok(der::octet_string(&point)),
+                _ => Err("CKR_ATTRIBUTE_TYPE_INVALID"),
+            }
+        }
+
+        fn mechanisms(&self) -> Result<Vec<u64>, &'static str> {
+            Ok(self.mechanisms.clone())
+        }
+
+        fn sign(&self, mechanism: u64, key: ObjectHandle, data: &[u8]) -> Result<Vec<u8>, &'static str> {
+            let (class, _, key) = self.objects.get(key as usize).ok_or("CKR_KEY_HANDLE_INVALID")?;
+            if *class != CKO_PRIVATE_KEY || !self.mechanisms.contains(&mechanism) {
+                return Err("CKR_MECHANISM_INVALID");
+            }
+            let ecdsa = key.curve().ecdsa();
+            let signature = match mechanism {
+                CKM_ECDSA_SHA256 => ecdsa.sign(data, key.scalar())?,
+                _ => ecdsa.sign_digest(data, key.scalar())?,
+            };
+            Ok(signature.to_fixed_bytes(key.curve().scalar_size()))
+        }
+    }
+
+    #[test]
+    fn test_sign_with_token() {
+        let key = SecretKey::random(&curves::p256());
+        let other = SecretKey::random(&curves::p384());
+        for mechanisms in [vec![CKM_ECDSA_SHA256, CKM_ECDSA], vec![CKM_ECDSA]] {
+            let token = MockToken::new(&[("signing", key.clone()), ("other", other.clone())], &mechanisms);
+            let signer = Pkcs11Signer::find_by_label(token, "signing").unwrap();
+            assert_eq!(signer.mechanism(), mechanisms[0]);
+            assert_eq!(signer.verifying_key(), key.verifying_key());
+
+            let signature: ECDSASignature = signer.sign(b"firmware image");
+            assert!(key.verifying_key().verify(b"firmware image", &signature).is_ok());
+            let prehash = Sha256::digest(b"firmware image");
+            let signature = signer.sign_prehash(&prehash).unwrap();
+            assert!(key.verifying_key().verify_prehash(&prehash, &signature).is_ok());
+        }
+
+        // P-384 key, bare CKA_EC_POINT
+        let mut token = MockToken::new(&[("other", other.clone())], &[CKM_ECDSA_SHA256]);
+        token.bare_ec_point = true;
+        let signer = Pkcs11Signer::find_by_label(token, "other").unwrap();
+        assert_eq!(signer.curve().name, "P-384");
+        let signature: ECDSASignature = signer.sign(b"firmware image");
+        assert!(other.verifying_key().verify(b"firmware image", &signature).is_ok());
+        assert!(signer.sign_prehash(&Sha256::digest(b"firmware image")).is_err());
+    }
+
+    #[test]
+    fn test_key_discovery_errors() {
+        let key = SecretKey::random(&curves::p256());
+        let token = MockToken::new(&[("a", key.clone()), ("a", key.clone())], &[CKM_ECDSA]);
+        assert!(Pkcs11Signer::find_by_label(token, "a").is_err());
+        let token = MockToken::new(&[("a", key.clone())], &[CKM_ECDSA]);
+        assert!(Pkcs11Signer::find_by_label(token, "b").is_err());
+        let token = MockToken::new(&[("a", key)], &[]);
+        assert!(Pkcs11Signer::find_by_label(token, "a").is_err());
+    }
+}
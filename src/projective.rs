@@ -0,0 +1,271 @@
+use crate::{ct, EllipticCurve, Point};
+use subtle::Choice;
+use num_bigint::{BigUint, RandBigInt};
+
+// Projective coordinates (X : Y : Z) for y² = x³ + ax + b, standing for the affine point
+// (X / Z, Y / Z), with (0 : 1 : 0) as the identity
+//
+// The affine add() branches on P = Q, P = -Q and the identity, and which branch ran shows up
+// in the timing. The Renes–Costello–Batina formulas (ePrint 2015/1060, Algorithm 1) compute
+// P + Q with one fixed sequence of 12 multiplications, 3 multiplications by a, 2 by 3b and
+// 23 additions for every pair of inputs, doubling and the identity included.
+// Complete on any curve of odd order; with a point of order 2 they can fail when P - Q has
+// order 2, which never happens for two points in an odd prime-order subgroup
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectivePoint {
+    pub x: BigUint,
+    pub y: BigUint,
+    pub z: BigUint,
+}
+
+impl ProjectivePoint {
+    pub fn identity() -> Self {
+        ProjectivePoint {
+            x: BigUint::from(0u32),
+            y: BigUint::from(1u32),
+            z: BigUint::from(0u32),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z == BigUint::from(0u32)
+    }
+
+    // Constant-time swap of all three coordinates, each padded to len limbs
+    pub fn conditional_swap(a: &mut ProjectivePoint, b: &mut ProjectivePoint, choice: Choice, len: usize) {
+        ct::swap_padded(&mut a.x, &mut b.x, choice, len);
+        ct::swap_padded(&mut a.y, &mut b.y, choice, len);
+        ct::swap_padded(&mut a.z, &mut b.z, choice, len);
+    }
+}
+
+impl EllipticCurve {
+    // (x, y) -> (x : y : 1)
+    pub fn to_projective(&self, point: &Point) -> ProjectivePoint {
+        match point {
+            Point::Identity => ProjectivePoint::identity(),
+            Point::Coordinate(x, y) => ProjectivePoint {
+                x: x % self.p(),
+                y: y % self.p(),
+                z: BigUint::from(1u32),
+            },
+        }
+    }
+
+    // (X : Y : Z) -> (X / Z, Y / Z), one inversion
+    pub fn to_affine(&self, point: &ProjectivePoint) -> Point {
+        if point.is_identity() {
+            return Point::Identity;
+        }
+        let field = &self.field;
+        let z_inv = field.element(&point.z).inv();
+        Point::Coordinate(
+            (field.element(&point.x) * &z_inv).into_value(),
+            (field.element(&point.y) * z_inv).into_value(),
+        )
+    }
+
+    // Complete addition: no branches on the inputs, valid for P = Q and for the identity
+    // Written as the paper's register program, updating seven temporaries in place
+    pub fn add_complete(&self, p: &ProjectivePoint, q: &ProjectivePoint) -> ProjectivePoint {
+        let f = &self.field;
+        let b3 = f.mul(&BigUint::from(3u32), self.b());
+        let (x1, y1, z1) = (&p.x, &p.y, &p.z);
+        let (x2, y2, z2) = (&q.x, &q.y, &q.z);
+
+        let mut t0 = f.mul(x1, x2);
+        let mut t1 = f.mul(y1, y2);
+        let mut t2 = f.mul(z1, z2);
+
+        // t3 = (X1 + Y1)(X2 + Y2) - t0 - t1
+        let mut t3 = x1.clone();
+        f.add_assign(&mut t3, y1);
+        let mut t4 = x2.clone();
+        f.add_assign(&mut t4, y2);
+        f.mul_assign(&mut t3, &t4);
+        t4.clone_from(&t0);
+        f.add_assign(&mut t4, &t1);
+        f.sub_assign(&mut t3, &t4);
+
+        // t4 = (X1 + Z1)(X2 + Z2) - t0 - t2
+        t4.clone_from(x1);
+        f.add_assign(&mut t4, z1);
+        let mut t5 = x2.clone();
+        f.add_assign(&mut t5, z2);
+        f.mul_assign(&mut t4, &t5);
+        t5.clone_from(&t0);
+        f.add_assign(&mut t5, &t2);
+        f.sub_assign(&mut t4, &t5);
+
+        // t5 = (Y1 + Z1)(Y2 + Z2) - t1 - t2
+        t5.clone_from(y1);
+        f.add_assign(&mut t5, z1);
+        let mut x3 = y2.clone();
+        f.add_assign(&mut x3, z2);
+        f.mul_assign(&mut t5, &x3);
+        x3.clone_from(&t1);
+        f.add_assign(&mut x3, &t2);
+        f.sub_assign(&mut t5, &x3);
+
+        // Z3 = a t4 + 3b t2, X3 = t1 - Z3, Z3 = t1 + Z3, Y3 = X3 Z3
+        let mut z3 = f.mul(self.a(), &t4);
+        x3 = f.mul(&b3, &t2);
+        f.add_assign(&mut z3, &x3);
+        x3.clone_from(&t1);
+        f.sub_assign(&mut x3, &z3);
+        f.add_assign(&mut z3, &t1);
+        let mut y3 = f.mul(&x3, &z3);
+
+        // t1 = 3 t0 + a t2, t2 = a (t0 - a t2), t4 = 3b t4 + t2
+        t1.clone_from(&t0);
+        f.add_assign(&mut t1, &t0);
+        f.add_assign(&mut t1, &t0);
+        f.mul_assign(&mut t2, self.a());
+        f.add_assign(&mut t1, &t2);
+        let mut t6 = t0.clone();
+        f.sub_assign(&mut t6, &t2);
+        f.mul_assign(&mut t6, self.a());
+        f.mul_assign(&mut t4, &b3);
+        f.add_assign(&mut t4, &t6);
+
+        // Y3 += t1 t4, X3 = X3 t3 - t5 t4, Z3 = Z3 t5 + t3 t1
+        t0 = f.mul(&t1, &t4);
+        f.add_assign(&mut y3, &t0);
+        t0 = f.mul(&t5, &t4);
+        f.mul_assign(&mut x3, &t3);
+        f.sub_assign(&mut x3, &t0);
+        t0 = f.mul(&t3, &t1);
+        f.mul_assign(&mut z3, &t5);
+        f.add_assign(&mut z3, &t0);
+
+        ProjectivePoint { x: x3, y: y3, z: z3 }
+    }
+
+    // (X : Y : Z) -> (λX : λY : λZ) for a random nonzero λ: the same point, different numbers
+    pub fn rerandomize<R: rand::Rng + ?Sized>(&self, point: &ProjectivePoint, rng: &mut R) -> ProjectivePoint {
+        let field = &self.field;
+        let lambda = rng.gen_biguint_range(&BigUint::from(1u32), self.p());
+        ProjectivePoint {
+            x: field.mul(&point.x, &lambda),
+            y: field.mul(&point.y, &lambda),
+            z: field.mul(&point.z, &lambda),
+        }
+    }
+
+    // k * P with a Montgomery ladder over complete additions: every bit costs one addition and
+    // one doubling, over a fixed number of bits (those of p + 1, or of k if it's longer), so the
+    // operation sequence depends on neither the bits of k nor its length.
+    // The starting coordinates are rerandomized, so multiplying the same point twice doesn't
+    // repeat a single intermediate value (which a side-channel observer could match up).
+    // For secret scalars (keys, nonces) on points of odd order
+    pub fn scalar_mult_complete(&self, point: &Point, k: &BigUint) -> Point {
+        let mut rng = crate::entropy::Entropy;
+        let bits = (self.p() + BigUint::from(1u32)).bits().max(k.bits()) + 1;
+        let mut r0 = self.rerandomize(&ProjectivePoint::identity(), &mut rng);
+        let mut r1 = self.rerandomize(&self.to_projective(point), &mut rng);
+
+        // Invariant: r1 = r0 + P
+        // A set bit swaps the roles of r0 and r1 around the step, with a masked swap
+        // rather than a branch on the key bit
+        let limbs = self.p().to_u64_digits().len();
+        for i in (0..bits).rev() {
+            let bit = Choice::from(k.bit(i) as u8);
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit, limbs);
+            r1 = self.add_complete(&r0, &r1);
+            r0 = self.add_complete(&r0, &r0);
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit, limbs);
+        }
+
+        self.to_affine(&r0)
+    }
+
+    // Scalar blinding (Coron's first countermeasure): k * P computed as (k + m * n) * P for a
+    // fresh random 64-bit m, where n is the order of P. Same result since n * P = O, but the
+    // ladder sees different bits on every call, so averaging many power traces of the same
+    // secret (DPA) no longer lines up
+    pub fn scalar_mult_blinded<R: rand::Rng + ?Sized>(&self, point: &Point, k: &BigUint, order: &BigUint, rng: &mut R) -> Point {
+        let mask = BigUint::from(rng.next_u64());
+        self.scalar_mult_complete(point, &(k + mask * order))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // y² = x³ + 2x + 2 (mod 17): 19 points, generated by (5, 1)
+    fn setup() -> (EllipticCurve, Point) {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        (curve, Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32)))
+    }
+
+    #[test]
+    fn test_add_complete_matches_affine() {
+        // Every pair in the group, so doubling, P + (-P) and the identity are all covered
+        let (curve, g) = setup();
+        let points: Vec<Point> = (0u32..19).map(|i| curve.scalar_mult(&g, &BigUint::from(i))).collect();
+        for p in &points {
+            for q in &points {
+                let sum = curve.add_complete(&curve.to_projective(p), &curve.to_projective(q));
+                assert_eq!(curve.to_affine(&sum), curve.add(p, q));
+            }
+        }
+    }
+
+    #[test]
+    fn test_scalar_mult_complete() {
+        let (curve, g) = setup();
+        for k in 0u32..40 {
+            let k = BigUint::from(k);
+            assert_eq!(curve.scalar_mult_complete(&g, &k), curve.scalar_mult(&g, &k));
+        }
+
+        // secp256k1-sized inputs
+        let secp = crate::curves::secp256k1();
+        let k = BigUint::parse_bytes(b"c0ffee1234567890deadbeef", 16).unwrap();
+        assert_eq!(
+            secp.curve.scalar_mult_complete(&secp.generator, &k),
+            secp.curve.scalar_mult(&secp.generator, &k)
+        );
+    }
+
+    #[test]
+    fn test_scalar_mult_blinded() {
+        let (curve, g) = setup();
+        let order = BigUint::from(19u32);
+        let mut rng = crate::entropy::Entropy;
+        for k in 0u32..19 {
+            let k = BigUint::from(k);
+            assert_eq!(curve.scalar_mult_blinded(&g, &k, &order, &mut rng), curve.scalar_mult(&g, &k));
+        }
+    }
+
+    #[test]
+    fn test_rerandomize() {
+        let (curve, g) = setup();
+        let mut rng = crate::entropy::Entropy;
+        let projective = curve.to_projective(&g);
+        let mut seen = Vec::new();
+        for _ in 0..20 {
+            let randomized = curve.rerandomize(&projective, &mut rng);
+            assert_eq!(curve.to_affine(&randomized), g);
+            seen.push(randomized.z);
+        }
+        // 16 possible λ: twenty draws all alike would mean no randomization at all
+        assert!(seen.iter().any(|z| *z != seen[0]));
+    }
+
+    #[test]
+    fn test_projective_representatives() {
+        // (λX : λY : λZ) is the same point for any λ ≠ 0: here λ = 3
+        let (curve, g) = setup();
+        let scaled = ProjectivePoint {
+            x: BigUint::from(15u32),
+            y: BigUint::from(3u32),
+            z: BigUint::from(3u32),
+        };
+        assert_eq!(curve.to_affine(&scaled), g);
+        assert_eq!(curve.to_affine(&ProjectivePoint::identity()), Point::Identity);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::curves::{self, NamedCurve};
+use crate::der::{self, DerReader};
+use crate::encoding;
+use crate::Point;
+use sha2::{Digest, Sha256};
+
+// A validated public key together with its curve, with SubjectPublicKeyInfo encoding (RFC 5480:
+// SEQUENCE { AlgorithmIdentifier { id-ecPublicKey, ECParameters }, BIT STRING point }) and
+// fingerprints for pinning, logs and trust databases:
+//   fingerprint()        SHA-256 of the SPKI DER with the uncompressed point, the same bytes as
+//                        `openssl pkey -pubout -outform DER`; its base64 is an HPKP/RFC 7469 pin
+//   point_fingerprint()  SHA-256 of the compressed SEC1 point, for protocols without SPKI
+// Both hash one canonical encoding, so a key gives the same fingerprint however it was read
+
+pub(crate) const EC_PUBLIC_KEY: [u64; 6] = [1, 2, 840, 10045, 2, 1];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicKey {
+    pub(crate) curve: NamedCurve,
+    pub(crate) point: Point,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint(pub [u8; 32]);
+
+impl Fingerprint {
+    pub fn to_hex(&self) -> String {
+        encoding::to_hex(&self.0)
+    }
+
+    pub fn to_base64(&self) -> String {
+        encoding::to_base64(&self.0)
+    }
+}
+
+// Colon-separated hex, as certificate tools print fingerprints
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let bytes: Vec<String> = self.0.iter().map(|b| format!("{:02X}", b)).collect();
+        write!(f, "{}", bytes.join(":"))
+    }
+}
+
+impl PublicKey {
+    pub fn new(curve: &NamedCurve, point: Point) -> Result<Self, &'static str> {
+        curve.ecdh().validate_public_key(&point)?;
+        Ok(PublicKey { curve: curve.clone(), point })
+    }
+
+    pub fn curve(&self) -> &NamedCurve {
+        &self.curve
+    }
+
+    pub fn point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn to_spki_der(&self) -> Vec<u8> {
+        der::sequence(&[
+            der::sequence(&[der::oid(&EC_PUBLIC_KEY), self.curve.to_der_parameters()]),
+            der::bit_string(&encoding::encode_point(&self.curve.curve, &self.point, false)),
+        ])
+    }
+
+    // Compressed points are accepted too
+    pub fn from_spki_der(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut outer = DerReader::new(bytes);
+        let mut spki = outer.read_sequence()?;
+        outer.finish()?;
+        let mut algorithm = spki.read_sequence()?;
+        if algorithm.read_oid()? != EC_PUBLIC_KEY {
+            return Err("not an EC public key");
+        }
+        let tag = algorithm.peek_tag().ok_or("missing EC parameters")?;
+        let curve = curves::from_der_parameters(&der::write(tag, algorithm.read(tag)?))?;
+        algorithm.finish()?;
+        let point = encoding::decode_point(&curve.curve, spki.read_bit_string()?)?;
+        spki.finish()?;
+        Self::new(&curve, point)
+    }
+
+    pub fn to_spki_pem(&self) -> String {
+        crate::pkcs8::pem("PUBLIC KEY", &self.to_spki_der())
+    }
+
+    pub fn from_spki_pem(text: &str) -> Result<Self, &'static str> {
+        Self::from_spki_der(&crate::ssh::dearmor("PUBLIC KEY", text)?)
+    }
+
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint(Sha256::digest(self.to_spki_der()).into())
+    }
+
+    pub fn point_fingerprint(&self) -> Fingerprint {
+        Fingerprint(Sha256::digest(encoding::encode_point(&self.curve.curve, &self.point, true)).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret_key::SecretKey;
+
+    #[test]
+    fn test_openssl_fingerprint() {
+        // openssl pkey -in p256.pem -pubout, and the SHA-256 of its DER form
+        let pem = "-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEQf5noE23apnpJueekLcgJ2oTVIQ3
+pJS/ZrvouWS+OvnrnceR46iYL4eefirrQ750DCb8KL8ou6fqqZeZpp+mmg==
+-----END PUBLIC KEY-----
+";
+        let public_key = PublicKey::from_spki_pem(pem).unwrap();
+        assert_eq!(public_key.to_spki_pem(), pem);
+        let fingerprint = public_key.fingerprint();
+        assert_eq!(fingerprint.to_hex(), "f8a89347d7a92a5dbdacccff203d42ccd98995429732dd936fb50b1d766386a7");
+        assert_eq!(fingerprint.to_base64(), "+KiTR9epKl29rMz/ID1CzNmJlUKXMt2Tb7ULHXZjhqc=");
+        assert!(fingerprint.to_string().starts_with("F8:A8:93:47:"));
+        assert_eq!(public_key.point_fingerprint().to_hex(), "0e841007a393879b12f84c147cd42efe31dc4369ea91d9f685661325178111e3");
+
+        let key = SecretKey::from_pkcs8_pem(include_str!("../testvectors/pkcs8/p256.pem")).unwrap();
+        assert_eq!(PublicKey::new(key.curve(), key.public_key()).unwrap(), public_key);
+    }
+
+    #[test]
+    fn test_fingerprint_is_canonical() {
+        // A compressed SPKI reads as the same key and fingerprint
+        let key = SecretKey::random(&curves::secp256k1());
+        let public_key = PublicKey::new(key.curve(), key.public_key()).unwrap();
+        let compressed = der::sequence(&[
+            der::sequence(&[der::oid(&EC_PUBLIC_KEY), key.curve().to_der_parameters()]),
+            der::bit_string(&encoding::encode_point(&key.curve().curve, &key.public_key(), true)),
+        ]);
+        let parsed = PublicKey::from_spki_der(&compressed).unwrap();
+        assert_eq!(parsed.fingerprint(), public_key.fingerprint());
+        assert_ne!(public_key.fingerprint(), public_key.point_fingerprint());
+
+        let other = SecretKey::random(&curves::secp256k1());
+        assert_ne!(PublicKey::new(other.curve(), other.public_key()).unwrap().fingerprint(), public_key.fingerprint());
+        assert!(PublicKey::from_spki_der(&compressed[..compressed.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{FiniteField, Point};
+use crate::ecdsa::{hash_to_scalar, ECDSASignature, ECDSA};
+use crate::encoding::to_fixed_bytes;
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use num_bigint::BigUint;
+
+// Public key recovery (SEC 1 4.1.6) and Ethereum-style recoverable signatures
+//
+// From (r, s) and the message alone there are up to four candidate keys: R is one of the
+// points with x = r or r + n, with either y. The recovery id pins it down:
+//   bit 0 = parity of R.y, bit 1 = whether R.x overflowed n
+// and then Q = r⁻¹ (s R - z G). Ethereum's ecrecover takes the id as v = 27 + id,
+// or v = 35 + 2 * chain_id + id under EIP-155 replay protection, and only accepts low s
+// (s ≤ n/2, EIP-2); signing here always normalizes s, flipping R's parity to match
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoverableSignature {
+    pub r: BigUint,
+    pub s: BigUint,
+    pub recovery_id: u8,
+}
+
+impl RecoverableSignature {
+    pub fn signature(&self) -> ECDSASignature {
+        ECDSASignature { r: self.r.clone(), s: self.s.clone() }
+    }
+
+    // 27 + id, or 35 + 2 * chain_id + id with EIP-155
+    pub fn v(&self, chain_id: Option<u64>) -> u64 {
+        match chain_id {
+            Some(chain_id) => 35 + 2 * chain_id + self.recovery_id as u64,
+            None => 27 + self.recovery_id as u64,
+        }
+    }
+
+    pub fn from_v(r: BigUint, s: BigUint, v: u64, chain_id: Option<u64>) -> Result<Self, &'static str> {
+        let base = match chain_id {
+            Some(chain_id) => 35 + 2 * chain_id,
+            None => 27,
+        };
+        let recovery_id = v.checked_sub(base).filter(|id| *id < 2).ok_or("v does not match the chain id")?;
+        Ok(RecoverableSignature { r, s, recovery_id: recovery_id as u8 })
+    }
+
+    // 65 bytes r || s || v with v = 27 + id, as ecrecover and eth_sign use
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&to_fixed_bytes(&self.r, 32));
+        bytes[32..64].copy_from_slice(&to_fixed_bytes(&self.s, 32));
+        bytes[64] = 27 + self.recovery_id;
+        bytes
+    }
+
+    // Accepts v as 0/1 or 27/28
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != 65 {
+            return Err("recoverable signatures are 65 bytes");
+        }
+        let recovery_id = match bytes[64] {
+            0 | 1 => bytes[64],
+            27 | 28 => bytes[64] - 27,
+            _ => return Err("invalid recovery byte"),
+        };
+        Ok(RecoverableSignature {
+            r: BigUint::from_bytes_be(&bytes[..32]),
+            s: BigUint::from_bytes_be(&bytes[32..64]),
+            recovery_id,
+        })
+    }
+}
+
+// z from an externally computed digest: its leftmost bits up to the order's length, mod n
+pub fn digest_to_scalar(digest: &[u8], order: &BigUint) -> BigUint {
+    let z = BigUint::from_bytes_be(digest);
+    let excess = (digest.len() as u64 * 8).saturating_sub(order.bits());
+    (z >> excess) % order
+}
+
+impl ECDSA {
+    // Sign with the usual SHA-256 message hash
+    pub fn sign_recoverable(&self, message: &[u8], private_key: &BigUint) -> Result<RecoverableSignature, &'static str> {
+        self.sign_scalar_recoverable(&hash_to_scalar(message, &self.order), private_key)
+    }
+
+    // Sign a 32-byte digest computed by the caller, e.g. Keccak-256 for Ethereum
+    pub fn sign_digest_recoverable(&self, digest: &[u8], private_key: &BigUint) -> Result<RecoverableSignature, &'static str> {
+        self.sign_scalar_recoverable(&digest_to_scalar(digest, &self.order), private_key)
+    }
+
+    pub fn recover_public_key(&self, message: &[u8], signature: &RecoverableSignature) -> Result<Point, &'static str> {
+        self.recover_from_scalar(&hash_to_scalar(message, &self.order), signature)
+    }
+
+    pub fn recover_from_digest(&self, digest: &[u8], signature: &RecoverableSignature) -> Result<Point, &'static str> {
+        self.recover_from_scalar(&digest_to_scalar(digest, &self.order), signature)
+    }
+
+    fn sign_scalar_recoverable(&self, z: &BigUint, private_key: &BigUint) -> Result<RecoverableSignature, &'static str> {
+        if *private_key == BigUint::from(0u32) || *private_key >= self.order {
+            return Err("private key out of range");
+        }
+        let field = FiniteField { p: self.order.clone() };
+        let mut rng = Entropy;
+        loop {
+            let k = Scalar::random(&self.order, &mut rng).into_value();
+            let Point::Coordinate(x, y) = self.curve.scalar_mult(&self.generator, &k) else {
+                continue;
+            };
+            let r = &x % &self.order;
+            if r == BigUint::from(0u32) {
+                continue;
+            }
+            let s = field.div(&field.add(z, &field.mul(&r, private_key)), &k);
+            if s == BigUint::from(0u32) {
+                continue;
+            }
+
+            let mut recovery_id = y.bit(0) as u8 | ((x >= self.order) as u8) << 1;
+            // Low s: (r, n - s) is the signature for -R, so R's parity flips
+            let s = if s > &self.order >> 1 {
+                recovery_id ^= 1;
+                &self.order - s
+            } else {
+                s
+            };
+            return Ok(RecoverableSignature { r, s, recovery_id });
+        }
+    }
+
+    // Q = r⁻¹ (s R - z G)
+    fn recover_from_scalar(&self, z: &BigUint, signature: &RecoverableSignature) -> Result<Point, &'static str> {
+        let zero = BigUint::from(0u32);
+        if signature.r == zero || signature.r >= self.order || signature.s == zero || signature.s >= self.order {
+            return Err("signature values out of range");
+        }
+        if signature.recovery_id > 3 {
+            return Err("recovery id must be 0 to 3");
+        }
+
+        let x = if signature.recovery_id & 2 == 2 { &signature.r + &self.order } else { signature.r.clone() };
+        if x >= *self.curve.p() {
+            return Err("no curve point for this recovery id");
+        }
+        let base = self.curve.field();
+        let rhs = base.add(&base.add(&base.mul(&base.mul(&x, &x), &x), &base.mul(self.curve.a(), &x)), self.curve.b());
+        let y = base.sqrt(&rhs).ok_or("no curve point for this recovery id")?;
+        let y = if y.bit(0) != (signature.recovery_id & 1 == 1) { base.sub(&zero, &y) } else { y };
+        let r_point = Point::Coordinate(x, y);
+        if self.curve.scalar_mult(&r_point, &self.order) != Point::Identity {
+            return Err("R is not in the prime-order subgroup");
+        }
+
+        let field = FiniteField { p: self.order.clone() };
+        let r_inverse = field.div(&BigUint::from(1u32), &signature.r);
+        let s_r = self.curve.scalar_mult(&r_point, &field.mul(&signature.s, &r_inverse));
+        let z_g = self.curve.scalar_mult(&self.generator, &field.mul(z, &r_inverse));
+        let public_key = self.curve.add(&s_r, &self.curve.neg(&z_g));
+        if public_key == Point::Identity {
+            return Err("recovered the point at infinity");
+        }
+        Ok(public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addresses::ethereum;
+    use crate::curves;
+
+    #[test]
+    fn test_recover_public_key() {
+        let ecdsa = curves::secp256k1().ecdsa();
+        let keypair = ecdsa.generate_keypair();
+        let message = b"recover me";
+
+        let signature = ecdsa.sign_recoverable(message, &keypair.private_key).unwrap();
+        assert!(signature.s <= &ecdsa.order >> 1);
+        assert!(ecdsa.verify(message, &signature.signature(), &keypair.public_key));
+        assert_eq!(ecdsa.recover_public_key(message, &signature).unwrap(), keypair.public_key);
+
+        // The other parity recovers a different key
+        let flipped = RecoverableSignature { recovery_id: signature.recovery_id ^ 1, ..signature.clone() };
+        assert_ne!(ecdsa.recover_public_key(message, &flipped).unwrap(), keypair.public_key);
+    }
+
+    #[test]
+    fn test_ethereum_ecrecover() {
+        // Key 1 signs a Keccak-256 digest; ecrecover gives back its address
+        let ecdsa = curves::secp256k1().ecdsa();
+        let private_key = BigUint::from(1u32);
+        let digest = ethereum::keccak256(b"transfer");
+        let signature = ecdsa.sign_digest_recoverable(&digest, &private_key).unwrap();
+
+        let bytes = signature.to_bytes();
+        assert!(bytes[64] == 27 || bytes[64] == 28);
+        let parsed = RecoverableSignature::from_bytes(&bytes).unwrap();
+        let recovered = ecdsa.recover_from_digest(&digest, &parsed).unwrap();
+        assert_eq!(ethereum::from_public_key(&recovered).unwrap(), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+    }
+
+    #[test]
+    fn test_eip155_v() {
+        let signature = RecoverableSignature { r: BigUint::from(1u32), s: BigUint::from(1u32), recovery_id: 1 };
+        assert_eq!(signature.v(None), 28);
+        assert_eq!(signature.v(Some(1)), 38);
+        assert_eq!(RecoverableSignature::from_v(BigUint::from(1u32), BigUint::from(1u32), 38, Some(1)).unwrap(), signature);
+        assert!(RecoverableSignature::from_v(BigUint::from(1u32), BigUint::from(1u32), 38, Some(5)).is_err());
+        assert!(RecoverableSignature::from_bytes(&[0u8; 64]).is_err());
+    }
+}
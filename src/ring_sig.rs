@@ -0,0 +1,261 @@
+use crate::{FiniteField, Point};
+use crate::curves::DomainParams;
+use crate::hash_to_curve::try_and_increment;
+use crate::zkp::Transcript;
+use crate::entropy::Entropy;
+use num_bigint::{BigUint, RandBigInt};
+
+// Ring signatures: a signature by "one of the keys in this ring" without revealing which
+//
+// SAG (spontaneous anonymous group, Abe–Ohkubo–Suzuki style), ring P_0..P_(n-1), signer π:
+//   α random, c_(π+1) = H(R, m, αG)
+//   for every other i: r_i random, c_(i+1) = H(R, m, r_i G + c_i P_i)
+//   close the ring: r_π = α - c_π x_π, so that r_π G + c_π P_π = αG
+//   σ = (c_0, r_0..r_(n-1)); the verifier recomputes the c_i around the ring back to c_0
+//
+// LSAG (linkable, Liu–Wei–Wong / Monero) adds a key image Ĩ = x_π H_p(P_π):
+//   c_(i+1) = H(R, Ĩ, m, r_i G + c_i P_i, r_i H_p(P_i) + c_i Ĩ)
+// Ĩ depends only on the key, so two signatures by the same signer share it (double-spend
+// detection) while still hiding which ring member made them
+
+#[derive(Clone, Debug)]
+pub struct RingKeyPair {
+    pub private_key: BigUint,
+    pub public_key: Point,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SAGSignature {
+    pub challenge: BigUint,
+    pub responses: Vec<BigUint>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LSAGSignature {
+    pub challenge: BigUint,
+    pub responses: Vec<BigUint>,
+    pub key_image: Point,
+}
+
+pub struct RingSig {
+    pub params: DomainParams,
+}
+
+const SAG_DOMAIN: &[u8] = b"ring-sig-SAG";
+const LSAG_DOMAIN: &[u8] = b"ring-sig-LSAG";
+const HASH_TO_POINT_DST: &[u8] = b"ring-sig-key-image";
+
+impl RingSig {
+    pub fn new(params: DomainParams) -> Self {
+        RingSig { params }
+    }
+
+    // Generate keypair
+    pub fn generate_keypair(&self) -> RingKeyPair {
+        let private_key = self.params.generate_private_key();
+        let public_key = self.params.generate_public_key(&private_key);
+        RingKeyPair {
+            private_key,
+            public_key,
+        }
+    }
+
+    // Ĩ = x * H_p(P)
+    pub fn key_image(&self, private_key: &BigUint) -> Point {
+        let public_key = self.params.generate_public_key(private_key);
+        self.params.curve.scalar_mult(&self.hash_to_point(&public_key), private_key)
+    }
+
+    pub fn sign_sag(&self, message: &[u8], ring: &[Point], private_key: &BigUint) -> Result<SAGSignature, &'static str> {
+        let (challenge, responses) = self.sign_ring(message, ring, private_key, None)?;
+        Ok(SAGSignature { challenge, responses })
+    }
+
+    pub fn verify_sag(&self, message: &[u8], ring: &[Point], signature: &SAGSignature) -> bool {
+        self.verify_ring(message, ring, &signature.challenge, &signature.responses, None)
+    }
+
+    pub fn sign_lsag(&self, message: &[u8], ring: &[Point], private_key: &BigUint) -> Result<LSAGSignature, &'static str> {
+        let key_image = self.key_image(private_key);
+        let (challenge, responses) = self.sign_ring(message, ring, private_key, Some(&key_image))?;
+        Ok(LSAGSignature { challenge, responses, key_image })
+    }
+
+    pub fn verify_lsag(&self, message: &[u8], ring: &[Point], signature: &LSAGSignature) -> bool {
+        // Ĩ must be a non-identity point of the prime-order subgroup: small-order
+        // components would let one key produce several distinct images
+        let image = &signature.key_image;
+        if *image == Point::Identity || !self.params.curve.is_torsion_point(image, &self.params.order) {
+            return false;
+        }
+        self.verify_ring(message, ring, &signature.challenge, &signature.responses, Some(image))
+    }
+
+    // Two valid LSAG signatures were made with the same private key
+    pub fn is_linked(&self, first: &LSAGSignature, second: &LSAGSignature) -> bool {
+        first.key_image == second.key_image
+    }
+
+    fn sign_ring(&self, message: &[u8], ring: &[Point], private_key: &BigUint, key_image: Option<&Point>) -> Result<(BigUint, Vec<BigUint>), &'static str> {
+        let n = ring.len();
+        let public_key = self.params.generate_public_key(private_key);
+        let signer = ring.iter().position(|p| *p == public_key).ok_or("signer's public key is not in the ring")?;
+
+        let field = FiniteField { p: self.params.order.clone() };
+        let mut rng = Entropy;
+        let base = self.base_transcript(message, ring, key_image);
+        let hashed: Vec<Point> = match key_image {
+            Some(_) => ring.iter().map(|p| self.hash_to_point(p)).collect(),
+            None => Vec::new(),
+        };
+
+        let mut challenges = vec![BigUint::from(0u32); n];
+        let mut responses = vec![BigUint::from(0u32); n];
+
+        // c_(π+1) from the signer's commitment αG (and αH_p(P_π))
+        let alpha = self.params.generate_private_key();
+        let l = self.generator_mult(&alpha);
+        let r = key_image.map(|_| self.params.curve.scalar_mult(&hashed[signer], &alpha));
+        challenges[(signer + 1) % n] = self.ring_challenge(&base, &l, r.as_ref());
+
+        // Walk the rest of the ring with simulated responses
+        let mut i = (signer + 1) % n;
+        while i != signer {
+            responses[i] = rng.gen_biguint_below(&self.params.order);
+            let (l, r) = self.ring_commitments(ring, &hashed, i, &challenges[i], &responses[i], key_image);
+            challenges[(i + 1) % n] = self.ring_challenge(&base, &l, r.as_ref());
+            i = (i + 1) % n;
+        }
+
+        // Close the ring: r_π = α - c_π x_π
+        responses[signer] = field.sub(&alpha, &field.mul(&challenges[signer], private_key));
+        Ok((challenges[0].clone(), responses))
+    }
+
+    fn verify_ring(&self, message: &[u8], ring: &[Point], challenge: &BigUint, responses: &[BigUint], key_image: Option<&Point>) -> bool {
+        if ring.is_empty() || responses.len() != ring.len() || *challenge >= self.params.order {
+            return false;
+        }
+        if responses.iter().any(|r| *r >= self.params.order) {
+            return false;
+        }
+        if ring.iter().any(|p| *p == Point::Identity || !self.params.curve.is_on_curve(p)) {
+            return false;
+        }
+
+        let base = self.base_transcript(message, ring, key_image);
+        let hashed: Vec<Point> = match key_image {
+            Some(_) => ring.iter().map(|p| self.hash_to_point(p)).collect(),
+            None => Vec::new(),
+        };
+
+        let mut c = challenge.clone();
+        for (i, response) in responses.iter().enumerate() {
+            let (l, r) = self.ring_commitments(ring, &hashed, i, &c, response, key_image);
+            c = self.ring_challenge(&base, &l, r.as_ref());
+        }
+        c == *challenge
+    }
+
+    // L_i = r_i G + c_i P_i and, for LSAG, R_i = r_i H_p(P_i) + c_i Ĩ
+    fn ring_commitments(&self, ring: &[Point], hashed: &[Point], i: usize, c: &BigUint, r: &BigUint, key_image: Option<&Point>) -> (Point, Option<Point>) {
+        let l = self.params.curve.add(&self.generator_mult(r), &self.params.curve.scalar_mult(&ring[i], c));
+        let r = key_image.map(|image| {
+            self.params.curve.add(&self.params.curve.scalar_mult(&hashed[i], r), &self.params.curve.scalar_mult(image, c))
+        });
+        (l, r)
+    }
+
+    // Everything but the per-member commitments: the ring, key image and message
+    fn base_transcript(&self, message: &[u8], ring: &[Point], key_image: Option<&Point>) -> Transcript {
+        let mut transcript = Transcript::new(if key_image.is_some() { LSAG_DOMAIN } else { SAG_DOMAIN });
+        transcript.append_message(b"ring-size", &(ring.len() as u64).to_be_bytes());
+        for member in ring {
+            transcript.append_point(b"P", &self.params.curve, member);
+        }
+        if let Some(image) = key_image {
+            transcript.append_point(b"I", &self.params.curve, image);
+        }
+        transcript.append_message(b"message", message);
+        transcript
+    }
+
+    fn ring_challenge(&self, base: &Transcript, l: &Point, r: Option<&Point>) -> BigUint {
+        let mut transcript = base.clone();
+        transcript.append_point(b"L", &self.params.curve, l);
+        if let Some(r) = r {
+            transcript.append_point(b"R", &self.params.curve, r);
+        }
+        transcript.challenge_scalar(b"c", &self.params.order)
+    }
+
+    fn generator_mult(&self, k: &BigUint) -> Point {
+        self.params.curve.scalar_mult(&self.params.generator, k)
+    }
+
+    // H_p: a point whose discrete log nobody knows
+    fn hash_to_point(&self, public_key: &Point) -> Point {
+        let encoded = crate::encoding::encode_point(&self.params.curve, public_key, true);
+        try_and_increment(&self.params.curve, &self.params.cofactor, &encoded, HASH_TO_POINT_DST)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    fn setup() -> (RingSig, Vec<RingKeyPair>) {
+        let p256 = curves::p256();
+        let ring_sig = RingSig::new(p256.params());
+        let keys = (0..3).map(|_| ring_sig.generate_keypair()).collect();
+        (ring_sig, keys)
+    }
+
+    fn ring_of(keys: &[RingKeyPair]) -> Vec<Point> {
+        keys.iter().map(|k| k.public_key.clone()).collect()
+    }
+
+    #[test]
+    fn test_sag_sign_verify() {
+        let (ring_sig, keys) = setup();
+        let ring = ring_of(&keys);
+
+        for signer in &keys {
+            let signature = ring_sig.sign_sag(b"whistleblower report", &ring, &signer.private_key).unwrap();
+            assert!(ring_sig.verify_sag(b"whistleblower report", &ring, &signature));
+            assert!(!ring_sig.verify_sag(b"edited report", &ring, &signature));
+        }
+
+        // A different ring (even a permutation) doesn't verify
+        let signature = ring_sig.sign_sag(b"msg", &ring, &keys[0].private_key).unwrap();
+        let swapped = vec![ring[1].clone(), ring[0].clone(), ring[2].clone()];
+        assert!(!ring_sig.verify_sag(b"msg", &swapped, &signature));
+
+        // Signing requires a key in the ring
+        let outsider = ring_sig.generate_keypair();
+        assert!(ring_sig.sign_sag(b"msg", &ring, &outsider.private_key).is_err());
+    }
+
+    #[test]
+    fn test_lsag_linkability() {
+        let (ring_sig, keys) = setup();
+        let ring = ring_of(&keys);
+
+        let first = ring_sig.sign_lsag(b"spend output 1", &ring, &keys[1].private_key).unwrap();
+        let second = ring_sig.sign_lsag(b"spend output 2", &ring, &keys[1].private_key).unwrap();
+        let other = ring_sig.sign_lsag(b"spend output 3", &ring, &keys[2].private_key).unwrap();
+
+        assert!(ring_sig.verify_lsag(b"spend output 1", &ring, &first));
+        assert!(ring_sig.verify_lsag(b"spend output 2", &ring, &second));
+        assert!(ring_sig.verify_lsag(b"spend output 3", &ring, &other));
+
+        assert!(ring_sig.is_linked(&first, &second));
+        assert!(!ring_sig.is_linked(&first, &other));
+
+        // The key image can't be swapped out to dodge linking
+        let mut forged = second.clone();
+        forged.key_image = other.key_image.clone();
+        assert!(!ring_sig.verify_lsag(b"spend output 2", &ring, &forged));
+    }
+}
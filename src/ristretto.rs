@@ -0,0 +1,287 @@
+use crate::FiniteField;
+use crate::ed25519::Ed25519;
+use crate::edwards::{EdwardsCurve, EdwardsPoint};
+use num_bigint::BigUint;
+use sha2::{Digest, Sha512};
+use std::sync::OnceLock;
+
+// Ristretto255 (RFC 9496): a prime-order group of size L built from edwards25519
+//
+// edwards25519 has 8L points, so protocols that assume a prime-order group can be broken by
+// small-order components (malleable signatures, double-spends in Monero). Ristretto treats the
+// four points of each coset P + E[4] as one element and gives it a single canonical encoding. Decoding accepts exactly those
+// encodings, so every decoded element is usable without cofactor multiplication or checks.
+//   equality   (x1, y1) ~ (x2, y2) iff x1 y2 = y1 x2 or y1 y2 = x1 x2
+//   encode     picks a canonical representative of the coset and outputs a non-negative s
+//   from_uniform_bytes  two Elligator-style maps added together, for hashing to the group
+// "Negative" field elements are the odd ones (least significant bit set)
+
+#[derive(Clone, Debug)]
+pub struct RistrettoPoint {
+    point: EdwardsPoint,
+}
+
+// F_p for p = 2^255 - 19, built on first use: points don't carry their curve, and equality
+// shouldn't rebuild the field on every comparison
+fn field() -> &'static FiniteField {
+    static FIELD: OnceLock<FiniteField> = OnceLock::new();
+    FIELD.get_or_init(|| FiniteField { p: (BigUint::from(1u32) << 255) - BigUint::from(19u32) })
+}
+
+impl PartialEq for RistrettoPoint {
+    fn eq(&self, other: &Self) -> bool {
+        let f = field();
+        let (a, b) = (&self.point, &other.point);
+        f.mul(&a.x, &b.y) == f.mul(&a.y, &b.x) || f.mul(&a.y, &b.y) == f.mul(&a.x, &b.x)
+    }
+}
+
+pub struct Ristretto255 {
+    pub curve: EdwardsCurve,
+    pub order: BigUint,
+    base: EdwardsPoint,
+    sqrt_m1: BigUint,
+    sqrt_ad_minus_one: BigUint,
+    invsqrt_a_minus_d: BigUint,
+}
+
+impl Ristretto255 {
+    pub fn new() -> Self {
+        let ed = Ed25519::new();
+        let dec = |s: &str| s.parse::<BigUint>().unwrap();
+        Ristretto255 {
+            curve: ed.curve,
+            order: ed.order,
+            base: ed.base,
+            sqrt_m1: dec("19681161376707505956807079304988542015446066515923890162744021073123829784752"),
+            sqrt_ad_minus_one: dec("25063068953384623474111414158702152701244531502492656460079210482610430750235"),
+            invsqrt_a_minus_d: dec("54469307008909316920995813868745141605393597292927456921205312896311721017578"),
+        }
+    }
+
+    fn field(&self) -> &FiniteField {
+        self.curve.field()
+    }
+
+    // The edwards25519 base point also generates Ristretto255
+    pub fn generator(&self) -> RistrettoPoint {
+        RistrettoPoint { point: self.base.clone() }
+    }
+
+    pub fn identity(&self) -> RistrettoPoint {
+        RistrettoPoint { point: EdwardsPoint::identity() }
+    }
+
+    pub fn add(&self, p: &RistrettoPoint, q: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint { point: self.curve.add(&p.point, &q.point) }
+    }
+
+    pub fn neg(&self, p: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint { point: self.curve.neg(&p.point) }
+    }
+
+    pub fn sub(&self, p: &RistrettoPoint, q: &RistrettoPoint) -> RistrettoPoint {
+        self.add(p, &self.neg(q))
+    }
+
+    pub fn scalar_mult(&self, p: &RistrettoPoint, k: &BigUint) -> RistrettoPoint {
+        RistrettoPoint { point: self.curve.scalar_mult(&p.point, k) }
+    }
+
+    fn is_negative(x: &BigUint) -> bool {
+        x.bit(0)
+    }
+
+    fn abs(&self, x: BigUint) -> BigUint {
+        if Self::is_negative(&x) { self.field().sub(&BigUint::from(0u32), &x) } else { x }
+    }
+
+    // (u/v is a square, sqrt(u/v)) or (false, sqrt(i u/v)); the root returned is non-negative
+    fn sqrt_ratio_m1(&self, u: &BigUint, v: &BigUint) -> (bool, BigUint) {
+        let f = self.field();
+        let zero = BigUint::from(0u32);
+        let v3 = f.mul(&f.mul(v, v), v);
+        let v7 = f.mul(&f.mul(&v3, &v3), v);
+        let exponent = (self.curve.p() - BigUint::from(5u32)) >> 3;
+        let mut r = f.mul(&f.mul(u, &v3), &f.mul(u, &v7).modpow(&exponent, self.curve.p()));
+
+        let check = f.mul(v, &f.mul(&r, &r));
+        let neg_u = f.sub(&zero, u);
+        let correct_sign = check == u % self.curve.p();
+        let flipped_sign = check == neg_u;
+        let flipped_sign_i = check == f.mul(&neg_u, &self.sqrt_m1);
+        if flipped_sign || flipped_sign_i {
+            r = f.mul(&r, &self.sqrt_m1);
+        }
+        (correct_sign || flipped_sign, self.abs(r))
+    }
+
+    pub fn encode(&self, p: &RistrettoPoint) -> [u8; 32] {
+        let f = self.field();
+        let one = BigUint::from(1u32);
+        let (x0, y0) = (&p.point.x, &p.point.y);
+        let t0 = f.mul(x0, y0);
+
+        let u1 = f.mul(&f.add(&one, y0), &f.sub(&one, y0));
+        let u2 = f.mul(x0, y0);
+        let (_, invsqrt) = self.sqrt_ratio_m1(&one, &f.mul(&u1, &f.mul(&u2, &u2)));
+        let den1 = f.mul(&invsqrt, &u1);
+        let den2 = f.mul(&invsqrt, &u2);
+        let z_inv = f.mul(&f.mul(&den1, &den2), &t0);
+
+        // Rotate by the 4-torsion when needed to reach the canonical representative
+        let rotate = Self::is_negative(&f.mul(&t0, &z_inv));
+        let (x, y, den_inv) = if rotate {
+            (f.mul(y0, &self.sqrt_m1), f.mul(x0, &self.sqrt_m1), f.mul(&den1, &self.invsqrt_a_minus_d))
+        } else {
+            (x0.clone(), y0.clone(), den2)
+        };
+        let y = if Self::is_negative(&f.mul(&x, &z_inv)) { f.sub(&BigUint::from(0u32), &y) } else { y };
+        let s = self.abs(f.mul(&den_inv, &f.sub(&one, &y)));
+
+        let mut bytes = s.to_bytes_le();
+        bytes.resize(32, 0);
+        bytes.try_into().unwrap()
+    }
+
+    // Only canonical encodings of group elements decode
+    pub fn decode(&self, bytes: &[u8; 32]) -> Result<RistrettoPoint, &'static str> {
+        let f = self.field();
+        let one = BigUint::from(1u32);
+        let s = BigUint::from_bytes_le(bytes);
+        if s >= *self.curve.p() || Self::is_negative(&s) {
+            return Err("non-canonical ristretto encoding");
+        }
+
+        let ss = f.mul(&s, &s);
+        let u1 = f.sub(&one, &ss);
+        let u2 = f.add(&one, &ss);
+        let u2_sqr = f.mul(&u2, &u2);
+        let v = f.sub(&f.sub(&BigUint::from(0u32), &f.mul(self.curve.d(), &f.mul(&u1, &u1))), &u2_sqr);
+        let (was_square, invsqrt) = self.sqrt_ratio_m1(&one, &f.mul(&v, &u2_sqr));
+
+        let den_x = f.mul(&invsqrt, &u2);
+        let den_y = f.mul(&f.mul(&invsqrt, &den_x), &v);
+        let x = self.abs(f.mul(&f.add(&s, &s), &den_x));
+        let y = f.mul(&u1, &den_y);
+        let t = f.mul(&x, &y);
+        if !was_square || Self::is_negative(&t) || y == BigUint::from(0u32) {
+            return Err("invalid ristretto encoding");
+        }
+        Ok(RistrettoPoint { point: EdwardsPoint { x, y } })
+    }
+
+    // Elligator map of one field element to an Edwards point
+    fn map(&self, t: &BigUint) -> EdwardsPoint {
+        let f = self.field();
+        let zero = BigUint::from(0u32);
+        let one = BigUint::from(1u32);
+        let d = self.curve.d();
+        let one_minus_d_sq = f.sub(&one, &f.mul(d, d));
+        let d_minus_one = f.sub(d, &one);
+        let d_minus_one_sq = f.mul(&d_minus_one, &d_minus_one);
+
+        let r = f.mul(&self.sqrt_m1, &f.mul(t, t));
+        let u = f.mul(&f.add(&r, &one), &one_minus_d_sq);
+        let v = f.mul(&f.sub(&f.sub(&zero, &one), &f.mul(&r, d)), &f.add(&r, d));
+        let (was_square, s) = self.sqrt_ratio_m1(&u, &v);
+        let (s, c) = if was_square {
+            (s, f.sub(&zero, &one))
+        } else {
+            (f.sub(&zero, &self.abs(f.mul(&s, t))), r.clone())
+        };
+        let n = f.sub(&f.mul(&f.mul(&c, &f.sub(&r, &one)), &d_minus_one_sq), &v);
+
+        let ss = f.mul(&s, &s);
+        let w0 = f.mul(&f.add(&s, &s), &v);
+        let w1 = f.mul(&n, &self.sqrt_ad_minus_one);
+        let w2 = f.sub(&one, &ss);
+        let w3 = f.add(&one, &ss);
+        // (X : Y : Z) = (w0 w3 : w2 w1 : w1 w3)
+        let z = f.mul(&w1, &w3);
+        EdwardsPoint {
+            x: f.div(&f.mul(&w0, &w3), &z),
+            y: f.div(&f.mul(&w2, &w1), &z),
+        }
+    }
+
+    // Hash-to-group from 64 uniformly random bytes: MAP(low half) + MAP(high half)
+    pub fn from_uniform_bytes(&self, bytes: &[u8; 64]) -> RistrettoPoint {
+        let mask = (BigUint::from(1u32) << 255) - BigUint::from(1u32);
+        let t1 = BigUint::from_bytes_le(&bytes[..32]) & &mask;
+        let t2 = BigUint::from_bytes_le(&bytes[32..]) & &mask;
+        RistrettoPoint { point: self.curve.add(&self.map(&t1), &self.map(&t2)) }
+    }
+
+    // SHA-512 of the message fed to from_uniform_bytes
+    pub fn hash_to_group(&self, message: &[u8]) -> RistrettoPoint {
+        self.from_uniform_bytes(&Sha512::digest(message).into())
+    }
+}
+
+impl Default for Ristretto255 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{from_hex, to_hex};
+
+    #[test]
+    fn test_generator_multiples() {
+        // RFC 9496 A.1
+        let group = Ristretto255::new();
+        let expected = [
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+            "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+            "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+        ];
+        let mut point = group.identity();
+        for hex in expected {
+            let encoded = group.encode(&point);
+            assert_eq!(to_hex(&encoded), hex);
+            assert_eq!(group.decode(&encoded).unwrap(), point);
+            point = group.add(&point, &group.generator());
+        }
+    }
+
+    #[test]
+    fn test_torsion_is_invisible() {
+        // Adding a 4-torsion point changes the Edwards point but not the Ristretto element
+        let group = Ristretto255::new();
+        let p = group.scalar_mult(&group.generator(), &BigUint::from(7u32));
+        let torsion = EdwardsPoint { x: group.sqrt_m1.clone(), y: BigUint::from(0u32) };
+        assert!(group.curve.is_on_curve(&torsion));
+        let shifted = RistrettoPoint { point: group.curve.add(&p.point, &torsion) };
+        assert_ne!(shifted.point, p.point);
+        assert_eq!(shifted, p);
+        assert_eq!(group.encode(&shifted), group.encode(&p));
+        assert_eq!(group.scalar_mult(&p, &group.order), group.identity());
+    }
+
+    #[test]
+    fn test_rejects_bad_encodings() {
+        // RFC 9496 A.2: non-canonical, negative and non-square encodings
+        let group = Ristretto255::new();
+        for hex in [
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            "0100000000000000000000000000000000000000000000000000000000000000",
+            "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+        ] {
+            let bytes: [u8; 32] = from_hex(hex).unwrap().try_into().unwrap();
+            assert!(group.decode(&bytes).is_err(), "{}", hex);
+        }
+    }
+
+    #[test]
+    fn test_hash_to_group() {
+        // RFC 9496 A.3
+        let group = Ristretto255::new();
+        let point = group.hash_to_group(b"Ristretto is traditionally a short shot of espresso coffee");
+        assert_eq!(to_hex(&group.encode(&point)), "3066f82a1a747d45120d1740f14358531a8f04bbffe6a819f86dfe50f44a0a46");
+    }
+}
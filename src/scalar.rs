@@ -0,0 +1,170 @@
+use crate::FiniteField;
+use num_bigint::{BigUint, RandBigInt};
+use rand::Rng;
+
+// Integers mod the group order n, kept apart from base-field elements mod p
+//
+// Private keys, nonces, challenges and signature values all live mod n. Mixing them up with
+// coordinates mod p type-checks as long as both are BigUint, so a Scalar carries its order and
+// refuses to combine with a scalar of a different order.
+// Uniform scalars: random() samples [1, n - 1] directly; from_bytes_wide() reduces a 512-bit
+// string, whose bias mod a 256-bit n is about 2^-256 (reducing 256 bits would be biased by n / 2^256)
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scalar {
+    value: BigUint,
+    order: BigUint,
+}
+
+impl Scalar {
+    pub fn new(value: BigUint, order: &BigUint) -> Self {
+        if *order == BigUint::from(0u32) {
+            panic!("?group order cannot be zero?");
+        }
+        Scalar { value: value % order, order: order.clone() }
+    }
+
+    pub fn zero(order: &BigUint) -> Self {
+        Scalar::new(BigUint::from(0u32), order)
+    }
+
+    pub fn one(order: &BigUint) -> Self {
+        Scalar::new(BigUint::from(1u32), order)
+    }
+
+    // Uniform in [1, n - 1], suitable for private keys and nonces
+    pub fn random<R: Rng + ?Sized>(order: &BigUint, rng: &mut R) -> Self {
+        Scalar::new(rng.gen_biguint_range(&BigUint::from(1u32), order), order)
+    }
+
+    // Big-endian 64 bytes reduced mod n
+    pub fn from_bytes_wide(bytes: &[u8; 64], order: &BigUint) -> Self {
+        Scalar::new(BigUint::from_bytes_be(bytes), order)
+    }
+
+    pub fn value(&self) -> &BigUint {
+        &self.value
+    }
+
+    pub fn into_value(self) -> BigUint {
+        self.value
+    }
+
+    pub fn order(&self) -> &BigUint {
+        &self.order
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == BigUint::from(0u32)
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> Scalar {
+        Scalar { value: self.value.modpow(exponent, &self.order), order: self.order.clone() }
+    }
+
+    // None for zero; n is prime, so every other scalar is invertible
+    pub fn inv(&self) -> Option<Scalar> {
+        if self.is_zero() {
+            return None;
+        }
+        let field = FiniteField { p: self.order.clone() };
+        Some(Scalar { value: field.div(&BigUint::from(1u32), &self.value), order: self.order.clone() })
+    }
+
+    fn combine(&self, other: &Scalar, op: fn(&FiniteField, &BigUint, &BigUint) -> BigUint) -> Scalar {
+        if self.order != other.order {
+            panic!("?cannot combine scalars of different orders?");
+        }
+        let field = FiniteField { p: self.order.clone() };
+        Scalar { value: op(&field, &self.value, &other.value), order: self.order.clone() }
+    }
+}
+
+macro_rules! scalar_op {
+    ($op:ident, $method:ident) => {
+        impl std::ops::$op<&Scalar> for &Scalar {
+            type Output = Scalar;
+            fn $method(self, other: &Scalar) -> Scalar {
+                self.combine(other, FiniteField::$method)
+            }
+        }
+
+        impl std::ops::$op<Scalar> for &Scalar {
+            type Output = Scalar;
+            fn $method(self, other: Scalar) -> Scalar {
+                self.combine(&other, FiniteField::$method)
+            }
+        }
+
+        impl std::ops::$op<&Scalar> for Scalar {
+            type Output = Scalar;
+            fn $method(self, other: &Scalar) -> Scalar {
+                self.combine(other, FiniteField::$method)
+            }
+        }
+
+        impl std::ops::$op<Scalar> for Scalar {
+            type Output = Scalar;
+            fn $method(self, other: Scalar) -> Scalar {
+                self.combine(&other, FiniteField::$method)
+            }
+        }
+    };
+}
+
+scalar_op!(Add, add);
+scalar_op!(Sub, sub);
+scalar_op!(Mul, mul);
+
+impl std::ops::Neg for &Scalar {
+    type Output = Scalar;
+    fn neg(self) -> Scalar {
+        Scalar { value: (&self.order - &self.value) % &self.order, order: self.order.clone() }
+    }
+}
+
+impl std::ops::Neg for Scalar {
+    type Output = Scalar;
+    fn neg(self) -> Scalar {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_arithmetic() {
+        let n = BigUint::from(19u32);
+        let a = Scalar::new(BigUint::from(25u32), &n);
+        let b = Scalar::new(BigUint::from(7u32), &n);
+        assert_eq!(a.value(), &BigUint::from(6u32));
+        assert_eq!((&a + &b).into_value(), BigUint::from(13u32));
+        assert_eq!((&a - &b).into_value(), BigUint::from(18u32));
+        assert_eq!((&a * &b).into_value(), BigUint::from(4u32));
+        assert_eq!(&a + -&a, Scalar::zero(&n));
+        assert_eq!(&b * b.inv().unwrap(), Scalar::one(&n));
+        assert_eq!(Scalar::zero(&n).inv(), None);
+    }
+
+    #[test]
+    fn test_random_and_wide_reduction() {
+        let n = BigUint::from(1_000_003u32);
+        let mut rng = crate::entropy::Entropy;
+        for _ in 0..100 {
+            let k = Scalar::random(&n, &mut rng);
+            assert!(!k.is_zero() && *k.value() < n);
+        }
+        let mut bytes = [0xffu8; 64];
+        bytes[0] = 0x7f;
+        let wide = Scalar::from_bytes_wide(&bytes, &n);
+        assert_eq!(wide.into_value(), BigUint::from_bytes_be(&bytes) % &n);
+    }
+
+    #[test]
+    #[should_panic(expected = "different orders")]
+    fn test_mixed_orders() {
+        let _ = Scalar::one(&BigUint::from(19u32)) + Scalar::one(&BigUint::from(23u32));
+    }
+}
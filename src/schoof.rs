@@ -0,0 +1,335 @@
+use crate::extension::poly;
+use crate::ntheory;
+use crate::EllipticCurve;
+use num_bigint::BigUint;
+
+// Schoof's algorithm: #E(F_p) in polynomial time, for curves too big to count point by point
+//
+// #E = p + 1 - t, where the Frobenius π(x, y) = (x^p, y^p) satisfies π² - tπ + p = 0 on every
+// point, and |t| <= 2√p (Hasse). So t is found mod small primes l until their product passes
+// 4√p, then put together with the CRT:
+//   t mod 2   t is even iff E has a point of order 2, i.e. x³ + ax + b has a root:
+//             gcd(x^p - x, x³ + ax + b) != 1
+//   t mod l   on the l-torsion E[l], π²P + (p mod l)P = τ πP holds for exactly one τ = t mod l.
+//             A generic P = (x, y) of E[l] is worked with in F_p[x, y] / (ψ_l(x), y² - f(x)),
+//             ψ_l the l-th division polynomial whose roots are the x-coordinates of E[l]
+// Points there are (X(x), Y(x)·y): y² = f(x) keeps everything linear in y, and the chord and
+// tangent slopes are y times a polynomial. A denominator that isn't invertible mod ψ_l shares
+// a factor with it; every root still belongs to E[l], so the search restarts mod that factor.
+// The work grows like log(p)^5 or so with schoolbook polynomial arithmetic: seconds for a 40-bit
+// field, most of a minute for a 61-bit one in a release build, hopeless at cryptographic sizes,
+// where the published order is the only practical source
+
+type Poly = Vec<BigUint>;
+
+// (X(x), Y(x)·y) with X and Y reduced mod the current factor of ψ_l
+#[derive(Clone, Debug, PartialEq)]
+enum TorsionPoint {
+    Infinity,
+    Affine(Poly, Poly),
+}
+
+// F_p[x] / (h) for a monic h dividing ψ_l, with f = x³ + ax + b. Operations fail with a
+// proper monic factor of h when they run into a zero divisor
+struct TorsionRing<'a> {
+    curve: &'a EllipticCurve,
+    h: Poly,
+    f: Poly,
+}
+
+fn constant(c: u32, p: &BigUint) -> Poly {
+    let mut poly = vec![BigUint::from(c) % p];
+    poly::trim(&mut poly);
+    poly
+}
+
+fn monic(poly: &[BigUint], p: &BigUint) -> Poly {
+    let mut poly = poly.to_vec();
+    poly::trim(&mut poly);
+    let lead_inv = ntheory::mod_inverse(poly.last().expect("?zero polynomial?"), p).expect("?p is prime?");
+    poly.iter().map(|c| (c * &lead_inv) % p).collect()
+}
+
+fn x_poly() -> Poly {
+    vec![BigUint::from(0u32), BigUint::from(1u32)]
+}
+
+// f = x³ + ax + b
+fn curve_polynomial(curve: &EllipticCurve) -> Poly {
+    vec![curve.b().clone(), curve.a().clone(), BigUint::from(0u32), BigUint::from(1u32)]
+}
+
+impl TorsionRing<'_> {
+    fn p(&self) -> &BigUint {
+        self.curve.p()
+    }
+
+    fn mul(&self, a: &[BigUint], b: &[BigUint]) -> Poly {
+        let mut product = poly::mul(a, b, self.p());
+        poly::reduce(&mut product, &self.h, self.p());
+        product
+    }
+
+    fn scale(&self, a: &[BigUint], c: u32) -> Poly {
+        self.mul(a, &constant(c, self.p()))
+    }
+
+    fn inverse(&self, a: &[BigUint]) -> Result<Poly, Poly> {
+        poly::inverse_mod(a, &self.h, self.p()).ok_or_else(|| self.factor(a))
+    }
+
+    // gcd(a, h) for an a that vanishes at some roots of h but not all
+    fn factor(&self, a: &[BigUint]) -> Poly {
+        monic(&poly::gcd(a, &self.h, self.p()), self.p())
+    }
+
+    fn neg(&self, a: &[BigUint]) -> Poly {
+        poly::sub(&[], a, self.p())
+    }
+
+    // X3 = λ² f - X1 - X2, Y3 = λ (X1 - X3) - Y1, for a slope λ·y
+    fn chord(&self, slope: &[BigUint], (x1, y1): (&Poly, &Poly), x2: &Poly) -> TorsionPoint {
+        let p = self.p();
+        let x3 = poly::sub(&poly::sub(&self.mul(&self.mul(slope, slope), &self.f), x1, p), x2, p);
+        let y3 = poly::sub(&self.mul(slope, &poly::sub(x1, &x3, p)), y1, p);
+        TorsionPoint::Affine(x3, y3)
+    }
+
+    fn add(&self, a: &TorsionPoint, b: &TorsionPoint) -> Result<TorsionPoint, Poly> {
+        let p = self.p();
+        let ((x1, y1), (x2, y2)) = match (a, b) {
+            (TorsionPoint::Infinity, _) => return Ok(b.clone()),
+            (_, TorsionPoint::Infinity) => return Ok(a.clone()),
+            (TorsionPoint::Affine(x1, y1), TorsionPoint::Affine(x2, y2)) => ((x1, y1), (x2, y2)),
+        };
+        if x1 == x2 {
+            return if y1 == y2 {
+                self.double(a)
+            } else if *y1 == self.neg(y2) {
+                Ok(TorsionPoint::Infinity)
+            } else {
+                // P = Q at some roots and P = -Q at the others
+                Err(self.factor(&poly::sub(y1, y2, p)))
+            };
+        }
+        let slope = self.mul(&poly::sub(y1, y2, p), &self.inverse(&poly::sub(x1, x2, p))?);
+        Ok(self.chord(&slope, (x1, y1), x2))
+    }
+
+    // Tangent slope (3X² + a) / (2Y·y) = y (3X² + a) / (2Y f); Y·y != 0 as l is odd
+    fn double(&self, a: &TorsionPoint) -> Result<TorsionPoint, Poly> {
+        let (x, y) = match a {
+            TorsionPoint::Infinity => return Ok(TorsionPoint::Infinity),
+            TorsionPoint::Affine(x, y) => (x, y),
+        };
+        let numerator = poly::add(&self.scale(&self.mul(x, x), 3), std::slice::from_ref(self.curve.a()), self.p());
+        let slope = self.mul(&numerator, &self.inverse(&self.scale(&self.mul(y, &self.f), 2))?);
+        Ok(self.chord(&slope, (x, y), x))
+    }
+
+    fn mul_scalar(&self, point: &TorsionPoint, k: u64) -> Result<TorsionPoint, Poly> {
+        let mut result = TorsionPoint::Infinity;
+        for i in (0..64 - k.leading_zeros()).rev() {
+            result = self.double(&result)?;
+            if k >> i & 1 == 1 {
+                result = self.add(&result, point)?;
+            }
+        }
+        Ok(result)
+    }
+
+    // τ with π²P + qP = τ πP for the generic point of E[l] mod h
+    fn trace(&self, l: u64) -> Result<u64, Poly> {
+        let p = self.p();
+        let point = TorsionPoint::Affine(self.mul(&x_poly(), &constant(1, p)), constant(1, p));
+
+        // π(x, y) = (x^p, y·f^((p-1)/2)) and π² = (x^(p²), y·f^((p²-1)/2)),
+        // with f^((p²-1)/2) = f^((p-1)/2) · (f^((p-1)/2))^p
+        let xp = poly::pow_mod(&x_poly(), p, &self.h, p);
+        let yp = poly::pow_mod(&self.f, &((p - 1u32) >> 1), &self.h, p);
+        let frobenius = TorsionPoint::Affine(xp.clone(), yp.clone());
+        let xpp = poly::pow_mod(&xp, p, &self.h, p);
+        let ypp = self.mul(&yp, &poly::pow_mod(&yp, p, &self.h, p));
+        let frobenius_squared = TorsionPoint::Affine(xpp, ypp);
+
+        let q = (p % l).to_u64_digits().first().copied().unwrap_or(0);
+        let target = self.add(&frobenius_squared, &self.mul_scalar(&point, q)?)?;
+        let mut multiple = TorsionPoint::Infinity;
+        for tau in 0..l {
+            if multiple == target {
+                return Ok(tau);
+            }
+            multiple = self.add(&multiple, &frobenius)?;
+        }
+        panic!("?no trace satisfies the Frobenius equation?");
+    }
+}
+
+// ψ_0..ψ_n with y factored out of the even ones (ψ_2m = y·g_2m), y² replaced by f:
+//   ψ_2m+1 = ψ_m+2 ψ_m³ - ψ_m-1 ψ_m+1³        (an f² on the even-index product)
+//   ψ_2m = ψ_m (ψ_m+2 ψ_m-1² - ψ_m-2 ψ_m+1²) / 2y
+fn division_polynomials(curve: &EllipticCurve, n: usize) -> Vec<Poly> {
+    let p = curve.p();
+    let field = curve.field();
+    let (a, b) = (curve.a(), curve.b());
+    let a2 = field.mul(a, a);
+    let f = curve_polynomial(curve);
+    let f_squared = poly::mul(&f, &f, p);
+    let cube = |g: &Poly| poly::mul(&poly::mul(g, g, p), g, p);
+    let square = |g: &Poly| poly::mul(g, g, p);
+
+    let g3 = vec![field.sub(&BigUint::from(0u32), &a2), field.mul(&BigUint::from(12u32), b), field.mul(&BigUint::from(6u32), a), BigUint::from(0u32), BigUint::from(3u32)];
+    // 4 (x⁶ + 5ax⁴ + 20bx³ - 5a²x² - 4abx - 8b² - a³)
+    let g4: Poly = [
+        field.sub(&BigUint::from(0u32), &field.add(&field.mul(&BigUint::from(8u32), &field.mul(b, b)), &field.mul(&a2, a))),
+        field.sub(&BigUint::from(0u32), &field.mul(&BigUint::from(4u32), &field.mul(a, b))),
+        field.sub(&BigUint::from(0u32), &field.mul(&BigUint::from(5u32), &a2)),
+        field.mul(&BigUint::from(20u32), b),
+        field.mul(&BigUint::from(5u32), a),
+        BigUint::from(0u32),
+        BigUint::from(1u32),
+    ]
+    .iter()
+    .map(|c| field.mul(c, &BigUint::from(4u32)))
+    .collect();
+    let mut psi = vec![Vec::new(), constant(1, p), constant(2, p), g3, g4];
+
+    let half = field.invert(&BigUint::from(2u32)).expect("?p is odd?");
+    for k in psi.len()..=n {
+        let m = k / 2;
+        let next = if k % 2 == 1 {
+            let (first, second) = (poly::mul(&psi[m + 2], &cube(&psi[m]), p), poly::mul(&psi[m - 1], &cube(&psi[m + 1]), p));
+            if m % 2 == 0 {
+                poly::sub(&poly::mul(&f_squared, &first, p), &second, p)
+            } else {
+                poly::sub(&first, &poly::mul(&f_squared, &second, p), p)
+            }
+        } else {
+            let inner = poly::sub(&poly::mul(&psi[m + 2], &square(&psi[m - 1]), p), &poly::mul(&psi[m - 2], &square(&psi[m + 1]), p), p);
+            poly::mul(&poly::mul(&psi[m], &inner, p), std::slice::from_ref(&half), p)
+        };
+        psi.push(next);
+    }
+    psi.truncate(n + 1);
+    psi
+}
+
+impl EllipticCurve {
+    // t mod 2: whether x³ + ax + b has a root in F_p
+    pub(crate) fn trace_mod_2(&self) -> u64 {
+        let p = self.p();
+        let f = curve_polynomial(self);
+        let xp_minus_x = poly::sub(&poly::pow_mod(&x_poly(), p, &f, p), &x_poly(), p);
+        if poly::gcd(&xp_minus_x, &f, p).len() > 1 { 0 } else { 1 }
+    }
+
+    fn trace_mod_l(&self, l: u64, psi: &[BigUint]) -> u64 {
+        let f = curve_polynomial(self);
+        let mut ring = TorsionRing { curve: self, h: monic(psi, self.p()), f };
+        loop {
+            match ring.trace(l) {
+                Ok(tau) => return tau,
+                Err(factor) => ring.h = factor,
+            }
+        }
+    }
+
+    // Group order #E by Schoof's algorithm, for p far beyond what count_points can enumerate
+    // p must be a prime above 3 and the curve nonsingular
+    pub fn count_points_schoof(&self) -> Result<BigUint, &'static str> {
+        let p = self.p();
+        if *p <= BigUint::from(3u32) || !ntheory::is_probable_prime(p) {
+            return Err("field modulus must be a prime above 3");
+        }
+        let field = self.field();
+        let discriminant = field.add(
+            &field.mul(&BigUint::from(4u32), &field.mul(&field.mul(self.a(), self.a()), self.a())),
+            &field.mul(&BigUint::from(27u32), &field.mul(self.b(), self.b())),
+        );
+        if discriminant == BigUint::from(0u32) {
+            return Err("singular curve");
+        }
+
+        // Primes l != p until ∏ l > 4√p, i.e. (∏ l)² > 16p
+        let mut primes = Vec::new();
+        let mut modulus = BigUint::from(2u32);
+        let mut l = 3u64;
+        while &modulus * &modulus <= p * 16u32 {
+            if (2..l).take_while(|d| d * d <= l).all(|d| !l.is_multiple_of(d)) && *p != BigUint::from(l) {
+                primes.push(l);
+                modulus *= l;
+            }
+            l += 2;
+        }
+
+        let psi = division_polynomials(self, *primes.last().unwrap_or(&3) as usize);
+        let mut congruences = vec![(BigUint::from(self.trace_mod_2()), BigUint::from(2u32))];
+        for &l in &primes {
+            congruences.push((BigUint::from(self.trace_mod_l(l, &psi[l as usize])), BigUint::from(l)));
+        }
+        let (t, modulus) = ntheory::crt(&congruences)?;
+
+        // t mod M to the representative in (-M/2, M/2], which holds |t| <= 2√p
+        let count = if &t * 2u32 <= modulus { p + 1u32 - &t } else { p + 1u32 + (&modulus - &t) };
+        if !self.within_hasse_bounds(&count) {
+            return Err("point count outside the Hasse bound");
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    #[test]
+    fn test_division_polynomials() {
+        // ψ_l vanishes exactly at the x-coordinates of points of order l
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let psi = division_polynomials(&curve, 7);
+        let eval = |poly: &Poly, x: &BigUint| poly.iter().rev().fold(BigUint::from(0u32), |acc, c| (acc * x + c) % curve.p());
+        // (5, 4) has order 7, (4, 0) order 2
+        assert_eq!(eval(&psi[7], &BigUint::from(5u32)), BigUint::from(0u32));
+        assert_ne!(eval(&psi[7], &BigUint::from(4u32)), BigUint::from(0u32));
+        assert_eq!(psi[7].len(), (7 * 7 - 1) / 2 + 1);
+        assert_eq!(psi[6].len(), (6 * 6 - 4) / 2 + 1);
+    }
+
+    #[test]
+    fn test_matches_point_counting() {
+        for p in [5u32, 7, 11, 13] {
+            for a in 0..p {
+                for b in 0..p {
+                    let curve = EllipticCurve::new(BigUint::from(a), BigUint::from(b), BigUint::from(p));
+                    match curve.count_points_schoof() {
+                        Ok(count) => assert_eq!(count, curve.count_points().unwrap(), "a = {}, b = {}, p = {}", a, b, p),
+                        Err(e) => assert_eq!(e, "singular curve"),
+                    }
+                }
+            }
+        }
+        for (a, b) in [(1u32, 1u32), (2, 3), (0, 7), (1009, 5), (3, 0)] {
+            let curve = EllipticCurve::new(BigUint::from(a), BigUint::from(b), BigUint::from(1019u32));
+            assert_eq!(curve.count_points_schoof(), curve.count_points());
+        }
+        let composite = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(91u32));
+        assert!(composite.count_points_schoof().is_err());
+    }
+
+    #[test]
+    fn test_medium_curve() {
+        // p = 2^40 - 87, far beyond enumeration; the order was found independently with
+        // baby-step giant-step on random points, and must kill every point
+        let p = (BigUint::from(1u32) << 40) - 87u32;
+        let curve = EllipticCurve::new(BigUint::from(3u32), BigUint::from(11u32), p);
+        let order = curve.count_points_schoof().unwrap();
+        assert_eq!(order, BigUint::from(1099510236051u64));
+        let mut rng = crate::entropy::Entropy;
+        for _ in 0..5 {
+            let point = curve.random_point(&mut rng).unwrap();
+            assert_eq!(curve.scalar_mult(&point, &order), Point::Identity);
+            assert_ne!(curve.scalar_mult(&point, &(&order + 2u32)), Point::Identity);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::curves::NamedCurve;
+use crate::Point;
+use hkdf::Hkdf;
+use num_bigint::BigUint;
+use sha2::Sha256;
+
+// A private scalar in [1, n - 1] together with its curve
+//
+// derive_from_seed turns one master secret into any number of independent keys, one per info
+// string ("payments/2024", "device-7/signing"), the same on every platform:
+//   prk = HKDF-Extract(salt = "elliptic-curve derive_from_seed v1", seed)
+//   candidate_i = HKDF-Expand(prk, curve name || 0x00 || info || i, scalar size), i = 0, 1, ...
+// with the bits above the order's bit length cleared, taking the first candidate in [1, n - 1].
+// Masking keeps each rejection below 1/2 (for P-256 and secp256k1 it's about 2^-32), so 256
+// tries never run out in practice, and rejection rather than reduction mod n keeps the result
+// uniform. The curve name is in the input so one seed gives unrelated keys on different curves
+
+const SEED_SALT: &[u8] = b"elliptic-curve derive_from_seed v1";
+const MIN_SEED_LEN: usize = 32;
+
+#[derive(Clone, PartialEq)]
+pub struct SecretKey {
+    curve: NamedCurve,
+    scalar: BigUint,
+}
+
+impl SecretKey {
+    pub fn new(curve: &NamedCurve, scalar: BigUint) -> Result<Self, &'static str> {
+        if scalar == BigUint::from(0u32) || scalar >= curve.order {
+            return Err("private key out of range");
+        }
+        Ok(SecretKey { curve: curve.clone(), scalar })
+    }
+
+    pub fn random(curve: &NamedCurve) -> Self {
+        SecretKey { curve: curve.clone(), scalar: curve.ecdsa().generate_private_key() }
+    }
+
+    // The seed should be at least 32 bytes of secret randomness; info scopes the key
+    pub fn derive_from_seed(curve: &NamedCurve, seed: &[u8], info: &[u8]) -> Result<Self, &'static str> {
+        if seed.len() < MIN_SEED_LEN {
+            return Err("seed must be at least 32 bytes");
+        }
+        let hkdf = Hkdf::<Sha256>::new(Some(SEED_SALT), seed);
+        let len = curve.scalar_size();
+        let excess_bits = (8 * len as u64 - curve.order.bits()) as u32;
+        let mut candidate = vec![0u8; len];
+        for counter in 0..=255u8 {
+            let expand_info = [curve.name.as_bytes(), &[0x00], info, &[counter]].concat();
+            hkdf.expand(&expand_info, &mut candidate).expect("?scalar longer than HKDF output?");
+            candidate[0] &= 0xff >> excess_bits;
+            if let Ok(key) = SecretKey::new(curve, BigUint::from_bytes_be(&candidate)) {
+                return Ok(key);
+            }
+        }
+        Err("no candidate scalar in range")
+    }
+
+    pub fn curve(&self) -> &NamedCurve {
+        &self.curve
+    }
+
+    pub fn scalar(&self) -> &BigUint {
+        &self.scalar
+    }
+
+    pub fn public_key(&self) -> Point {
+        self.curve.ecdsa().generate_public_key(&self.scalar)
+    }
+}
+
+// The scalar stays out of logs
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "SecretKey({}, ..)", self.curve.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use crate::encoding::{from_hex, to_hex};
+
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_derive_from_seed() {
+        // Computed independently with Python's hmac module
+        let seed = from_hex(SEED).unwrap();
+        let p256 = SecretKey::derive_from_seed(&curves::p256(), &seed, b"payments").unwrap();
+        assert_eq!(to_hex(&p256.scalar().to_bytes_be()), "edda93807171fce655a2cc8b91e01ade49c25aa37b4c760a5e6ca56aa8115e6b");
+        let p521 = SecretKey::derive_from_seed(&curves::p521(), &seed, b"payments").unwrap();
+        assert_eq!(to_hex(&p521.scalar().to_bytes_be()), "01f4767689e374c777ca0539f479dcb5a7d0bd3d0ae14d43f5ce6192423da2a0473dbdb227affaf50e4f46858f3119c3ded76b16af0f2e2b18f6b018cbeba135f3ae");
+
+        // Reproducible, and independent across info strings and curves
+        assert_eq!(SecretKey::derive_from_seed(&curves::p256(), &seed, b"payments").unwrap(), p256);
+        let other = SecretKey::derive_from_seed(&curves::p256(), &seed, b"payments/2").unwrap();
+        assert_ne!(other.scalar(), p256.scalar());
+        let k1 = SecretKey::derive_from_seed(&curves::secp256k1(), &seed, b"payments").unwrap();
+        assert_ne!(k1.scalar(), p256.scalar());
+
+        assert!(SecretKey::derive_from_seed(&curves::p256(), &seed[..31], b"payments").is_err());
+    }
+
+    #[test]
+    fn test_range_and_debug() {
+        let curve = curves::secp256k1();
+        assert!(SecretKey::new(&curve, BigUint::from(0u32)).is_err());
+        assert!(SecretKey::new(&curve, curve.order.clone()).is_err());
+        let key = SecretKey::new(&curve, BigUint::from(7u32)).unwrap();
+        assert_eq!(key.public_key(), curve.curve.scalar_mult(&curve.generator, &BigUint::from(7u32)));
+        assert_eq!(format!("{:?}", key), "SecretKey(secp256k1, ..)");
+
+        // Masking leaves no scalar at or above n
+        for i in 0..50u8 {
+            let key = SecretKey::derive_from_seed(&curve, &[i; 32], b"range").unwrap();
+            assert!(*key.scalar() < curve.order);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{EllipticCurve, FiniteField, Point};
+use crate::entropy::Entropy;
+use num_bigint::{BigUint, RandBigInt};
+
+// Shamir secret sharing over Z/n with Feldman commitments
+//
+// A random polynomial f of degree t - 1 with f(0) = secret hands party i the share f(i);
+// any t shares determine f (and the secret) by Lagrange interpolation, t - 1 reveal nothing.
+// Publishing C_j = a_j * G for the coefficients lets each party check its share:
+//   f(i) * G = Σ C_j * i^j
+// Party identifiers are 1..=n; 0 would be the secret itself
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Share {
+    pub identifier: u32,
+    pub value: BigUint,
+}
+
+// Random polynomial of degree threshold - 1 with the given constant term
+pub fn random_polynomial(secret: &BigUint, threshold: usize, order: &BigUint) -> Vec<BigUint> {
+    let mut rng = Entropy;
+    let mut coefficients = vec![secret % order];
+    for _ in 1..threshold {
+        coefficients.push(rng.gen_biguint_below(order));
+    }
+    coefficients
+}
+
+// f(x) by Horner's rule
+pub fn evaluate_polynomial(coefficients: &[BigUint], x: &BigUint, order: &BigUint) -> BigUint {
+    let field = FiniteField { p: order.clone() };
+    coefficients.iter().rev().fold(BigUint::from(0u32), |acc, c| field.add(&field.mul(&acc, x), c))
+}
+
+// Split a secret into n shares, any threshold of which recover it
+// Also returns the polynomial so callers can publish Feldman commitments
+pub fn split(secret: &BigUint, threshold: usize, shares: usize, order: &BigUint) -> Result<(Vec<Share>, Vec<BigUint>), &'static str> {
+    if threshold == 0 || threshold > shares {
+        return Err("threshold must be between 1 and the number of shares");
+    }
+    if BigUint::from(shares) >= *order {
+        return Err("too many shares for the field");
+    }
+    let coefficients = random_polynomial(secret, threshold, order);
+    let shares = (1..=shares as u32)
+        .map(|i| Share {
+            identifier: i,
+            value: evaluate_polynomial(&coefficients, &BigUint::from(i), order),
+        })
+        .collect();
+    Ok((shares, coefficients))
+}
+
+// λ_i(0) = ∏_{j ≠ i} j / (j - i), the weight of share i when interpolating at 0
+pub fn lagrange_coefficient(identifier: u32, participants: &[u32], order: &BigUint) -> Result<BigUint, &'static str> {
+    let field = FiniteField { p: order.clone() };
+    let i = BigUint::from(identifier);
+    let mut numerator = BigUint::from(1u32);
+    let mut denominator = BigUint::from(1u32);
+    let mut found = false;
+    for &j in participants {
+        if j == identifier {
+            if found {
+                return Err("duplicate participant identifier");
+            }
+            found = true;
+            continue;
+        }
+        let j = BigUint::from(j);
+        numerator = field.mul(&numerator, &j);
+        denominator = field.mul(&denominator, &field.sub(&j, &i));
+    }
+    if !found {
+        return Err("identifier is not among the participants");
+    }
+    Ok(field.div(&numerator, &denominator))
+}
+
+// Recover f(0) from at least threshold shares
+pub fn reconstruct(shares: &[Share], order: &BigUint) -> Result<BigUint, &'static str> {
+    let field = FiniteField { p: order.clone() };
+    let participants: Vec<u32> = shares.iter().map(|s| s.identifier).collect();
+    let mut secret = BigUint::from(0u32);
+    for share in shares {
+        let lambda = lagrange_coefficient(share.identifier, &participants, order)?;
+        secret = field.add(&secret, &field.mul(&lambda, &share.value));
+    }
+    Ok(secret)
+}
+
+// Feldman commitments C_j = a_j * G
+pub fn commit_polynomial(curve: &EllipticCurve, generator: &Point, coefficients: &[BigUint]) -> Vec<Point> {
+    coefficients.iter().map(|c| curve.scalar_mult(generator, c)).collect()
+}
+
+// Σ C_j * i^j: the public image f(i) * G of party i's share
+pub fn evaluate_commitments(curve: &EllipticCurve, commitments: &[Point], identifier: u32, order: &BigUint) -> Point {
+    let field = FiniteField { p: order.clone() };
+    let i = BigUint::from(identifier);
+    let mut power = BigUint::from(1u32);
+    let mut result = Point::Identity;
+    for commitment in commitments {
+        result = curve.add(&result, &curve.scalar_mult(commitment, &power));
+        power = field.mul(&power, &i);
+    }
+    result
+}
+
+// f(i) * G = Σ C_j * i^j
+pub fn verify_share(curve: &EllipticCurve, generator: &Point, share: &Share, commitments: &[Point], order: &BigUint) -> bool {
+    curve.scalar_mult(generator, &share.value) == evaluate_commitments(curve, commitments, share.identifier, order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    #[test]
+    fn test_split_reconstruct() {
+        let order = curves::p256().order;
+        let secret = BigUint::from(123456789u32);
+        let (shares, _) = split(&secret, 3, 5, &order).unwrap();
+
+        assert_eq!(reconstruct(&shares[..3], &order).unwrap(), secret);
+        assert_eq!(reconstruct(&[shares[4].clone(), shares[0].clone(), shares[2].clone()], &order).unwrap(), secret);
+        assert_ne!(reconstruct(&shares[..2], &order).unwrap(), secret);
+
+        assert!(split(&secret, 6, 5, &order).is_err());
+        assert!(reconstruct(&[shares[0].clone(), shares[0].clone()], &order).is_err());
+    }
+
+    #[test]
+    fn test_feldman_commitments() {
+        let p256 = curves::p256();
+        let (shares, coefficients) = split(&BigUint::from(42u32), 2, 3, &p256.order).unwrap();
+        let commitments = commit_polynomial(&p256.curve, &p256.generator, &coefficients);
+
+        for share in &shares {
+            assert!(verify_share(&p256.curve, &p256.generator, share, &commitments, &p256.order));
+        }
+        let tampered = Share { identifier: 1, value: &shares[0].value + 1u32 };
+        assert!(!verify_share(&p256.curve, &p256.generator, &tampered, &commitments, &p256.order));
+    }
+}
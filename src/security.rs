@@ -0,0 +1,269 @@
+use crate::cm::{self, CLASS_NUMBER_ONE};
+use crate::entropy::Entropy;
+use crate::ntheory;
+use crate::pairing::embedding_degree;
+use crate::{EllipticCurve, Point, TwistSecurity};
+use num_bigint::BigUint;
+
+// A checklist of the known ways a curve's discrete log gets easier than generic, for reviewing
+// custom parameters before trusting them. From the group order #E = p + 1 - t:
+//   rho             Pollard's rho takes √(πr/4) steps in the subgroup of prime order r
+//   embedding deg.  the smallest k with r | p^k - 1: pairings move the DLP into F_{p^k}* (MOV),
+//                   where index calculus is subexponential, so only small k matter
+//   anomalous       #E = p: Smart's attack lifts to the p-adics and solves it in linear time
+//   supersingular   t = 0 mod p: embedding degree at most 2 (for p > 3)
+//   CM discriminant t² - 4p = Dv² for the fundamental discriminant D of the endomorphism ring.
+//                   A tiny |D| (secp256k1 has -3) means an efficient endomorphism: faster
+//                   scalar multiplication for everyone, √|Aut| speedup of rho, no known break
+//   twist           x-only code accepts points of the twist, of order 2(p + 1) - #E, so its
+//                   largest prime factor bounds the security of such code
+// D is known exactly when the j-invariant is one of the nine class-number-one values, else by
+// factoring 4p - t², which like the twist factorization is only done for small fields
+
+// Embedding degrees searched up to this; beyond it F_{p^k} is out of reach
+pub const MAX_EMBEDDING_DEGREE: u32 = 20;
+// Fields up to this size get factorization-based checks
+pub const SMALL_FIELD_BITS: u64 = 40;
+// Cofactor primes are stripped from #E below this bound
+const COFACTOR_BOUND: u32 = 1 << 16;
+// Rho security below this is reported as a weakness
+pub const MIN_RHO_BITS: f64 = 100.0;
+// Random points checked against p + 1 before counting, and the largest field counted point by
+// point and with Schoof's algorithm
+const SUPERSINGULAR_CHECKS: u32 = 16;
+const ENUMERATION_BITS: u64 = 16;
+const SCHOOF_BITS: u64 = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecurityReport {
+    pub group_order: BigUint,
+    // #E = cofactor * subgroup_order, with every factor below 2^16 moved into the cofactor
+    pub subgroup_order: BigUint,
+    pub cofactor: BigUint,
+    pub subgroup_prime: bool,
+    // log2 of the expected rho steps in the subgroup
+    pub rho_bits: f64,
+    pub embedding_degree: Option<u32>,
+    pub anomalous: bool,
+    pub supersingular: bool,
+    // 4p - t² = |D|v²
+    pub frobenius_discriminant: BigUint,
+    pub cm_discriminant: Option<i64>,
+    pub class_number_one: bool,
+    // Only for fields of at most SMALL_FIELD_BITS bits
+    pub twist: Option<TwistSecurity>,
+}
+
+impl SecurityReport {
+    // The findings that weaken the DLP, empty for a curve that passes every check
+    pub fn weaknesses(&self) -> Vec<&'static str> {
+        let mut weaknesses = Vec::new();
+        if self.anomalous {
+            weaknesses.push("anomalous: #E = p falls to Smart's attack");
+        }
+        if self.supersingular {
+            weaknesses.push("supersingular: the MOV reduction applies");
+        } else if self.embedding_degree.is_some() {
+            weaknesses.push("small embedding degree: the MOV reduction applies");
+        }
+        if !self.subgroup_prime {
+            weaknesses.push("group order is not a small cofactor times a prime");
+        }
+        if self.rho_bits < MIN_RHO_BITS {
+            weaknesses.push("subgroup too small for Pollard's rho");
+        }
+        if self.twist.as_ref().is_some_and(|twist| twist.largest_prime_factor.bits() * 2 < self.subgroup_order.bits()) {
+            weaknesses.push("twist order has no large prime factor");
+        }
+        weaknesses
+    }
+}
+
+fn log2(n: &BigUint) -> f64 {
+    let shift = n.bits().saturating_sub(53);
+    let top = (n >> shift).to_u64_digits().first().copied().unwrap_or(0);
+    (top as f64).log2() + shift as f64
+}
+
+// (r, h) with order = h * r and h made of the primes below COFACTOR_BOUND. The last prime is
+// never stripped, so a smooth order still gets its largest prime factor as r
+fn split_cofactor(order: &BigUint) -> (BigUint, BigUint) {
+    let mut rest = order.clone();
+    let mut d = 2u32;
+    while d < COFACTOR_BOUND && BigUint::from(d) * d <= rest {
+        while &rest % d == BigUint::from(0u32) && rest != BigUint::from(d) {
+            rest /= d;
+        }
+        d += 1;
+    }
+    (rest.clone(), order / rest)
+}
+
+// Fundamental discriminant of Q(√(t² - 4p)) from 4p - t², by factoring
+fn fundamental_discriminant(frobenius_discriminant: &BigUint) -> Option<i64> {
+    let squarefree = ntheory::factorize(frobenius_discriminant)
+        .into_iter()
+        .filter(|(_, exponent)| exponent % 2 == 1)
+        .fold(BigUint::from(1u32), |product, (prime, _)| product * prime);
+    let m = i64::try_from(squarefree.to_u64_digits().first().copied().unwrap_or(1)).ok()?;
+    Some(if m % 4 == 3 { -m } else { -4 * m })
+}
+
+impl EllipticCurve {
+    // Whether t = 0 mod p, i.e. #E = p + 1 for p > 3: then the embedding degree is at most 2 and
+    // the MOV reduction puts the DLP in F_p²*. j = 0 and j = 1728 are decided by p alone
+    // (supersingular iff p = 2 mod 3, resp. p = 3 mod 4). Otherwise an ordinary curve almost
+    // always has a random point not killed by p + 1; if none shows up the order is counted,
+    // which limits the remaining case to fields of at most 64 bits
+    pub fn is_supersingular(&self) -> Result<bool, &'static str> {
+        let p = self.p();
+        if *p <= BigUint::from(3u32) {
+            return Err("field modulus must be a prime above 3");
+        }
+        let j = self.j_invariant()?;
+        if j == BigUint::from(0u32) {
+            return Ok(p % 3u32 == BigUint::from(2u32));
+        }
+        if j == BigUint::from(1728u32) % p {
+            return Ok(p % 4u32 == BigUint::from(3u32));
+        }
+
+        let sum = p + 1u32;
+        for _ in 0..SUPERSINGULAR_CHECKS {
+            if self.scalar_mult(&self.random_point(&mut Entropy)?, &sum) != Point::Identity {
+                return Ok(false);
+            }
+        }
+        let order = if p.bits() <= ENUMERATION_BITS {
+            self.count_points()?
+        } else if p.bits() <= SCHOOF_BITS {
+            self.count_points_schoof()?
+        } else {
+            return Err("field too large to count points");
+        };
+        Ok(order == sum)
+    }
+
+    // Security review of the curve given its group order #E (from count_points,
+    // count_points_schoof or the curve's published n * h)
+    pub fn security_report(&self, order: &BigUint) -> Result<SecurityReport, &'static str> {
+        let p = self.p();
+        let j = self.j_invariant()?;
+        if !self.within_hasse_bounds(order) {
+            return Err("order is outside the Hasse bound");
+        }
+
+        let (subgroup_order, cofactor) = split_cofactor(order);
+        let subgroup_prime = ntheory::is_probable_prime(&subgroup_order);
+        let rho_bits = (log2(&subgroup_order) + (std::f64::consts::PI / 4.0).log2()) / 2.0;
+
+        // |t| and 4p - t²; Hasse keeps the latter positive
+        let sum = p + 1u32;
+        let trace = if order <= &sum { &sum - order } else { order - &sum };
+        let frobenius_discriminant = p * 4u32 - &trace * &trace;
+        let supersingular = &trace % p == BigUint::from(0u32);
+        let anomalous = order == p;
+
+        let small = p.bits() <= SMALL_FIELD_BITS;
+        let class_number_one = CLASS_NUMBER_ONE.iter().find(|(_, j_d)| cm::reduce(*j_d, p) == j).map(|(d, _)| *d);
+        let cm_discriminant = class_number_one.or_else(|| small.then(|| fundamental_discriminant(&frobenius_discriminant)).flatten());
+
+        let twist = small.then(|| {
+            let twist_order = sum * 2u32 - order;
+            let factors = ntheory::factorize(&twist_order);
+            let largest_prime_factor = factors.last().map(|(prime, _)| prime.clone()).unwrap_or_else(|| BigUint::from(1u32));
+            TwistSecurity { twist_order, factors, largest_prime_factor }
+        });
+
+        Ok(SecurityReport {
+            group_order: order.clone(),
+            embedding_degree: embedding_degree(p, &subgroup_order, MAX_EMBEDDING_DEGREE),
+            subgroup_order,
+            cofactor,
+            subgroup_prime,
+            rho_bits,
+            anomalous,
+            supersingular,
+            frobenius_discriminant,
+            cm_discriminant,
+            class_number_one: class_number_one.is_some(),
+            twist,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    #[test]
+    fn test_standard_curves() {
+        let secp256k1 = curves::secp256k1();
+        let report = secp256k1.curve.security_report(&secp256k1.order).unwrap();
+        assert_eq!(report.subgroup_order, secp256k1.order);
+        assert_eq!(report.cofactor, BigUint::from(1u32));
+        assert!(report.rho_bits > 127.0 && report.rho_bits < 128.0);
+        assert_eq!(report.embedding_degree, None);
+        assert_eq!(report.cm_discriminant, Some(-3));
+        assert!(report.class_number_one);
+        assert_eq!(report.twist, None);
+        assert!(report.weaknesses().is_empty());
+
+        let p256 = curves::p256();
+        let report = p256.curve.security_report(&p256.order).unwrap();
+        assert_eq!(report.cm_discriminant, None);
+        assert!(!report.class_number_one && !report.anomalous && !report.supersingular);
+        assert!(report.weaknesses().is_empty());
+        assert!(p256.curve.security_report(&(&p256.order * 2u32)).is_err());
+    }
+
+    #[test]
+    fn test_weak_curves() {
+        // y² = x³ + x over p = 3 mod 4 is supersingular: #E = p + 1, embedding degree 2
+        let p = BigUint::from(1019u32);
+        let supersingular = EllipticCurve::new(BigUint::from(1u32), BigUint::from(0u32), p.clone());
+        let report = supersingular.security_report(&supersingular.count_points().unwrap()).unwrap();
+        assert!(report.supersingular);
+        assert_eq!(report.embedding_degree, Some(2));
+        assert_eq!(report.cm_discriminant, Some(-4));
+        assert!(report.weaknesses().contains(&"supersingular: the MOV reduction applies"));
+
+        // y² = x³ + 5x + 33 over F_1019 has exactly 1019 points
+        let anomalous = EllipticCurve::new(BigUint::from(5u32), BigUint::from(33u32), p.clone());
+        let report = anomalous.security_report(&p).unwrap();
+        assert!(report.anomalous && !report.supersingular);
+        assert_eq!(report.subgroup_order, p);
+        // 4p - 1 = 4075 = 5² · 163
+        assert_eq!(report.frobenius_discriminant, BigUint::from(4075u32));
+        assert_eq!(report.cm_discriminant, Some(-163));
+        assert!(report.weaknesses().contains(&"anomalous: #E = p falls to Smart's attack"));
+
+        // The twist report matches twist_security
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(3u32), BigUint::from(97u32));
+        let report = curve.security_report(&curve.count_points().unwrap()).unwrap();
+        assert_eq!(report.twist, Some(curve.twist_security().unwrap()));
+        assert_eq!(&report.subgroup_order * &report.cofactor, report.group_order);
+        let singular = EllipticCurve::new(BigUint::from(0u32), BigUint::from(0u32), p);
+        assert!(singular.security_report(&BigUint::from(1019u32)).is_err());
+    }
+
+    #[test]
+    fn test_is_supersingular() {
+        let p = BigUint::from(1019u32);
+        let curve = |a: u32, b: u32, p: &BigUint| EllipticCurve::new(BigUint::from(a), BigUint::from(b), p.clone());
+        // j = 0 and j = 1728 by p mod 3 and p mod 4
+        assert_eq!(curve(0, 1, &p).is_supersingular(), Ok(true));
+        assert_eq!(curve(1, 0, &p).is_supersingular(), Ok(true));
+        assert_eq!(curve(1, 0, &BigUint::from(1021u32)).is_supersingular(), Ok(false));
+        assert_eq!(curves::secp256k1().curve.is_supersingular(), Ok(false));
+        assert_eq!(curves::p256().curve.is_supersingular(), Ok(false));
+
+        // j = -32768 (D = -11, inert at 1019) and j = 8000 (D = -8, inert at 16777213), counted
+        assert_eq!(curve(207, 138, &p).is_supersingular(), Ok(true));
+        assert_eq!(curve(207, 138, &p).count_points(), Ok(BigUint::from(1020u32)));
+        assert_eq!(curve(12839700, 8559800, &BigUint::from(16777213u32)).is_supersingular(), Ok(true));
+        assert_eq!(curve(5, 33, &p).is_supersingular(), Ok(false));
+        assert!(curve(0, 0, &p).is_supersingular().is_err());
+    }
+}
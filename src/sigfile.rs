@@ -0,0 +1,206 @@
+use crate::ecdsa::ECDSASignature;
+use crate::ed25519::{Ed25519, Ed25519Signature};
+use crate::encoding::{from_base64, to_base64};
+use crate::ssh::{SshCurve, SshPrivateKey, SshPublicKey};
+use sha2::{Digest, Sha256, Sha512};
+
+// Detached signature files in the style of minisign/signify, for signing releases with an
+// OpenSSH key (ssh.rs). Four lines:
+//   untrusted comment: <anything>
+//   base64(algorithm id || key id || signature)
+//   trusted comment: <text>
+//   base64(global signature)
+// The key signs SHA-512 of the file, so a file only has to be hashed once; the global signature
+// covers signature || trusted comment, which binds the comment (typically a timestamp and file
+// name) to this signature. The untrusted comment is covered by nothing and only for humans.
+// The key id, the first 8 bytes of SHA-256 of the public key blob, picks out the key without
+// trying every one. Algorithm ids:
+//   ED  Ed25519
+//   E2  ECDSA P-256 with SHA-256, r || s
+//   E3  ECDSA P-384 with SHA-384
+//   E5  ECDSA P-521 with SHA-512
+// The layout is minisign's, but not its hash (BLAKE2b), so the files aren't interchangeable
+
+const UNTRUSTED_PREFIX: &str = "untrusted comment: ";
+const TRUSTED_PREFIX: &str = "trusted comment: ";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetachedSignature {
+    pub untrusted_comment: String,
+    pub algorithm: [u8; 2],
+    pub key_id: [u8; 8],
+    pub signature: Vec<u8>,
+    pub trusted_comment: String,
+    pub global_signature: Vec<u8>,
+}
+
+pub fn key_id(public_key: &SshPublicKey) -> [u8; 8] {
+    Sha256::digest(public_key.to_blob())[..8].try_into().unwrap()
+}
+
+fn algorithm_id(public_key: &SshPublicKey) -> [u8; 2] {
+    match public_key {
+        SshPublicKey::Ed25519(_) => *b"ED",
+        SshPublicKey::Ecdsa(SshCurve::NistP256, _) => *b"E2",
+        SshPublicKey::Ecdsa(SshCurve::NistP384, _) => *b"E3",
+        SshPublicKey::Ecdsa(SshCurve::NistP521, _) => *b"E5",
+    }
+}
+
+fn raw_sign(key: &SshPrivateKey, data: &[u8]) -> Vec<u8> {
+    match key {
+        SshPrivateKey::Ed25519(secret) => Ed25519::new().sign(secret, data).to_bytes().to_vec(),
+        SshPrivateKey::Ecdsa(curve, d) => {
+            let named = curve.named_curve();
+            named.ecdsa().sign_digest(&curve.digest(data), d).unwrap().to_fixed_bytes(named.scalar_size())
+        }
+    }
+}
+
+fn raw_verify(public_key: &SshPublicKey, data: &[u8], signature: &[u8]) -> bool {
+    match public_key {
+        SshPublicKey::Ed25519(key) => match Ed25519Signature::from_bytes(signature) {
+            Ok(signature) => Ed25519::new().verify(key, data, &signature),
+            Err(_) => false,
+        },
+        SshPublicKey::Ecdsa(curve, point) => {
+            let named = curve.named_curve();
+            match ECDSASignature::from_fixed_bytes(signature, named.scalar_size()) {
+                Ok(signature) => named.ecdsa().verify_digest(&curve.digest(data), &signature, point),
+                Err(_) => false,
+            }
+        }
+    }
+}
+
+// Comments are single lines
+fn check_comment(comment: &str) -> Result<(), &'static str> {
+    if comment.contains(['\n', '\r']) {
+        return Err("comments must be a single line");
+    }
+    Ok(())
+}
+
+impl DetachedSignature {
+    pub fn sign(key: &SshPrivateKey, message: &[u8], trusted_comment: &str, untrusted_comment: &str) -> Result<Self, &'static str> {
+        check_comment(trusted_comment)?;
+        check_comment(untrusted_comment)?;
+        let public_key = key.public_key();
+        let signature = raw_sign(key, &Sha512::digest(message));
+        let global_signature = raw_sign(key, &[&signature[..], trusted_comment.as_bytes()].concat());
+        Ok(DetachedSignature {
+            untrusted_comment: untrusted_comment.to_string(),
+            algorithm: algorithm_id(&public_key),
+            key_id: key_id(&public_key),
+            signature,
+            trusted_comment: trusted_comment.to_string(),
+            global_signature,
+        })
+    }
+
+    // Both signatures have to check out against the trusted key; the trusted comment is only
+    // meaningful after this returns Ok
+    pub fn verify(&self, public_key: &SshPublicKey, message: &[u8]) -> Result<(), &'static str> {
+        if self.key_id != key_id(public_key) {
+            return Err("signature was made with a different key");
+        }
+        if self.algorithm != algorithm_id(public_key) {
+            return Err("signature algorithm does not match the key");
+        }
+        if !raw_verify(public_key, &Sha512::digest(message), &self.signature) {
+            return Err("signature verification failed");
+        }
+        if !raw_verify(public_key, &[&self.signature[..], self.trusted_comment.as_bytes()].concat(), &self.global_signature) {
+            return Err("trusted comment signature verification failed");
+        }
+        Ok(())
+    }
+
+    pub fn to_text(&self) -> String {
+        let blob = [&self.algorithm[..], &self.key_id, &self.signature].concat();
+        format!(
+            "{}{}\n{}\n{}{}\n{}\n",
+            UNTRUSTED_PREFIX,
+            self.untrusted_comment,
+            to_base64(&blob),
+            TRUSTED_PREFIX,
+            self.trusted_comment,
+            to_base64(&self.global_signature)
+        )
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, &'static str> {
+        let lines: Vec<&str> = text.lines().collect();
+        let [untrusted, blob, trusted, global] = lines[..] else {
+            return Err("signature file has four lines");
+        };
+        let untrusted_comment = untrusted.strip_prefix(UNTRUSTED_PREFIX).ok_or("missing untrusted comment")?;
+        let trusted_comment = trusted.strip_prefix(TRUSTED_PREFIX).ok_or("missing trusted comment")?;
+        let blob = from_base64(blob)?;
+        if blob.len() < 10 {
+            return Err("truncated signature");
+        }
+        Ok(DetachedSignature {
+            untrusted_comment: untrusted_comment.to_string(),
+            algorithm: blob[..2].try_into().unwrap(),
+            key_id: blob[2..10].try_into().unwrap(),
+            signature: blob[10..].to_vec(),
+            trusted_comment: trusted_comment.to_string(),
+            global_signature: from_base64(global)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_hex;
+
+    fn keys() -> Vec<SshPrivateKey> {
+        [
+            include_str!("../testvectors/openssh/ed25519"),
+            include_str!("../testvectors/openssh/nistp256"),
+            include_str!("../testvectors/openssh/nistp384"),
+            include_str!("../testvectors/openssh/nistp521"),
+        ]
+        .iter()
+        .map(|pem| SshPrivateKey::from_openssh(pem, None).unwrap().0)
+        .collect()
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let message = b"release-1.0.tar.gz contents";
+        for key in keys() {
+            let public_key = key.public_key();
+            let signature = DetachedSignature::sign(&key, message, "timestamp:1700000000\tfile:release-1.0.tar.gz", "signature from ec").unwrap();
+            let text = signature.to_text();
+            assert_eq!(text.lines().count(), 4);
+            let parsed = DetachedSignature::from_text(&text).unwrap();
+            assert_eq!(parsed, signature);
+            assert!(parsed.verify(&public_key, message).is_ok());
+            assert!(parsed.verify(&public_key, b"something else").is_err());
+
+            // The untrusted comment can change freely, the trusted one can't
+            let relabeled = text.replacen("signature from ec", "anything", 1);
+            assert!(DetachedSignature::from_text(&relabeled).unwrap().verify(&public_key, message).is_ok());
+            let retimed = text.replacen("1700000000", "1800000000", 1);
+            assert_eq!(DetachedSignature::from_text(&retimed).unwrap().verify(&public_key, message), Err("trusted comment signature verification failed"));
+        }
+    }
+
+    #[test]
+    fn test_wrong_key() {
+        let keys = keys();
+        let signature = DetachedSignature::sign(&keys[0], b"x", "", "").unwrap();
+        assert_eq!(to_hex(&signature.key_id), to_hex(&Sha256::digest(keys[0].public_key().to_blob())[..8]));
+        assert_eq!(signature.verify(&keys[1].public_key(), b"x"), Err("signature was made with a different key"));
+
+        // Same key id, different algorithm
+        let mut swapped = signature.clone();
+        swapped.algorithm = *b"E2";
+        assert!(swapped.verify(&keys[0].public_key(), b"x").is_err());
+        assert!(DetachedSignature::sign(&keys[0], b"x", "two\nlines", "").is_err());
+        assert!(DetachedSignature::from_text("untrusted comment: x\nAAAA\n").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::curves::NamedCurve;
+use crate::encoding;
+use crate::Point;
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use aes::Aes256;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use num_bigint::BigUint;
+use sha2::Sha256;
+
+// Zheng's signcryption (SCS1, "Digital signcryption or how to achieve cost(signature &
+// encryption) << cost(signature) + cost(encryption)", 1997) on a named curve
+//
+// Sender (a, A = aG) to recipient (b, B = bG):
+//   x random, K = xB, (k1, k2) = KDF(K, A, B)
+//   c = Enc_k1(m), r = MAC_k2(m) mod n, s = x / (r + a) mod n
+// The recipient recovers K = (s b)(A + rG), since s (a + r) = x, decrypts c and checks r.
+// One scalar multiplication each way and an artifact of |m| + 2|n| bytes, where
+// sign-then-encrypt needs an ECDSA signature plus an ECIES ephemeral key on top.
+// The KDF input carries both public keys, so the artifact is bound to one sender and one
+// recipient and can't be re-signcrypted or redirected. Unlike a signature, origin is
+// only proven to the recipient: checking it needs b
+//
+// Here the KDF is HKDF-SHA256 over x(K), Enc is AES-256-CTR (the key is used once, so the IV
+// is fixed) and the MAC is HMAC-SHA256. Encoded as r || s || c, r and s at the order's size
+
+const KDF_INFO: &[u8] = b"zheng signcryption";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signcrypted {
+    pub r: BigUint,
+    pub s: BigUint,
+    pub ciphertext: Vec<u8>,
+}
+
+// (encryption key, MAC key) from K and both parties' keys
+fn derive_keys(curve: &NamedCurve, k: &Point, sender: &Point, recipient: &Point) -> Result<([u8; 32], [u8; 32]), &'static str> {
+    let Point::Coordinate(x, _) = k else {
+        return Err("shared point is the point at infinity");
+    };
+    let info = [
+        KDF_INFO,
+        &encoding::encode_point(&curve.curve, sender, true),
+        &encoding::encode_point(&curve.curve, recipient, true),
+    ]
+    .concat();
+    let mut okm = [0u8; 64];
+    Hkdf::<Sha256>::new(None, &encoding::to_fixed_bytes(x, curve.field_size())).expand(&info, &mut okm).unwrap();
+    Ok((okm[..32].try_into().unwrap(), okm[32..].try_into().unwrap()))
+}
+
+fn apply_keystream(key: &[u8; 32], data: &mut [u8]) {
+    ctr::Ctr128BE::<Aes256>::new(key.into(), &[0u8; 16].into()).apply_keystream(data);
+}
+
+fn mac_scalar(curve: &NamedCurve, key: &[u8; 32], message: &[u8]) -> BigUint {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).unwrap();
+    mac.update(message);
+    BigUint::from_bytes_be(&mac.finalize().into_bytes()) % &curve.order
+}
+
+pub fn signcrypt(curve: &NamedCurve, sender_private_key: &BigUint, recipient_public_key: &Point, message: &[u8]) -> Result<Signcrypted, &'static str> {
+    let ecdh = curve.ecdh();
+    ecdh.validate_public_key(recipient_public_key)?;
+    let sender_public_key = ecdh.generate_public_key(sender_private_key);
+    loop {
+        let x = Scalar::random(&curve.order, &mut Entropy).into_value();
+        let k = curve.curve.scalar_mult(recipient_public_key, &x);
+        let (enc_key, mac_key) = derive_keys(curve, &k, &sender_public_key, recipient_public_key)?;
+        let r = mac_scalar(curve, &mac_key, message);
+        // r + a has to be invertible and s nonzero; both fail with negligible probability
+        let Some(inverse) = ((&r + sender_private_key) % &curve.order).modinv(&curve.order) else {
+            continue;
+        };
+        let s = (x * inverse) % &curve.order;
+        if s == BigUint::from(0u32) || r == BigUint::from(0u32) {
+            continue;
+        }
+        let mut ciphertext = message.to_vec();
+        apply_keystream(&enc_key, &mut ciphertext);
+        return Ok(Signcrypted { r, s, ciphertext });
+    }
+}
+
+// The message, if it was signcrypted by sender_public_key for this recipient
+pub fn unsigncrypt(curve: &NamedCurve, recipient_private_key: &BigUint, sender_public_key: &Point, signcrypted: &Signcrypted) -> Result<Vec<u8>, &'static str> {
+    let ecdh = curve.ecdh();
+    ecdh.validate_public_key(sender_public_key)?;
+    let zero = BigUint::from(0u32);
+    if signcrypted.r == zero || signcrypted.r >= curve.order || signcrypted.s == zero || signcrypted.s >= curve.order {
+        return Err("signcryption scalars out of range");
+    }
+    let u = (&signcrypted.s * recipient_private_key) % &curve.order;
+    let k = curve.curve.scalar_mult(&curve.curve.add(sender_public_key, &curve.curve.scalar_mult(&curve.generator, &signcrypted.r)), &u);
+    let recipient_public_key = ecdh.generate_public_key(recipient_private_key);
+    let (enc_key, mac_key) = derive_keys(curve, &k, sender_public_key, &recipient_public_key)?;
+
+    let mut message = signcrypted.ciphertext.clone();
+    apply_keystream(&enc_key, &mut message);
+    if mac_scalar(curve, &mac_key, &message) != signcrypted.r {
+        return Err("signcryption verification failed");
+    }
+    Ok(message)
+}
+
+impl Signcrypted {
+    pub fn to_bytes(&self, curve: &NamedCurve) -> Vec<u8> {
+        let len = curve.scalar_size();
+        [encoding::to_fixed_bytes(&self.r, len), encoding::to_fixed_bytes(&self.s, len), self.ciphertext.clone()].concat()
+    }
+
+    pub fn from_bytes(curve: &NamedCurve, bytes: &[u8]) -> Result<Self, &'static str> {
+        let len = curve.scalar_size();
+        if bytes.len() < 2 * len {
+            return Err("truncated signcryption");
+        }
+        Ok(Signcrypted {
+            r: BigUint::from_bytes_be(&bytes[..len]),
+            s: BigUint::from_bytes_be(&bytes[len..2 * len]),
+            ciphertext: bytes[2 * len..].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    #[test]
+    fn test_signcrypt_unsigncrypt() {
+        for curve in [curves::p256(), curves::secp256k1(), curves::p521()] {
+            let ecdh = curve.ecdh();
+            let (alice, bob) = (ecdh.generate_keypair(), ecdh.generate_keypair());
+            let message = b"meet at the usual place";
+
+            let signcrypted = signcrypt(&curve, &alice.private_key, &bob.public_key, message).unwrap();
+            let bytes = signcrypted.to_bytes(&curve);
+            assert_eq!(bytes.len(), message.len() + 2 * curve.scalar_size());
+            assert_ne!(&bytes[2 * curve.scalar_size()..], message);
+            let parsed = Signcrypted::from_bytes(&curve, &bytes).unwrap();
+            assert_eq!(unsigncrypt(&curve, &bob.private_key, &alice.public_key, &parsed).unwrap(), message);
+
+            // Someone else as the claimed sender, or as the recipient
+            let mallory = ecdh.generate_keypair();
+            assert!(unsigncrypt(&curve, &bob.private_key, &mallory.public_key, &parsed).is_err());
+            assert!(unsigncrypt(&curve, &mallory.private_key, &alice.public_key, &parsed).is_err());
+        }
+    }
+
+    #[test]
+    fn test_tampering() {
+        let curve = curves::p256();
+        let ecdh = curve.ecdh();
+        let (alice, bob) = (ecdh.generate_keypair(), ecdh.generate_keypair());
+        let signcrypted = signcrypt(&curve, &alice.private_key, &bob.public_key, b"transfer 10").unwrap();
+
+        let mut flipped = signcrypted.clone();
+        flipped.ciphertext[9] ^= 0x01;
+        assert!(unsigncrypt(&curve, &bob.private_key, &alice.public_key, &flipped).is_err());
+        let mut bumped = signcrypted.clone();
+        bumped.s += 1u32;
+        assert!(unsigncrypt(&curve, &bob.private_key, &alice.public_key, &bumped).is_err());
+        let mut unreduced = signcrypted.clone();
+        unreduced.r += &curve.order;
+        assert!(unsigncrypt(&curve, &bob.private_key, &alice.public_key, &unreduced).is_err());
+
+        assert!(signcrypt(&curve, &alice.private_key, &Point::Identity, b"x").is_err());
+        assert!(Signcrypted::from_bytes(&curve, &[0u8; 63]).is_err());
+    }
+}
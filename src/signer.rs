@@ -0,0 +1,214 @@
+use crate::ecdsa::ECDSASignature;
+use crate::ed25519::{Ed25519, Ed25519Signature};
+use crate::public_key::PublicKey;
+use crate::schnorr::SchnorrSignature;
+use crate::secret_key::SecretKey;
+use sha2::Digest;
+
+// The RustCrypto `signature` traits on this crate's keys, so they can be passed to code written
+// against Signer<S> / Verifier<S> instead of calling ECDSA, Schnorr or Ed25519 directly
+//   SecretKey             Signer<ECDSASignature>, Signer<SchnorrSignature>,
+//                         DigestSigner<D, ECDSASignature>, Keypair
+//   VerifyingKey          (= PublicKey) Verifier and DigestVerifier for the same signatures
+//   Ed25519SigningKey     Signer<Ed25519Signature>, Keypair
+//   Ed25519VerifyingKey   Verifier<Ed25519Signature>
+// Signing messages hashes with SHA-256 like ECDSA::sign; DigestSigner takes any hash, e.g.
+// Sha384 for P-384, truncated to the order's length, and the hazmat PrehashSigner takes the
+// finished digest bytes (ECDSA::sign_prehashed). The crate's &'static str errors are carried
+// as the source of signature::Error
+
+pub use signature::hazmat::{PrehashSigner, PrehashVerifier};
+pub use signature::{DigestSigner, DigestVerifier, Error, Keypair, Signer, Verifier};
+
+fn error(message: &'static str) -> Error {
+    Error::from_source(message)
+}
+
+// The signature crate's name for a public key
+pub type VerifyingKey = PublicKey;
+
+impl Keypair for SecretKey {
+    type VerifyingKey = VerifyingKey;
+
+    fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey { curve: self.curve().clone(), point: self.public_key() }
+    }
+}
+
+impl Signer<ECDSASignature> for SecretKey {
+    fn try_sign(&self, message: &[u8]) -> Result<ECDSASignature, Error> {
+        self.curve().ecdsa().sign(message, self.scalar()).map_err(error)
+    }
+}
+
+impl<D: Digest> DigestSigner<D, ECDSASignature> for SecretKey {
+    fn try_sign_digest(&self, digest: D) -> Result<ECDSASignature, Error> {
+        self.curve().ecdsa().sign_digest(&digest.finalize(), self.scalar()).map_err(error)
+    }
+}
+
+impl PrehashSigner<ECDSASignature> for SecretKey {
+    fn sign_prehash(&self, prehash: &[u8]) -> Result<ECDSASignature, Error> {
+        self.curve().ecdsa().sign_prehashed(prehash, self.scalar()).map_err(error)
+    }
+}
+
+impl Signer<SchnorrSignature> for SecretKey {
+    fn try_sign(&self, message: &[u8]) -> Result<SchnorrSignature, Error> {
+        Ok(self.curve().schnorr().sign(message, self.scalar()))
+    }
+}
+
+impl Verifier<ECDSASignature> for VerifyingKey {
+    fn verify(&self, message: &[u8], signature: &ECDSASignature) -> Result<(), Error> {
+        match self.curve.ecdsa().verify(message, signature, &self.point) {
+            true => Ok(()),
+            false => Err(error("invalid ECDSA signature")),
+        }
+    }
+}
+
+impl<D: Digest> DigestVerifier<D, ECDSASignature> for VerifyingKey {
+    fn verify_digest(&self, digest: D, signature: &ECDSASignature) -> Result<(), Error> {
+        match self.curve.ecdsa().verify_digest(&digest.finalize(), signature, &self.point) {
+            true => Ok(()),
+            false => Err(error("invalid ECDSA signature")),
+        }
+    }
+}
+
+impl PrehashVerifier<ECDSASignature> for VerifyingKey {
+    fn verify_prehash(&self, prehash: &[u8], signature: &ECDSASignature) -> Result<(), Error> {
+        match self.curve.ecdsa().verify_prehashed(prehash, signature, &self.point) {
+            true => Ok(()),
+            false => Err(error("invalid ECDSA signature")),
+        }
+    }
+}
+
+impl Verifier<SchnorrSignature> for VerifyingKey {
+    fn verify(&self, message: &[u8], signature: &SchnorrSignature) -> Result<(), Error> {
+        match self.curve.schnorr().verify(message, signature, &self.point) {
+            true => Ok(()),
+            false => Err(error("invalid Schnorr signature")),
+        }
+    }
+}
+
+// The 32-byte Ed25519 secret (RFC 8032 5.1.5), not the clamped scalar
+#[derive(Clone)]
+pub struct Ed25519SigningKey {
+    secret: [u8; 32],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ed25519VerifyingKey(pub [u8; 32]);
+
+impl Ed25519SigningKey {
+    pub fn from_bytes(secret: &[u8; 32]) -> Self {
+        Ed25519SigningKey { secret: *secret }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.secret
+    }
+}
+
+impl std::fmt::Debug for Ed25519SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Ed25519SigningKey(..)")
+    }
+}
+
+impl Keypair for Ed25519SigningKey {
+    type VerifyingKey = Ed25519VerifyingKey;
+
+    fn verifying_key(&self) -> Ed25519VerifyingKey {
+        Ed25519VerifyingKey(Ed25519::new().public_key(&self.secret))
+    }
+}
+
+impl Signer<Ed25519Signature> for Ed25519SigningKey {
+    fn try_sign(&self, message: &[u8]) -> Result<Ed25519Signature, Error> {
+        Ok(Ed25519::new().sign(&self.secret, message))
+    }
+}
+
+impl Verifier<Ed25519Signature> for Ed25519VerifyingKey {
+    fn verify(&self, message: &[u8], signature: &Ed25519Signature) -> Result<(), Error> {
+        match Ed25519::new().verify(&self.0, message, signature) {
+            true => Ok(()),
+            false => Err(error("invalid Ed25519 signature")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use crate::Point;
+    use sha2::{Sha256, Sha384};
+
+    // Written only against the traits, the way a generic caller would
+    fn sign_and_verify<S, K>(key: &K, message: &[u8]) -> Result<(), Error>
+    where
+        K: Signer<S> + Keypair,
+        K::VerifyingKey: Verifier<S>,
+    {
+        let signature = key.try_sign(message)?;
+        key.verifying_key().verify(message, &signature)
+    }
+
+    #[test]
+    fn test_generic_signers() {
+        let key = SecretKey::random(&curves::p256());
+        assert!(sign_and_verify::<ECDSASignature, _>(&key, b"hello").is_ok());
+        assert!(sign_and_verify::<SchnorrSignature, _>(&key, b"hello").is_ok());
+        let ed = Ed25519SigningKey::from_bytes(&[7u8; 32]);
+        assert!(sign_and_verify::<Ed25519Signature, _>(&ed, b"hello").is_ok());
+
+        // Same results as the direct APIs
+        let signature: ECDSASignature = key.sign(b"hello");
+        assert!(curves::p256().ecdsa().verify(b"hello", &signature, &key.public_key()));
+        let signature: Ed25519Signature = ed.sign(b"hello");
+        assert_eq!(signature, Ed25519::new().sign(&[7u8; 32], b"hello"));
+
+        // Wrong message or key
+        let verifying_key = key.verifying_key();
+        let signature: ECDSASignature = key.sign(b"hello");
+        assert!(verifying_key.verify(b"hellO", &signature).is_err());
+        let other = SecretKey::random(&curves::p256()).verifying_key();
+        assert!(other.verify(b"hello", &signature).is_err());
+        let schnorr: SchnorrSignature = key.sign(b"hello");
+        assert!(other.verify(b"hello", &schnorr).is_err());
+        let ed_signature: Ed25519Signature = ed.sign(b"hello");
+        assert!(Ed25519SigningKey::from_bytes(&[8u8; 32]).verifying_key().verify(b"hello", &ed_signature).is_err());
+    }
+
+    #[test]
+    fn test_digest_signer() {
+        // SHA-384 with P-384, checked against the digest API
+        let curve = curves::p384();
+        let key = SecretKey::random(&curve);
+        let signature: ECDSASignature = key.sign_digest(Sha384::new_with_prefix(b"streamed"));
+        assert!(curve.ecdsa().verify_digest(&Sha384::digest(b"streamed"), &signature, &key.public_key()));
+        let verifying_key = key.verifying_key();
+        assert!(verifying_key.verify_digest(Sha384::new_with_prefix(b"streamed"), &signature).is_ok());
+        assert!(verifying_key.verify_digest(Sha384::new_with_prefix(b"streamed!"), &signature).is_err());
+
+        // With SHA-256 the digest and message forms agree
+        let signature: ECDSASignature = key.sign_digest(Sha256::new_with_prefix(b"abc"));
+        assert!(verifying_key.verify(b"abc", &signature).is_ok());
+
+        // Prehash traits: the bytes are the digest, not a message
+        let prehash = Sha384::digest(b"streamed");
+        let signature = key.sign_prehash(&prehash).unwrap();
+        assert!(verifying_key.verify_prehash(&prehash, &signature).is_ok());
+        assert!(verifying_key.verify_digest(Sha384::new_with_prefix(b"streamed"), &signature).is_ok());
+        assert!(key.sign_prehash(&prehash[..8]).is_err());
+
+        assert!(VerifyingKey::new(&curve, Point::Identity).is_err());
+        assert!(VerifyingKey::new(&curve, curves::p256().generator).is_err());
+    }
+}
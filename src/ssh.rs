@@ -0,0 +1,540 @@
+use crate::curves::{self, NamedCurve};
+use crate::ecdsa::ECDSASignature;
+use crate::ed25519::{Ed25519, Ed25519Signature};
+use crate::encoding;
+use crate::Point;
+use crate::entropy::Entropy;
+use aes::Aes256;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use num_bigint::BigUint;
+use rand::RngCore;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+// OpenSSH keys: ecdsa-sha2-nistp256/384/521 (RFC 5656) and ssh-ed25519 (RFC 8709)
+//
+// Everything is built from the RFC 4251 wire types: uint32, string (uint32 length || bytes)
+// and mpint (a string holding a minimal big-endian two's-complement integer). Public key blobs:
+//   ecdsa-sha2-nistpNNN  string "ecdsa-sha2-nistpNNN" || string "nistpNNN" || string Q (SEC1 uncompressed)
+//   ssh-ed25519          string "ssh-ed25519" || string A (32 bytes)
+// and .pub / authorized_keys lines are "<type> <base64 blob> <comment>"
+//
+// Private keys are in the openssh-key-v1 container (PROTOCOL.key in the OpenSSH sources):
+//   "openssh-key-v1\0" || string cipher || string kdf || string kdf options || uint32 1
+//   || string public blob || string private section
+// with the private section, encrypted when there's a passphrase,
+//   uint32 check || uint32 check || string type || key fields || string comment || 1, 2, 3, ...
+// padded to the cipher block size. The two check words are a random value written twice: if
+// they differ after decryption the passphrase was wrong. Encryption is what ssh-keygen does by
+// default, aes256-ctr with key || IV = bcrypt_pbkdf(passphrase, salt, rounds)
+
+const AUTH_MAGIC: &[u8] = b"openssh-key-v1\0";
+const PRIVATE_KEY_LABEL: &str = "OPENSSH PRIVATE KEY";
+const KDF_ROUNDS: u32 = 16;
+
+type Aes256Ctr = ctr::Ctr128BE<Aes256>;
+
+pub fn write_u32(out: &mut Vec<u8>, x: u32) {
+    out.extend_from_slice(&x.to_be_bytes());
+}
+
+pub fn write_string(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_u32(out, bytes.len() as u32);
+    out.extend_from_slice(bytes);
+}
+
+// Non-negative values only: zero is the empty string, a set top bit gets a 0x00 in front
+pub fn write_mpint(out: &mut Vec<u8>, x: &BigUint) {
+    let mut bytes = if *x == BigUint::from(0u32) { Vec::new() } else { x.to_bytes_be() };
+    if bytes.first().is_some_and(|b| b & 0x80 != 0) {
+        bytes.insert(0, 0);
+    }
+    write_string(out, &bytes);
+}
+
+// Reads wire types one after another from a byte string
+pub struct SshReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SshReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        SshReader { bytes, pos: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    pub fn finish(&self) -> Result<(), &'static str> {
+        if self.is_empty() { Ok(()) } else { Err("trailing data after SSH value") }
+    }
+
+    // Whatever hasn't been read yet
+    pub fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.bytes[self.pos..];
+        self.pos = self.bytes.len();
+        rest
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        let end = self.pos.checked_add(len).ok_or("truncated SSH value")?;
+        let bytes = self.bytes.get(self.pos..end).ok_or("truncated SSH value")?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, &'static str> {
+        Ok(u32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    pub fn read_string(&mut self) -> Result<&'a [u8], &'static str> {
+        let len = self.read_u32()? as usize;
+        self.read_bytes(len)
+    }
+
+    pub fn read_str(&mut self) -> Result<&'a str, &'static str> {
+        std::str::from_utf8(self.read_string()?).map_err(|_| "SSH string is not UTF-8")
+    }
+
+    // Strict like DER integers: non-negative and minimal
+    pub fn read_mpint(&mut self) -> Result<BigUint, &'static str> {
+        let bytes = self.read_string()?;
+        match bytes {
+            [] => Ok(BigUint::from(0u32)),
+            [first, ..] if first & 0x80 != 0 => Err("negative mpint"),
+            [0] | [0, 0x00..=0x7f, ..] => Err("non-minimal mpint"),
+            _ => Ok(BigUint::from_bytes_be(bytes)),
+        }
+    }
+}
+
+// The curves OpenSSH has names for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SshCurve {
+    NistP256,
+    NistP384,
+    NistP521,
+}
+
+impl SshCurve {
+    const ALL: [SshCurve; 3] = [SshCurve::NistP256, SshCurve::NistP384, SshCurve::NistP521];
+
+    // Curve identifier inside the key blob
+    pub fn identifier(self) -> &'static str {
+        match self {
+            SshCurve::NistP256 => "nistp256",
+            SshCurve::NistP384 => "nistp384",
+            SshCurve::NistP521 => "nistp521",
+        }
+    }
+
+    pub fn key_type(self) -> &'static str {
+        match self {
+            SshCurve::NistP256 => "ecdsa-sha2-nistp256",
+            SshCurve::NistP384 => "ecdsa-sha2-nistp384",
+            SshCurve::NistP521 => "ecdsa-sha2-nistp521",
+        }
+    }
+
+    pub fn named_curve(self) -> NamedCurve {
+        match self {
+            SshCurve::NistP256 => curves::p256(),
+            SshCurve::NistP384 => curves::p384(),
+            SshCurve::NistP521 => curves::p521(),
+        }
+    }
+
+    fn from_key_type(key_type: &str) -> Option<Self> {
+        SshCurve::ALL.into_iter().find(|curve| curve.key_type() == key_type)
+    }
+
+    // The hash ECDSA signatures use on this curve (RFC 5656 6.2.1)
+    pub(crate) fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            SshCurve::NistP256 => Sha256::digest(data).to_vec(),
+            SshCurve::NistP384 => Sha384::digest(data).to_vec(),
+            SshCurve::NistP521 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SshPublicKey {
+    Ecdsa(SshCurve, Point),
+    Ed25519([u8; 32]),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SshPrivateKey {
+    Ecdsa(SshCurve, BigUint),
+    // The 32-byte secret, not the expanded scalar
+    Ed25519([u8; 32]),
+}
+
+impl SshPublicKey {
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            SshPublicKey::Ecdsa(curve, _) => curve.key_type(),
+            SshPublicKey::Ed25519(_) => "ssh-ed25519",
+        }
+    }
+
+    pub fn to_blob(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_string(&mut out, self.algorithm().as_bytes());
+        match self {
+            SshPublicKey::Ecdsa(curve, point) => {
+                write_string(&mut out, curve.identifier().as_bytes());
+                write_string(&mut out, &encoding::encode_point(&curve.named_curve().curve, point, false));
+            }
+            SshPublicKey::Ed25519(key) => write_string(&mut out, key),
+        }
+        out
+    }
+
+    // The point is validated (on the curve, not the identity) so a parsed key is safe to use
+    pub fn from_blob(blob: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = SshReader::new(blob);
+        let algorithm = reader.read_str()?;
+        let key = if algorithm == "ssh-ed25519" {
+            let key: [u8; 32] = reader.read_string()?.try_into().map_err(|_| "Ed25519 keys are 32 bytes")?;
+            Ed25519::new().decode(&key)?;
+            SshPublicKey::Ed25519(key)
+        } else {
+            let curve = SshCurve::from_key_type(algorithm).ok_or("unsupported SSH key type")?;
+            if reader.read_str()? != curve.identifier() {
+                return Err("curve does not match key type");
+            }
+            let point = encoding::decode_point(&curve.named_curve().curve, reader.read_string()?)?;
+            if point == Point::Identity {
+                return Err("public key is the point at infinity");
+            }
+            SshPublicKey::Ecdsa(curve, point)
+        };
+        reader.finish()?;
+        Ok(key)
+    }
+
+    // One .pub / authorized_keys line, without the trailing newline
+    pub fn to_openssh(&self, comment: &str) -> String {
+        let line = format!("{} {}", self.algorithm(), encoding::to_base64(&self.to_blob()));
+        if comment.is_empty() { line } else { format!("{} {}", line, comment) }
+    }
+
+    // (key, comment) from a .pub line; authorized_keys options in front aren't supported
+    pub fn from_openssh(line: &str) -> Result<(Self, String), &'static str> {
+        let mut parts = line.trim().splitn(3, ' ');
+        let algorithm = parts.next().ok_or("empty SSH public key line")?;
+        let blob = encoding::from_base64(parts.next().ok_or("missing SSH key data")?)?;
+        let key = SshPublicKey::from_blob(&blob)?;
+        if key.algorithm() != algorithm {
+            return Err("key type does not match key data");
+        }
+        Ok((key, parts.next().unwrap_or("").trim().to_string()))
+    }
+
+    // Check an SSH signature blob from SshPrivateKey::sign
+    pub fn verify(&self, data: &[u8], signature: &[u8]) -> bool {
+        let mut reader = SshReader::new(signature);
+        let (Ok(algorithm), Ok(raw)) = (reader.read_str(), reader.read_string()) else {
+            return false;
+        };
+        if algorithm != self.algorithm() || !reader.is_empty() {
+            return false;
+        }
+        match self {
+            SshPublicKey::Ecdsa(curve, point) => {
+                let mut raw = SshReader::new(raw);
+                let (Ok(r), Ok(s)) = (raw.read_mpint(), raw.read_mpint()) else {
+                    return false;
+                };
+                raw.is_empty() && curve.named_curve().ecdsa().verify_digest(&curve.digest(data), &ECDSASignature { r, s }, point)
+            }
+            SshPublicKey::Ed25519(key) => match Ed25519Signature::from_bytes(raw) {
+                Ok(signature) => Ed25519::new().verify(key, data, &signature),
+                Err(_) => false,
+            },
+        }
+    }
+
+    // SHA256:<unpadded base64 of the blob's hash>, as printed by ssh-keygen -l
+    pub fn fingerprint(&self) -> String {
+        let hash = encoding::to_base64(&Sha256::digest(self.to_blob()));
+        format!("SHA256:{}", hash.trim_end_matches('='))
+    }
+}
+
+impl SshPrivateKey {
+    pub fn public_key(&self) -> SshPublicKey {
+        match self {
+            SshPrivateKey::Ecdsa(curve, d) => SshPublicKey::Ecdsa(*curve, curve.named_curve().ecdsa().generate_public_key(d)),
+            SshPrivateKey::Ed25519(secret) => SshPublicKey::Ed25519(Ed25519::new().public_key(secret)),
+        }
+    }
+
+    // SSH signature blob (RFC 5656 3.1.2, RFC 8709 6): string key type || string signature, the
+    // signature being mpint r || mpint s for ECDSA and the raw 64 bytes for Ed25519
+    pub fn sign(&self, data: &[u8]) -> Vec<u8> {
+        let mut raw = Vec::new();
+        match self {
+            SshPrivateKey::Ecdsa(curve, d) => {
+                let signature = curve.named_curve().ecdsa().sign_digest(&curve.digest(data), d).unwrap();
+                write_mpint(&mut raw, &signature.r);
+                write_mpint(&mut raw, &signature.s);
+            }
+            SshPrivateKey::Ed25519(secret) => raw.extend_from_slice(&Ed25519::new().sign(secret, data).to_bytes()),
+        }
+        let mut out = Vec::new();
+        write_string(&mut out, self.public_key().algorithm().as_bytes());
+        write_string(&mut out, &raw);
+        out
+    }
+
+    // The private key file; an empty passphrase means no encryption, as with ssh-keygen -N ""
+    pub fn to_openssh(&self, comment: &str, passphrase: Option<&str>) -> String {
+        let mut rng = Entropy;
+        let public = self.public_key();
+        let passphrase = passphrase.filter(|p| !p.is_empty());
+
+        let check = rng.next_u32();
+        let mut private = Vec::new();
+        write_u32(&mut private, check);
+        write_u32(&mut private, check);
+        write_string(&mut private, public.algorithm().as_bytes());
+        match (self, &public) {
+            (SshPrivateKey::Ecdsa(curve, d), SshPublicKey::Ecdsa(_, point)) => {
+                write_string(&mut private, curve.identifier().as_bytes());
+                write_string(&mut private, &encoding::encode_point(&curve.named_curve().curve, point, false));
+                write_mpint(&mut private, d);
+            }
+            (SshPrivateKey::Ed25519(secret), SshPublicKey::Ed25519(key)) => {
+                write_string(&mut private, key);
+                write_string(&mut private, &[&secret[..], &key[..]].concat());
+            }
+            _ => unreachable!(),
+        }
+        write_string(&mut private, comment.as_bytes());
+        let block = if passphrase.is_some() { 16 } else { 8 };
+        let mut pad = 1u8;
+        while !private.len().is_multiple_of(block) {
+            private.push(pad);
+            pad += 1;
+        }
+
+        let mut out = AUTH_MAGIC.to_vec();
+        match passphrase {
+            None => {
+                write_string(&mut out, b"none");
+                write_string(&mut out, b"none");
+                write_string(&mut out, b"");
+            }
+            Some(passphrase) => {
+                let mut salt = [0u8; 16];
+                rng.fill_bytes(&mut salt);
+                let mut options = Vec::new();
+                write_string(&mut options, &salt);
+                write_u32(&mut options, KDF_ROUNDS);
+                cipher(passphrase, &salt, KDF_ROUNDS).unwrap().apply_keystream(&mut private);
+                write_string(&mut out, b"aes256-ctr");
+                write_string(&mut out, b"bcrypt");
+                write_string(&mut out, &options);
+            }
+        }
+        write_u32(&mut out, 1);
+        write_string(&mut out, &public.to_blob());
+        write_string(&mut out, &private);
+
+        armor(PRIVATE_KEY_LABEL, &out)
+    }
+
+    // (key, comment) from a private key file
+    pub fn from_openssh(pem: &str, passphrase: Option<&str>) -> Result<(Self, String), &'static str> {
+        let bytes = dearmor(PRIVATE_KEY_LABEL, pem)?;
+        let mut reader = SshReader::new(bytes.strip_prefix(AUTH_MAGIC).ok_or("not an openssh-key-v1 container")?);
+        let cipher_name = reader.read_str()?;
+        let kdf_name = reader.read_str()?;
+        let kdf_options = reader.read_string()?;
+        if reader.read_u32()? != 1 {
+            return Err("only single-key files are supported");
+        }
+        let public_blob = reader.read_string()?;
+        let mut private = reader.read_string()?.to_vec();
+        reader.finish()?;
+
+        let block = match (cipher_name, kdf_name) {
+            ("none", "none") if kdf_options.is_empty() => 8,
+            ("aes256-ctr", "bcrypt") => {
+                let passphrase = passphrase.filter(|p| !p.is_empty()).ok_or("key is encrypted, passphrase required")?;
+                let mut options = SshReader::new(kdf_options);
+                let salt = options.read_string()?;
+                let rounds = options.read_u32()?;
+                options.finish()?;
+                cipher(passphrase, salt, rounds)?.apply_keystream(&mut private);
+                16
+            }
+            _ => return Err("unsupported cipher or KDF"),
+        };
+        if !private.len().is_multiple_of(block) {
+            return Err("private section is not padded to the block size");
+        }
+
+        let mut section = SshReader::new(&private);
+        let (check1, check2) = (section.read_u32()?, section.read_u32()?);
+        if check1 != check2 {
+            return Err("wrong passphrase or corrupt key");
+        }
+        let algorithm = section.read_str()?;
+        let (key, inner_public) = if algorithm == "ssh-ed25519" {
+            let public: [u8; 32] = section.read_string()?.try_into().map_err(|_| "Ed25519 keys are 32 bytes")?;
+            let secret = section.read_string()?;
+            if secret.len() != 64 || secret[32..] != public {
+                return Err("malformed Ed25519 private key");
+            }
+            (SshPrivateKey::Ed25519(secret[..32].try_into().unwrap()), SshPublicKey::Ed25519(public))
+        } else {
+            let curve = SshCurve::from_key_type(algorithm).ok_or("unsupported SSH key type")?;
+            if section.read_str()? != curve.identifier() {
+                return Err("curve does not match key type");
+            }
+            let point = encoding::decode_point(&curve.named_curve().curve, section.read_string()?)?;
+            let d = section.read_mpint()?;
+            if d == BigUint::from(0u32) || d >= curve.named_curve().order {
+                return Err("private key out of range");
+            }
+            (SshPrivateKey::Ecdsa(curve, d), SshPublicKey::Ecdsa(curve, point))
+        };
+        let comment = section.read_str()?.to_string();
+        let padding = section.rest();
+        if padding.iter().enumerate().any(|(i, &b)| b as usize != i + 1) {
+            return Err("invalid private section padding");
+        }
+
+        // Both copies of the public key have to belong to the private key
+        if key.public_key() != inner_public || inner_public.to_blob() != public_blob {
+            return Err("public key does not match private key");
+        }
+        Ok((key, comment))
+    }
+}
+
+// Base64 between BEGIN/END lines, wrapped at 70 columns like ssh-keygen
+pub fn armor(label: &str, bytes: &[u8]) -> String {
+    let body = encoding::to_base64(bytes);
+    let lines: Vec<&str> = body.as_bytes().chunks(70).map(|line| std::str::from_utf8(line).unwrap()).collect();
+    format!("-----BEGIN {}-----\n{}\n-----END {}-----\n", label, lines.join("\n"), label)
+}
+
+pub fn dearmor(label: &str, text: &str) -> Result<Vec<u8>, &'static str> {
+    let body: String = text
+        .trim()
+        .strip_prefix(&format!("-----BEGIN {}-----", label))
+        .and_then(|s| s.strip_suffix(&format!("-----END {}-----", label)))
+        .ok_or("missing or mismatched armor lines")?
+        .split_whitespace()
+        .collect();
+    encoding::from_base64(&body)
+}
+
+// aes256-ctr keyed from bcrypt_pbkdf: 32 bytes of key then 16 of IV
+fn cipher(passphrase: &str, salt: &[u8], rounds: u32) -> Result<Aes256Ctr, &'static str> {
+    let mut key_iv = [0u8; 48];
+    bcrypt_pbkdf::bcrypt_pbkdf(passphrase, salt, rounds, &mut key_iv).map_err(|_| "invalid bcrypt parameters")?;
+    Ok(Aes256Ctr::new(key_iv[..32].into(), key_iv[32..].into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: [(&str, &str, &str); 4] = [
+        (include_str!("../testvectors/openssh/nistp256"), include_str!("../testvectors/openssh/nistp256.pub"), "alice@example"),
+        (include_str!("../testvectors/openssh/nistp384"), include_str!("../testvectors/openssh/nistp384.pub"), ""),
+        (include_str!("../testvectors/openssh/nistp521"), include_str!("../testvectors/openssh/nistp521.pub"), "p521 key"),
+        (include_str!("../testvectors/openssh/ed25519"), include_str!("../testvectors/openssh/ed25519.pub"), "bob@example"),
+    ];
+
+    #[test]
+    fn test_wire_encoding() {
+        // RFC 4251 section 5 mpint examples
+        for (value, encoded) in [("0", "00000000"), ("9a378f9b2e332a7", "0000000809a378f9b2e332a7"), ("80", "000000020080")] {
+            let mut out = Vec::new();
+            write_mpint(&mut out, &BigUint::parse_bytes(value.as_bytes(), 16).unwrap());
+            assert_eq!(encoding::to_hex(&out), encoded);
+            assert_eq!(SshReader::new(&out).read_mpint().unwrap(), BigUint::parse_bytes(value.as_bytes(), 16).unwrap());
+        }
+        assert!(SshReader::new(&[0, 0, 0, 1, 0x80]).read_mpint().is_err());
+        assert!(SshReader::new(&[0, 0, 0, 2, 0, 0x7f]).read_mpint().is_err());
+        assert!(SshReader::new(&[0, 0, 0, 5, 1]).read_string().is_err());
+    }
+
+    #[test]
+    fn test_public_keys() {
+        for (_, line, comment) in FIXTURES {
+            let (key, parsed_comment) = SshPublicKey::from_openssh(line).unwrap();
+            assert_eq!(parsed_comment, comment);
+            assert_eq!(key.to_openssh(comment), line.trim_end());
+        }
+
+        // ssh-keygen -l
+        let (key, _) = SshPublicKey::from_openssh(FIXTURES[0].1).unwrap();
+        assert_eq!(key.fingerprint(), "SHA256:GxcZH7stdPzSjmzNY+dk6V8ax9Tt+DJBDhxgCHLIzTg");
+        let (key, _) = SshPublicKey::from_openssh(FIXTURES[3].1).unwrap();
+        assert_eq!(key.fingerprint(), "SHA256:UcrvOEqJOWrYNpIjTl7aKp51lCUIeU3wSOoRPtFFamM");
+
+        // Mismatched type, and a P-256 point that is off the curve
+        let mismatched = FIXTURES[0].1.replacen("ecdsa-sha2-nistp256", "ssh-ed25519", 1);
+        assert!(SshPublicKey::from_openssh(&mismatched).is_err());
+        let SshPublicKey::Ecdsa(curve, Point::Coordinate(x, y)) = key_of(FIXTURES[0].1) else { panic!() };
+        let off_curve = SshPublicKey::Ecdsa(curve, Point::Coordinate(x, y + 1u32));
+        assert!(SshPublicKey::from_blob(&off_curve.to_blob()).is_err());
+    }
+
+    fn key_of(line: &str) -> SshPublicKey {
+        SshPublicKey::from_openssh(line).unwrap().0
+    }
+
+    #[test]
+    fn test_private_keys() {
+        for (pem, line, comment) in FIXTURES {
+            let (key, parsed_comment) = SshPrivateKey::from_openssh(pem, None).unwrap();
+            assert_eq!(parsed_comment, comment);
+            assert_eq!(key.public_key(), key_of(line));
+
+            let reencoded = key.to_openssh(comment, None);
+            assert_eq!(SshPrivateKey::from_openssh(&reencoded, None).unwrap(), (key.clone(), comment.to_string()));
+            assert!(reencoded.lines().all(|l| l.len() <= 70));
+        }
+    }
+
+    #[test]
+    fn test_signature_blobs() {
+        for (pem, _, _) in FIXTURES {
+            let (key, _) = SshPrivateKey::from_openssh(pem, None).unwrap();
+            let public = key.public_key();
+            let signature = key.sign(b"session id");
+            assert!(public.verify(b"session id", &signature));
+            assert!(!public.verify(b"other session", &signature));
+            assert!(!public.verify(b"session id", &signature[1..]));
+        }
+    }
+
+    #[test]
+    fn test_encrypted_private_keys() {
+        // ssh-keygen -N "correct horse" / -N hunter2: aes256-ctr with bcrypt_pbkdf, 16 rounds
+        let pem = include_str!("../testvectors/openssh/ed25519_encrypted");
+        let (key, comment) = SshPrivateKey::from_openssh(pem, Some("correct horse")).unwrap();
+        assert_eq!(comment, "encrypted");
+        assert_eq!(key.public_key(), key_of(include_str!("../testvectors/openssh/ed25519_encrypted.pub")));
+        assert_eq!(SshPrivateKey::from_openssh(pem, Some("wrong horse")), Err("wrong passphrase or corrupt key"));
+        assert!(SshPrivateKey::from_openssh(pem, None).is_err());
+
+        let pem = include_str!("../testvectors/openssh/nistp256_encrypted");
+        let (key, _) = SshPrivateKey::from_openssh(pem, Some("hunter2")).unwrap();
+        assert_eq!(key.public_key(), key_of(include_str!("../testvectors/openssh/nistp256_encrypted.pub")));
+
+        let encrypted = key.to_openssh("roundtrip", Some("hunter3"));
+        assert_eq!(SshPrivateKey::from_openssh(&encrypted, Some("hunter3")).unwrap(), (key, "roundtrip".to_string()));
+        assert!(SshPrivateKey::from_openssh(&encrypted, Some("hunter2")).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::ecdsa::ECDSASignature;
+use crate::encoding::encode_point;
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+// proptest strategies for the crate's types (feature "proptest"), valid and structured-invalid,
+// for property tests ("add is associative") and for throwing malformed input at the decoders.
+// Valid points are k * G for a random k, so they're always in the generator's subgroup
+
+// Uniform-ish in [0, n): 8 spare bytes keep the bias of the reduction below 2^-64
+pub fn scalar(order: &BigUint) -> impl Strategy<Value = BigUint> + use<> {
+    let order = order.clone();
+    let len = order.bits().div_ceil(8) as usize + 8;
+    vec(any::<u8>(), len).prop_map(move |bytes| BigUint::from_bytes_be(&bytes) % &order)
+}
+
+// [1, n)
+pub fn nonzero_scalar(order: &BigUint) -> impl Strategy<Value = BigUint> + use<> {
+    scalar(&(order - BigUint::from(1u32))).prop_map(|k| k + BigUint::from(1u32))
+}
+
+// A subgroup point other than the identity
+pub fn point(curve: &EllipticCurve, generator: &Point, order: &BigUint) -> impl Strategy<Value = Point> + use<> {
+    let (curve, generator) = (curve.clone(), generator.clone());
+    nonzero_scalar(order).prop_map(move |k| curve.scalar_mult(&generator, &k))
+}
+
+// Points a validating decoder or key check has to reject: off the curve, coordinates that
+// aren't reduced mod p, or the identity
+pub fn invalid_point(curve: &EllipticCurve) -> impl Strategy<Value = Point> + use<> {
+    let p = curve.p().clone();
+    let off_curve = {
+        let curve = curve.clone();
+        (scalar(&p), scalar(&p))
+            .prop_map(|(x, y)| Point::Coordinate(x, y))
+            .prop_filter("on the curve", move |point| !curve.is_on_curve(point))
+    };
+    let unreduced = (scalar(&p), scalar(&p)).prop_map(move |(x, y)| Point::Coordinate(x + &p, y));
+    prop_oneof![
+        4 => off_curve,
+        2 => unreduced,
+        1 => Just(Point::Identity),
+    ]
+}
+
+// r and s in [1, n): well-formed, though almost never a valid signature on anything
+pub fn signature(order: &BigUint) -> impl Strategy<Value = ECDSASignature> + use<> {
+    (nonzero_scalar(order), nonzero_scalar(order)).prop_map(|(r, s)| ECDSASignature { r, s })
+}
+
+// r or s out of range: zero, n, or above n
+pub fn invalid_signature(order: &BigUint) -> impl Strategy<Value = ECDSASignature> + use<> {
+    let n = order.clone();
+    let zero = BigUint::from(0u32);
+    let (n1, n2, n3) = (n.clone(), n.clone(), n.clone());
+    prop_oneof![
+        nonzero_scalar(&n).prop_map({ let zero = zero.clone(); move |s| ECDSASignature { r: zero.clone(), s } }),
+        nonzero_scalar(&n).prop_map(move |r| ECDSASignature { r, s: zero.clone() }),
+        nonzero_scalar(&n).prop_map(move |s| ECDSASignature { r: n1.clone(), s }),
+        (scalar(&n), nonzero_scalar(&n)).prop_map(move |(x, r)| ECDSASignature { r, s: &n2 + x }),
+        (scalar(&n), nonzero_scalar(&n)).prop_map(move |(x, s)| ECDSASignature { r: &n3 + x, s }),
+    ]
+}
+
+// Input for point decoders: encodings of valid points with a byte flipped or cut off,
+// plausible prefixes with random bodies, and plain garbage
+pub fn point_encoding(curve: &EllipticCurve, generator: &Point, order: &BigUint) -> impl Strategy<Value = Vec<u8>> + use<> {
+    let len = crate::encoding::field_size(curve);
+    let mutated = {
+        let curve = curve.clone();
+        (point(&curve, generator, order), any::<bool>(), any::<prop::sample::Index>(), any::<u8>(), any::<bool>())
+            .prop_map(move |(point, compressed, index, flip, truncate)| {
+                let mut bytes = encode_point(&curve, &point, compressed);
+                let i = index.index(bytes.len());
+                if truncate {
+                    bytes.truncate(i);
+                } else {
+                    bytes[i] ^= flip;
+                }
+                bytes
+            })
+    };
+    let prefixed = (0u8..6, prop_oneof![Just(len), Just(2 * len), 0..3 * len])
+        .prop_flat_map(|(prefix, body)| vec(any::<u8>(), body).prop_map(move |rest| [vec![prefix], rest].concat()));
+    prop_oneof![mutated, prefixed, vec(any::<u8>(), 0..100)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use crate::encoding::decode_point;
+
+    // y² = x³ + 2x + 2 (mod 17): 19 points, generated by (5, 1)
+    fn toy() -> (EllipticCurve, Point, BigUint) {
+        let curve = EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32));
+        (curve, Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32)), BigUint::from(19u32))
+    }
+
+    fn toy_point() -> impl Strategy<Value = Point> {
+        let (curve, g, n) = toy();
+        prop_oneof![point(&curve, &g, &n), Just(Point::Identity)]
+    }
+
+    proptest! {
+        #[test]
+        fn add_is_associative_and_commutative(p in toy_point(), q in toy_point(), r in toy_point()) {
+            let (curve, _, _) = toy();
+            prop_assert_eq!(curve.add(&curve.add(&p, &q), &r), curve.add(&p, &curve.add(&q, &r)));
+            prop_assert_eq!(curve.add(&p, &q), curve.add(&q, &p));
+            prop_assert_eq!(curve.add(&p, &curve.neg(&p)), Point::Identity);
+        }
+
+        #[test]
+        fn scalar_mult_distributes(a in scalar(&BigUint::from(19u32)), b in scalar(&BigUint::from(19u32))) {
+            let (curve, g, _) = toy();
+            let sum = curve.add(&curve.scalar_mult(&g, &a), &curve.scalar_mult(&g, &b));
+            prop_assert_eq!(curve.scalar_mult(&g, &(a + b)), sum);
+        }
+
+        #[test]
+        fn invalid_points_fail_validation(point in invalid_point(&toy().0)) {
+            let (curve, g, n) = toy();
+            let ecdh = crate::ecdh::ECDH::new(curve, g, n);
+            prop_assert!(ecdh.validate_public_key(&point).is_err());
+        }
+
+        #[test]
+        fn der_roundtrips(signature in signature(&curves::secp256k1().order)) {
+            prop_assert_eq!(ECDSASignature::from_der(&signature.to_der()).unwrap(), signature);
+        }
+
+        #[test]
+        fn der_decoder_never_panics(bytes in vec(any::<u8>(), 0..80)) {
+            if let Ok(signature) = ECDSASignature::from_der(&bytes) {
+                // Strict DER: anything accepted re-encodes to the same bytes
+                prop_assert_eq!(signature.to_der(), bytes);
+            }
+        }
+
+        #[test]
+        fn invalid_signatures_never_verify(signature in invalid_signature(&BigUint::from(19u32))) {
+            let (curve, g, n) = toy();
+            let ecdsa = crate::ecdsa::ECDSA::new(curve, g.clone(), n);
+            prop_assert!(!ecdsa.verify(b"message", &signature, &g));
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn point_decoder_accepts_only_curve_points(bytes in point_encoding(&curves::secp256k1().curve, &curves::secp256k1().generator, &curves::secp256k1().order)) {
+            let secp = curves::secp256k1();
+            if let Ok(point) = decode_point(&secp.curve, &bytes) {
+                prop_assert!(secp.curve.is_on_curve(&point));
+            }
+        }
+    }
+}
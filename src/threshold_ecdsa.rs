@@ -0,0 +1,321 @@
+use crate::{FiniteField, Point};
+use crate::ecdsa::{hash_to_scalar, ECDSASignature, ECDSA};
+use crate::secret_sharing::{self, Share};
+use num_bigint::BigUint;
+use std::collections::BTreeMap;
+
+// Threshold ECDSA, a simplified honest-majority variant of Gennaro–Jarecki–Krawczyk–Rabin
+//
+// The key x is shared with a degree-t polynomial; any 2t + 1 parties can sign.
+// ECDSA's s = k⁻¹ (z + r x) is not linear in the secrets, so the parties:
+//   round 1: jointly share a random nonce k and a random mask a (each party deals degree-t
+//            sub-shares, with Feldman commitments for k so everyone learns R = k * G),
+//            plus two degree-2t sharings of zero used to re-randomize products
+//   round 2: publish v_i = k_i a_i + z_i, shares of a degree-2t polynomial with constant
+//            k * a; 2t + 1 of them interpolate μ = k a, which reveals nothing about k alone
+//   round 3: k⁻¹_i = a_i μ⁻¹ shares k⁻¹; publish s_i = k⁻¹_i (z + r x_i) + w_i, again degree 2t
+//   combine: interpolate s from 2t + 1 shares; (r, s) verifies with ECDSA::verify
+// Products of two degree-t sharings have degree 2t, which is why 2t + 1 signers are needed.
+// Nonce sub-shares are checked against commitments, but products aren't proven correct:
+// a cheating signer makes the final signature fail verification rather than leak the key
+
+#[derive(Clone, Debug)]
+pub struct ThresholdKeyShare {
+    pub identifier: u32,
+    pub secret_share: BigUint,
+    pub public_key: Point,
+    pub degree: usize,
+}
+
+// Round 1, broadcast: Feldman commitments to the sender's nonce polynomial
+#[derive(Clone, Debug)]
+pub struct NonceCommitment {
+    pub sender: u32,
+    pub commitments: Vec<Point>,
+}
+
+// Round 1, private: the sender's sub-shares for one receiver
+#[derive(Clone, Debug)]
+pub struct NonceDeal {
+    pub sender: u32,
+    pub receiver: u32,
+    pub k_share: BigUint,
+    pub a_share: BigUint,
+    pub zero_share: BigUint,
+    pub second_zero_share: BigUint,
+}
+
+// Round 2, broadcast: v_i = k_i a_i + z_i
+#[derive(Clone, Debug)]
+pub struct MaskedProduct {
+    pub sender: u32,
+    pub value: BigUint,
+}
+
+// Round 3, broadcast: s_i and the r the sender computed
+#[derive(Clone, Debug)]
+pub struct SignatureShare {
+    pub sender: u32,
+    pub r: BigUint,
+    pub s_share: BigUint,
+}
+
+// Trusted-dealer key generation: x shared with a degree-t polynomial among n parties
+// (a DKG run with threshold t + 1 produces equivalent shares)
+pub fn dealer_keygen(ecdsa: &ECDSA, degree: usize, parties: usize) -> Result<Vec<ThresholdKeyShare>, &'static str> {
+    if 2 * degree + 1 > parties {
+        return Err("signing needs 2t + 1 parties, more than exist");
+    }
+    let secret = ecdsa.generate_private_key();
+    let public_key = ecdsa.generate_public_key(&secret);
+    let (shares, _) = secret_sharing::split(&secret, degree + 1, parties, &ecdsa.order)?;
+    Ok(shares
+        .into_iter()
+        .map(|share| ThresholdKeyShare {
+            identifier: share.identifier,
+            secret_share: share.value,
+            public_key: public_key.clone(),
+            degree,
+        })
+        .collect())
+}
+
+// One party's state for one signing session
+pub struct SigningParty<'a> {
+    ecdsa: &'a ECDSA,
+    key: &'a ThresholdKeyShare,
+    signers: Vec<u32>,
+    message_hash: BigUint,
+    nonce_commitments: BTreeMap<u32, Vec<Point>>,
+    deals: BTreeMap<u32, NonceDeal>,
+    products: BTreeMap<u32, BigUint>,
+    dealt: bool,
+}
+
+impl<'a> SigningParty<'a> {
+    pub fn new(ecdsa: &'a ECDSA, key: &'a ThresholdKeyShare, signers: &[u32], message: &[u8]) -> Result<Self, &'static str> {
+        let mut signers = signers.to_vec();
+        signers.sort_unstable();
+        signers.dedup();
+        if signers.len() < 2 * key.degree + 1 {
+            return Err("signing needs at least 2t + 1 parties");
+        }
+        if !signers.contains(&key.identifier) {
+            return Err("party is not among the signers");
+        }
+        Ok(SigningParty {
+            ecdsa,
+            key,
+            signers,
+            message_hash: hash_to_scalar(message, &ecdsa.order),
+            nonce_commitments: BTreeMap::new(),
+            deals: BTreeMap::new(),
+            products: BTreeMap::new(),
+            dealt: false,
+        })
+    }
+
+    // Round 1: commitments to broadcast and one deal per signer (including ourselves)
+    pub fn round1(&mut self) -> Result<(NonceCommitment, Vec<NonceDeal>), &'static str> {
+        if self.dealt {
+            return Err("round 1 already run");
+        }
+        self.dealt = true;
+
+        let order = &self.ecdsa.order;
+        let t = self.key.degree;
+        let zero = BigUint::from(0u32);
+        let k_poly = secret_sharing::random_polynomial(&self.ecdsa.generate_private_key(), t + 1, order);
+        let a_poly = secret_sharing::random_polynomial(&self.ecdsa.generate_private_key(), t + 1, order);
+        let zero_poly = secret_sharing::random_polynomial(&zero, 2 * t + 1, order);
+        let second_zero_poly = secret_sharing::random_polynomial(&zero, 2 * t + 1, order);
+
+        let commitment = NonceCommitment {
+            sender: self.key.identifier,
+            commitments: secret_sharing::commit_polynomial(&self.ecdsa.curve, &self.ecdsa.generator, &k_poly),
+        };
+        let deals = self
+            .signers
+            .iter()
+            .map(|&receiver| {
+                let x = BigUint::from(receiver);
+                NonceDeal {
+                    sender: self.key.identifier,
+                    receiver,
+                    k_share: secret_sharing::evaluate_polynomial(&k_poly, &x, order),
+                    a_share: secret_sharing::evaluate_polynomial(&a_poly, &x, order),
+                    zero_share: secret_sharing::evaluate_polynomial(&zero_poly, &x, order),
+                    second_zero_share: secret_sharing::evaluate_polynomial(&second_zero_poly, &x, order),
+                }
+            })
+            .collect();
+        Ok((commitment, deals))
+    }
+
+    // Round 1: accept a signer's commitments and our deal from it
+    pub fn receive_round1(&mut self, commitment: &NonceCommitment, deal: &NonceDeal) -> Result<(), &'static str> {
+        if commitment.sender != deal.sender || !self.signers.contains(&deal.sender) {
+            return Err("round-1 message from a non-signer");
+        }
+        if deal.receiver != self.key.identifier {
+            return Err("deal addressed to another party");
+        }
+        if commitment.commitments.len() != self.key.degree + 1 {
+            return Err("nonce commitments have the wrong degree");
+        }
+        let share = Share { identifier: self.key.identifier, value: deal.k_share.clone() };
+        if !secret_sharing::verify_share(&self.ecdsa.curve, &self.ecdsa.generator, &share, &commitment.commitments, &self.ecdsa.order) {
+            return Err("nonce share does not match its commitments");
+        }
+        self.nonce_commitments.insert(deal.sender, commitment.commitments.clone());
+        self.deals.insert(deal.sender, deal.clone());
+        Ok(())
+    }
+
+    // Round 2: v_i = k_i a_i + z_i
+    pub fn round2(&mut self) -> Result<MaskedProduct, &'static str> {
+        let (k, a, z, _) = self.summed_shares()?;
+        let field = FiniteField { p: self.ecdsa.order.clone() };
+        Ok(MaskedProduct {
+            sender: self.key.identifier,
+            value: field.add(&field.mul(&k, &a), &z),
+        })
+    }
+
+    pub fn receive_round2(&mut self, product: &MaskedProduct) -> Result<(), &'static str> {
+        if !self.signers.contains(&product.sender) {
+            return Err("round-2 message from a non-signer");
+        }
+        self.products.insert(product.sender, product.value.clone());
+        Ok(())
+    }
+
+    // Round 3: μ = interpolate(v), R = Σ C_j0, s_i = a_i μ⁻¹ (z + r x_i) + w_i
+    pub fn round3(&self) -> Result<SignatureShare, &'static str> {
+        let field = FiniteField { p: self.ecdsa.order.clone() };
+        let (_, a, _, w) = self.summed_shares()?;
+        if self.products.len() != self.signers.len() {
+            return Err("round 2 is not complete");
+        }
+
+        let product_shares: Vec<Share> = self.products.iter().map(|(&i, v)| Share { identifier: i, value: v.clone() }).collect();
+        let mu = secret_sharing::reconstruct(&product_shares, &self.ecdsa.order)?;
+        if mu == BigUint::from(0u32) {
+            return Err("k * a is zero; restart the session");
+        }
+
+        let r_point = self.nonce_commitments.values().fold(Point::Identity, |acc, c| self.ecdsa.curve.add(&acc, &c[0]));
+        let r = match r_point {
+            Point::Coordinate(x, _) => x % &self.ecdsa.order,
+            Point::Identity => return Err("nonce gives point at infinity"),
+        };
+        if r == BigUint::from(0u32) {
+            return Err("nonce gives r = 0");
+        }
+
+        let k_inverse_share = field.div(&a, &mu);
+        let inner = field.add(&self.message_hash, &field.mul(&r, &self.key.secret_share));
+        Ok(SignatureShare {
+            sender: self.key.identifier,
+            r,
+            s_share: field.add(&field.mul(&k_inverse_share, &inner), &w),
+        })
+    }
+
+    // k_i, a_i, z_i, w_i: sums of the sub-shares from every signer
+    fn summed_shares(&self) -> Result<(BigUint, BigUint, BigUint, BigUint), &'static str> {
+        if self.deals.len() != self.signers.len() {
+            return Err("round 1 is not complete");
+        }
+        let field = FiniteField { p: self.ecdsa.order.clone() };
+        let zero = BigUint::from(0u32);
+        Ok(self.deals.values().fold((zero.clone(), zero.clone(), zero.clone(), zero), |(k, a, z, w), deal| {
+            (
+                field.add(&k, &deal.k_share),
+                field.add(&a, &deal.a_share),
+                field.add(&z, &deal.zero_share),
+                field.add(&w, &deal.second_zero_share),
+            )
+        }))
+    }
+}
+
+// Interpolate s from the shares and check the result under the group public key
+pub fn combine(ecdsa: &ECDSA, message: &[u8], public_key: &Point, shares: &[SignatureShare]) -> Result<ECDSASignature, &'static str> {
+    let r = shares.first().ok_or("no signature shares")?.r.clone();
+    if shares.iter().any(|s| s.r != r) {
+        return Err("signers disagree on r");
+    }
+    let s_shares: Vec<Share> = shares.iter().map(|s| Share { identifier: s.sender, value: s.s_share.clone() }).collect();
+    let s = secret_sharing::reconstruct(&s_shares, &ecdsa.order)?;
+
+    let signature = ECDSASignature { r, s };
+    if !ecdsa.verify(message, &signature, public_key) {
+        return Err("combined signature does not verify");
+    }
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    // Runs all three rounds among the given signers, optionally corrupting one share
+    fn run(ecdsa: &ECDSA, keys: &[ThresholdKeyShare], signers: &[u32], message: &[u8], corrupt: bool) -> Result<ECDSASignature, &'static str> {
+        let mut parties: Vec<SigningParty> = signers
+            .iter()
+            .map(|&i| SigningParty::new(ecdsa, &keys[i as usize - 1], signers, message).unwrap())
+            .collect();
+
+        let round1: Vec<(NonceCommitment, Vec<NonceDeal>)> = parties.iter_mut().map(|p| p.round1().unwrap()).collect();
+        for party in parties.iter_mut() {
+            for (commitment, deals) in &round1 {
+                let deal = deals.iter().find(|d| d.receiver == party.key.identifier).unwrap();
+                party.receive_round1(commitment, deal).unwrap();
+            }
+        }
+
+        let round2: Vec<MaskedProduct> = parties.iter_mut().map(|p| p.round2().unwrap()).collect();
+        for party in parties.iter_mut() {
+            for product in &round2 {
+                party.receive_round2(product).unwrap();
+            }
+        }
+
+        let mut shares: Vec<SignatureShare> = parties.iter().map(|p| p.round3().unwrap()).collect();
+        if corrupt {
+            shares[0].s_share += 1u32;
+        }
+        combine(ecdsa, message, &keys[0].public_key, &shares)
+    }
+
+    #[test]
+    fn test_threshold_signature_verifies() {
+        let ecdsa = curves::p256().ecdsa();
+        let keys = dealer_keygen(&ecdsa, 1, 4).unwrap();
+        let message = b"withdraw 1 BTC from cold storage";
+
+        for signers in [[1u32, 2, 3], [2, 3, 4], [1, 3, 4]] {
+            let signature = run(&ecdsa, &keys, &signers, message, false).unwrap();
+            assert!(ecdsa.verify(message, &signature, &keys[0].public_key));
+        }
+    }
+
+    #[test]
+    fn test_threshold_rejects_bad_sessions() {
+        let ecdsa = curves::p256().ecdsa();
+        let keys = dealer_keygen(&ecdsa, 1, 3).unwrap();
+
+        assert_eq!(run(&ecdsa, &keys, &[1, 2, 3], b"msg", true), Err("combined signature does not verify"));
+        assert!(SigningParty::new(&ecdsa, &keys[0], &[1, 2], b"msg").is_err());
+        assert!(dealer_keygen(&ecdsa, 2, 4).is_err());
+
+        // A deal that doesn't match the sender's commitments is refused
+        let mut first = SigningParty::new(&ecdsa, &keys[0], &[1, 2, 3], b"msg").unwrap();
+        let mut second = SigningParty::new(&ecdsa, &keys[1], &[1, 2, 3], b"msg").unwrap();
+        let (commitment, mut deals) = second.round1().unwrap();
+        deals[0].k_share += 1u32;
+        assert!(first.receive_round1(&commitment, &deals[0]).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+use std::fmt;
+
+// Step-by-step versions of add, double and scalar_mult for teaching frontends: the same
+// arithmetic, but every branch taken and every intermediate value is kept in a trace
+//   add/double    which case of the group law applied, the slope λ as numerator / denominator
+//                 and its value, and the result (x3, y3)
+//   scalar_mult   one step per bit of k from the lowest, as scalar_mult runs double-and-add:
+//                 the bit, R = R + A when it's set, then A = 2A
+// Display writes the steps out as formulas with the numbers filled in. Nothing here is
+// constant time, and the trace holds the scalar: keep real keys away from it
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GroupLawCase {
+    // P + O = P, O + Q = Q, 2O = O
+    Identity,
+    // x1 = x2 and y1 = -y2: the vertical line, P + Q = O
+    Inverse,
+    // y = 0 when doubling: the tangent is vertical, 2P = O
+    OrderTwo,
+    Chord,
+    Tangent,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupLawStep {
+    pub case: GroupLawCase,
+    pub p: Point,
+    pub q: Point,
+    // λ = numerator / denominator, for chord and tangent only
+    pub numerator: Option<BigUint>,
+    pub denominator: Option<BigUint>,
+    pub slope: Option<BigUint>,
+    // (x3, y3), or O
+    pub result: Point,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalarStep {
+    pub bit_index: u64,
+    pub bit: bool,
+    // R = R + A, when the bit is set
+    pub addition: Option<GroupLawStep>,
+    // A = 2A
+    pub doubling: GroupLawStep,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalarTrace {
+    pub scalar: BigUint,
+    pub point: Point,
+    pub steps: Vec<ScalarStep>,
+    pub result: Point,
+}
+
+fn coordinates(point: &Point) -> String {
+    match point {
+        Point::Coordinate(x, y) => format!("({}, {})", x, y),
+        Point::Identity => "O".to_string(),
+    }
+}
+
+impl fmt::Display for GroupLawStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (p, q, result) = (coordinates(&self.p), coordinates(&self.q), coordinates(&self.result));
+        let slope = || {
+            let (Some(numerator), Some(denominator), Some(slope)) = (&self.numerator, &self.denominator, &self.slope) else {
+                return String::new();
+            };
+            format!("{} / {} = {}", numerator, denominator, slope)
+        };
+        match self.case {
+            GroupLawCase::Identity => write!(f, "{} + {} = {} (identity)", p, q, result),
+            GroupLawCase::Inverse => write!(f, "{} + {} = O (x1 = x2, y1 = -y2)", p, q),
+            GroupLawCase::OrderTwo => write!(f, "2{} = O (y = 0)", p),
+            GroupLawCase::Chord => write!(
+                f,
+                "{} + {}: λ = (y2 - y1) / (x2 - x1) = {}, x3 = λ² - x1 - x2, y3 = λ(x1 - x3) - y1: {}",
+                p, q, slope(), result
+            ),
+            GroupLawCase::Tangent => write!(f, "2{}: λ = (3x² + a) / 2y = {}, x3 = λ² - 2x, y3 = λ(x - x3) - y: {}", p, slope(), result),
+        }
+    }
+}
+
+impl fmt::Display for ScalarTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} * {}, R = O, A = {}", self.scalar, coordinates(&self.point), coordinates(&self.point))?;
+        for step in &self.steps {
+            writeln!(f, "bit {} = {}", step.bit_index, step.bit as u8)?;
+            if let Some(addition) = &step.addition {
+                writeln!(f, "  R = R + A: {}", addition)?;
+            }
+            writeln!(f, "  A = 2A: {}", step.doubling)?;
+        }
+        write!(f, "result {}", coordinates(&self.result))
+    }
+}
+
+impl EllipticCurve {
+    // (x3, y3) from the slope, as in add and double
+    fn chord_or_tangent(&self, case: GroupLawCase, p: &Point, q: &Point, numerator: BigUint, denominator: BigUint) -> GroupLawStep {
+        let (Point::Coordinate(x1, y1), Point::Coordinate(x2, _)) = (p, q) else {
+            panic!("?chord or tangent through the identity?");
+        };
+        let field = self.field();
+        let slope = field.div(&numerator, &denominator);
+        let x3 = field.sub(&field.sub(&field.mul(&slope, &slope), x1), x2);
+        let y3 = field.sub(&field.mul(&slope, &field.sub(x1, &x3)), y1);
+        GroupLawStep {
+            case,
+            p: p.clone(),
+            q: q.clone(),
+            numerator: Some(numerator),
+            denominator: Some(denominator),
+            slope: Some(slope),
+            result: Point::Coordinate(x3, y3),
+        }
+    }
+
+    fn trivial_step(case: GroupLawCase, p: &Point, q: &Point, result: Point) -> GroupLawStep {
+        GroupLawStep { case, p: p.clone(), q: q.clone(), numerator: None, denominator: None, slope: None, result }
+    }
+
+    // add, recording the case taken and the slope
+    pub fn add_traced(&self, p: &Point, q: &Point) -> GroupLawStep {
+        match (p, q) {
+            (Point::Identity, _) => Self::trivial_step(GroupLawCase::Identity, p, q, q.clone()),
+            (_, Point::Identity) => Self::trivial_step(GroupLawCase::Identity, p, q, p.clone()),
+            (Point::Coordinate(x1, y1), Point::Coordinate(x2, y2)) => {
+                if x1 == x2 {
+                    if y1 == y2 {
+                        return GroupLawStep { q: q.clone(), ..self.double_traced(p) };
+                    }
+                    return Self::trivial_step(GroupLawCase::Inverse, p, q, Point::Identity);
+                }
+                let field = self.field();
+                self.chord_or_tangent(GroupLawCase::Chord, p, q, field.sub(y2, y1), field.sub(x2, x1))
+            }
+        }
+    }
+
+    // double, recording the case taken and the slope; q is P again
+    pub fn double_traced(&self, p: &Point) -> GroupLawStep {
+        let Point::Coordinate(x, y) = p else {
+            return Self::trivial_step(GroupLawCase::Identity, p, p, Point::Identity);
+        };
+        if *y == BigUint::from(0u32) {
+            return Self::trivial_step(GroupLawCase::OrderTwo, p, p, Point::Identity);
+        }
+        let field = self.field();
+        let numerator = field.add(&field.mul(&BigUint::from(3u32), &field.mul(x, x)), self.a());
+        self.chord_or_tangent(GroupLawCase::Tangent, p, p, numerator, field.add(y, y))
+    }
+
+    // scalar_mult's double-and-add, one step per bit of k
+    pub fn scalar_mult_traced(&self, point: &Point, k: &BigUint) -> ScalarTrace {
+        let mut result = Point::Identity;
+        let mut addend = point.clone();
+        let mut steps = Vec::new();
+        for bit_index in 0..k.bits() {
+            let bit = k.bit(bit_index);
+            let addition = bit.then(|| self.add_traced(&result, &addend));
+            if let Some(step) = &addition {
+                result = step.result.clone();
+            }
+            let doubling = self.double_traced(&addend);
+            addend = doubling.result.clone();
+            steps.push(ScalarStep { bit_index, bit, addition, doubling });
+        }
+        ScalarTrace { scalar: k.clone(), point: point.clone(), steps, result }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo_curve() -> EllipticCurve {
+        EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32))
+    }
+
+    #[test]
+    fn test_group_law_cases() {
+        let curve = demo_curve();
+        let g = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        let two_g = curve.double(&g);
+
+        // 2(5, 1): λ = (3·25 + 2) / 2 = 9 / 2 = 13, x3 = 169 - 10 = 6, y3 = 13(5 - 6) - 1 = 3 (mod 17)
+        let doubling = curve.double_traced(&g);
+        assert_eq!(doubling.case, GroupLawCase::Tangent);
+        assert_eq!((doubling.numerator.clone(), doubling.denominator.clone()), (Some(BigUint::from(9u32)), Some(BigUint::from(2u32))));
+        assert_eq!(doubling.slope, Some(BigUint::from(13u32)));
+        assert_eq!(doubling.result, Point::Coordinate(BigUint::from(6u32), BigUint::from(3u32)));
+        assert_eq!(doubling.result, two_g);
+
+        let chord = curve.add_traced(&g, &two_g);
+        assert_eq!(chord.case, GroupLawCase::Chord);
+        assert_eq!(chord.result, curve.add(&g, &two_g));
+        assert!(chord.to_string().contains("λ = (y2 - y1) / (x2 - x1) = 2 / 1 = 2"));
+
+        assert_eq!(curve.add_traced(&g, &g).case, GroupLawCase::Tangent);
+        assert_eq!(curve.add_traced(&g, &curve.neg(&g)).case, GroupLawCase::Inverse);
+        assert_eq!(curve.add_traced(&Point::Identity, &g).result, g);
+        let order_two = EllipticCurve::new(BigUint::from(0u32), BigUint::from(7u32), BigUint::from(23u32));
+        let step = order_two.double_traced(&Point::Coordinate(BigUint::from(9u32), BigUint::from(0u32)));
+        assert_eq!((step.case, step.result), (GroupLawCase::OrderTwo, Point::Identity));
+    }
+
+    #[test]
+    fn test_scalar_mult_trace() {
+        let curve = demo_curve();
+        let g = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        for k in 0u32..40 {
+            let k = BigUint::from(k);
+            let trace = curve.scalar_mult_traced(&g, &k);
+            assert_eq!(trace.result, curve.scalar_mult(&g, &k));
+            assert_eq!(trace.steps.len() as u64, k.bits());
+            assert!(trace.steps.iter().all(|step| step.bit == step.addition.is_some()));
+        }
+
+        // 13 = 0b1101: additions on bits 0, 2 and 3
+        let trace = curve.scalar_mult_traced(&g, &BigUint::from(13u32));
+        let bits: Vec<bool> = trace.steps.iter().map(|step| step.bit).collect();
+        assert_eq!(bits, vec![true, false, true, true]);
+        let text = trace.to_string();
+        assert!(text.starts_with("13 * (5, 1)"));
+        assert_eq!(text.matches("R = R + A").count(), 3);
+        assert!(text.ends_with(&format!("result {}", coordinates(&trace.result))));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::curves::NamedCurve;
+use crate::encoding::from_hex;
+use crate::{EllipticCurve, FiniteField, Point};
+use num_bigint::BigUint;
+use sha1::{Digest, Sha1};
+
+// Verifiably random curves as in ANSI X9.62 and FIPS 186: b is derived from a published seed by
+// SHA-1, so nobody could have steered the curve towards a weakness without bending SHA-1 to it.
+// (Brainpool, RFC 5639, hashes a seed into a as well by a variant of the same idea; only the
+// X9.62 derivation is implemented here.) For a t-bit p with s = ⌊(t - 1) / 160⌋ and v = t - 160s:
+//   W0 = the rightmost v bits of SHA-1(seed), with its leftmost bit cleared
+//   Wi = SHA-1((seed + i) mod 2^g) for i = 1..s, g the seed length in bits
+//   c  = W0 || W1 || ... || Ws as an integer
+// and the curve y² = x³ + ax + b must have b²c = a³ (mod p). FIPS fixes a = -3, so b² = -27 / c
+// (either root; both give isomorphic curves). Generation tries seed, seed + 1, ... until b
+// exists, the curve is nonsingular and its order is prime. The order is found with Schoof's
+// algorithm, so generation only goes as far as that (about 64-bit fields); verification works
+// at any size and is how the NIST seeds below can be audited
+
+// The seeds behind the NIST prime curves (FIPS 186-4 D.1.2)
+pub const NIST_SEEDS: [(&str, &str); 5] = [
+    ("P-192", "3045ae6fc8422f64ed579528d38120eae12196d5"),
+    ("P-224", "bd71344799d5c7fcdc45b59fa3b9ab8f6a948bc5"),
+    ("P-256", "c49d360886e704936a6678e1139d26b7819f7e90"),
+    ("P-384", "a335926aa319a27a1d00896a6773a4827acdac73"),
+    ("P-521", "d09e8800291cb85396cc6717393284aaa0da64ba"),
+];
+
+pub fn nist_seed(name: &str) -> Option<Vec<u8>> {
+    let (_, seed) = NIST_SEEDS.iter().find(|(curve, _)| *curve == name)?;
+    from_hex(seed).ok()
+}
+
+// (seed + i) mod 2^g, the same length as the seed
+fn add_to_seed(seed: &[u8], i: u32) -> Vec<u8> {
+    let mut bytes = seed.to_vec();
+    let mut carry = i as u64;
+    for byte in bytes.iter_mut().rev() {
+        carry += *byte as u64;
+        *byte = carry as u8;
+        carry >>= 8;
+    }
+    bytes
+}
+
+pub fn seed_to_c(seed: &[u8], p: &BigUint) -> BigUint {
+    let t = p.bits();
+    let s = (t - 1) / 160;
+    let v = t - 160 * s;
+    let w0 = BigUint::from_bytes_be(&Sha1::digest(seed)) % (BigUint::from(1u32) << (v - 1));
+    (1..=s as u32).fold(w0, |c, i| (c << 160) + BigUint::from_bytes_be(&Sha1::digest(add_to_seed(seed, i))))
+}
+
+// Whether b²c = a³ (mod p) for the c derived from the seed
+pub fn verify(curve: &EllipticCurve, seed: &[u8]) -> bool {
+    let field = curve.field();
+    let c = seed_to_c(seed, curve.p());
+    c != BigUint::from(0u32)
+        && field.mul(&field.mul(curve.b(), curve.b()), &c) == field.mul(&field.mul(curve.a(), curve.a()), curve.a())
+}
+
+// y² = x³ - 3x + b with b² = -27 / c, if c allows one: c != 0, 4c + 27 != 0 (nonsingular)
+// and -27 / c a square. Of the two roots the smaller is taken
+pub fn curve_from_seed(seed: &[u8], p: &BigUint) -> Option<EllipticCurve> {
+    let field = FiniteField { p: p.clone() };
+    let c = seed_to_c(seed, p);
+    let minus_27 = field.sub(&BigUint::from(0u32), &BigUint::from(27u32));
+    if c == BigUint::from(0u32) || field.add(&field.mul(&BigUint::from(4u32), &c), &BigUint::from(27u32)) == BigUint::from(0u32) {
+        return None;
+    }
+    let root = field.sqrt(&field.div(&minus_27, &c))?;
+    let b = root.clone().min(field.sub(&BigUint::from(0u32), &root));
+    Some(EllipticCurve::new(field.sub(&BigUint::from(0u32), &BigUint::from(3u32)), b, p.clone()))
+}
+
+// A prime-order curve over F_p from the first of seed, seed + 1, ... (up to max_tries) that
+// works, returned with the seed that produced it. The generator is derived too: the point with
+// the smallest x >= 1 and the smaller of its two y values
+pub fn generate(p: &BigUint, seed: &[u8], max_tries: u32) -> Result<(Vec<u8>, NamedCurve), &'static str> {
+    if seed.len() < 20 {
+        return Err("seed must be at least 160 bits");
+    }
+    for i in 0..max_tries {
+        let candidate = add_to_seed(seed, i);
+        let Some(curve) = curve_from_seed(&candidate, p) else { continue };
+        // p + 1 - t is even when t is, which is cheap to see before counting
+        if curve.trace_mod_2() == 0 {
+            continue;
+        }
+        let order = curve.count_points_schoof()?;
+        if !crate::ntheory::is_probable_prime(&order) {
+            continue;
+        }
+        let mut x = BigUint::from(1u32);
+        let generator = loop {
+            if let Some((Point::Coordinate(x, y), _)) = curve.lift_x(&x) {
+                let y = (curve.p() - &y).min(y);
+                break Point::Coordinate(x, y);
+            }
+            x += 1u32;
+        };
+        let named = NamedCurve { name: "verifiably random", curve, generator, order, cofactor: BigUint::from(1u32) };
+        return Ok((candidate, named));
+    }
+    Err("no prime-order curve from these seeds")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+
+    #[test]
+    fn test_nist_seeds() {
+        for (name, _) in NIST_SEEDS {
+            let curve = curves::by_name(name).unwrap();
+            let seed = nist_seed(name).unwrap();
+            assert!(verify(&curve.curve, &seed), "{}", name);
+            assert!(!verify(&curve.curve, &add_to_seed(&seed, 1)));
+
+            // The derived b is the curve's own b or its negative
+            let derived = curve_from_seed(&seed, curve.curve.p()).unwrap();
+            assert_eq!(derived.a(), curve.curve.a());
+            assert!(derived.b() == curve.curve.b() || *derived.b() == curve.curve.p() - curve.curve.b());
+        }
+        // b of secp256k1 isn't from any seed this way
+        assert!(!verify(&curves::secp256k1().curve, &nist_seed("P-256").unwrap()));
+        assert_eq!(add_to_seed(&[0xff, 0xff], 2), vec![0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_generate_and_audit() {
+        // p = 2^19 - 1
+        let p = BigUint::from(0x7ffffu32);
+        let seed = Sha1::digest(b"elliptic-curve verifiably random example").to_vec();
+        let (used_seed, curve) = generate(&p, &seed, 200).unwrap();
+        assert!(verify(&curve.curve, &used_seed));
+        assert_eq!(curve.ecdsa().validate(), Ok(()));
+
+        // Anyone with the seed gets the same curve, and a tampered b fails the audit
+        assert_eq!(generate(&p, &seed, 200).unwrap(), (used_seed.clone(), curve.clone()));
+        let tampered = EllipticCurve::new(curve.curve.a().clone(), curve.curve.b() + 1u32, curve.curve.p().clone());
+        assert!(!verify(&tampered, &used_seed));
+        assert!(generate(&p, &seed[..19], 1).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{EllipticCurve, Point};
+use num_bigint::BigUint;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+// Pictures of small curves for teaching material: the point set of y² = x³ + ax + b over F_p
+// as a grid with x across and y up, either ASCII for a terminal or an SVG scatter plot. With a
+// generator G given, the points of <G> are highlighted (and in the SVG labelled kG on hover),
+// which shows how a subgroup is scattered across the plane rather than along any curve.
+// Every x is plotted, so only small p make sense:
+//   ASCII  '.' no point, 'o' a point, '#' a multiple of G, 'G' the generator itself
+//   SVG    grey dots, red for multiples of G, p up to MAX_SVG_MODULUS
+
+pub const MAX_ASCII_MODULUS: u32 = 128;
+pub const MAX_SVG_MODULUS: u32 = 1024;
+
+// Pixels per grid cell in the SVG
+const CELL: u64 = 8;
+
+// (x, y) of every affine point as machine integers
+fn grid_points(curve: &EllipticCurve) -> Vec<(u64, u64)> {
+    let small = |n: &BigUint| n.to_u64_digits().first().copied().unwrap_or(0);
+    curve
+        .points()
+        .filter_map(|point| match point {
+            Point::Coordinate(x, y) => Some((small(&x), small(&y))),
+            Point::Identity => None,
+        })
+        .collect()
+}
+
+// k for each kG != O, k from 1 up to the order of G
+fn multiples(curve: &EllipticCurve, generator: &Point) -> Result<HashMap<Point, u64>, &'static str> {
+    if *generator == Point::Identity || !curve.is_on_curve(generator) {
+        return Err("generator is not a point on the curve");
+    }
+    let mut multiples = HashMap::new();
+    let mut point = generator.clone();
+    let mut k = 1u64;
+    while point != Point::Identity {
+        multiples.insert(point.clone(), k);
+        point = curve.add(&point, generator);
+        k += 1;
+    }
+    Ok(multiples)
+}
+
+fn modulus(curve: &EllipticCurve, max: u32) -> Result<u64, &'static str> {
+    if *curve.p() > BigUint::from(max) {
+        return Err("field too large to plot");
+    }
+    Ok(curve.p().to_u64_digits().first().copied().unwrap_or(0))
+}
+
+fn point_at(x: u64, y: u64) -> Point {
+    Point::Coordinate(BigUint::from(x), BigUint::from(y))
+}
+
+// One row per y from p - 1 down to 0, labelled, over an x axis
+pub fn ascii(curve: &EllipticCurve, generator: Option<&Point>) -> Result<String, &'static str> {
+    let p = modulus(curve, MAX_ASCII_MODULUS)?;
+    let highlighted = generator.map(|g| multiples(curve, g)).transpose()?.unwrap_or_default();
+    let points: HashSet<(u64, u64)> = grid_points(curve).into_iter().collect();
+    let width = (p - 1).to_string().len();
+
+    let mut out = String::new();
+    for y in (0..p).rev() {
+        let _ = write!(out, "{:>width$} |", y);
+        for x in 0..p {
+            let symbol = if !points.contains(&(x, y)) {
+                '.'
+            } else {
+                match highlighted.get(&point_at(x, y)) {
+                    Some(1) => 'G',
+                    Some(_) => '#',
+                    None => 'o',
+                }
+            };
+            out.push(symbol);
+        }
+        out.push('\n');
+    }
+    let _ = writeln!(out, "{:>width$} +{}", "", "-".repeat(p as usize));
+    Ok(out)
+}
+
+pub fn svg(curve: &EllipticCurve, generator: Option<&Point>) -> Result<String, &'static str> {
+    let p = modulus(curve, MAX_SVG_MODULUS)?;
+    let highlighted = generator.map(|g| multiples(curve, g)).transpose()?.unwrap_or_default();
+    let size = (p + 2) * CELL;
+
+    let mut out = String::new();
+    let _ = writeln!(out, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{}" viewBox="0 0 {size} {}">"#, size + 2 * CELL, size + 2 * CELL);
+    let _ = writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    let _ = writeln!(out, r#"<rect x="{CELL}" y="{CELL}" width="{}" height="{}" fill="none" stroke="black"/>"#, p * CELL, p * CELL);
+    for (x, y) in grid_points(curve) {
+        // Cell centres, y growing upwards
+        let (cx, cy) = ((x + 1) * CELL + CELL / 2, (p - y) * CELL + CELL / 2);
+        match highlighted.get(&point_at(x, y)) {
+            Some(k) => {
+                let _ = writeln!(out, r#"<circle cx="{cx}" cy="{cy}" r="{}" fill="red"><title>{k}G = ({x}, {y})</title></circle>"#, CELL / 2);
+            }
+            None => {
+                let _ = writeln!(out, r#"<circle cx="{cx}" cy="{cy}" r="{}" fill="grey"><title>({x}, {y})</title></circle>"#, CELL / 2 - 1);
+            }
+        }
+    }
+    let _ = writeln!(out, r#"<text x="{CELL}" y="{}" font-family="monospace" font-size="{}">y² = x³ + {}x + {} over F_{p}</text>"#, size + CELL + CELL / 2, CELL + 2, curve.a(), curve.b());
+    out.push_str("</svg>\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo_curve() -> EllipticCurve {
+        // 19 points, so any affine point generates everything
+        EllipticCurve::new(BigUint::from(2u32), BigUint::from(2u32), BigUint::from(17u32))
+    }
+
+    #[test]
+    fn test_ascii() {
+        let curve = demo_curve();
+        let plain = ascii(&curve, None).unwrap();
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines.len(), 18);
+        assert_eq!(plain.matches('o').count(), 18);
+        // (5, 1) sits in the row for y = 1, at column 5
+        assert!(lines[15].starts_with(" 1 |"));
+        assert_eq!(lines[15].as_bytes()[4 + 5], b'o');
+
+        let generator = Point::Coordinate(BigUint::from(5u32), BigUint::from(1u32));
+        let marked = ascii(&curve, Some(&generator)).unwrap();
+        assert_eq!(marked.matches('#').count(), 17);
+        assert_eq!(lines[15].len(), marked.lines().nth(15).unwrap().len());
+        assert_eq!(marked.lines().nth(15).unwrap().as_bytes()[4 + 5], b'G');
+
+        let large = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(1019u32));
+        assert!(ascii(&large, None).is_err());
+        assert!(ascii(&curve, Some(&Point::Identity)).is_err());
+    }
+
+    #[test]
+    fn test_svg() {
+        // y² = x³ + x + 1 (mod 23): 27 affine points, the multiples of (3, 10) in red
+        let curve = EllipticCurve::new(BigUint::from(1u32), BigUint::from(1u32), BigUint::from(23u32));
+        let generator = Point::Coordinate(BigUint::from(3u32), BigUint::from(10u32));
+        let image = svg(&curve, Some(&generator)).unwrap();
+        assert!(image.starts_with("<svg") && image.ends_with("</svg>\n"));
+        assert_eq!(image.matches("<circle").count(), 27);
+        let order = curve.point_order(&generator, &BigUint::from(28u32)).unwrap();
+        assert_eq!(BigUint::from(image.matches(r#"fill="red""#).count()), order - 1u32);
+        assert!(image.contains("<title>1G = (3, 10)</title>"));
+        assert!(image.contains("over F_23"));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::curves;
+use crate::encoding::{self, to_fixed_bytes};
+use num_bigint::BigUint;
+
+// Wallet Import Format for secp256k1 private keys
+//
+//   base58check(network byte || 32-byte key || [0x01 if the public key is compressed])
+//
+// The compression flag doesn't change the key, only which public key encoding (and so which
+// address) the wallet derives from it. Mainnet keys start with 5 (uncompressed) or K/L,
+// testnet keys with 9 or c
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+}
+
+impl Network {
+    pub fn wif_prefix(&self) -> u8 {
+        match self {
+            Network::Mainnet => 0x80,
+            Network::Testnet => 0xef,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WifKey {
+    pub private_key: BigUint,
+    pub network: Network,
+    pub compressed: bool,
+}
+
+pub fn encode(key: &WifKey) -> Result<String, &'static str> {
+    if key.private_key == BigUint::from(0u32) || key.private_key >= curves::secp256k1().order {
+        return Err("private key out of range");
+    }
+    let mut payload = vec![key.network.wif_prefix()];
+    payload.extend(to_fixed_bytes(&key.private_key, 32));
+    if key.compressed {
+        payload.push(0x01);
+    }
+    Ok(encoding::to_base58check(&payload))
+}
+
+pub fn decode(s: &str) -> Result<WifKey, &'static str> {
+    let payload = encoding::from_base58check(s)?;
+    let network = match payload.first() {
+        Some(0x80) => Network::Mainnet,
+        Some(0xef) => Network::Testnet,
+        _ => return Err("unknown WIF network byte"),
+    };
+    let compressed = match payload.len() {
+        33 => false,
+        34 if payload[33] == 0x01 => true,
+        34 => return Err("invalid WIF compression flag"),
+        _ => return Err("WIF payload has the wrong length"),
+    };
+    let private_key = BigUint::from_bytes_be(&payload[1..33]);
+    if private_key == BigUint::from(0u32) || private_key >= curves::secp256k1().order {
+        return Err("private key out of range");
+    }
+    Ok(WifKey { private_key, network, compressed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wif_vectors() {
+        let private_key = BigUint::from_bytes_be(&encoding::from_hex("0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d").unwrap());
+        let vectors = [
+            (Network::Mainnet, false, "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"),
+            (Network::Mainnet, true, "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617"),
+            (Network::Testnet, true, "cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx"),
+        ];
+        for (network, compressed, wif) in vectors {
+            let key = WifKey { private_key: private_key.clone(), network, compressed };
+            assert_eq!(encode(&key).unwrap(), wif);
+            assert_eq!(decode(wif).unwrap(), key);
+        }
+    }
+
+    #[test]
+    fn test_wif_rejects_invalid() {
+        // Checksum broken by changing the last character
+        assert!(decode("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTK").is_err());
+
+        // Valid base58check, but a P2PKH-style version byte
+        assert!(decode(&encoding::to_base58check(&[0x00; 33])).is_err());
+
+        let mut bad_flag = vec![0x80];
+        bad_flag.extend([0x11; 32]);
+        bad_flag.push(0x02);
+        assert!(decode(&encoding::to_base58check(&bad_flag)).is_err());
+
+        let zero = WifKey { private_key: BigUint::from(0u32), network: Network::Mainnet, compressed: true };
+        assert!(encode(&zero).is_err());
+    }
+}
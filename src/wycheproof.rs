@@ -0,0 +1,138 @@
+use crate::curves;
+use crate::ecdsa::ECDSASignature;
+use crate::encoding::{decode_point, from_hex, to_fixed_bytes};
+use crate::Point;
+use num_bigint::BigUint;
+use serde_json::Value;
+use std::path::PathBuf;
+
+// Conformance tests driven by Google Wycheproof test vectors (github.com/C2SP/wycheproof)
+//
+// Every .json file in testvectors/wycheproof is loaded and dispatched on its "schema":
+//   ecdsa_verify_schema.json        DER signatures against ECDSA::verify
+//   ecdh_ecpoint_test_schema.json   SEC1 public points against decode_point + ECDH
+// Groups for curves or hashes the crate doesn't support are skipped, so upstream files can be
+// dropped into the directory as they are. "acceptable" cases may pass or fail;
+// "valid" must pass and "invalid" must fail
+
+struct Outcome {
+    passed: usize,
+    skipped: usize,
+    failures: Vec<String>,
+}
+
+fn vector_files() -> Vec<PathBuf> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testvectors/wycheproof");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .expect("?missing testvectors/wycheproof?")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+fn load(schema: &str) -> Vec<(String, Value)> {
+    vector_files()
+        .into_iter()
+        .filter_map(|path| {
+            let json: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (json["schema"] == schema).then_some((name, json))
+        })
+        .collect()
+}
+
+fn hex_field(test: &Value, key: &str) -> Vec<u8> {
+    from_hex(test[key].as_str().unwrap_or_default()).expect("?test vector field is not hex?")
+}
+
+// Compare the crate's verdict with the expected result
+fn check(outcome: &mut Outcome, file: &str, test: &Value, accepted: bool) {
+    let ok = match test["result"].as_str() {
+        Some("valid") => accepted,
+        Some("invalid") => !accepted,
+        _ => true,
+    };
+    if ok {
+        outcome.passed += 1;
+    } else {
+        outcome.failures.push(format!("{} tcId {}: {} ({})", file, test["tcId"], test["comment"], test["result"]));
+    }
+}
+
+fn run_ecdsa() -> Outcome {
+    let mut outcome = Outcome { passed: 0, skipped: 0, failures: Vec::new() };
+    for (file, json) in load("ecdsa_verify_schema.json") {
+        for group in json["testGroups"].as_array().unwrap() {
+            // Older files call the key "key", newer ones "publicKey"
+            let key = if group["publicKey"].is_null() { &group["key"] } else { &group["publicKey"] };
+            let named = key["curve"].as_str().and_then(curves::by_name);
+            let tests = group["tests"].as_array().unwrap();
+            let named = match named {
+                Some(named) if group["sha"] == "SHA-256" => named,
+                _ => {
+                    outcome.skipped += tests.len();
+                    continue;
+                }
+            };
+            let ecdsa = named.ecdsa();
+            let public_key = decode_point(&named.curve, &hex_field(key, "uncompressed")).unwrap();
+
+            for test in tests {
+                let accepted = match ECDSASignature::from_der(&hex_field(test, "sig")) {
+                    Ok(signature) => ecdsa.verify(&hex_field(test, "msg"), &signature, &public_key),
+                    Err(_) => false,
+                };
+                check(&mut outcome, &file, test, accepted);
+            }
+        }
+    }
+    outcome
+}
+
+fn run_ecdh() -> Outcome {
+    let mut outcome = Outcome { passed: 0, skipped: 0, failures: Vec::new() };
+    for (file, json) in load("ecdh_ecpoint_test_schema.json") {
+        for group in json["testGroups"].as_array().unwrap() {
+            let tests = group["tests"].as_array().unwrap();
+            let Some(named) = group["curve"].as_str().and_then(curves::by_name) else {
+                outcome.skipped += tests.len();
+                continue;
+            };
+            let ecdh = named.ecdh();
+
+            for test in tests {
+                let private_key = BigUint::from_bytes_be(&hex_field(test, "private"));
+                let shared = decode_point(&named.curve, &hex_field(test, "public"))
+                    .and_then(|public_key| ecdh.shared_secret(&private_key, &public_key));
+                // The shared secret is the x-coordinate, at the field size
+                let accepted = match shared {
+                    Ok(Point::Coordinate(x, _)) => to_fixed_bytes(&x, named.field_size()) == hex_field(test, "shared"),
+                    _ => false,
+                };
+                check(&mut outcome, &file, test, accepted);
+            }
+        }
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wycheproof_ecdsa() {
+        let outcome = run_ecdsa();
+        assert!(outcome.failures.is_empty(), "{:#?}", outcome.failures);
+        assert!(outcome.passed > 0, "no ECDSA vectors ran ({} skipped)", outcome.skipped);
+    }
+
+    #[test]
+    fn test_wycheproof_ecdh() {
+        let outcome = run_ecdh();
+        assert!(outcome.failures.is_empty(), "{:#?}", outcome.failures);
+        assert!(outcome.passed > 0, "no ECDH vectors ran ({} skipped)", outcome.skipped);
+    }
+}
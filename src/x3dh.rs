@@ -0,0 +1,364 @@
+use crate::ed25519::{Ed25519, Ed25519Signature};
+use crate::edwards::EdwardsPoint;
+use crate::montgomery::{x25519_public_key, x25519_shared_secret};
+use crate::entropy::Entropy;
+use hkdf::Hkdf;
+use num_bigint::BigUint;
+use rand::RngCore;
+use sha2::{Digest, Sha256, Sha512};
+
+// X3DH (Signal's Extended Triple Diffie-Hellman) over X25519 with SHA-256
+//
+// Bob publishes an identity key IK_B, a signed prekey SPK_B with Sig(IK_B, Encode(SPK_B)), and
+// a supply of one-time prekeys OPK_B. Alice, who may find Bob offline, fetches a bundle,
+// checks the prekey signature, makes an ephemeral key EK_A and computes
+//   DH1 = DH(IK_A, SPK_B)   DH2 = DH(EK_A, IK_B)   DH3 = DH(EK_A, SPK_B)   DH4 = DH(EK_A, OPK_B)
+//   SK = HKDF-SHA256(salt = 0^32, ikm = 0xff^32 || DH1 || DH2 || DH3 [|| DH4], info, 32)
+// DH1 and DH2 authenticate both parties, DH3 and DH4 give forward secrecy; DH4 is left out when
+// Bob has run out of one-time prekeys. Alice sends IK_A, EK_A and the ids of the prekeys used,
+// and Bob repeats the DHs on the other side. Both also get AD = Encode(IK_A) || Encode(IK_B) to bind
+// the identities into whatever AEAD protocol carries on with SK. Encode(PK) = 0x05 || PK.
+// A one-time prekey must be deleted once Bob has used it, or replays get the same SK
+//
+// Identity keys are X25519 keys that also sign, with XEdDSA: the Montgomery key is mapped to
+// the Edwards point with sign bit 0 (negating the scalar if needed) and signs like Ed25519,
+// with a nonce from the key, the message and 64 random bytes. The signatures verify as plain
+// Ed25519 under that Edwards point
+
+const KEY_TYPE_X25519: u8 = 0x05;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct X3dhKeyPair {
+    pub private_key: [u8; 32],
+    pub public_key: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedPreKey {
+    pub id: u32,
+    pub key_pair: X3dhKeyPair,
+    pub signature: [u8; 64],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OneTimePreKey {
+    pub id: u32,
+    pub key_pair: X3dhKeyPair,
+}
+
+// What Bob's server hands out, one one-time prekey per bundle
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreKeyBundle {
+    pub identity_key: [u8; 32],
+    pub signed_prekey_id: u32,
+    pub signed_prekey: [u8; 32],
+    pub signed_prekey_signature: [u8; 64],
+    pub one_time_prekey: Option<(u32, [u8; 32])>,
+}
+
+// The header of Alice's first message
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitialMessage {
+    pub identity_key: [u8; 32],
+    pub ephemeral_key: [u8; 32],
+    pub signed_prekey_id: u32,
+    pub one_time_prekey_id: Option<u32>,
+}
+
+// The shared secret and associated data both sides end up with
+#[derive(Clone, Debug, PartialEq)]
+pub struct X3dhOutput {
+    pub shared_secret: [u8; 32],
+    pub associated_data: Vec<u8>,
+}
+
+pub fn encode_public_key(public_key: &[u8; 32]) -> [u8; 33] {
+    let mut out = [KEY_TYPE_X25519; 33];
+    out[1..].copy_from_slice(public_key);
+    out
+}
+
+impl X3dhKeyPair {
+    pub fn generate() -> Self {
+        let mut private_key = [0u8; 32];
+        Entropy.fill_bytes(&mut private_key);
+        X3dhKeyPair::from_private(private_key)
+    }
+
+    pub fn from_private(private_key: [u8; 32]) -> Self {
+        X3dhKeyPair { private_key, public_key: x25519_public_key(&private_key) }
+    }
+
+    fn dh(&self, public_key: &[u8; 32]) -> Result<[u8; 32], &'static str> {
+        x25519_shared_secret(&self.private_key, public_key)
+    }
+
+    // XEdDSA signature: R || s
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        let mut z = [0u8; 64];
+        Entropy.fill_bytes(&mut z);
+        xeddsa_sign(&self.private_key, message, &z)
+    }
+}
+
+impl SignedPreKey {
+    pub fn generate(id: u32, identity: &X3dhKeyPair) -> Self {
+        let key_pair = X3dhKeyPair::generate();
+        let signature = identity.sign(&encode_public_key(&key_pair.public_key));
+        SignedPreKey { id, key_pair, signature }
+    }
+}
+
+impl OneTimePreKey {
+    pub fn generate(id: u32) -> Self {
+        OneTimePreKey { id, key_pair: X3dhKeyPair::generate() }
+    }
+}
+
+impl PreKeyBundle {
+    pub fn new(identity_key: &[u8; 32], signed_prekey: &SignedPreKey, one_time_prekey: Option<&OneTimePreKey>) -> Self {
+        PreKeyBundle {
+            identity_key: *identity_key,
+            signed_prekey_id: signed_prekey.id,
+            signed_prekey: signed_prekey.key_pair.public_key,
+            signed_prekey_signature: signed_prekey.signature,
+            one_time_prekey: one_time_prekey.map(|otp| (otp.id, otp.key_pair.public_key)),
+        }
+    }
+
+    pub fn verify(&self) -> bool {
+        xeddsa_verify(&self.identity_key, &encode_public_key(&self.signed_prekey), &self.signed_prekey_signature)
+    }
+}
+
+fn kdf(dh_outputs: &[[u8; 32]], info: &[u8]) -> [u8; 32] {
+    let mut ikm = vec![0xffu8; 32];
+    for dh in dh_outputs {
+        ikm.extend_from_slice(dh);
+    }
+    let mut shared_secret = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&[0u8; 32]), &ikm).expand(info, &mut shared_secret).unwrap();
+    shared_secret
+}
+
+fn associated_data(initiator: &[u8; 32], responder: &[u8; 32]) -> Vec<u8> {
+    [encode_public_key(initiator), encode_public_key(responder)].concat()
+}
+
+// Alice's side; the bundle's signature is checked before anything else
+pub fn initiate(identity: &X3dhKeyPair, bundle: &PreKeyBundle, info: &[u8]) -> Result<(X3dhOutput, InitialMessage), &'static str> {
+    initiate_with_ephemeral(identity, bundle, info, &X3dhKeyPair::generate())
+}
+
+// initiate with a chosen ephemeral key, for tests
+pub fn initiate_with_ephemeral(
+    identity: &X3dhKeyPair,
+    bundle: &PreKeyBundle,
+    info: &[u8],
+    ephemeral: &X3dhKeyPair,
+) -> Result<(X3dhOutput, InitialMessage), &'static str> {
+    if !bundle.verify() {
+        return Err("invalid signed prekey signature");
+    }
+    let mut dh = vec![identity.dh(&bundle.signed_prekey)?, ephemeral.dh(&bundle.identity_key)?, ephemeral.dh(&bundle.signed_prekey)?];
+    if let Some((_, one_time_prekey)) = &bundle.one_time_prekey {
+        dh.push(ephemeral.dh(one_time_prekey)?);
+    }
+    let output = X3dhOutput {
+        shared_secret: kdf(&dh, info),
+        associated_data: associated_data(&identity.public_key, &bundle.identity_key),
+    };
+    let message = InitialMessage {
+        identity_key: identity.public_key,
+        ephemeral_key: ephemeral.public_key,
+        signed_prekey_id: bundle.signed_prekey_id,
+        one_time_prekey_id: bundle.one_time_prekey.map(|(id, _)| id),
+    };
+    Ok((output, message))
+}
+
+// Bob's side, with the prekeys the message names
+pub fn respond(
+    identity: &X3dhKeyPair,
+    signed_prekey: &SignedPreKey,
+    one_time_prekey: Option<&OneTimePreKey>,
+    message: &InitialMessage,
+    info: &[u8],
+) -> Result<X3dhOutput, &'static str> {
+    if message.signed_prekey_id != signed_prekey.id {
+        return Err("message uses a different signed prekey");
+    }
+    if message.one_time_prekey_id != one_time_prekey.map(|otp| otp.id) {
+        return Err("message uses a different one-time prekey");
+    }
+    let mut dh = vec![
+        signed_prekey.key_pair.dh(&message.identity_key)?,
+        identity.dh(&message.ephemeral_key)?,
+        signed_prekey.key_pair.dh(&message.ephemeral_key)?,
+    ];
+    if let Some(otp) = one_time_prekey {
+        dh.push(otp.key_pair.dh(&message.ephemeral_key)?);
+    }
+    Ok(X3dhOutput {
+        shared_secret: kdf(&dh, info),
+        associated_data: associated_data(&message.identity_key, &identity.public_key),
+    })
+}
+
+// hash_i(X) = SHA-512(2^256 - 1 - i || X), the domain-separated hash XEdDSA takes nonces from
+fn hash_i(i: u8, parts: &[&[u8]]) -> [u8; 64] {
+    let mut prefix = [0xffu8; 32];
+    prefix[0] -= i;
+    parts.iter().fold(Sha512::new().chain_update(prefix), |hasher, part| hasher.chain_update(part)).finalize().into()
+}
+
+fn scalar_from_hash(ed: &Ed25519, hash: &[u8]) -> BigUint {
+    BigUint::from_bytes_le(hash) % &ed.order
+}
+
+fn le_bytes(x: &BigUint) -> [u8; 32] {
+    let mut bytes = x.to_bytes_le();
+    bytes.resize(32, 0);
+    bytes.try_into().unwrap()
+}
+
+// calculate_key_pair: the clamped X25519 scalar k gives E = kB; A is E with its sign bit
+// cleared and a is k or -k to match
+fn calculate_key_pair(ed: &Ed25519, private_key: &[u8; 32]) -> ([u8; 32], BigUint) {
+    let mut k = *private_key;
+    k[0] &= 0xf8;
+    k[31] &= 0x7f;
+    k[31] |= 0x40;
+    let k = BigUint::from_bytes_le(&k) % &ed.order;
+    let mut a_encoded = ed.encode(&ed.curve.scalar_mult(&ed.base, &k));
+    let a = if a_encoded[31] & 0x80 != 0 { (&ed.order - &k) % &ed.order } else { k };
+    a_encoded[31] &= 0x7f;
+    (a_encoded, a)
+}
+
+pub fn xeddsa_sign(private_key: &[u8; 32], message: &[u8], random: &[u8; 64]) -> [u8; 64] {
+    let ed = Ed25519::new();
+    let (public_key, a) = calculate_key_pair(&ed, private_key);
+    let r = scalar_from_hash(&ed, &hash_i(1, &[&le_bytes(&a), message, random]));
+    let r_encoded = ed.encode(&ed.curve.scalar_mult(&ed.base, &r));
+    let h = scalar_from_hash(&ed, &Sha512::new().chain_update(r_encoded).chain_update(public_key).chain_update(message).finalize());
+    let s = (r + h * a) % &ed.order;
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r_encoded);
+    signature[32..].copy_from_slice(&le_bytes(&s));
+    signature
+}
+
+// The Edwards public key for a Montgomery u: y = (u - 1) / (u + 1), sign bit 0
+pub fn xeddsa_public_key(public_key: &[u8; 32]) -> Result<[u8; 32], &'static str> {
+    let ed = Ed25519::new();
+    let mut u = *public_key;
+    u[31] &= 0x7f;
+    let u = BigUint::from_bytes_le(&u);
+    if u >= *ed.curve.p() {
+        return Err("u is not a field element");
+    }
+    let field = ed.curve.field();
+    let one = BigUint::from(1u32);
+    let u_plus_one = field.add(&u, &one);
+    if u_plus_one == BigUint::from(0u32) {
+        return Err("u = -1 has no Edwards point");
+    }
+    Ok(le_bytes(&field.div(&field.sub(&u, &one), &u_plus_one)))
+}
+
+// R || s under the X25519 public key; R must equal s B - h A exactly
+pub fn xeddsa_verify(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let ed = Ed25519::new();
+    let Ok(a_encoded) = xeddsa_public_key(public_key) else {
+        return false;
+    };
+    let Ok(signature) = Ed25519Signature::from_bytes(signature) else {
+        return false;
+    };
+    let (Ok(a), true) = (ed.decode(&a_encoded), signature.s < ed.order) else {
+        return false;
+    };
+    let h = scalar_from_hash(&ed, &Sha512::new().chain_update(signature.r).chain_update(a_encoded).chain_update(message).finalize());
+    let r_check: EdwardsPoint = ed.curve.add(&ed.curve.scalar_mult(&ed.base, &signature.s), &ed.curve.neg(&ed.curve.scalar_mult(&a, &h)));
+    ed.encode(&r_check) == signature.r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handshake() {
+        let alice = X3dhKeyPair::generate();
+        let bob = X3dhKeyPair::generate();
+        let signed_prekey = SignedPreKey::generate(1, &bob);
+        let one_time_prekey = OneTimePreKey::generate(42);
+        let info = b"MyProtocol";
+
+        for otp in [Some(&one_time_prekey), None] {
+            let bundle = PreKeyBundle::new(&bob.public_key, &signed_prekey, otp);
+            let (alice_output, message) = initiate(&alice, &bundle, info).unwrap();
+            assert_eq!(message.one_time_prekey_id, otp.map(|otp| otp.id));
+            let bob_output = respond(&bob, &signed_prekey, otp, &message, info).unwrap();
+            assert_eq!(alice_output, bob_output);
+            assert_eq!(alice_output.associated_data, [encode_public_key(&alice.public_key), encode_public_key(&bob.public_key)].concat());
+
+            // A different info string derives a different key
+            assert_ne!(respond(&bob, &signed_prekey, otp, &message, b"Other").unwrap().shared_secret, alice_output.shared_secret);
+        }
+
+        // Three DHs and four give different keys from the same ephemeral
+        let ephemeral = X3dhKeyPair::generate();
+        let with_otp = initiate_with_ephemeral(&alice, &PreKeyBundle::new(&bob.public_key, &signed_prekey, Some(&one_time_prekey)), info, &ephemeral).unwrap();
+        let without = initiate_with_ephemeral(&alice, &PreKeyBundle::new(&bob.public_key, &signed_prekey, None), info, &ephemeral).unwrap();
+        assert_ne!(with_otp.0.shared_secret, without.0.shared_secret);
+
+        // Bob without the one-time prekey the message names, or with another signed prekey
+        assert!(respond(&bob, &signed_prekey, None, &with_otp.1, info).is_err());
+        assert!(respond(&bob, &SignedPreKey::generate(2, &bob), Some(&one_time_prekey), &with_otp.1, info).is_err());
+    }
+
+    #[test]
+    fn test_rejected_bundles() {
+        let alice = X3dhKeyPair::generate();
+        let bob = X3dhKeyPair::generate();
+        let signed_prekey = SignedPreKey::generate(1, &bob);
+
+        // A signature from someone else's identity, and a swapped prekey
+        let mut bundle = PreKeyBundle::new(&bob.public_key, &SignedPreKey::generate(1, &alice), None);
+        assert_eq!(initiate(&alice, &bundle, b"").unwrap_err(), "invalid signed prekey signature");
+        bundle = PreKeyBundle::new(&bob.public_key, &signed_prekey, None);
+        bundle.signed_prekey = X3dhKeyPair::generate().public_key;
+        assert!(initiate(&alice, &bundle, b"").is_err());
+
+        // A small-order signed prekey
+        bundle = PreKeyBundle::new(&bob.public_key, &signed_prekey, None);
+        bundle.signed_prekey = [0u8; 32];
+        bundle.signed_prekey_signature = bob.sign(&encode_public_key(&[0u8; 32]));
+        assert!(initiate(&alice, &bundle, b"").is_err());
+    }
+
+    #[test]
+    fn test_xeddsa() {
+        let key = X3dhKeyPair::from_private([0x42; 32]);
+        let signature = key.sign(b"message");
+        assert!(xeddsa_verify(&key.public_key, b"message", &signature));
+        assert!(!xeddsa_verify(&key.public_key, b"massage", &signature));
+        assert!(!xeddsa_verify(&X3dhKeyPair::generate().public_key, b"message", &signature));
+
+        // The same signature verifies as Ed25519 under the converted key, and fresh randomness
+        // gives a fresh signature
+        let ed = Ed25519::new();
+        let edwards_key = xeddsa_public_key(&key.public_key).unwrap();
+        assert!(ed.verify(&edwards_key, b"message", &Ed25519Signature::from_bytes(&signature).unwrap()));
+        assert_ne!(key.sign(b"message"), signature);
+        assert_eq!(xeddsa_sign(&key.private_key, b"m", &[1; 64]), xeddsa_sign(&key.private_key, b"m", &[1; 64]));
+
+        // s must be reduced
+        let mut unreduced = signature;
+        let s = BigUint::from_bytes_le(&signature[32..]) + &ed.order;
+        unreduced[32..].copy_from_slice(&le_bytes(&s));
+        assert!(!xeddsa_verify(&key.public_key, b"message", &unreduced));
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{EllipticCurve, FiniteField, Point};
+use crate::encoding::{encode_point, to_fixed_bytes};
+use crate::entropy::Entropy;
+use crate::scalar::Scalar;
+use num_bigint::{BigUint, RandBigInt};
+use sha2::{Digest, Sha256};
+
+// Zero-knowledge proofs over the curve group, made non-interactive with Fiat–Shamir:
+// the verifier's random challenge is replaced by a hash of everything said so far
+
+// Fiat–Shamir transcript: a running hash of a domain label and labeled messages
+// Labels and length prefixes keep different protocols (and fields) from colliding
+#[derive(Clone)]
+pub struct Transcript {
+    hasher: Sha256,
+}
+
+impl Transcript {
+    pub fn new(domain: &[u8]) -> Self {
+        let mut transcript = Transcript { hasher: Sha256::new() };
+        transcript.append_message(b"domain", domain);
+        transcript
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        for part in [label, message] {
+            self.hasher.update((part.len() as u64).to_be_bytes());
+            self.hasher.update(part);
+        }
+    }
+
+    pub fn append_point(&mut self, label: &[u8], curve: &EllipticCurve, point: &Point) {
+        self.append_message(label, &encode_point(curve, point, true));
+    }
+
+    pub fn append_scalar(&mut self, label: &[u8], scalar: &BigUint) {
+        self.append_message(label, &scalar.to_bytes_be());
+    }
+
+    // 512 hash bits reduced mod n (negligible bias), then fed back so later challenges
+    // depend on earlier ones
+    pub fn challenge_scalar(&mut self, label: &[u8], order: &BigUint) -> BigUint {
+        self.append_message(b"challenge", label);
+        let mut wide = Vec::with_capacity(64);
+        for counter in 0u8..2 {
+            wide.extend(self.hasher.clone().chain_update([counter]).finalize());
+        }
+        let challenge = BigUint::from_bytes_be(&wide) % order;
+        self.append_scalar(label, &challenge);
+        challenge
+    }
+}
+
+// Statement for a discrete-log equality proof: log_G(A) = log_H(B)
+#[derive(Clone, Debug, PartialEq)]
+pub struct DLEQStatement {
+    pub g: Point,
+    pub a: Point,
+    pub h: Point,
+    pub b: Point,
+}
+
+impl DLEQStatement {
+    // A = x * G, B = x * H
+    pub fn new(curve: &EllipticCurve, g: &Point, h: &Point, secret: &BigUint) -> Self {
+        DLEQStatement {
+            g: g.clone(),
+            a: curve.scalar_mult(g, secret),
+            h: h.clone(),
+            b: curve.scalar_mult(h, secret),
+        }
+    }
+
+    fn append_to(&self, transcript: &mut Transcript, curve: &EllipticCurve) {
+        transcript.append_message(b"protocol", b"dleq");
+        transcript.append_point(b"G", curve, &self.g);
+        transcript.append_point(b"A", curve, &self.a);
+        transcript.append_point(b"H", curve, &self.h);
+        transcript.append_point(b"B", curve, &self.b);
+    }
+}
+
+// Chaum–Pedersen proof: knowledge of x with A = x * G and B = x * H
+//   prover:   k random, T1 = k * G, T2 = k * H, c = H(statement, T1, T2), s = k - c * x
+//   verifier: T1 = s * G + c * A, T2 = s * H + c * B, check c = H(statement, T1, T2)
+// Proves that two Diffie–Hellman style values share an exponent without revealing it
+// (verifiable OPRF evaluations, VRFs, correct decryption shares in mixnets)
+#[derive(Clone, Debug, PartialEq)]
+pub struct DLEQProof {
+    pub challenge: BigUint,
+    pub response: BigUint,
+}
+
+impl DLEQProof {
+    pub fn prove(curve: &EllipticCurve, order: &BigUint, statement: &DLEQStatement, secret: &BigUint, transcript: &mut Transcript) -> Result<Self, &'static str> {
+        if *statement != DLEQStatement::new(curve, &statement.g, &statement.h, secret) {
+            return Err("secret does not match the statement");
+        }
+        let k = Scalar::random(order, &mut Entropy).into_value();
+        let t1 = curve.scalar_mult(&statement.g, &k);
+        let t2 = curve.scalar_mult(&statement.h, &k);
+
+        statement.append_to(transcript, curve);
+        transcript.append_point(b"T1", curve, &t1);
+        transcript.append_point(b"T2", curve, &t2);
+        let challenge = transcript.challenge_scalar(b"c", order);
+
+        let field = FiniteField { p: order.clone() };
+        let response = field.sub(&k, &field.mul(&challenge, &(secret % order)));
+        Ok(DLEQProof { challenge, response })
+    }
+
+    pub fn verify(&self, curve: &EllipticCurve, order: &BigUint, statement: &DLEQStatement, transcript: &mut Transcript) -> bool {
+        if self.challenge >= *order || self.response >= *order {
+            return false;
+        }
+        let points = [&statement.g, &statement.a, &statement.h, &statement.b];
+        if points.iter().any(|p| !curve.is_on_curve(p)) {
+            return false;
+        }
+
+        let t1 = curve.add(&curve.scalar_mult(&statement.g, &self.response), &curve.scalar_mult(&statement.a, &self.challenge));
+        let t2 = curve.add(&curve.scalar_mult(&statement.h, &self.response), &curve.scalar_mult(&statement.b, &self.challenge));
+
+        statement.append_to(transcript, curve);
+        transcript.append_point(b"T1", curve, &t1);
+        transcript.append_point(b"T2", curve, &t2);
+        transcript.challenge_scalar(b"c", order) == self.challenge
+    }
+
+    // c || s, each padded to the scalar size
+    pub fn to_bytes(&self, order: &BigUint) -> Vec<u8> {
+        let len = order.bits().div_ceil(8) as usize;
+        let mut bytes = to_fixed_bytes(&self.challenge, len);
+        bytes.extend(to_fixed_bytes(&self.response, len));
+        bytes
+    }
+}
+
+// Schnorr proof of knowledge of a discrete log: knowledge of x with X = x * G
+//   prover:   k random, commits T = k * G
+//   verifier: random challenge c
+//   prover:   s = k - c * x
+//   verifier: T = s * G + c * X
+// Special soundness: two accepting transcripts (T, c, s), (T, c', s') give x = (s - s') / (c' - c),
+// which is also why a commitment must never be answered twice
+
+// Prover state after the first move; consumed by respond so the nonce is used once
+pub struct SchnorrCommitment {
+    nonce: BigUint,
+    pub point: Point,
+}
+
+impl SchnorrCommitment {
+    pub fn new(curve: &EllipticCurve, order: &BigUint, generator: &Point) -> Self {
+        let nonce = Scalar::random(order, &mut Entropy).into_value();
+        SchnorrCommitment {
+            point: curve.scalar_mult(generator, &nonce),
+            nonce,
+        }
+    }
+
+    // s = k - c * x
+    pub fn respond(self, order: &BigUint, secret: &BigUint, challenge: &BigUint) -> BigUint {
+        let field = FiniteField { p: order.clone() };
+        field.sub(&self.nonce, &field.mul(&(challenge % order), &(secret % order)))
+    }
+}
+
+// Verifier's challenge in the interactive protocol
+pub fn schnorr_challenge(order: &BigUint) -> BigUint {
+    let mut rng = Entropy;
+    rng.gen_biguint_below(order)
+}
+
+// Verifier's check in the interactive protocol: T = s * G + c * X
+pub fn verify_schnorr_response(curve: &EllipticCurve, generator: &Point, public_key: &Point, commitment: &Point, challenge: &BigUint, response: &BigUint) -> bool {
+    if !curve.is_on_curve(public_key) || !curve.is_on_curve(commitment) {
+        return false;
+    }
+    let expected = curve.add(&curve.scalar_mult(generator, response), &curve.scalar_mult(public_key, challenge));
+    expected == *commitment
+}
+
+// Non-interactive version: c = H(transcript, G, X, T), proof is (c, s)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchnorrProof {
+    pub challenge: BigUint,
+    pub response: BigUint,
+}
+
+impl SchnorrProof {
+    pub fn prove(curve: &EllipticCurve, order: &BigUint, generator: &Point, secret: &BigUint, transcript: &mut Transcript) -> Self {
+        let public_key = curve.scalar_mult(generator, secret);
+        let commitment = SchnorrCommitment::new(curve, order, generator);
+
+        append_schnorr(transcript, curve, generator, &public_key, &commitment.point);
+        let challenge = transcript.challenge_scalar(b"c", order);
+        let response = commitment.respond(order, secret, &challenge);
+        SchnorrProof { challenge, response }
+    }
+
+    pub fn verify(&self, curve: &EllipticCurve, order: &BigUint, generator: &Point, public_key: &Point, transcript: &mut Transcript) -> bool {
+        if self.challenge >= *order || self.response >= *order || !curve.is_on_curve(public_key) {
+            return false;
+        }
+        let commitment = curve.add(&curve.scalar_mult(generator, &self.response), &curve.scalar_mult(public_key, &self.challenge));
+
+        append_schnorr(transcript, curve, generator, public_key, &commitment);
+        transcript.challenge_scalar(b"c", order) == self.challenge
+    }
+
+    // c || s, each padded to the scalar size
+    pub fn to_bytes(&self, order: &BigUint) -> Vec<u8> {
+        let len = order.bits().div_ceil(8) as usize;
+        let mut bytes = to_fixed_bytes(&self.challenge, len);
+        bytes.extend(to_fixed_bytes(&self.response, len));
+        bytes
+    }
+}
+
+fn append_schnorr(transcript: &mut Transcript, curve: &EllipticCurve, generator: &Point, public_key: &Point, commitment: &Point) {
+    transcript.append_message(b"protocol", b"schnorr-pok");
+    transcript.append_point(b"G", curve, generator);
+    transcript.append_point(b"X", curve, public_key);
+    transcript.append_point(b"T", curve, commitment);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves;
+    use crate::hash_to_curve::HashToCurve;
+
+    fn setup() -> (curves::NamedCurve, Point) {
+        let p256 = curves::p256();
+        // A second generator with unknown discrete log relative to G
+        let h = HashToCurve::p256().hash_to_curve(b"second generator", b"DLEQ-TEST-GENERATOR").unwrap();
+        (p256, h)
+    }
+
+    #[test]
+    fn test_dleq_prove_verify() {
+        let (p256, h) = setup();
+        let secret = BigUint::from(0xdeadbeefu32);
+        let statement = DLEQStatement::new(&p256.curve, &p256.generator, &h, &secret);
+
+        let proof = DLEQProof::prove(&p256.curve, &p256.order, &statement, &secret, &mut Transcript::new(b"test")).unwrap();
+        assert!(proof.verify(&p256.curve, &p256.order, &statement, &mut Transcript::new(b"test")));
+        assert_eq!(proof.to_bytes(&p256.order).len(), 64);
+
+        // A proof is bound to its transcript domain
+        assert!(!proof.verify(&p256.curve, &p256.order, &statement, &mut Transcript::new(b"other")));
+    }
+
+    #[test]
+    fn test_dleq_rejects_unequal_logs() {
+        let (p256, h) = setup();
+        let secret = BigUint::from(12345u32);
+        let mut statement = DLEQStatement::new(&p256.curve, &p256.generator, &h, &secret);
+
+        // The prover refuses a false statement
+        statement.b = p256.curve.scalar_mult(&h, &BigUint::from(12346u32));
+        assert!(DLEQProof::prove(&p256.curve, &p256.order, &statement, &secret, &mut Transcript::new(b"test")).is_err());
+
+        // A valid proof doesn't transfer to a modified statement
+        let honest = DLEQStatement::new(&p256.curve, &p256.generator, &h, &secret);
+        let proof = DLEQProof::prove(&p256.curve, &p256.order, &honest, &secret, &mut Transcript::new(b"test")).unwrap();
+        assert!(!proof.verify(&p256.curve, &p256.order, &statement, &mut Transcript::new(b"test")));
+    }
+
+    #[test]
+    fn test_transcript_challenges_chain() {
+        let order = curves::p256().order;
+        let mut first = Transcript::new(b"test");
+        let mut second = Transcript::new(b"test");
+        assert_eq!(first.challenge_scalar(b"c", &order), second.challenge_scalar(b"c", &order));
+
+        // Each challenge feeds into the next, and labels matter
+        let c1 = first.challenge_scalar(b"c", &order);
+        let c2 = first.challenge_scalar(b"c", &order);
+        assert_ne!(c1, c2);
+        assert_ne!(second.challenge_scalar(b"d", &order), c1);
+    }
+
+    #[test]
+    fn test_schnorr_interactive() {
+        let p256 = curves::p256();
+        let secret = BigUint::from(424242u32);
+        let public_key = p256.curve.scalar_mult(&p256.generator, &secret);
+
+        let commitment = SchnorrCommitment::new(&p256.curve, &p256.order, &p256.generator);
+        let t = commitment.point.clone();
+        let challenge = schnorr_challenge(&p256.order);
+        let response = commitment.respond(&p256.order, &secret, &challenge);
+        assert!(verify_schnorr_response(&p256.curve, &p256.generator, &public_key, &t, &challenge, &response));
+
+        let wrong_key = p256.curve.scalar_mult(&p256.generator, &BigUint::from(424243u32));
+        assert!(!verify_schnorr_response(&p256.curve, &p256.generator, &wrong_key, &t, &challenge, &response));
+    }
+
+    #[test]
+    fn test_schnorr_special_soundness() {
+        // Answering one commitment twice leaks x = (s - s') / (c' - c)
+        let p256 = curves::p256();
+        let field = FiniteField { p: p256.order.clone() };
+        let secret = BigUint::from(987654321u32);
+        let commitment = SchnorrCommitment::new(&p256.curve, &p256.order, &p256.generator);
+        let replay = SchnorrCommitment { nonce: commitment.nonce.clone(), point: commitment.point.clone() };
+
+        let (c1, c2) = (BigUint::from(5u32), BigUint::from(9u32));
+        let s1 = commitment.respond(&p256.order, &secret, &c1);
+        let s2 = replay.respond(&p256.order, &secret, &c2);
+        assert_eq!(field.div(&field.sub(&s1, &s2), &field.sub(&c2, &c1)), secret);
+    }
+
+    #[test]
+    fn test_schnorr_non_interactive() {
+        let p256 = curves::p256();
+        let secret = BigUint::from(31337u32);
+        let public_key = p256.curve.scalar_mult(&p256.generator, &secret);
+
+        let proof = SchnorrProof::prove(&p256.curve, &p256.order, &p256.generator, &secret, &mut Transcript::new(b"key-attestation"));
+        assert!(proof.verify(&p256.curve, &p256.order, &p256.generator, &public_key, &mut Transcript::new(b"key-attestation")));
+        assert!(!proof.verify(&p256.curve, &p256.order, &p256.generator, &public_key, &mut Transcript::new(b"other-protocol")));
+
+        let other_key = p256.curve.scalar_mult(&p256.generator, &BigUint::from(31338u32));
+        assert!(!proof.verify(&p256.curve, &p256.order, &p256.generator, &other_key, &mut Transcript::new(b"key-attestation")));
+        assert_eq!(proof.to_bytes(&p256.order).len(), 64);
+    }
+}